@@ -6,6 +6,7 @@
 use rustc_ast::tokenstream::TokenStream;
 use rustc_ast::{self as ast, AstDeref, GenericArg};
 use rustc_expand::base::{self, *};
+use rustc_session::Session;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::Span;
 use std::env;
@@ -23,7 +24,7 @@ pub fn expand_option_env<'cx>(
     };
 
     let sp = cx.with_def_site_ctxt(sp);
-    let value = env::var(var.as_str()).ok().as_deref().map(Symbol::intern);
+    let value = lookup_env(cx.sess, var).as_deref().map(Symbol::intern);
     cx.sess.parse_sess.env_depinfo.borrow_mut().insert((var, value));
     let e = match value {
         None => {
@@ -77,7 +78,7 @@ pub fn expand_env<'cx>(
     };
 
     let span = cx.with_def_site_ctxt(sp);
-    let value = env::var(var.as_str()).ok().as_deref().map(Symbol::intern);
+    let value = lookup_env(cx.sess, var).as_deref().map(Symbol::intern);
     cx.sess.parse_sess.env_depinfo.borrow_mut().insert((var, value));
     let e = match value {
         None => {
@@ -113,6 +114,15 @@ pub fn expand_env<'cx>(
     MacEager::expr(e)
 }
 
+/// Reads `var` from the logical environment of the session if it has one, and
+/// from the environment of the process otherwise.
+fn lookup_env(sess: &Session, var: Symbol) -> Option<String> {
+    match &sess.opts.logical_env {
+        Some(logical_env) => logical_env.get(var.as_str()).cloned(),
+        None => env::var(var.as_str()).ok(),
+    }
+}
+
 /// Returns `true` if an environment variable from `env!` is one used by Cargo.
 fn is_cargo_env_var(var: &str) -> bool {
     var.starts_with("CARGO_")
@@ -39,6 +39,9 @@ codegen_llvm_lto_dylib = lto cannot be used for `dylib` crate type without `-Zdy
 
 codegen_llvm_lto_proc_macro = lto cannot be used for `proc-macro` crate type without `-Zdylib-lto`
 
+codegen_llvm_mcdc_requires_llvm_18 =
+    `-C instrument-coverage=mcdc` requires LLVM 18 or later
+
 codegen_llvm_missing_features =
     add the missing features in a `target_feature` attribute
 
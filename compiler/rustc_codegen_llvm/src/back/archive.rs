@@ -199,6 +199,10 @@ fn create_dll_import_lib(
                 _ => panic!("unsupported arch {}", sess.target.arch),
             };
             let mut dlltool_cmd = std::process::Command::new(&dlltool);
+            if let Some(env) = &sess.opts.logical_env {
+                dlltool_cmd.env_clear().envs(env);
+            }
+            dlltool_cmd.current_dir(sess.opts.working_dir.local_path_if_available());
             dlltool_cmd.args([
                 "-d",
                 def_file_path.to_str().unwrap(),
@@ -461,7 +465,7 @@ fn find_binutils_dlltool(sess: &Session) -> OsString {
     .into();
 
     // NOTE: it's not clear how useful it is to explicitly search PATH.
-    for dir in env::split_paths(&env::var_os("PATH").unwrap_or_default()) {
+    for dir in env::split_paths(&sess.env_var_os("PATH").unwrap_or_default()) {
         let full_path = dir.join(&tool_name);
         if full_path.is_file() {
             return full_path.into_os_string();
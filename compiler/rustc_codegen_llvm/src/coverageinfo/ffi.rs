@@ -1,4 +1,4 @@
-use rustc_middle::mir::coverage::{CounterId, CovTerm, ExpressionId};
+use rustc_middle::mir::coverage::{ConditionInfo, CounterId, CovTerm, DecisionInfo, ExpressionId};
 
 /// Must match the layout of `LLVMRustCounterKind`.
 #[derive(Copy, Clone, Debug)]
@@ -99,6 +99,49 @@ pub enum RegionKind {
     /// associated with two counters, each representing the number of times the
     /// expression evaluates to true or false.
     BranchRegion = 4,
+
+    /// A DecisionRegion represents a top-level boolean expression and is
+    /// associated with a variable length bitmap index and condition number.
+    MCDCDecisionRegion = 5,
+
+    /// A Branch Region can be extended to include IDs to facilitate MC/DC.
+    MCDCBranchRegion = 6,
+}
+
+/// The MC/DC parameters of a mapping region. Decision regions use `bitmap_idx`
+/// and `conditions_num`, and branch regions use the condition IDs; the unused
+/// fields are zero.
+///
+/// Corresponds to struct `llvm::coverage::CounterMappingRegion::MCDCParameters`.
+///
+/// Must match the layout of `LLVMRustMCDCParameters`.
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct MCDCParameters {
+    bitmap_idx: u32,
+    conditions_num: u32,
+    condition_id: u32,
+    true_id: u32,
+    false_id: u32,
+}
+
+impl MCDCParameters {
+    fn decision(decision_info: DecisionInfo) -> Self {
+        Self {
+            bitmap_idx: decision_info.bitmap_idx,
+            conditions_num: decision_info.conditions_num.into(),
+            ..Default::default()
+        }
+    }
+
+    fn branch(condition_info: ConditionInfo) -> Self {
+        Self {
+            condition_id: condition_info.condition_id.as_u32(),
+            true_id: condition_info.true_next_id.as_u32(),
+            false_id: condition_info.false_next_id.as_u32(),
+            ..Default::default()
+        }
+    }
 }
 
 /// This struct provides LLVM's representation of a "CoverageMappingRegion", encoded into the
@@ -122,6 +165,10 @@ pub struct CounterMappingRegion {
     /// for the false branch of the region.
     false_counter: Counter,
 
+    /// If the `RegionKind` is an `MCDCDecisionRegion` or an `MCDCBranchRegion`,
+    /// this holds the MC/DC parameters of the region.
+    mcdc_params: MCDCParameters,
+
     /// An indirect reference to the source filename. In the LLVM Coverage Mapping Format, the
     /// file_id is an index into a function-specific `virtual_file_mapping` array of indexes
     /// that, in turn, are used to look up the filename for this region.
@@ -160,6 +207,7 @@ pub(crate) fn code_region(
         Self {
             counter,
             false_counter: Counter::ZERO,
+            mcdc_params: MCDCParameters::default(),
             file_id,
             expanded_file_id: 0,
             start_line,
@@ -170,9 +218,6 @@ pub(crate) fn code_region(
         }
     }
 
-    // This function might be used in the future; the LLVM API is still evolving, as is coverage
-    // support.
-    #[allow(dead_code)]
     pub(crate) fn branch_region(
         counter: Counter,
         false_counter: Counter,
@@ -185,6 +230,7 @@ pub(crate) fn branch_region(
         Self {
             counter,
             false_counter,
+            mcdc_params: MCDCParameters::default(),
             file_id,
             expanded_file_id: 0,
             start_line,
@@ -195,6 +241,52 @@ pub(crate) fn branch_region(
         }
     }
 
+    pub(crate) fn mcdc_branch_region(
+        counter: Counter,
+        false_counter: Counter,
+        condition_info: ConditionInfo,
+        file_id: u32,
+        start_line: u32,
+        start_col: u32,
+        end_line: u32,
+        end_col: u32,
+    ) -> Self {
+        Self {
+            counter,
+            false_counter,
+            mcdc_params: MCDCParameters::branch(condition_info),
+            file_id,
+            expanded_file_id: 0,
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+            kind: RegionKind::MCDCBranchRegion,
+        }
+    }
+
+    pub(crate) fn decision_region(
+        decision_info: DecisionInfo,
+        file_id: u32,
+        start_line: u32,
+        start_col: u32,
+        end_line: u32,
+        end_col: u32,
+    ) -> Self {
+        Self {
+            counter: Counter::ZERO,
+            false_counter: Counter::ZERO,
+            mcdc_params: MCDCParameters::decision(decision_info),
+            file_id,
+            expanded_file_id: 0,
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+            kind: RegionKind::MCDCDecisionRegion,
+        }
+    }
+
     // This function might be used in the future; the LLVM API is still evolving, as is coverage
     // support.
     #[allow(dead_code)]
@@ -209,6 +301,7 @@ pub(crate) fn expansion_region(
         Self {
             counter: Counter::ZERO,
             false_counter: Counter::ZERO,
+            mcdc_params: MCDCParameters::default(),
             file_id,
             expanded_file_id,
             start_line,
@@ -232,6 +325,7 @@ pub(crate) fn skipped_region(
         Self {
             counter: Counter::ZERO,
             false_counter: Counter::ZERO,
+            mcdc_params: MCDCParameters::default(),
             file_id,
             expanded_file_id: 0,
             start_line,
@@ -256,6 +350,7 @@ pub(crate) fn gap_region(
         Self {
             counter,
             false_counter: Counter::ZERO,
+            mcdc_params: MCDCParameters::default(),
             file_id,
             expanded_file_id: 0,
             start_line,
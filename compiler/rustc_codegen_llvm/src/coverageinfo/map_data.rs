@@ -4,7 +4,8 @@
 use rustc_data_structures::fx::FxIndexSet;
 use rustc_index::bit_set::BitSet;
 use rustc_middle::mir::coverage::{
-    CodeRegion, CounterId, CovTerm, Expression, ExpressionId, FunctionCoverageInfo, Mapping, Op,
+    CodeRegion, CounterId, CovTerm, Expression, ExpressionId, FunctionCoverageInfo, Mapping,
+    MappingKind, Op,
 };
 use rustc_middle::ty::Instance;
 use rustc_span::Symbol;
@@ -64,8 +65,8 @@ fn create(
         // For each expression ID that is directly used by one or more mappings,
         // mark it as not-yet-seen. This indicates that we expect to see a
         // corresponding `ExpressionUsed` statement during MIR traversal.
-        for Mapping { term, .. } in &function_coverage_info.mappings {
-            if let &CovTerm::Expression(id) = term {
+        for term in function_coverage_info.mappings.iter().flat_map(|m| m.kind.terms()) {
+            if let CovTerm::Expression(id) = term {
                 expressions_seen.remove(id);
             }
         }
@@ -219,23 +220,29 @@ pub(crate) fn counter_expressions(
 
     /// Converts this function's coverage mappings into an intermediate form
     /// that will be used by `mapgen` when preparing for FFI.
+    ///
+    /// Terms that are known to always be zero are replaced with `CovTerm::Zero`.
     pub(crate) fn counter_regions(
         &self,
-    ) -> impl Iterator<Item = (Counter, &CodeRegion)> + ExactSizeIterator {
+    ) -> impl Iterator<Item = (MappingKind, &CodeRegion)> + ExactSizeIterator {
         self.function_coverage_info.mappings.iter().map(move |mapping| {
-            let &Mapping { term, ref code_region } = mapping;
-            let counter = self.counter_for_term(term);
-            (counter, code_region)
+            let Mapping { kind, code_region } = mapping;
+            let kind = kind.map_terms(|term| self.simplify_term(term));
+            (kind, code_region)
         })
     }
 
-    fn counter_for_term(&self, term: CovTerm) -> Counter {
+    fn simplify_term(&self, term: CovTerm) -> CovTerm {
         if is_zero_term(&self.counters_seen, &self.zero_expressions, term) {
-            Counter::ZERO
+            CovTerm::Zero
         } else {
-            Counter::from_term(term)
+            term
         }
     }
+
+    fn counter_for_term(&self, term: CovTerm) -> Counter {
+        Counter::from_term(self.simplify_term(term))
+    }
 }
 
 /// Set of expression IDs that are known to always evaluate to zero.
@@ -1,6 +1,6 @@
 use crate::common::CodegenCx;
 use crate::coverageinfo;
-use crate::coverageinfo::ffi::CounterMappingRegion;
+use crate::coverageinfo::ffi::{Counter, CounterMappingRegion};
 use crate::coverageinfo::map_data::{FunctionCoverage, FunctionCoverageCollector};
 use crate::llvm;
 
@@ -12,7 +12,7 @@
 use rustc_index::IndexVec;
 use rustc_middle::bug;
 use rustc_middle::mir;
-use rustc_middle::mir::coverage::CodeRegion;
+use rustc_middle::mir::coverage::{CodeRegion, MappingKind};
 use rustc_middle::ty::{self, TyCtxt};
 use rustc_span::def_id::DefIdSet;
 use rustc_span::Symbol;
@@ -239,7 +239,7 @@ fn encode_mappings_for_function(
     // Prepare file IDs for each filename, and prepare the mapping data so that
     // we can pass it through FFI to LLVM.
     for (file_name, counter_regions_for_file) in
-        &counter_regions.group_by(|(_counter, region)| region.file_name)
+        &counter_regions.group_by(|(_kind, region)| region.file_name)
     {
         // Look up the global file ID for this filename.
         let global_file_id = global_file_table.global_file_id_for_file_name(file_name);
@@ -250,18 +250,53 @@ fn encode_mappings_for_function(
 
         // For each counter/region pair in this function+file, convert it to a
         // form suitable for FFI.
-        for (counter, region) in counter_regions_for_file {
+        for (kind, region) in counter_regions_for_file {
             let CodeRegion { file_name: _, start_line, start_col, end_line, end_col } = *region;
-
-            debug!("Adding counter {counter:?} to map for {region:?}");
-            mapping_regions.push(CounterMappingRegion::code_region(
-                counter,
-                local_file_id.as_u32(),
-                start_line,
-                start_col,
-                end_line,
-                end_col,
-            ));
+            let file_id = local_file_id.as_u32();
+
+            debug!("Adding {kind:?} to map for {region:?}");
+            let mapping_region = match kind {
+                MappingKind::Code(term) => CounterMappingRegion::code_region(
+                    Counter::from_term(term),
+                    file_id,
+                    start_line,
+                    start_col,
+                    end_line,
+                    end_col,
+                ),
+                MappingKind::Branch { true_term, false_term } => {
+                    CounterMappingRegion::branch_region(
+                        Counter::from_term(true_term),
+                        Counter::from_term(false_term),
+                        file_id,
+                        start_line,
+                        start_col,
+                        end_line,
+                        end_col,
+                    )
+                }
+                MappingKind::MCDCBranch { true_term, false_term, mcdc_params } => {
+                    CounterMappingRegion::mcdc_branch_region(
+                        Counter::from_term(true_term),
+                        Counter::from_term(false_term),
+                        mcdc_params,
+                        file_id,
+                        start_line,
+                        start_col,
+                        end_line,
+                        end_col,
+                    )
+                }
+                MappingKind::MCDCDecision(decision_info) => CounterMappingRegion::decision_region(
+                    decision_info,
+                    file_id,
+                    start_line,
+                    start_col,
+                    end_line,
+                    end_col,
+                ),
+            };
+            mapping_regions.push(mapping_region);
         }
     }
 
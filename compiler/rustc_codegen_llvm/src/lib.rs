@@ -272,6 +272,11 @@ fn locale_resource(&self) -> &'static str {
 
     fn init(&self, sess: &Session) {
         llvm_util::init(sess); // Make sure llvm is inited
+
+        // MC/DC coverage instrumentation relies on intrinsics added in LLVM 18.
+        if sess.instrument_coverage_mcdc() && llvm_util::get_version() < (18, 0, 0) {
+            sess.emit_fatal(errors::McdcRequiresLlvm18);
+        }
     }
 
     fn provide(&self, providers: &mut Providers) {
@@ -5,8 +5,10 @@
 use std::fmt;
 use std::io;
 use std::mem;
+use std::path::{Path, PathBuf};
 use std::process::{self, Output};
 
+use rustc_session::Session;
 use rustc_target::spec::LldFlavor;
 
 #[derive(Clone)]
@@ -15,6 +17,8 @@ pub struct Command {
     args: Vec<OsString>,
     env: Vec<(OsString, OsString)>,
     env_remove: Vec<OsString>,
+    env_clear: bool,
+    current_dir: Option<PathBuf>,
 }
 
 #[derive(Clone)]
@@ -38,7 +42,14 @@ pub fn lld<P: AsRef<OsStr>>(program: P, flavor: LldFlavor) -> Command {
     }
 
     fn _new(program: Program) -> Command {
-        Command { program, args: Vec::new(), env: Vec::new(), env_remove: Vec::new() }
+        Command {
+            program,
+            args: Vec::new(),
+            env: Vec::new(),
+            env_remove: Vec::new(),
+            env_clear: false,
+            current_dir: None,
+        }
     }
 
     pub fn arg<P: AsRef<OsStr>>(&mut self, arg: P) -> &mut Command {
@@ -85,6 +96,29 @@ fn _env_remove(&mut self, key: &OsStr) {
         self.env_remove.push(key.to_owned());
     }
 
+    pub fn env_clear(&mut self) -> &mut Command {
+        self.env_clear = true;
+        self.env.clear();
+        self
+    }
+
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Command {
+        self.current_dir = Some(dir.as_ref().to_owned());
+        self
+    }
+
+    /// Runs the command in the working directory and the environment of the compilation, which
+    /// are not the ones of the process when the session has a logical environment.
+    pub fn session_env(&mut self, sess: &Session) -> &mut Command {
+        if let Some(env) = &sess.opts.logical_env {
+            self.env_clear();
+            for (key, value) in env {
+                self.env(key, value);
+            }
+        }
+        self.current_dir(sess.opts.working_dir.local_path_if_available())
+    }
+
     pub fn output(&mut self) -> io::Result<Output> {
         self.command().output()
     }
@@ -110,10 +144,16 @@ pub fn command(&self) -> process::Command {
             }
         };
         ret.args(&self.args);
+        if self.env_clear {
+            ret.env_clear();
+        }
         ret.envs(self.env.clone());
         for k in &self.env_remove {
             ret.env_remove(k);
         }
+        if let Some(dir) = &self.current_dir {
+            ret.current_dir(dir);
+        }
         ret
     }
 
@@ -143,7 +143,10 @@ pub fn link_binary<'a>(
                 }
             }
             if sess.opts.json_artifact_notifications {
-                sess.parse_sess.span_diagnostic.emit_artifact_notification(&out_filename, "link");
+                sess.parse_sess.span_diagnostic.emit_artifact_notification(
+                    &sess.opts.display_output_path(&out_filename),
+                    "link",
+                );
             }
 
             if sess.prof.enabled() {
@@ -1005,7 +1008,7 @@ fn is_illegal_instruction(_status: &ExitStatus) -> bool {
         // debug information. Note that this will read debug information from
         // the objects on the filesystem which we'll clean up later.
         SplitDebuginfo::Packed if sess.target.is_like_osx => {
-            let prog = Command::new("dsymutil").arg(out_filename).output();
+            let prog = Command::new("dsymutil").session_env(sess).arg(out_filename).output();
             match prog {
                 Ok(prog) => {
                     if !prog.status.success() {
@@ -1085,6 +1088,7 @@ fn strip_symbols_with_external_utility<'a>(
     option: Option<&str>,
 ) {
     let mut cmd = Command::new(util);
+    cmd.session_env(sess);
     if let Some(option) = option {
         cmd.arg(option);
     }
@@ -1703,7 +1707,7 @@ fn detect_self_contained_mingw(sess: &Session) -> bool {
     } else {
         linker
     };
-    for dir in env::split_paths(&env::var_os("PATH").unwrap_or_default()) {
+    for dir in env::split_paths(&sess.env_var_os("PATH").unwrap_or_default()) {
         let full_path = dir.join(&linker_with_extension);
         // If linker comes from sysroot assume self-contained mode
         if full_path.is_file() && !full_path.starts_with(&sess.sysroot) {
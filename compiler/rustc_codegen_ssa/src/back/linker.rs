@@ -70,6 +70,7 @@ pub fn get_linker<'a>(
             _ => Command::new(linker),
         },
     };
+    cmd.session_env(sess);
 
     // UWP apps have API restrictions enforced during Store submissions.
     // To comply with the Windows App Certification Kit,
@@ -121,7 +122,7 @@ pub fn get_linker<'a>(
     }
 
     if !msvc_changed_path {
-        if let Some(path) = env::var_os("PATH") {
+        if let Some(path) = sess.env_var_os("PATH") {
             new_path.extend(env::split_paths(&path));
         }
     }
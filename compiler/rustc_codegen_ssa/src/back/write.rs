@@ -182,8 +182,18 @@ macro_rules! if_regular {
                 sess.opts.cg.profile_generate.clone(),
                 SwitchWithOptPath::Disabled
             ),
-            pgo_use: if_regular!(sess.opts.cg.profile_use.clone(), None),
-            pgo_sample_use: if_regular!(sess.opts.unstable_opts.profile_sample_use.clone(), None),
+            pgo_use: if_regular!(
+                sess.opts.cg.profile_use.as_deref().map(|path| sess.opts.resolve_path(path)),
+                None
+            ),
+            pgo_sample_use: if_regular!(
+                sess.opts
+                    .unstable_opts
+                    .profile_sample_use
+                    .as_deref()
+                    .map(|path| sess.opts.resolve_path(path)),
+                None
+            ),
             debug_info_for_profiling: sess.opts.unstable_opts.debug_info_for_profiling,
             instrument_coverage: if_regular!(sess.instrument_coverage(), false),
             instrument_gcov: if_regular!(
@@ -618,7 +628,11 @@ fn produce_final_output_artifacts(
                 user_wants_objects = true;
                 copy_if_one_unit(OutputType::Object, true);
             }
-            OutputType::Mir | OutputType::Metadata | OutputType::Exe | OutputType::DepInfo => {}
+            OutputType::Mir
+            | OutputType::Metadata
+            | OutputType::Exe
+            | OutputType::DepInfo
+            | OutputType::StableMir => {}
         }
     }
 
@@ -1,12 +1,19 @@
+driver_impl_cant_emit_stable_mir = could not emit Stable MIR: {$error}
+
 driver_impl_ice = the compiler unexpectedly panicked. this is a bug.
 driver_impl_ice_bug_report = we would appreciate a bug report: {$bug_report_url}
 driver_impl_ice_bug_report_internal_feature = using internal features is not supported and expected to cause internal compiler errors when used incorrectly
 driver_impl_ice_exclude_cargo_defaults = some of the compiler flags provided by cargo are hidden
 
 driver_impl_ice_flags = compiler flags: {$flags}
+driver_impl_ice_module_not_inlined = the ICE couldn't be reduced, as the file of the module `{$name}` couldn't be inlined: {$error}
+driver_impl_ice_not_reproduced = the ICE couldn't be reduced, as compiling the crate again didn't reproduce it
 driver_impl_ice_path = please attach the file at `{$path}` to your bug report
 driver_impl_ice_path_error = the ICE couldn't be written to `{$path}`: {$error}
 driver_impl_ice_path_error_env = the environment variable `RUSTC_ICE` is set to `{$env_var}`
+driver_impl_ice_reduced = a reduced program that triggers the same ICE was written to `{$path}`
+driver_impl_ice_reduced_error = the reduced program couldn't be written to `{$path}`: {$error}
+driver_impl_ice_reducing = reducing the crate to a smaller program that triggers the same ICE
 driver_impl_ice_version = rustc {$version} running on {$triple}
 
 driver_impl_rlink_empty_version_number = The input does not contain version number
@@ -34,19 +34,24 @@
 use rustc_feature::find_gated_cfg;
 use rustc_fluent_macro::fluent_messages;
 use rustc_interface::util::{self, collect_crate_types, get_codegen_backend};
-use rustc_interface::{interface, Queries};
+use rustc_interface::{interface, Caches, Queries};
 use rustc_lint::{unerased_lint_store, LintStore};
 use rustc_metadata::locator;
+use rustc_middle::ty::TyCtxt;
 use rustc_session::config::{nightly_options, CG_OPTIONS, Z_OPTIONS};
-use rustc_session::config::{ErrorOutputType, Input, OutFileName, OutputType, TrimmedDefPaths};
-use rustc_session::cstore::MetadataLoader;
+use rustc_session::config::{
+    ErrorOutputType, Input, OutFileName, OutputType, PrintRequest, TrimmedDefPaths,
+};
+use rustc_session::cstore::{ExternCrateSource, MetadataLoader};
 use rustc_session::getopts::{self, Matches};
 use rustc_session::lint::{Lint, LintId};
 use rustc_session::{config, EarlyErrorHandler, Session};
+use rustc_span::def_id::LOCAL_CRATE;
 use rustc_span::source_map::{FileLoader, FileName};
-use rustc_span::symbol::sym;
+use rustc_span::symbol::{sym, Symbol};
 use rustc_target::json::ToJson;
 use rustc_target::spec::{Target, TargetTriple};
+use serde_json::json;
 
 use std::cmp::max;
 use std::collections::BTreeMap;
@@ -86,6 +91,9 @@
 pub mod pretty;
 #[macro_use]
 mod print;
+mod reduce_ice;
+#[cfg(unix)]
+mod server;
 mod session_diagnostics;
 #[cfg(all(unix, any(target_env = "gnu", target_os = "macos")))]
 mod signal_handler;
@@ -97,9 +105,29 @@ mod signal_handler {
     pub(super) fn install() {}
 }
 
+#[cfg(not(unix))]
+mod server {
+    use crate::Callbacks;
+    use rustc_errors::ErrorGuaranteed;
+    use rustc_session::EarlyErrorHandler;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    /// The server redirects the standard streams of the process with `dup2`,
+    /// which only exists on Unix.
+    pub(super) fn serve(
+        handler: &EarlyErrorHandler,
+        _socket: &str,
+        _callbacks: &mut (dyn Callbacks + Send),
+        _using_internal_features: Arc<AtomicBool>,
+    ) -> Result<(), ErrorGuaranteed> {
+        handler.early_error("`--server` is only supported on Unix platforms")
+    }
+}
+
 use crate::session_diagnostics::{
-    RLinkEmptyVersionNumber, RLinkEncodingVersionMismatch, RLinkRustcVersionMismatch,
-    RLinkWrongFileType, RlinkNotAFile, RlinkUnableToRead,
+    CantEmitStableMir, RLinkEmptyVersionNumber, RLinkEncodingVersionMismatch,
+    RLinkRustcVersionMismatch, RLinkWrongFileType, RlinkNotAFile, RlinkUnableToRead,
 };
 
 fluent_messages! { "../messages.ftl" }
@@ -223,6 +251,8 @@ pub struct RunCompiler<'a, 'b> {
     make_codegen_backend:
         Option<Box<dyn FnOnce(&config::Options) -> Box<dyn CodegenBackend> + Send>>,
     using_internal_features: Arc<std::sync::atomic::AtomicBool>,
+    working_dir: Option<PathBuf>,
+    caches: Option<Arc<Caches>>,
 }
 
 impl<'a, 'b> RunCompiler<'a, 'b> {
@@ -233,6 +263,8 @@ pub fn new(at_args: &'a [String], callbacks: &'b mut (dyn Callbacks + Send)) ->
             file_loader: None,
             make_codegen_backend: None,
             using_internal_features: Arc::default(),
+            working_dir: None,
+            caches: None,
         }
     }
 
@@ -273,6 +305,23 @@ pub fn set_using_internal_features(mut self, using_internal_features: Arc<Atomic
         self
     }
 
+    /// Run the compilation as if it was started in `working_dir`, without changing the working
+    /// directory of the process. Relative paths in the arguments are resolved against it, and
+    /// outputs are named the way they would be by a `rustc` process started there.
+    #[must_use]
+    pub fn set_working_dir(mut self, working_dir: PathBuf) -> Self {
+        self.working_dir = Some(working_dir);
+        self
+    }
+
+    /// Set the caches that the compilation reuses from earlier compilations of the process,
+    /// and fills in for later ones.
+    #[must_use]
+    pub fn set_caches(mut self, caches: Arc<Caches>) -> Self {
+        self.caches = Some(caches);
+        self
+    }
+
     /// Parse args and run the compiler.
     pub fn run(self) -> interface::Result<()> {
         run_compiler(
@@ -281,6 +330,8 @@ pub fn run(self) -> interface::Result<()> {
             self.file_loader,
             self.make_codegen_backend,
             self.using_internal_features,
+            self.working_dir,
+            self.caches,
         )
     }
 }
@@ -293,6 +344,8 @@ fn run_compiler(
         Box<dyn FnOnce(&config::Options) -> Box<dyn CodegenBackend> + Send>,
     >,
     using_internal_features: Arc<std::sync::atomic::AtomicBool>,
+    working_dir: Option<PathBuf>,
+    caches: Option<Arc<Caches>>,
 ) -> interface::Result<()> {
     let mut early_error_handler = EarlyErrorHandler::new(ErrorOutputType::default());
 
@@ -310,14 +363,29 @@ fn run_compiler(
 
     let Some(matches) = handle_options(&early_error_handler, &args) else { return Ok(()) };
 
-    let sopts = config::build_session_options(&mut early_error_handler, &matches);
+    if let Some(socket) = matches.opt_str("server") {
+        if file_loader.is_some() || make_codegen_backend.is_some() || working_dir.is_some() {
+            early_error_handler.early_error(
+                "`--server` can't be used with a custom file loader, codegen backend or working \
+                directory",
+            );
+        }
+        return server::serve(&early_error_handler, &socket, callbacks, using_internal_features);
+    }
+
+    let resolve_paths = working_dir.is_some();
+    let mut sopts =
+        config::build_session_options_in(&mut early_error_handler, &matches, working_dir);
 
     if let Some(ref code) = matches.opt_str("explain") {
         handle_explain(&early_error_handler, diagnostics_registry(), code, sopts.color);
         return Ok(());
     }
 
-    let (odir, ofile) = make_output(&matches);
+    let (mut odir, mut ofile) = make_output(&matches);
+    if resolve_paths {
+        sopts.resolve_output_paths(&mut odir, &mut ofile);
+    }
     let mut config = interface::Config {
         opts: sopts,
         crate_cfg: matches.opt_strs("cfg"),
@@ -337,6 +405,7 @@ fn run_compiler(
         registry: diagnostics_registry(),
         using_internal_features,
         expanded_args: args,
+        caches,
     };
 
     match make_input(&early_error_handler, &matches.free) {
@@ -393,13 +462,26 @@ fn run_compiler(
 
     early_error_handler.abort_if_errors();
 
+    if config.opts.unstable_opts.reduce_ice {
+        return reduce_ice::run_and_reduce(config, callbacks);
+    }
+
+    run_config(config, callbacks)
+}
+
+/// Runs the compiler on a fully built configuration, up to the point where the configuration
+/// and the callbacks ask it to stop.
+fn run_config(
+    config: interface::Config,
+    callbacks: &mut (dyn Callbacks + Send),
+) -> interface::Result<()> {
     interface::run_compiler(config, |compiler| {
         let sess = compiler.session();
         let handler = EarlyErrorHandler::new(sess.opts.error_format);
 
         let should_stop = print_crate_info(&handler, &**compiler.codegen_backend(), sess, true)
             .and_then(|| {
-                list_metadata(&handler, sess, &*compiler.codegen_backend().metadata_loader())
+                list_metadata(&handler, sess, &*compiler.metadata_loader())
             })
             .and_then(|| try_process_rlink(sess, compiler));
 
@@ -438,6 +520,18 @@ fn run_compiler(
                 return early_exit();
             }
 
+            if let Some(req) =
+                sess.opts.prints.iter().find(|req| req.kind == config::PrintKind::CrateInfo)
+            {
+                queries.global_ctxt()?.enter(|tcx| {
+                    tcx.resolver_for_lowering(());
+                    sess.compile_status()?;
+                    print_crate_info_json(tcx, req);
+                    Ok(())
+                })?;
+                return early_exit();
+            }
+
             if sess.opts.describe_lints {
                 queries
                     .global_ctxt()?
@@ -472,17 +566,27 @@ fn run_compiler(
                 return early_exit();
             }
 
+            if sess.opts.output_types.contains_key(&OutputType::StableMir) {
+                queries.global_ctxt()?.enter(|tcx| {
+                    if let Err(error) = rustc_smir::rustc_internal::json::emit_stable_mir(tcx) {
+                        sess.emit_err(CantEmitStableMir { error });
+                        sess.abort_if_errors();
+                    }
+                });
+            }
+
             let ongoing_codegen = queries.ongoing_codegen()?;
 
             if sess.opts.unstable_opts.print_type_sizes {
-                sess.code_stats.print_type_sizes();
+                sess.code_stats.print_type_sizes(sess.opts.unstable_opts.print_sizes_format);
             }
 
             if sess.opts.unstable_opts.print_vtable_sizes {
                 let crate_name =
                     compiler.session().opts.crate_name.as_deref().unwrap_or("<UNKNOWN_CRATE>");
 
-                sess.code_stats.print_vtable_sizes(crate_name);
+                sess.code_stats
+                    .print_vtable_sizes(crate_name, sess.opts.unstable_opts.print_sizes_format);
             }
 
             let linker = queries.linker(ongoing_codegen)?;
@@ -681,7 +785,7 @@ fn try_process_rlink(sess: &Session, compiler: &interface::Compiler) -> Compilat
     if sess.opts.unstable_opts.link_only {
         if let Input::File(file) = &sess.io.input {
             let outputs = compiler.build_output_filenames(sess, &[]);
-            let rlink_data = fs::read(file).unwrap_or_else(|err| {
+            let rlink_data = fs::read(sess.opts.resolve_path(file)).unwrap_or_else(|err| {
                 sess.emit_fatal(RlinkUnableToRead { err });
             });
             let codegen_results = match CodegenResults::deserialize_rlink(sess, rlink_data) {
@@ -727,9 +831,9 @@ fn list_metadata(
     if !ls_kinds.is_empty() {
         match sess.io.input {
             Input::File(ref ifile) => {
-                let path = &(*ifile);
+                let path = sess.opts.resolve_path(ifile);
                 let mut v = Vec::new();
-                locator::list_file_metadata(&sess.target, path, metadata_loader, &mut v, ls_kinds)
+                locator::list_file_metadata(&sess.target, &path, metadata_loader, &mut v, ls_kinds)
                     .unwrap();
                 safe_println!("{}", String::from_utf8(v).unwrap());
             }
@@ -759,7 +863,13 @@ fn print_crate_info(
 
     // NativeStaticLibs and LinkArgs are special - printed during linking
     // (empty iterator returns true)
-    if sess.opts.prints.iter().all(|p| p.kind == NativeStaticLibs || p.kind == LinkArgs) {
+    // CrateInfo is printed after expansion, see `print_crate_info_json`.
+    if sess
+        .opts
+        .prints
+        .iter()
+        .all(|p| p.kind == NativeStaticLibs || p.kind == LinkArgs || p.kind == CrateInfo)
+    {
         return Compilation::Continue;
     }
 
@@ -824,31 +934,15 @@ fn print_crate_info(
                 let id = rustc_session::output::find_crate_name(sess, attrs);
                 println_info!("{id}");
             }
+            // Printed after expansion, once the extern crates are known.
+            CrateInfo => {}
             Cfg => {
-                let mut cfgs = sess
-                    .parse_sess
-                    .config
-                    .iter()
-                    .filter_map(|&(name, value)| {
-                        // Note that crt-static is a specially recognized cfg
-                        // directive that's printed out here as part of
-                        // rust-lang/rust#37406, but in general the
-                        // `target_feature` cfg is gated under
-                        // rust-lang/rust#29717. For now this is just
-                        // specifically allowing the crt-static cfg and that's
-                        // it, this is intended to get into Cargo and then go
-                        // through to build scripts.
-                        if (name != sym::target_feature || value != Some(sym::crt_dash_static))
-                            && !sess.is_nightly_build()
-                            && find_gated_cfg(|cfg_sym| cfg_sym == name).is_some()
-                        {
-                            return None;
-                        }
-
+                let mut cfgs = printable_cfgs(sess)
+                    .map(|(name, value)| {
                         if let Some(value) = value {
-                            Some(format!("{name}=\"{value}\""))
+                            format!("{name}=\"{value}\"")
                         } else {
-                            Some(name.to_string())
+                            name.to_string()
                         }
                     })
                     .collect::<Vec<String>>();
@@ -899,7 +993,95 @@ fn print_crate_info(
 
         req.out.overwrite(&crate_info, sess);
     }
-    Compilation::Stop
+
+    if sess.opts.prints.iter().any(|p| p.kind == CrateInfo) {
+        Compilation::Continue
+    } else {
+        Compilation::Stop
+    }
+}
+
+/// The `cfg`s that `--print` reports, which are those set for the session
+/// minus the ones that are still feature gated.
+fn printable_cfgs(sess: &Session) -> impl Iterator<Item = (Symbol, Option<Symbol>)> + '_ {
+    sess.parse_sess.config.iter().copied().filter(|&(name, value)| {
+        // Note that crt-static is a specially recognized cfg
+        // directive that's printed out here as part of
+        // rust-lang/rust#37406, but in general the
+        // `target_feature` cfg is gated under
+        // rust-lang/rust#29717. For now this is just
+        // specifically allowing the crt-static cfg and that's
+        // it, this is intended to get into Cargo and then go
+        // through to build scripts.
+        (name == sym::target_feature && value == Some(sym::crt_dash_static))
+            || sess.is_nightly_build()
+            || find_gated_cfg(|cfg_sym| cfg_sym == name).is_none()
+    })
+}
+
+/// Prints `--print=crate-info`: everything a build system needs to know
+/// about the crate to schedule its compilation, as a single JSON object.
+///
+/// This runs after expansion, so that the extern crates that are listed are
+/// the ones that the crate actually refers to, including those that are
+/// only named by macros or injected by the compiler.
+fn print_crate_info_json(tcx: TyCtxt<'_>, req: &PrintRequest) {
+    let sess = tcx.sess;
+    let krate = &tcx.resolver_for_lowering(()).borrow().1;
+
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    let outputs = util::build_output_filenames(&krate.attrs, sess);
+    let crate_types = tcx.crate_types();
+    let file_names = crate_types
+        .iter()
+        .map(|&style| {
+            let fname =
+                rustc_session::output::filename_for_input(sess, style, crate_name, &outputs);
+            fname.as_path().file_name().unwrap().to_string_lossy().into_owned()
+        })
+        .collect::<Vec<_>>();
+
+    let mut cfgs = printable_cfgs(sess)
+        .map(|(name, value)| (name.to_string(), value.map(|value| value.to_string())))
+        .collect::<Vec<_>>();
+    cfgs.sort();
+    let cfgs = cfgs
+        .into_iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect::<Vec<_>>();
+
+    let externs = tcx
+        .crates(())
+        .iter()
+        .filter_map(|&cnum| {
+            let extern_crate = tcx.extern_crate(cnum.as_def_id())?;
+            if !extern_crate.is_direct() {
+                return None;
+            }
+            let name = tcx.crate_name(cnum);
+            let source = match extern_crate.src {
+                ExternCrateSource::Extern(_) => "extern-crate",
+                ExternCrateSource::Path => "path",
+            };
+            let path = tcx.used_crate_source(cnum).paths().next().cloned();
+            Some(json!({
+                "name": name.as_str(),
+                "source": source,
+                "from_extern_flag": sess.opts.externs.get(name.as_str()).is_some(),
+                "path": path,
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    let crate_info = json!({
+        "crate_name": crate_name.as_str(),
+        "crate_types": crate_types.iter().map(|ty| ty.to_string()).collect::<Vec<_>>(),
+        "edition": sess.edition().to_string(),
+        "file_names": file_names,
+        "cfg": cfgs,
+        "externs": externs,
+    });
+    req.out.overwrite(&format!("{}\n", serde_json::to_string_pretty(&crate_info).unwrap()), sess);
 }
 
 /// Prints version information
@@ -0,0 +1,651 @@
+//! `-Z reduce-ice`: when the compiler panics, shrinks the crate to a smaller program that still
+//! triggers the same internal compiler error, and writes it next to the ICE report.
+//!
+//! The files of the `mod foo;` items are first read through the file loader of the session and
+//! inlined into the crate root, so that the reduced program is a single self-contained file.
+//! The compiler is then run again in-process on variants of that program in which a single item
+//! is deleted, a single function body is replaced with `loop {}`, or a single statement is
+//! deleted. A variant is kept if it panics with the same message as the original program, and
+//! the reduction goes on until no variant of the current program reproduces the ICE anymore.
+
+use crate::session_diagnostics::{
+    IceModuleNotInlined, IceNotReproduced, IceReduced, IceReducedError, IceReducing,
+};
+use crate::{ice_path, run_config, Callbacks};
+use rustc_ast::mut_visit::{self, MutVisitor};
+use rustc_ast::ptr::P;
+use rustc_ast::{self as ast, token, DUMMY_NODE_ID};
+use rustc_ast_pretty::pprust;
+use rustc_data_structures::sync::Lrc;
+use rustc_errors::emitter::{Emitter, EmitterWriter};
+use rustc_errors::registry::Registry;
+use rustc_errors::translation::{to_fluent_args, Translate};
+use rustc_errors::{ColorConfig, Diagnostic, FluentBundle, Handler, LazyFallbackBundle, Level};
+use rustc_expand::module::{self, DirOwnership, ModError};
+use rustc_interface::interface;
+use rustc_session::config::{self, Input, OutputTypes};
+use rustc_session::parse::ParseSess;
+use rustc_span::source_map::{FileLoader, FileName, FilePathMapping, RealFileLoader, SourceMap};
+use rustc_span::symbol::{sym, Ident};
+use rustc_span::{SourceFileHashAlgorithm, DUMMY_SP};
+use smallvec::{smallvec, SmallVec};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::{env, fs, io, mem, process};
+use thin_vec::{thin_vec, ThinVec};
+
+/// Everything needed to configure the reduction runs of the compiler, as the original
+/// configuration is consumed by the first run.
+struct Setup {
+    opts: config::Options,
+    crate_cfg: Vec<String>,
+    crate_check_cfg: Vec<String>,
+    /// The path of the crate root, or `None` if its source isn't read from a file.
+    path: Option<PathBuf>,
+    name: FileName,
+    /// The file loader of the session, which the files of the modules are read with.
+    file_loader: Arc<dyn FileLoader + Send + Sync>,
+    locale_resources: &'static [&'static str],
+    registry: Registry,
+    using_internal_features: Arc<AtomicBool>,
+    expanded_args: Vec<String>,
+    /// The directory that the outputs of the reduction runs are written to.
+    output_dir: PathBuf,
+}
+
+/// Runs the compiler, and if it panics, reduces the crate before resuming the panic.
+pub(crate) fn run_and_reduce(
+    mut config: interface::Config,
+    callbacks: &mut (dyn Callbacks + Send),
+) -> interface::Result<()> {
+    let file_loader: Arc<dyn FileLoader + Send + Sync> =
+        Arc::from(config.file_loader.take().unwrap_or_else(|| Box::new(RealFileLoader)));
+    config.file_loader =
+        Some(Box::new(ReductionFileLoader { file_loader: file_loader.clone(), root: None }));
+    let (path, name, src) = match &config.input {
+        Input::File(path) => {
+            (Some(path.clone()), FileName::from(path.clone()), file_loader.read_file(path).ok())
+        }
+        Input::Str { name, input } => (None, name.clone(), Some(input.clone())),
+    };
+    let setup = Setup {
+        opts: config.opts.clone(),
+        crate_cfg: config.crate_cfg.clone(),
+        crate_check_cfg: config.crate_check_cfg.clone(),
+        path,
+        name,
+        file_loader,
+        locale_resources: config.locale_resources,
+        registry: config.registry.clone(),
+        using_internal_features: config.using_internal_features.clone(),
+        expanded_args: config.expanded_args.clone(),
+        output_dir: env::temp_dir().join(format!("rustc-reduce-ice-{}", process::id())),
+    };
+
+    let payload = match panic::catch_unwind(AssertUnwindSafe(|| run_config(config, callbacks))) {
+        Ok(result) => return result,
+        Err(payload) => payload,
+    };
+    // Fatal errors unwind as well, but they aren't ICEs.
+    if let Some(src) = src
+        && !payload.is::<rustc_errors::FatalErrorMarker>()
+    {
+        reduce(&setup, callbacks, &src);
+    }
+    panic::resume_unwind(payload)
+}
+
+fn reduce(setup: &Setup, callbacks: &mut (dyn Callbacks + Send), src: &str) {
+    let fallback_bundle =
+        rustc_errors::fallback_fluent_bundle(crate::DEFAULT_LOCALE_RESOURCES.to_vec(), false);
+    let emitter = Box::new(EmitterWriter::stderr(ColorConfig::Auto, fallback_bundle));
+    let handler = Handler::with_emitter(emitter);
+    handler.emit_note(IceReducing);
+
+    let mut not_inlined = None;
+    let inlined = print_edited(setup, src, |sess, krate| {
+        let mut inliner = ModuleInliner::new(setup, sess);
+        inliner.visit_crate(krate);
+        not_inlined = inliner.not_inlined;
+        true
+    });
+    if let Some(not_inlined) = not_inlined {
+        handler.emit_note(not_inlined);
+        return;
+    }
+
+    // The ICE report has already been printed, the reduction runs must not print their own.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let reduced = inlined.and_then(|src| shrink(setup, callbacks, &src));
+    panic::set_hook(hook);
+    let _ = fs::remove_dir_all(&setup.output_dir);
+
+    let Some(reduced) = reduced else {
+        handler.emit_note(IceNotReproduced);
+        return;
+    };
+    let path = match ice_path() {
+        Some(path) => path.with_extension("rs"),
+        None => PathBuf::from("rustc-ice-reduced.rs"),
+    };
+    match fs::write(&path, reduced) {
+        Ok(()) => {
+            handler.emit_note(IceReduced { path });
+        }
+        Err(err) => handler.emit_warning(IceReducedError { path, error: err.to_string() }),
+    }
+}
+
+/// Returns the smallest variant of `src` found that triggers the same ICE, or `None` if
+/// compiling `src` again doesn't trigger an ICE.
+fn shrink(setup: &Setup, callbacks: &mut (dyn Callbacks + Send), src: &str) -> Option<String> {
+    // The variants are pretty-printed, so the ICE is first reproduced with the pretty-printed
+    // program, which the variants are then compared with.
+    let mut current = print_edited(setup, src, |_, _| true)?;
+    let signature = run_attempt(setup, callbacks, &current)?;
+
+    loop {
+        let mut progress = false;
+        for reduction in [Reduction::Items, Reduction::Bodies, Reduction::Statements] {
+            let mut target = 0;
+            while let Some(candidate) = print_edited(setup, &current, |_, krate| {
+                let mut reducer = Reducer { reduction, target, seen: 0 };
+                reducer.visit_crate(krate);
+                reducer.seen > target
+            }) {
+                if candidate != current
+                    && run_attempt(setup, callbacks, &candidate).as_ref() == Some(&signature)
+                {
+                    // The sites after the reduced one have moved up by one.
+                    current = candidate;
+                    progress = true;
+                } else {
+                    target += 1;
+                }
+            }
+        }
+        if !progress {
+            return Some(current);
+        }
+    }
+}
+
+/// Compiles `src` as the crate root, and returns a description of the ICE that it triggers,
+/// if any: the panic message, or the message of the last bug reported before panicking.
+fn run_attempt(setup: &Setup, callbacks: &mut (dyn Callbacks + Send), src: &str) -> Option<String> {
+    let mut opts = setup.opts.clone();
+    opts.unstable_opts.reduce_ice = false;
+    opts.incremental = None;
+    opts.prints.clear();
+    let output_types: Vec<_> = opts.output_types.keys().map(|&ty| (ty, None)).collect();
+    opts.output_types = OutputTypes::new(&output_types);
+    let flags = opts.unstable_opts.diagnostic_handler_flags(true);
+
+    let (input, root) = match &setup.path {
+        Some(path) => (Input::File(path.clone()), Some((path.clone(), src.to_owned()))),
+        None => (Input::Str { name: setup.name.clone(), input: src.to_owned() }, None),
+    };
+    let file_loader = ReductionFileLoader { file_loader: setup.file_loader.clone(), root };
+
+    let mut config = interface::Config {
+        opts,
+        crate_cfg: setup.crate_cfg.clone(),
+        crate_check_cfg: setup.crate_check_cfg.clone(),
+        input,
+        output_dir: Some(setup.output_dir.clone()),
+        output_file: None,
+        ice_file: None,
+        file_loader: Some(Box::new(file_loader)),
+        locale_resources: setup.locale_resources,
+        lint_caps: Default::default(),
+        parse_sess_created: None,
+        hash_untracked_state: None,
+        register_lints: None,
+        override_queries: None,
+        make_codegen_backend: None,
+        registry: setup.registry.clone(),
+        using_internal_features: setup.using_internal_features.clone(),
+        expanded_args: setup.expanded_args.clone(),
+        caches: None,
+    };
+    callbacks.config(&mut config);
+
+    let last_bug = Arc::new(Mutex::new(None));
+    let recorded_bug = last_bug.clone();
+    let locale_resources = setup.locale_resources;
+    let parse_sess_created = config.parse_sess_created.take();
+    config.parse_sess_created = Some(Box::new(move |parse_sess| {
+        if let Some(parse_sess_created) = parse_sess_created {
+            parse_sess_created(parse_sess);
+        }
+        let recorder = BugRecorder::new(locale_resources, recorded_bug);
+        parse_sess.span_diagnostic = Handler::with_emitter(Box::new(recorder)).with_flags(flags);
+    }));
+
+    let payload = panic::catch_unwind(AssertUnwindSafe(|| run_config(config, callbacks))).err()?;
+    if payload.is::<rustc_errors::FatalErrorMarker>() {
+        return None;
+    }
+    let message = match payload.downcast_ref::<String>() {
+        Some(message) => Some(message.clone()),
+        None => payload.downcast_ref::<&str>().map(|message| message.to_string()),
+    };
+    Some(message.or_else(|| last_bug.lock().unwrap().take()).unwrap_or_default())
+}
+
+/// Parses `src` as a crate root, applies `edit` to it, and prints it back. Returns `None` if
+/// `src` doesn't parse, or if `edit` returns `false` as it had nothing to edit.
+fn print_edited(
+    setup: &Setup,
+    src: &str,
+    edit: impl FnOnce(&ParseSess, &mut ast::Crate) -> bool,
+) -> Option<String> {
+    let edition = setup.opts.edition;
+    rustc_span::create_session_globals_then(edition, || {
+        // Nothing reads the bugs of the parser, they would be ICEs of the parser itself.
+        let recorder = BugRecorder::new(&[], Arc::new(Mutex::new(None)));
+        let file_loader =
+            ReductionFileLoader { file_loader: setup.file_loader.clone(), root: None };
+        let sess = ParseSess::with_span_handler(
+            Handler::with_emitter(Box::new(recorder)),
+            Lrc::new(SourceMap::with_file_loader_and_hash_kind(
+                Box::new(file_loader),
+                FilePathMapping::empty(),
+                SourceFileHashAlgorithm::Md5,
+            )),
+        );
+        let mut krate =
+            rustc_parse::parse_crate_from_source_str(setup.name.clone(), src.to_owned(), &sess)
+                .map_err(|err| err.cancel())
+                .ok()?;
+        if sess.span_diagnostic.has_errors().is_some() {
+            return None;
+        }
+
+        if !edit(&sess, &mut krate) {
+            return None;
+        }
+
+        // Comments aren't kept, as they can't be attached to the nodes of the AST.
+        Some(pprust::print_crate(
+            sess.source_map(),
+            &krate,
+            setup.name.clone(),
+            String::new(),
+            &NoAnn,
+            false,
+            edition,
+            &sess.attr_id_generator,
+        ))
+    })
+}
+
+struct NoAnn;
+
+impl pprust::PpAnn for NoAnn {}
+
+/// The file loader of the session, shared by all the runs of the compiler. In the reduction
+/// runs, the crate root is replaced with the program to try.
+struct ReductionFileLoader {
+    file_loader: Arc<dyn FileLoader + Send + Sync>,
+    root: Option<(PathBuf, String)>,
+}
+
+impl ReductionFileLoader {
+    fn root(&self, path: &Path) -> Option<&str> {
+        self.root.as_ref().filter(|(root, _)| root == path).map(|(_, src)| &src[..])
+    }
+}
+
+impl FileLoader for ReductionFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.root(path).is_some() || self.file_loader.file_exists(path)
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        match self.root(path) {
+            Some(src) => Ok(src.to_owned()),
+            None => self.file_loader.read_file(path),
+        }
+    }
+
+    fn read_binary_file(&self, path: &Path) -> io::Result<Lrc<[u8]>> {
+        match self.root(path) {
+            Some(src) => Ok(Lrc::from(src.as_bytes())),
+            None => self.file_loader.read_binary_file(path),
+        }
+    }
+
+    fn is_virtual_file(&self, path: &Path) -> bool {
+        self.root(path).is_some() || self.file_loader.is_virtual_file(path)
+    }
+}
+
+/// Replaces the `mod foo;` items with inline modules that hold the contents of their files, like
+/// the expansion of the crate loads them, so that the reduced program is self-contained.
+struct ModuleInliner<'a> {
+    sess: &'a ParseSess,
+    file_loader: &'a dyn FileLoader,
+    /// The directory that the files of the modules declared in the current module are in.
+    dir_path: PathBuf,
+    dir_ownership: DirOwnership,
+    /// The files of the current module and of its parents, to detect circular modules.
+    file_paths: Vec<PathBuf>,
+    /// The first module whose file couldn't be loaded, if any.
+    not_inlined: Option<IceModuleNotInlined>,
+}
+
+impl<'a> ModuleInliner<'a> {
+    fn new(setup: &'a Setup, sess: &'a ParseSess) -> ModuleInliner<'a> {
+        let root = setup.path.clone().unwrap_or_default();
+        ModuleInliner {
+            sess,
+            file_loader: &*setup.file_loader,
+            dir_path: root.parent().unwrap_or(Path::new("")).to_owned(),
+            dir_ownership: DirOwnership::Owned { relative: None },
+            file_paths: vec![root],
+            not_inlined: None,
+        }
+    }
+
+    /// Finds and parses the file of the module `ident`, or describes why it couldn't.
+    fn load(&self, ident: Ident, attrs: &[ast::Attribute]) -> Result<LoadedModule, String> {
+        let path_attr = attrs.iter().find(|attr| attr.has_name(sym::path));
+        let (file_path, dir_ownership) = match path_attr.and_then(|attr| attr.value_str()) {
+            // Like in `rustc_expand`, `#[path]` files are treated as `mod.rs` files.
+            Some(path) => {
+                #[cfg(windows)]
+                let path = path.as_str().replace("/", "\\");
+                (self.dir_path.join(path.as_str()), DirOwnership::Owned { relative: None })
+            }
+            None if path_attr.is_some() => return Err("its `#[path]` isn't a string".to_owned()),
+            None => {
+                let relative = match self.dir_ownership {
+                    DirOwnership::Owned { relative } => relative,
+                    DirOwnership::UnownedViaBlock => {
+                        return Err("it is declared in a block".to_owned());
+                    }
+                };
+                match module::default_submod_path(self.sess, ident, relative, &self.dir_path) {
+                    Ok(success) => (success.file_path, success.dir_ownership),
+                    Err(ModError::FileNotFound(_, default_path, secondary_path)) => {
+                        return Err(format!(
+                            "neither `{}` nor `{}` exist",
+                            default_path.display(),
+                            secondary_path.display()
+                        ));
+                    }
+                    Err(ModError::MultipleCandidates(_, default_path, secondary_path)) => {
+                        return Err(format!(
+                            "both `{}` and `{}` exist",
+                            default_path.display(),
+                            secondary_path.display()
+                        ));
+                    }
+                    Err(_) => return Err("its file couldn't be found".to_owned()),
+                }
+            }
+        };
+        if self.file_paths.contains(&file_path) {
+            return Err(format!("`{}` is already being inlined", file_path.display()));
+        }
+
+        let src = self
+            .file_loader
+            .read_file(&file_path)
+            .map_err(|err| format!("`{}` couldn't be read: {err}", file_path.display()))?;
+        let doesnt_parse = || format!("`{}` doesn't parse", file_path.display());
+        let mut parser =
+            rustc_parse::maybe_new_parser_from_source_str(self.sess, file_path.clone().into(), src)
+                .map_err(|_| doesnt_parse())?;
+        let (attrs, items, spans) = parser.parse_mod(&token::Eof).map_err(|err| {
+            err.cancel();
+            doesnt_parse()
+        })?;
+        if self.sess.span_diagnostic.has_errors().is_some() {
+            return Err(doesnt_parse());
+        }
+        Ok(LoadedModule { attrs, items, spans, file_path, dir_ownership })
+    }
+}
+
+/// The contents of the file of a module.
+struct LoadedModule {
+    /// The inner attributes of the module.
+    attrs: ast::AttrVec,
+    items: ThinVec<P<ast::Item>>,
+    spans: ast::ModSpans,
+    file_path: PathBuf,
+    /// The ownership of the directory of the modules declared in the file.
+    dir_ownership: DirOwnership,
+}
+
+impl MutVisitor for ModuleInliner<'_> {
+    fn flat_map_item(&mut self, mut item: P<ast::Item>) -> SmallVec<[P<ast::Item>; 1]> {
+        if self.not_inlined.is_some() {
+            return smallvec![item];
+        }
+        let ast::Item { attrs, ident, kind: ast::ItemKind::Mod(_, mod_kind), .. } = &mut *item
+        else {
+            return mut_visit::noop_flat_map_item(item, self);
+        };
+
+        let (dir_path, dir_ownership, file_path) = match mod_kind {
+            ast::ModKind::Unloaded => match self.load(*ident, attrs) {
+                Ok(module) => {
+                    *mod_kind = ast::ModKind::Loaded(module.items, ast::Inline::Yes, module.spans);
+                    // The `#[path]` of an inline module names a directory, not a file.
+                    attrs.retain(|attr| !attr.has_name(sym::path));
+                    attrs.extend(module.attrs);
+                    let file_path = module.file_path;
+                    let dir_path = file_path.parent().unwrap_or(&file_path).to_owned();
+                    (dir_path, module.dir_ownership, Some(file_path))
+                }
+                // The file of a module that is configured out is never loaded.
+                Err(_) if attrs.iter().any(|attr| attr.has_name(sym::cfg)) => {
+                    return smallvec![item];
+                }
+                Err(error) => {
+                    let name = ident.to_string();
+                    self.not_inlined = Some(IceModuleNotInlined { name, error });
+                    return smallvec![item];
+                }
+            },
+            ast::ModKind::Loaded(..) => {
+                let path_attr = attrs.iter().find(|attr| attr.has_name(sym::path));
+                match path_attr.and_then(|attr| attr.value_str()) {
+                    Some(path) => {
+                        let dir_ownership = DirOwnership::Owned { relative: None };
+                        (self.dir_path.join(path.as_str()), dir_ownership, None)
+                    }
+                    None => {
+                        let mut dir_path = self.dir_path.clone();
+                        let mut dir_ownership = self.dir_ownership;
+                        if let DirOwnership::Owned { relative } = &mut dir_ownership
+                            && let Some(relative) = relative.take()
+                        {
+                            dir_path.push(relative.as_str());
+                        }
+                        dir_path.push(ident.as_str());
+                        (dir_path, dir_ownership, None)
+                    }
+                }
+            }
+        };
+
+        let dir_path = mem::replace(&mut self.dir_path, dir_path);
+        let dir_ownership = mem::replace(&mut self.dir_ownership, dir_ownership);
+        let inlined = file_path.is_some();
+        self.file_paths.extend(file_path);
+        let items = mut_visit::noop_flat_map_item(item, self);
+        if inlined {
+            self.file_paths.pop();
+        }
+        self.dir_path = dir_path;
+        self.dir_ownership = dir_ownership;
+        items
+    }
+
+    fn visit_block(&mut self, block: &mut P<ast::Block>) {
+        let dir_ownership = mem::replace(&mut self.dir_ownership, DirOwnership::UnownedViaBlock);
+        mut_visit::noop_visit_block(block, self);
+        self.dir_ownership = dir_ownership;
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Reduction {
+    /// Deletes an item, including associated and foreign items.
+    Items,
+    /// Replaces the body of a function with `{ loop {} }`.
+    Bodies,
+    /// Deletes a statement.
+    Statements,
+}
+
+/// Applies a reduction to the `target`-th site that it applies to, in source order.
+struct Reducer {
+    reduction: Reduction,
+    target: usize,
+    /// The number of sites of `reduction` visited so far.
+    seen: usize,
+}
+
+impl Reducer {
+    /// Counts a site of `reduction`, and returns whether it is the one to reduce.
+    fn is_target(&mut self, reduction: Reduction) -> bool {
+        if reduction != self.reduction {
+            return false;
+        }
+        self.seen += 1;
+        self.seen - 1 == self.target
+    }
+
+    fn reduce_fn(&mut self, kind: &mut ast::Fn) {
+        if kind.body.as_ref().is_some_and(|body| !body.stmts.is_empty())
+            && self.is_target(Reduction::Bodies)
+        {
+            kind.body = Some(loop_body());
+        }
+    }
+
+    fn flat_map_assoc_item(
+        &mut self,
+        mut item: P<ast::AssocItem>,
+    ) -> SmallVec<[P<ast::AssocItem>; 1]> {
+        if self.is_target(Reduction::Items) {
+            return SmallVec::new();
+        }
+        if let ast::AssocItemKind::Fn(kind) = &mut item.kind {
+            self.reduce_fn(kind);
+        }
+        mut_visit::noop_flat_map_assoc_item(item, self)
+    }
+}
+
+impl MutVisitor for Reducer {
+    fn flat_map_item(&mut self, mut item: P<ast::Item>) -> SmallVec<[P<ast::Item>; 1]> {
+        if self.is_target(Reduction::Items) {
+            return SmallVec::new();
+        }
+        if let ast::ItemKind::Fn(kind) = &mut item.kind {
+            self.reduce_fn(kind);
+        }
+        mut_visit::noop_flat_map_item(item, self)
+    }
+
+    fn flat_map_trait_item(&mut self, item: P<ast::AssocItem>) -> SmallVec<[P<ast::AssocItem>; 1]> {
+        self.flat_map_assoc_item(item)
+    }
+
+    fn flat_map_impl_item(&mut self, item: P<ast::AssocItem>) -> SmallVec<[P<ast::AssocItem>; 1]> {
+        self.flat_map_assoc_item(item)
+    }
+
+    fn flat_map_foreign_item(
+        &mut self,
+        item: P<ast::ForeignItem>,
+    ) -> SmallVec<[P<ast::ForeignItem>; 1]> {
+        if self.is_target(Reduction::Items) {
+            return SmallVec::new();
+        }
+        mut_visit::noop_flat_map_foreign_item(item, self)
+    }
+
+    fn flat_map_stmt(&mut self, stmt: ast::Stmt) -> SmallVec<[ast::Stmt; 1]> {
+        if self.is_target(Reduction::Statements) {
+            return SmallVec::new();
+        }
+        mut_visit::noop_flat_map_stmt(stmt, self)
+    }
+}
+
+/// The body `{ loop {} }`, whose type is `!`.
+fn loop_body() -> P<ast::Block> {
+    let block = |stmts| {
+        P(ast::Block {
+            stmts,
+            id: DUMMY_NODE_ID,
+            rules: ast::BlockCheckMode::Default,
+            span: DUMMY_SP,
+            tokens: None,
+            could_be_bare_literal: false,
+        })
+    };
+    let expr = P(ast::Expr {
+        id: DUMMY_NODE_ID,
+        kind: ast::ExprKind::Loop(block(ThinVec::new()), None, DUMMY_SP),
+        span: DUMMY_SP,
+        attrs: ast::AttrVec::new(),
+        tokens: None,
+    });
+    block(thin_vec![ast::Stmt {
+        id: DUMMY_NODE_ID,
+        kind: ast::StmtKind::Expr(expr),
+        span: DUMMY_SP
+    }])
+}
+
+/// An emitter that discards all diagnostics, but records the message of the last bug.
+struct BugRecorder {
+    fallback_bundle: LazyFallbackBundle,
+    last_bug: Arc<Mutex<Option<String>>>,
+}
+
+impl BugRecorder {
+    fn new(
+        locale_resources: &'static [&'static str],
+        last_bug: Arc<Mutex<Option<String>>>,
+    ) -> BugRecorder {
+        let fallback_bundle =
+            rustc_errors::fallback_fluent_bundle(locale_resources.to_vec(), false);
+        BugRecorder { fallback_bundle, last_bug }
+    }
+}
+
+impl Translate for BugRecorder {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        None
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &self.fallback_bundle
+    }
+}
+
+impl Emitter for BugRecorder {
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        None
+    }
+
+    fn emit_diagnostic(&mut self, diag: &Diagnostic) {
+        if diag.level == Level::Bug {
+            let args = to_fluent_args(diag.args());
+            let message = self.translate_messages(&diag.message, &args).into_owned();
+            *self.last_bug.lock().unwrap() = Some(message);
+        }
+    }
+}
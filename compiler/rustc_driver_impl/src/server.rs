@@ -0,0 +1,348 @@
+//! `rustc --server`: run compilations requested over a Unix domain socket.
+//!
+//! The server accepts one connection at a time. Each connection carries a
+//! single request, written as one line of JSON:
+//!
+//! ```json
+//! {"args": ["--crate-type=lib", "src/lib.rs"], "cwd": "/path/to/crate", "env": {"PATH": "..."}}
+//! ```
+//!
+//! `args` doesn't include the name of the binary. `cwd` is the absolute path
+//! of the directory that the compilation runs in, as if a separate `rustc`
+//! process was started there, and `env` is its environment. Neither replaces
+//! the working directory or the environment of the server, which other threads
+//! may be reading at any time. Instead, the relative paths of the options are
+//! resolved against `cwd` once they are parsed, source files are read through
+//! a file loader that resolves them against `cwd`, outputs are named in dep-info
+//! files the way they were given, and `env` is what `env!` and `option_env!`
+//! read and what the linker and other child processes run with. The few
+//! variables that the compiler reads from the environment of the process, like
+//! `RUSTC_BOOTSTRAP`, must have the values that the server was started with.
+//!
+//! The response is one line of JSON, followed by the raw bytes that the
+//! compilation wrote to stdout and stderr:
+//!
+//! ```json
+//! {"exit_code": 0, "stdout": 0, "stderr": 1234}
+//! ```
+//!
+//! A request that can't be parsed gets `{"error": "..."}` instead.
+//!
+//! Requests are run one after the other in the same process. Besides the cost
+//! of starting `rustc`, loading the codegen backend and initializing LLVM, this
+//! saves reading again what a compilation left for the next ones in
+//! [`Caches`]: the crate metadata of rlibs and dylibs, and the dep graph and the
+//! query result cache of incremental directories. Everything that is interned
+//! in a `TyCtxt` belongs to its session and is built again for every request.
+//! After responding to a request, the server decodes the files that it wrote to
+//! its incremental directory while it waits for the next one.
+//!
+//! A few things are set up once for the whole process, from the environment of
+//! the server or by the first request that needs them: the jobserver, the
+//! codegen backend, LLVM plugins, and the dylibs of proc macros, which stay
+//! loaded, so that a proc macro rebuilt at the same path needs a new server.
+
+use crate::{catch_with_exit_code, Callbacks, Compilation, RunCompiler};
+use rustc_data_structures::sync::Lrc;
+use rustc_errors::ErrorGuaranteed;
+use rustc_interface::{interface, Caches, Queries};
+use rustc_session::EarlyErrorHandler;
+use rustc_span::source_map::{FileLoader, RealFileLoader};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::fd::{FromRawFd, RawFd};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// Exit status code reported for requests that ended with an ICE, matching the
+/// status of a `rustc` process that panicked.
+const EXIT_ICE: i32 = 101;
+
+/// The environment variables that the compiler reads from the environment of
+/// the process rather than from the environment of the session.
+const PROCESS_ENV_VARS: &[&str] = &[
+    "RUSTC_BOOTSTRAP",
+    "RUST_TARGET_PATH",
+    "SDKROOT",
+    "MACOSX_DEPLOYMENT_TARGET",
+    "IPHONEOS_DEPLOYMENT_TARGET",
+    "TVOS_DEPLOYMENT_TARGET",
+    "WATCHOS_DEPLOYMENT_TARGET",
+];
+
+struct Request {
+    args: Vec<String>,
+    cwd: PathBuf,
+    env: BTreeMap<String, String>,
+}
+
+pub(crate) fn serve(
+    handler: &EarlyErrorHandler,
+    socket: &str,
+    callbacks: &mut (dyn Callbacks + Send),
+    using_internal_features: Arc<AtomicBool>,
+) -> Result<(), ErrorGuaranteed> {
+    let listener = UnixListener::bind(socket).unwrap_or_else(|err| {
+        handler.early_error(format!("failed to listen on `{socket}`: {err}"))
+    });
+
+    let caches = Arc::new(Caches::default());
+    for stream in listener.incoming() {
+        let stream = stream.unwrap_or_else(|err| {
+            handler.early_error(format!("failed to accept a connection on `{socket}`: {err}"))
+        });
+        // A client that went away before reading its response shouldn't bring
+        // the server down.
+        let _ = handle_connection(stream, callbacks, &using_internal_features, &caches);
+        caches.prepare_next_compilation();
+    }
+
+    Ok(())
+}
+
+fn handle_connection(
+    stream: UnixStream,
+    callbacks: &mut (dyn Callbacks + Send),
+    using_internal_features: &Arc<AtomicBool>,
+    caches: &Arc<Caches>,
+) -> io::Result<()> {
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let mut stream = stream;
+
+    let request = match parse_request(&line) {
+        Ok(request) => request,
+        Err(error) => return writeln!(stream, "{}", json!({ "error": error })),
+    };
+
+    let (exit_code, stdout, stderr) =
+        run_request(request, callbacks, using_internal_features, caches)?;
+    writeln!(
+        stream,
+        "{}",
+        json!({ "exit_code": exit_code, "stdout": stdout.len(), "stderr": stderr.len() })
+    )?;
+    stream.write_all(&stdout)?;
+    stream.write_all(&stderr)?;
+    stream.flush()
+}
+
+fn parse_request(line: &str) -> Result<Request, String> {
+    let value: Value =
+        serde_json::from_str(line).map_err(|err| format!("invalid request: {err}"))?;
+
+    let args = value["args"]
+        .as_array()
+        .and_then(|args| args.iter().map(|arg| Some(arg.as_str()?.to_owned())).collect())
+        .ok_or("`args` must be an array of strings")?;
+    let cwd = PathBuf::from(value["cwd"].as_str().ok_or("`cwd` must be a string")?);
+    if !cwd.is_absolute() {
+        return Err("`cwd` must be an absolute path".to_owned());
+    }
+    let env = value["env"]
+        .as_object()
+        .and_then(|env| {
+            env.iter().map(|(key, value)| Some((key.clone(), value.as_str()?.to_owned()))).collect()
+        })
+        .ok_or("`env` must be an object with string values")?;
+
+    Ok(Request { args, cwd, env })
+}
+
+/// Runs the compiler for `request` and returns its exit code, together with
+/// everything it wrote to stdout and stderr.
+fn run_request(
+    request: Request,
+    callbacks: &mut (dyn Callbacks + Send),
+    using_internal_features: &Arc<AtomicBool>,
+    caches: &Arc<Caches>,
+) -> io::Result<(i32, Vec<u8>, Vec<u8>)> {
+    if !request.cwd.is_dir() {
+        let stderr = format!("error: `{}` is not a directory\n", request.cwd.display());
+        return Ok((crate::EXIT_FAILURE, Vec::new(), stderr.into_bytes()));
+    }
+    let process_env_var = PROCESS_ENV_VARS
+        .iter()
+        .find(|&&var| request.env.get(var).map(String::as_str) != env::var(var).ok().as_deref());
+    if let Some(var) = process_env_var {
+        let stderr = format!(
+            "error: `{var}` must be set like it is in the environment of the server, since \
+            the compiler reads it from there\n"
+        );
+        return Ok((crate::EXIT_FAILURE, Vec::new(), stderr.into_bytes()));
+    }
+
+    let stdout = Capture::start(libc::STDOUT_FILENO)?;
+    let stderr = match Capture::start(libc::STDERR_FILENO) {
+        Ok(stderr) => stderr,
+        Err(err) => {
+            // Don't leave the output of the server redirected into the pipe.
+            let _ = stdout.finish();
+            return Err(err);
+        }
+    };
+
+    let Request { args, cwd, env } = request;
+    // Argument files are read before the options are parsed, so they are the
+    // only paths that have to be resolved up front.
+    let args: Vec<_> = ["rustc".to_owned()]
+        .into_iter()
+        .chain(args.into_iter().map(|arg| match arg.strip_prefix('@') {
+            Some(path) => format!("@{}", cwd.join(path).display()),
+            None => arg,
+        }))
+        .collect();
+    let mut callbacks = RequestCallbacks { callbacks, cwd: cwd.clone(), env };
+    using_internal_features.store(false, Ordering::Relaxed);
+    // An ICE has already been reported by the panic hook when it gets here, so
+    // all that is left to do is to keep the server alive.
+    let exit_code = panic::catch_unwind(AssertUnwindSafe(|| {
+        catch_with_exit_code(|| {
+            RunCompiler::new(&args, &mut callbacks)
+                .set_using_internal_features(using_internal_features.clone())
+                .set_working_dir(cwd)
+                .set_caches(caches.clone())
+                .run()
+        })
+    }))
+    .unwrap_or(EXIT_ICE);
+
+    io::stdout().flush()?;
+    let stdout = stdout.finish()?;
+    let stderr = stderr.finish()?;
+
+    Ok((exit_code, stdout, stderr))
+}
+
+/// Wraps the callbacks of the server to hand the environment of a request to
+/// its session, and to read its source files from its working directory.
+struct RequestCallbacks<'a> {
+    callbacks: &'a mut (dyn Callbacks + Send),
+    cwd: PathBuf,
+    env: BTreeMap<String, String>,
+}
+
+impl Callbacks for RequestCallbacks<'_> {
+    fn config(&mut self, config: &mut interface::Config) {
+        self.callbacks.config(config);
+        config.opts.logical_env = Some(std::mem::take(&mut self.env));
+
+        let file_loader = config.file_loader.take().unwrap_or_else(|| Box::new(RealFileLoader));
+        config.file_loader =
+            Some(Box::new(WorkingDirFileLoader { file_loader, working_dir: self.cwd.clone() }));
+    }
+
+    fn after_crate_root_parsing<'tcx>(
+        &mut self,
+        compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        self.callbacks.after_crate_root_parsing(compiler, queries)
+    }
+
+    fn after_expansion<'tcx>(
+        &mut self,
+        compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        self.callbacks.after_expansion(compiler, queries)
+    }
+
+    fn after_analysis<'tcx>(
+        &mut self,
+        compiler: &interface::Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        self.callbacks.after_analysis(compiler, queries)
+    }
+}
+
+/// Resolves relative paths against the working directory of a request before
+/// handing them to another file loader.
+struct WorkingDirFileLoader {
+    file_loader: Box<dyn FileLoader + Send + Sync>,
+    working_dir: PathBuf,
+}
+
+impl FileLoader for WorkingDirFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.file_loader.file_exists(&self.working_dir.join(path))
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        self.file_loader.read_file(&self.working_dir.join(path))
+    }
+
+    fn read_binary_file(&self, path: &Path) -> io::Result<Lrc<[u8]>> {
+        self.file_loader.read_binary_file(&self.working_dir.join(path))
+    }
+
+    fn is_virtual_file(&self, path: &Path) -> bool {
+        self.file_loader.is_virtual_file(&self.working_dir.join(path))
+    }
+}
+
+/// Redirects a file descriptor of the process into a pipe, and collects
+/// everything that is written to it until the capture is finished.
+///
+/// This catches all output, including output of the codegen backend and of
+/// child processes such as the linker, which an in-process hook wouldn't.
+struct Capture {
+    fd: RawFd,
+    saved_fd: RawFd,
+    reader: JoinHandle<io::Result<Vec<u8>>>,
+}
+
+impl Capture {
+    fn start(fd: RawFd) -> io::Result<Capture> {
+        let mut pipe = [0; 2];
+        // SAFETY: `pipe` points to the two writable `c_int`s that `pipe` fills in.
+        if unsafe { libc::pipe(pipe.as_mut_ptr()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let [read_fd, write_fd] = pipe;
+
+        // SAFETY: `dup` and `dup2` only take file descriptors by value and
+        // report invalid ones through their return value. `fd` is one of the
+        // standard streams, which nothing in the process closes, and the
+        // redirection is undone in `finish`.
+        let saved_fd = unsafe { libc::dup(fd) };
+        if saved_fd < 0 || unsafe { libc::dup2(write_fd, fd) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `write_fd` was created by `pipe` above, isn't owned by
+        // anything else, and `fd` now refers to the same pipe.
+        unsafe { libc::close(write_fd) };
+
+        // SAFETY: `read_fd` was just created by `pipe` and isn't owned by anything else.
+        let mut read_end = unsafe { File::from_raw_fd(read_fd) };
+        let reader = thread::spawn(move || {
+            let mut output = Vec::new();
+            read_end.read_to_end(&mut output)?;
+            Ok(output)
+        });
+
+        Ok(Capture { fd, saved_fd, reader })
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        // Restoring the file descriptor closes the last write end of the pipe,
+        // which lets the reader see the end of the output.
+        // SAFETY: `saved_fd` was created by `dup` in `start` and is only used
+        // by this capture, which is consumed here.
+        if unsafe { libc::dup2(self.saved_fd, self.fd) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: as above, nothing else owns `saved_fd`, and `fd` now refers
+        // to the same file.
+        unsafe { libc::close(self.saved_fd) };
+        self.reader.join().unwrap()
+    }
+}
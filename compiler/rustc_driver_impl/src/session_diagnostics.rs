@@ -1,5 +1,11 @@
 use rustc_macros::{Diagnostic, Subdiagnostic};
 
+#[derive(Diagnostic)]
+#[diag(driver_impl_cant_emit_stable_mir)]
+pub(crate) struct CantEmitStableMir {
+    pub error: std::io::Error,
+}
+
 #[derive(Diagnostic)]
 #[diag(driver_impl_rlink_unable_to_read)]
 pub(crate) struct RlinkUnableToRead {
@@ -83,3 +89,31 @@ pub(crate) struct IceFlags {
 #[derive(Diagnostic)]
 #[diag(driver_impl_ice_exclude_cargo_defaults)]
 pub(crate) struct IceExcludeCargoDefaults;
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_ice_reducing)]
+pub(crate) struct IceReducing;
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_ice_reduced)]
+pub(crate) struct IceReduced {
+    pub path: std::path::PathBuf,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_ice_reduced_error)]
+pub(crate) struct IceReducedError {
+    pub path: std::path::PathBuf,
+    pub error: String,
+}
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_ice_not_reproduced)]
+pub(crate) struct IceNotReproduced;
+
+#[derive(Diagnostic)]
+#[diag(driver_impl_ice_module_not_inlined)]
+pub(crate) struct IceModuleNotInlined {
+    pub name: String,
+    pub error: String,
+}
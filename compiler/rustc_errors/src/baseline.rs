@@ -0,0 +1,162 @@
+//! Diagnostic baselines, for `-Z diagnostic-baseline`.
+//!
+//! A baseline is a file listing lint diagnostics that are known and accepted, so that a new lint
+//! can be enabled on a large codebase without fixing or `#[allow]`ing every existing occurrence
+//! first. Diagnostics that match an entry of the baseline are suppressed, and everything else is
+//! emitted as usual. With `-Z write-baseline`, the baseline is recorded instead: all lint
+//! diagnostics are emitted, and written to the baseline file at the end of the session.
+//!
+//! An entry identifies a diagnostic by the name of its lint, the file of its primary span, and a
+//! fingerprint of its message and of the source code under the primary span. The fingerprint
+//! ignores line numbers and whitespace, so that entries keep matching when unrelated code is
+//! added to the file, or when the code is reformatted.
+
+use crate::emitter::Emitter;
+use crate::translation::{to_fluent_args, Translate};
+use crate::{Diagnostic, DiagnosticId};
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::stable_hasher::{Hash128, StableHasher};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// The version of the baseline file format.
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+struct BaselineEntry {
+    lint: String,
+    file: String,
+    fingerprint: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineFileEntry {
+    #[serde(flatten)]
+    entry: BaselineEntry,
+    /// How many diagnostics with this entry there are.
+    count: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    entries: Vec<BaselineFileEntry>,
+}
+
+pub struct DiagnosticBaseline {
+    path: PathBuf,
+    /// Whether the baseline is being recorded rather than applied.
+    record: bool,
+    /// When applying the baseline, the number of diagnostics of each entry that are still left to
+    /// be suppressed. When recording it, the number of diagnostics of each entry seen so far.
+    entries: FxHashMap<BaselineEntry, usize>,
+}
+
+impl DiagnosticBaseline {
+    /// Reads the baseline at `path`, to suppress the diagnostics it lists.
+    pub fn load(path: &Path) -> io::Result<DiagnosticBaseline> {
+        let file: BaselineFile = serde_json::from_slice(&fs::read(path)?)?;
+        if file.version != FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported baseline version {}", file.version),
+            ));
+        }
+
+        let mut entries = FxHashMap::default();
+        for BaselineFileEntry { entry, count } in file.entries {
+            *entries.entry(entry).or_default() += count;
+        }
+        Ok(DiagnosticBaseline { path: path.to_owned(), record: false, entries })
+    }
+
+    /// Creates an empty baseline that records the diagnostics of this session, to be written to
+    /// `path` by [`DiagnosticBaseline::write`].
+    pub fn record(path: PathBuf) -> DiagnosticBaseline {
+        DiagnosticBaseline { path, record: true, entries: FxHashMap::default() }
+    }
+
+    /// Checks `diagnostic` against the baseline, and returns `true` if it must be suppressed.
+    pub(crate) fn suppress(&mut self, emitter: &dyn Emitter, diagnostic: &Diagnostic) -> bool {
+        let Some(entry) = BaselineEntry::new(emitter, diagnostic) else {
+            return false;
+        };
+
+        if self.record {
+            *self.entries.entry(entry).or_default() += 1;
+            return false;
+        }
+
+        match self.entries.get_mut(&entry) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Writes a recorded baseline to its file. Does nothing when the baseline is being applied.
+    pub(crate) fn write(&self) -> Result<(), String> {
+        if !self.record {
+            return Ok(());
+        }
+
+        let mut entries: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(entry, &count)| BaselineFileEntry { entry: entry.clone(), count })
+            .collect();
+        entries.sort_by(|a, b| a.entry.cmp(&b.entry));
+
+        let file = BaselineFile { version: FORMAT_VERSION, entries };
+        let mut contents = serde_json::to_string_pretty(&file).unwrap();
+        contents.push('\n');
+        fs::write(&self.path, contents).map_err(|err| {
+            format!("failed to write diagnostic baseline `{}`: {err}", self.path.display())
+        })
+    }
+}
+
+impl BaselineEntry {
+    /// Computes the entry of a diagnostic, or `None` for diagnostics that can't be part of a
+    /// baseline: those that don't come from a lint, or that don't point at any source code.
+    fn new(emitter: &dyn Emitter, diagnostic: &Diagnostic) -> Option<BaselineEntry> {
+        let Some(DiagnosticId::Lint { name, .. }) = &diagnostic.code else {
+            return None;
+        };
+        let sm = emitter.source_map()?;
+        let span = diagnostic.span.primary_span()?;
+        if span.is_dummy() {
+            return None;
+        }
+
+        let file = sm.filename_for_diagnostics(&sm.span_to_filename(span)).to_string();
+        let args = to_fluent_args(diagnostic.args());
+        let message = emitter.translate_messages(&diagnostic.message, &args);
+        let snippet = sm.span_to_snippet(span).unwrap_or_default();
+
+        let mut hasher = StableHasher::new();
+        name.hash(&mut hasher);
+        normalize_whitespace(&message).hash(&mut hasher);
+        normalize_whitespace(&snippet).hash(&mut hasher);
+        let fingerprint: Hash128 = hasher.finish();
+
+        Some(BaselineEntry {
+            lint: name.clone(),
+            file,
+            fingerprint: format!("{:032x}", fingerprint.as_u128()),
+        })
+    }
+}
+
+fn normalize_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
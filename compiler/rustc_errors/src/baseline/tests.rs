@@ -0,0 +1,117 @@
+use super::*;
+
+use crate::emitter::EmitterWriter;
+use crate::Level;
+use rustc_data_structures::sync::Lrc;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::{BytePos, Span};
+
+/// Computes the entries of lint diagnostics with the given `(lint, message, lo, hi)`, with their
+/// primary spans in a file called `test.rs` containing `code`.
+fn entries(code: &str, diagnostics: &[(&str, &str, u32, u32)]) -> Vec<Option<BaselineEntry>> {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+        let emitter =
+            EmitterWriter::new(Box::new(termcolor::NoColor::new(io::sink())), fallback_bundle)
+                .sm(Some(sm));
+
+        diagnostics
+            .iter()
+            .map(|&(lint, message, lo, hi)| {
+                let code = DiagnosticId::Lint {
+                    name: lint.to_owned(),
+                    has_future_breakage: false,
+                    is_force_warn: false,
+                };
+                let mut diagnostic =
+                    Diagnostic::new_with_code(Level::Warning(None), Some(code), message);
+                diagnostic.set_span(Span::with_root_ctxt(BytePos(lo), BytePos(hi)));
+                BaselineEntry::new(&emitter, &diagnostic)
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn entry_of_lint() {
+    let entries = entries("fn main() { let x = 1; }", &[("unused_variables", "unused", 16, 17)]);
+    let entry = entries[0].as_ref().unwrap();
+    assert_eq!(entry.lint, "unused_variables");
+    assert_eq!(entry.file, "test.rs");
+    assert_eq!(entry.fingerprint.len(), 32);
+}
+
+#[test]
+fn no_entry_without_lint_or_span() {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+        let emitter =
+            EmitterWriter::new(Box::new(termcolor::NoColor::new(io::sink())), fallback_bundle)
+                .sm(Some(sm));
+
+        // Not a lint.
+        let error = Diagnostic::new_with_code(
+            Level::Error { lint: false },
+            Some(DiagnosticId::Error("E0001".to_owned())),
+            "error",
+        );
+        assert!(BaselineEntry::new(&emitter, &error).is_none());
+
+        // A lint without a primary span.
+        let code = DiagnosticId::Lint {
+            name: "unused_variables".to_owned(),
+            has_future_breakage: false,
+            is_force_warn: false,
+        };
+        let lint = Diagnostic::new_with_code(Level::Warning(None), Some(code), "unused");
+        assert!(BaselineEntry::new(&emitter, &lint).is_none());
+    });
+}
+
+#[test]
+fn fingerprint_depends_on_lint_message_and_snippet() {
+    let code = "fn main() { let x = 1; let y = 1; }";
+    let entries = entries(
+        code,
+        &[
+            ("unused_variables", "unused", 16, 17),
+            ("unused_variables", "unused", 16, 17),
+            ("non_snake_case", "unused", 16, 17),
+            ("unused_variables", "unused variable", 16, 17),
+            ("unused_variables", "unused", 27, 28),
+        ],
+    );
+    let fingerprints: Vec<_> = entries.iter().map(|e| &e.as_ref().unwrap().fingerprint).collect();
+    assert_eq!(fingerprints[0], fingerprints[1]);
+    for other in &fingerprints[2..] {
+        assert_ne!(fingerprints[0], *other);
+    }
+}
+
+#[test]
+fn fingerprint_ignores_line_numbers_and_whitespace() {
+    let code = "fn f() {\n    g(1, 2);\n}\n\n\nfn h() {\n    g(1,\n      2);\n}\n";
+    let first = code.find("g(1, 2)").unwrap() as u32;
+    let second = code.rfind("g(1,").unwrap() as u32;
+    let second_end = code.rfind("2)").unwrap() as u32 + 2;
+    let entries = entries(
+        code,
+        &[
+            ("unused_must_use", "unused  result", first, first + 7),
+            ("unused_must_use", "unused\nresult", second, second_end),
+        ],
+    );
+    assert_eq!(entries[0], entries[1]);
+}
+
+#[test]
+fn normalize() {
+    assert_eq!(normalize_whitespace("  a \t b\n\nc  "), "a b c");
+    assert_eq!(normalize_whitespace("g(1,\n      2)"), "g(1, 2)");
+    assert_eq!(normalize_whitespace(""), "");
+}
@@ -10,6 +10,7 @@
 use rustc_span::source_map::SourceMap;
 use rustc_span::{FileLines, FileName, SourceFile, Span};
 
+use crate::file_uri::path_to_uri;
 use crate::snippet::{
     Annotation, AnnotationColumn, AnnotationType, Line, MultilineAnnotation, Style, StyledString,
 };
@@ -33,7 +34,7 @@
 use std::io::prelude::*;
 use std::io::{self, IsTerminal};
 use std::iter;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use termcolor::{Ansi, Buffer, BufferWriter, ColorChoice, ColorSpec, StandardStream};
 use termcolor::{Color, WriteColor};
 
@@ -46,6 +47,7 @@ pub enum HumanReadableErrorType {
     Default(ColorConfig),
     AnnotateSnippet(ColorConfig),
     Short(ColorConfig),
+    Unicode(ColorConfig),
 }
 
 impl HumanReadableErrorType {
@@ -55,6 +57,14 @@ pub fn unzip(self) -> (bool, ColorConfig) {
             HumanReadableErrorType::Default(cc) => (false, cc),
             HumanReadableErrorType::Short(cc) => (true, cc),
             HumanReadableErrorType::AnnotateSnippet(cc) => (false, cc),
+            HumanReadableErrorType::Unicode(cc) => (false, cc),
+        }
+    }
+
+    pub fn theme(self) -> OutputTheme {
+        match self {
+            HumanReadableErrorType::Unicode(_) => OutputTheme::Unicode,
+            _ => OutputTheme::Ascii,
         }
     }
     pub fn new_emitter(
@@ -67,10 +77,21 @@ pub fn new_emitter(
         if !dst.supports_color() && color {
             dst = Box::new(Ansi::new(dst));
         }
-        EmitterWriter::new(dst, fallback_bundle).short_message(short)
+        EmitterWriter::new(dst, fallback_bundle).short_message(short).theme(self.theme())
     }
 }
 
+/// The characters that `EmitterWriter` draws the margins and annotations of
+/// source code with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OutputTheme {
+    /// `-->`, `|`, `^^^` and `---`.
+    Ascii,
+    /// Unicode box drawing characters. When terminal URLs are enabled, file
+    /// paths are also hyperlinks to the files.
+    Unicode,
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Margin {
     /// The available whitespace in the left that can be consumed when centering.
@@ -639,6 +660,9 @@ pub struct EmitterWriter {
     macro_backtrace: bool,
     track_diagnostics: bool,
     terminal_url: TerminalUrl,
+    theme: OutputTheme,
+    /// The directory that relative paths in the source map are relative to.
+    working_dir: Option<PathBuf>,
 }
 
 #[derive(Debug)]
@@ -668,6 +692,8 @@ fn create(dst: Destination, fallback_bundle: LazyFallbackBundle) -> EmitterWrite
             macro_backtrace: false,
             track_diagnostics: false,
             terminal_url: TerminalUrl::No,
+            theme: OutputTheme::Ascii,
+            working_dir: None,
         }
     }
 
@@ -686,6 +712,121 @@ fn maybe_anonymized(&self, line_num: usize) -> Cow<'static, str> {
         }
     }
 
+    /// Makes `location`, which points into `file`, a hyperlink to the file, for terminals that
+    /// support OSC 8 hyperlinks.
+    fn file_link(&self, file: &FileName, location: String) -> String {
+        if self.theme != OutputTheme::Unicode || self.terminal_url != TerminalUrl::Yes {
+            return location;
+        }
+        let Some(path) = file.local_path() else {
+            return location;
+        };
+        let path = match &self.working_dir {
+            Some(working_dir) => working_dir.join(path),
+            None if path.is_absolute() => path.to_path_buf(),
+            None => return location,
+        };
+        let Some(path) = path.to_str() else {
+            return location;
+        };
+        let url = path_to_uri(path);
+        format!("\x1b]8;;{url}\x07{location}\x1b]8;;\x07")
+    }
+
+    /// The arrow in front of the location of the primary file.
+    fn file_start(&self) -> &'static str {
+        match self.theme {
+            OutputTheme::Ascii => "--> ",
+            OutputTheme::Unicode => " ╭▸ ",
+        }
+    }
+
+    /// The marker in front of the location of secondary files.
+    fn secondary_file_start(&self) -> &'static str {
+        match self.theme {
+            OutputTheme::Ascii => "::: ",
+            OutputTheme::Unicode => " ⸬  ",
+        }
+    }
+
+    fn col_separator(&self) -> char {
+        match self.theme {
+            OutputTheme::Ascii => '|',
+            OutputTheme::Unicode => '│',
+        }
+    }
+
+    /// The line that links a label, or a multiline span, to the code it points at.
+    fn vertical_line(&self) -> char {
+        match self.theme {
+            OutputTheme::Ascii => '|',
+            OutputTheme::Unicode => '│',
+        }
+    }
+
+    fn horizontal_line(&self) -> char {
+        match self.theme {
+            OutputTheme::Ascii => '_',
+            OutputTheme::Unicode => '─',
+        }
+    }
+
+    /// The corner where a multiline span starts, and where it ends.
+    fn multiline_corners(&self) -> Option<(char, char)> {
+        match self.theme {
+            OutputTheme::Ascii => None,
+            OutputTheme::Unicode => Some(('╭', '╰')),
+        }
+    }
+
+    /// The start of a multiline span that starts at the beginning of a line.
+    fn multiline_start_down(&self) -> char {
+        match self.theme {
+            OutputTheme::Ascii => '/',
+            OutputTheme::Unicode => '╭',
+        }
+    }
+
+    fn underline(&self, is_primary: bool) -> char {
+        match (self.theme, is_primary) {
+            (OutputTheme::Ascii, true) => '^',
+            (OutputTheme::Ascii, false) => '-',
+            (OutputTheme::Unicode, true) => '━',
+            (OutputTheme::Unicode, false) => '─',
+        }
+    }
+
+    fn draw_col_separator(&self, buffer: &mut StyledBuffer, line: usize, col: usize) {
+        buffer.putc(line, col, self.col_separator(), Style::LineNumber);
+        buffer.putc(line, col + 1, ' ', Style::LineNumber);
+    }
+
+    fn draw_col_separator_no_space(&self, buffer: &mut StyledBuffer, line: usize, col: usize) {
+        buffer.putc(line, col, self.col_separator(), Style::LineNumber);
+    }
+
+    fn note_separator(&self) -> &'static str {
+        match self.theme {
+            OutputTheme::Ascii => "= ",
+            OutputTheme::Unicode => "╰ ",
+        }
+    }
+
+    fn draw_note_separator(&self, buffer: &mut StyledBuffer, line: usize, col: usize) {
+        buffer.puts(line, col, self.note_separator(), Style::LineNumber);
+    }
+
+    fn draw_multiline_line(
+        &self,
+        buffer: &mut StyledBuffer,
+        line: usize,
+        offset: usize,
+        depth: usize,
+        style: Style,
+    ) {
+        buffer.putc(line, offset + depth - 1, self.vertical_line(), style);
+    }
+
     fn draw_line(
         &self,
         buffer: &mut StyledBuffer,
@@ -730,7 +871,7 @@ fn draw_line(
         }
         buffer.puts(line_offset, 0, &self.maybe_anonymized(line_index), Style::LineNumber);
 
-        draw_col_separator_no_space(buffer, line_offset, width_offset - 2);
+        self.draw_col_separator_no_space(buffer, line_offset, width_offset - 2);
     }
 
     #[instrument(level = "trace", skip(self), ret)]
@@ -817,7 +958,12 @@ fn render_source_line(
                         Style::UnderlineSecondary
                     };
                     annotations.push((depth, style));
-                    buffer_ops.push((line_offset, width_offset + depth - 1, '/', style));
+                    buffer_ops.push((
+                        line_offset,
+                        width_offset + depth - 1,
+                        self.multiline_start_down(),
+                        style,
+                    ));
                 } else {
                     short_start = false;
                     break;
@@ -1016,7 +1162,7 @@ fn render_source_line(
         // 4 |   }
         //   |
         for pos in 0..=line_len {
-            draw_col_separator(buffer, line_offset + pos + 1, width_offset - 2);
+            self.draw_col_separator(buffer, line_offset + pos + 1, width_offset - 2);
         }
 
         // Write the horizontal lines for multiline annotations
@@ -1042,7 +1188,7 @@ fn render_source_line(
                 AnnotationType::MultilineStart(depth) | AnnotationType::MultilineEnd(depth) => {
                     draw_range(
                         buffer,
-                        '_',
+                        self.horizontal_line(),
                         line_offset + pos,
                         width_offset + depth,
                         (code_offset + annotation.start_col.display).saturating_sub(left),
@@ -1086,7 +1232,7 @@ fn render_source_line(
                     buffer.putc(
                         p,
                         (code_offset + annotation.start_col.display).saturating_sub(left),
-                        '|',
+                        self.vertical_line(),
                         style,
                     );
                 }
@@ -1094,12 +1240,18 @@ fn render_source_line(
             match annotation.annotation_type {
                 AnnotationType::MultilineStart(depth) => {
                     for p in line_offset + pos + 1..line_offset + line_len + 2 {
-                        buffer.putc(p, width_offset + depth - 1, '|', style);
+                        buffer.putc(p, width_offset + depth - 1, self.vertical_line(), style);
+                    }
+                    if let Some((start, _)) = self.multiline_corners() {
+                        buffer.putc(line_offset + pos, width_offset + depth - 1, start, style);
                     }
                 }
                 AnnotationType::MultilineEnd(depth) => {
                     for p in line_offset..=line_offset + pos {
-                        buffer.putc(p, width_offset + depth - 1, '|', style);
+                        buffer.putc(p, width_offset + depth - 1, self.vertical_line(), style);
+                    }
+                    if let Some((_, end)) = self.multiline_corners() {
+                        buffer.putc(line_offset + pos, width_offset + depth - 1, end, style);
                     }
                 }
                 _ => (),
@@ -1155,10 +1307,11 @@ fn render_source_line(
         // 4 |   }
         //   |  _^  test
         for &(_, annotation) in &annotations_position {
-            let (underline, style) = if annotation.is_primary {
-                ('^', Style::UnderlinePrimary)
+            let underline = self.underline(annotation.is_primary);
+            let style = if annotation.is_primary {
+                Style::UnderlinePrimary
             } else {
-                ('-', Style::UnderlineSecondary)
+                Style::UnderlineSecondary
             };
             for p in annotation.start_col.display..annotation.end_col.display {
                 buffer.putc(
@@ -1322,7 +1475,7 @@ fn emit_message_default(
             for _ in 0..max_line_num_len {
                 buffer.prepend(0, " ", Style::NoStyle);
             }
-            draw_note_separator(&mut buffer, 0, max_line_num_len + 1);
+            self.draw_note_separator(&mut buffer, 0, max_line_num_len + 1);
             if *level != Level::FailureNote {
                 buffer.append(0, level.to_str(), Style::MainHeaderMsg);
                 buffer.append(0, ": ", Style::NoStyle);
@@ -1414,23 +1567,25 @@ fn emit_message_default(
                             .collect();
 
                         if annotation_id == 0 || !labels.is_empty() {
+                            let location = format!(
+                                "{}:{}:{}",
+                                sm.filename_for_diagnostics(&annotated_file.file.name),
+                                sm.doctest_offset_line(&annotated_file.file.name, line.line_index),
+                                annotations[0].start_col.file + 1,
+                            );
                             buffer.append(
                                 line_idx,
-                                &format!(
-                                    "{}:{}:{}",
-                                    sm.filename_for_diagnostics(&annotated_file.file.name),
-                                    sm.doctest_offset_line(
-                                        &annotated_file.file.name,
-                                        line.line_index
-                                    ),
-                                    annotations[0].start_col.file + 1,
-                                ),
+                                &self.file_link(&annotated_file.file.name, location),
                                 Style::LineAndColumn,
                             );
                             if annotation_id == 0 {
-                                buffer.prepend(line_idx, "--> ", Style::LineNumber);
+                                buffer.prepend(line_idx, self.file_start(), Style::LineNumber);
                             } else {
-                                buffer.prepend(line_idx, "::: ", Style::LineNumber);
+                                buffer.prepend(
+                                    line_idx,
+                                    self.secondary_file_start(),
+                                    Style::LineNumber,
+                                );
                             }
                             for _ in 0..max_line_num_len {
                                 buffer.prepend(line_idx, " ", Style::NoStyle);
@@ -1443,12 +1598,17 @@ fn emit_message_default(
                             } else {
                                 Style::LabelSecondary
                             };
-                            buffer.prepend(line_idx, " |", Style::LineNumber);
+                            buffer.prepend(
+                                line_idx,
+                                &format!(" {}", self.col_separator()),
+                                Style::LineNumber,
+                            );
                             for _ in 0..max_line_num_len {
                                 buffer.prepend(line_idx, " ", Style::NoStyle);
                             }
                             line_idx += 1;
-                            buffer.append(line_idx, " = note: ", style);
+                            let note = format!(" {}note: ", self.note_separator());
+                            buffer.append(line_idx, &note, style);
                             for _ in 0..max_line_num_len {
                                 buffer.prepend(line_idx, " ", Style::NoStyle);
                             }
@@ -1469,15 +1629,16 @@ fn emit_message_default(
                     // remember where we are in the output buffer for easy reference
                     let buffer_msg_line_offset = buffer.num_lines();
 
-                    buffer.prepend(buffer_msg_line_offset, "--> ", Style::LineNumber);
+                    buffer.prepend(buffer_msg_line_offset, self.file_start(), Style::LineNumber);
+                    let location = format!(
+                        "{}:{}:{}",
+                        sm.filename_for_diagnostics(&loc.file.name),
+                        sm.doctest_offset_line(&loc.file.name, loc.line),
+                        loc.col.0 + 1,
+                    );
                     buffer.append(
                         buffer_msg_line_offset,
-                        &format!(
-                            "{}:{}:{}",
-                            sm.filename_for_diagnostics(&loc.file.name),
-                            sm.doctest_offset_line(&loc.file.name, loc.line),
-                            loc.col.0 + 1,
-                        ),
+                        &self.file_link(&loc.file.name, location),
                         Style::LineAndColumn,
                     );
                     for _ in 0..max_line_num_len {
@@ -1500,14 +1661,18 @@ fn emit_message_default(
                 let buffer_msg_line_offset = buffer.num_lines();
 
                 // Add spacing line
-                draw_col_separator_no_space(
+                self.draw_col_separator_no_space(
                     &mut buffer,
                     buffer_msg_line_offset,
                     max_line_num_len + 1,
                 );
 
                 // Then, the secondary file indicator
-                buffer.prepend(buffer_msg_line_offset + 1, "::: ", Style::LineNumber);
+                buffer.prepend(
+                    buffer_msg_line_offset + 1,
+                    self.secondary_file_start(),
+                    Style::LineNumber,
+                );
                 let loc = if let Some(first_line) = annotated_file.lines.first() {
                     let col = if let Some(first_annotation) = first_line.annotations.first() {
                         format!(":{}", first_annotation.start_col.file + 1)
@@ -1523,6 +1688,7 @@ fn emit_message_default(
                 } else {
                     format!("{}", sm.filename_for_diagnostics(&annotated_file.file.name))
                 };
+                let loc = self.file_link(&annotated_file.file.name, loc);
                 buffer.append(buffer_msg_line_offset + 1, &loc, Style::LineAndColumn);
                 for _ in 0..max_line_num_len {
                     buffer.prepend(buffer_msg_line_offset + 1, " ", Style::NoStyle);
@@ -1532,7 +1698,7 @@ fn emit_message_default(
             if !self.short_message {
                 // Put in the spacer between the location and annotated source
                 let buffer_msg_line_offset = buffer.num_lines();
-                draw_col_separator_no_space(
+                self.draw_col_separator_no_space(
                     &mut buffer,
                     buffer_msg_line_offset,
                     max_line_num_len + 1,
@@ -1649,7 +1815,13 @@ fn emit_message_default(
                     // the code in this line.
                     for (depth, style) in &multilines {
                         for line in previous_buffer_line..buffer.num_lines() {
-                            draw_multiline_line(&mut buffer, line, width_offset, *depth, *style);
+                            self.draw_multiline_line(
+                                &mut buffer,
+                                line,
+                                width_offset,
+                                *depth,
+                                *style,
+                            );
                         }
                     }
                     // check to see if we need to print out or elide lines that come between
@@ -1663,7 +1835,7 @@ fn emit_message_default(
 
                             // Set the multiline annotation vertical lines on `...` bridging line.
                             for (depth, style) in &multilines {
-                                draw_multiline_line(
+                                self.draw_multiline_line(
                                     &mut buffer,
                                     last_buffer_line_num,
                                     width_offset,
@@ -1690,7 +1862,7 @@ fn emit_message_default(
                             );
 
                             for (depth, style) in &multilines {
-                                draw_multiline_line(
+                                self.draw_multiline_line(
                                     &mut buffer,
                                     last_buffer_line_num,
                                     width_offset,
@@ -1757,7 +1929,7 @@ fn emit_suggestion_default(
         );
 
         let mut row_num = 2;
-        draw_col_separator_no_space(&mut buffer, 1, max_line_num_len + 1);
+        self.draw_col_separator_no_space(&mut buffer, 1, max_line_num_len + 1);
         let mut notice_capitalization = false;
         for (complete, parts, highlights, only_capitalization) in
             suggestions.iter().take(MAX_SUGGESTIONS)
@@ -1775,13 +1947,13 @@ fn emit_suggestion_default(
                 // telling users to make a change but not clarifying *where*.
                 let loc = sm.lookup_char_pos(parts[0].span.lo());
                 if loc.file.name != sm.span_to_filename(span) && loc.file.name.is_real() {
-                    let arrow = "--> ";
+                    let arrow = self.file_start();
                     buffer.puts(row_num - 1, 0, arrow, Style::LineNumber);
                     let filename = sm.filename_for_diagnostics(&loc.file.name);
                     let offset = sm.doctest_offset_line(&loc.file.name, loc.line);
                     let message = format!("{}:{}:{}", filename, offset, loc.col.0 + 1);
                     if row_num == 2 {
-                        let col = usize::max(max_line_num_len + 1, arrow.len());
+                        let col = usize::max(max_line_num_len + 1, arrow.chars().count());
                         buffer.puts(1, col, &message, Style::LineAndColumn);
                     } else {
                         buffer.append(row_num - 1, &message, Style::LineAndColumn);
@@ -1819,7 +1991,7 @@ fn emit_suggestion_default(
             assert!(!file_lines.lines.is_empty() || parts[0].span.is_dummy());
 
             let line_start = sm.lookup_char_pos(parts[0].span.lo()).line;
-            draw_col_separator_no_space(&mut buffer, row_num - 1, max_line_num_len + 1);
+            self.draw_col_separator_no_space(&mut buffer, row_num - 1, max_line_num_len + 1);
             let mut lines = complete.lines();
             if lines.clone().next().is_none() {
                 // Account for a suggestion to completely remove a line(s) with whitespace (#94192).
@@ -1977,7 +2149,7 @@ fn emit_suggestion_default(
             if let DisplaySuggestion::Diff | DisplaySuggestion::Underline | DisplaySuggestion::Add =
                 show_code_change
             {
-                draw_col_separator_no_space(&mut buffer, row_num, max_line_num_len + 1);
+                self.draw_col_separator_no_space(&mut buffer, row_num, max_line_num_len + 1);
                 for part in parts {
                     let span_start_pos = sm.lookup_char_pos(part.span.lo()).col_display;
                     let span_end_pos = sm.lookup_char_pos(part.span.hi()).col_display;
@@ -2054,7 +2226,7 @@ fn emit_suggestion_default(
             if lines.next().is_some() {
                 buffer.puts(row_num, max_line_num_len - 1, "...", Style::LineNumber);
             } else if let DisplaySuggestion::None = show_code_change {
-                draw_col_separator_no_space(&mut buffer, row_num, max_line_num_len + 1);
+                self.draw_col_separator_no_space(&mut buffer, row_num, max_line_num_len + 1);
                 row_num += 1;
             }
         }
@@ -2105,7 +2277,7 @@ fn emit_messages_default(
                 {
                     let mut buffer = StyledBuffer::new();
                     if !self.short_message {
-                        draw_col_separator_no_space(&mut buffer, 0, max_line_num_len + 1);
+                        self.draw_col_separator_no_space(&mut buffer, 0, max_line_num_len + 1);
                     }
                     if let Err(e) = emit_to_destination(
                         &buffer.render(),
@@ -2246,7 +2418,7 @@ fn draw_code_line(
                     buffer.puts(*row_num, max_line_num_len + 1, "+ ", Style::Addition);
                 }
                 [] => {
-                    draw_col_separator(buffer, *row_num, max_line_num_len + 1);
+                    self.draw_col_separator(buffer, *row_num, max_line_num_len + 1);
                 }
                 _ => {
                     buffer.puts(*row_num, max_line_num_len + 1, "~ ", Style::Addition);
@@ -2259,7 +2431,7 @@ fn draw_code_line(
             buffer.append(*row_num, &normalize_whitespace(line_to_add), Style::NoStyle);
         } else {
             buffer.puts(*row_num, 0, &self.maybe_anonymized(line_num), Style::LineNumber);
-            draw_col_separator(buffer, *row_num, max_line_num_len + 1);
+            self.draw_col_separator(buffer, *row_num, max_line_num_len + 1);
             buffer.append(*row_num, &normalize_whitespace(line_to_add), Style::NoStyle);
         }
 
@@ -2519,23 +2691,6 @@ fn normalize_whitespace(str: &str) -> String {
     s
 }
 
-fn draw_col_separator(buffer: &mut StyledBuffer, line: usize, col: usize) {
-    buffer.puts(line, col, "| ", Style::LineNumber);
-}
-
-fn draw_col_separator_no_space(buffer: &mut StyledBuffer, line: usize, col: usize) {
-    draw_col_separator_no_space_with_style(buffer, line, col, Style::LineNumber);
-}
-
-fn draw_col_separator_no_space_with_style(
-    buffer: &mut StyledBuffer,
-    line: usize,
-    col: usize,
-    style: Style,
-) {
-    buffer.putc(line, col, '|', style);
-}
-
 fn draw_range(
     buffer: &mut StyledBuffer,
     symbol: char,
@@ -2549,20 +2704,6 @@ fn draw_range(
     }
 }
 
-fn draw_note_separator(buffer: &mut StyledBuffer, line: usize, col: usize) {
-    buffer.puts(line, col, "= ", Style::LineNumber);
-}
-
-fn draw_multiline_line(
-    buffer: &mut StyledBuffer,
-    line: usize,
-    offset: usize,
-    depth: usize,
-    style: Style,
-) {
-    buffer.putc(line, offset + depth - 1, '|', style);
-}
-
 fn num_overlap(
     a_start: usize,
     a_end: usize,
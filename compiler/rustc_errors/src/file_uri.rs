@@ -0,0 +1,27 @@
+//! Turning file paths into URIs, for SARIF artifact locations and for hyperlinks in terminal
+//! output.
+
+use std::fmt::Write;
+use std::path::Path;
+
+/// Turns a file path into a URI reference. Relative paths stay relative, absolute ones become
+/// `file` URIs.
+pub(crate) fn path_to_uri(path: &str) -> String {
+    let path = if cfg!(windows) { path.replace('\\', "/") } else { path.to_owned() };
+    let mut uri = String::with_capacity(path.len());
+    if Path::new(&path).is_absolute() || path.starts_with('/') {
+        uri.push_str("file://");
+        if !path.starts_with('/') {
+            uri.push('/');
+        }
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => write!(uri, "%{byte:02X}").unwrap(),
+        }
+    }
+    uri
+}
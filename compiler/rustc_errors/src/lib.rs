@@ -62,15 +62,18 @@
 pub use termcolor::{Color, ColorSpec, WriteColor};
 
 pub mod annotate_snippet_emitter_writer;
+pub mod baseline;
 mod diagnostic;
 mod diagnostic_builder;
 mod diagnostic_impls;
 pub mod emitter;
 pub mod error;
+mod file_uri;
 pub mod json;
 mod lock;
 pub mod markdown;
 pub mod registry;
+pub mod sarif;
 mod snippet;
 mod styled_buffer;
 #[cfg(test)]
@@ -398,6 +401,7 @@ fn push_trailing(
 /// rather than a failed assertion, etc.
 pub struct DelayedBugPanic;
 
+use crate::baseline::DiagnosticBaseline;
 use crate::diagnostic_impls::{DelayedAtWithNewline, DelayedAtWithoutNewline};
 pub use diagnostic::{
     AddToDiagnostic, DecorateLint, Diagnostic, DiagnosticArg, DiagnosticArgValue, DiagnosticId,
@@ -489,6 +493,10 @@ struct HandlerInner {
     /// The file where the ICE information is stored. This allows delayed_span_bug backtraces to be
     /// stored along side the main panic backtrace.
     ice_file: Option<PathBuf>,
+
+    /// The baseline of lint diagnostics that are suppressed, or that is being recorded.
+    /// (rustc: see `-Z diagnostic-baseline` and `-Z write-baseline`)
+    baseline: Option<DiagnosticBaseline>,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -601,6 +609,11 @@ pub fn with_ice_file(mut self, ice_file: PathBuf) -> Self {
         self
     }
 
+    pub fn with_baseline(mut self, baseline: DiagnosticBaseline) -> Self {
+        self.inner.get_mut().baseline = Some(baseline);
+        self
+    }
+
     pub fn with_emitter(emitter: Box<DynEmitter>) -> Self {
         Self {
             inner: Lock::new(HandlerInner {
@@ -623,6 +636,7 @@ pub fn with_emitter(emitter: Box<DynEmitter>) -> Self {
                 unstable_expect_diagnostics: Vec::new(),
                 fulfilled_expectations: Default::default(),
                 ice_file: None,
+                baseline: None,
             }),
         }
     }
@@ -1104,6 +1118,15 @@ pub fn print_error_count(&self, registry: &Registry) {
         self.inner.borrow_mut().print_error_count(registry)
     }
 
+    /// Writes the diagnostic baseline if it is being recorded, see [`DiagnosticBaseline`].
+    pub fn write_baseline(&self) {
+        let mut inner = self.inner.borrow_mut();
+        let Some(Err(msg)) = inner.baseline.as_ref().map(|baseline| baseline.write()) else {
+            return;
+        };
+        inner.emit_diagnostic(&mut Diagnostic::new(Error { lint: false }, msg));
+    }
+
     pub fn take_future_breakage_diagnostics(&self) -> Vec<Diagnostic> {
         std::mem::take(&mut self.inner.borrow_mut().future_breakage_diagnostics)
     }
@@ -1377,6 +1400,14 @@ fn emit_diagnostic(&mut self, diagnostic: &mut Diagnostic) -> Option<ErrorGuaran
 
         let mut guaranteed = None;
         (*TRACK_DIAGNOSTICS)(diagnostic, &mut |diagnostic| {
+            // This is checked after the diagnostic was tracked, so that suppressed lints are still
+            // replayed from the incremental cache, and checked against the baseline again then.
+            if let Some(baseline) = &mut self.baseline
+                && baseline.suppress(&*self.emitter, diagnostic)
+            {
+                return;
+            }
+
             if let Some(ref code) = diagnostic.code {
                 self.emitted_diagnostic_codes.insert(code.clone());
             }
@@ -0,0 +1,478 @@
+//! A SARIF emitter for errors.
+//!
+//! This collects the diagnostics emitted during a compilation session and writes them out as a
+//! single [SARIF 2.1.0] log when the emitter is dropped, which is the format that static analysis
+//! dashboards and code review tools consume. Every diagnostic becomes a `result`, error codes and
+//! lint names become the `rules` of the log, notes and helps become related locations, and
+//! structured suggestions become `fixes`.
+//!
+//! [SARIF 2.1.0]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+
+use crate::emitter::Emitter;
+use crate::file_uri::path_to_uri;
+use crate::registry::Registry;
+use crate::translation::{to_fluent_args, Translate};
+use crate::DiagnosticId;
+use crate::{CodeSuggestion, FluentBundle, LazyFallbackBundle, Level, SubDiagnostic};
+use rustc_lint_defs::Applicability;
+
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::sync::{IntoDynSyncSend, Lrc};
+use rustc_error_messages::FluentArgs;
+use rustc_span::{FileName, Span};
+use std::error::Report;
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+#[cfg(test)]
+mod tests;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const VERSION: &str = "2.1.0";
+
+pub struct SarifEmitter {
+    dst: IntoDynSyncSend<Box<dyn Write + Send>>,
+    registry: Option<Registry>,
+    sm: Lrc<SourceMap>,
+    fluent_bundle: Option<Lrc<FluentBundle>>,
+    fallback_bundle: LazyFallbackBundle,
+    /// Whether to write a log when no diagnostics were emitted.
+    emit_empty_log: bool,
+    /// The rules referenced by the results, keyed by error code or lint name.
+    rules: FxIndexMap<String, Rule>,
+    results: Vec<SarifResult>,
+}
+
+impl SarifEmitter {
+    pub fn stderr(
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter::new(
+            Box::new(io::BufWriter::new(io::stderr())),
+            registry,
+            source_map,
+            fluent_bundle,
+            fallback_bundle,
+        )
+    }
+
+    pub fn basic(
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        let file_path_mapping = FilePathMapping::empty();
+        SarifEmitter::stderr(
+            None,
+            Lrc::new(SourceMap::new(file_path_mapping)),
+            fluent_bundle,
+            fallback_bundle,
+        )
+    }
+
+    pub fn new(
+        dst: Box<dyn Write + Send>,
+        registry: Option<Registry>,
+        source_map: Lrc<SourceMap>,
+        fluent_bundle: Option<Lrc<FluentBundle>>,
+        fallback_bundle: LazyFallbackBundle,
+    ) -> SarifEmitter {
+        SarifEmitter {
+            dst: IntoDynSyncSend(dst),
+            registry,
+            sm: source_map,
+            fluent_bundle,
+            fallback_bundle,
+            emit_empty_log: true,
+            rules: FxIndexMap::default(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Sets whether a log is written even if no diagnostics were emitted. This is on by default,
+    /// so that a clean compilation still produces a log, but handlers that only exist to report
+    /// early errors turn it off to avoid writing a second log next to the session's.
+    pub fn emit_empty_log(mut self, emit_empty_log: bool) -> Self {
+        self.emit_empty_log = emit_empty_log;
+        self
+    }
+
+    /// Returns the index of the rule for `code`, adding the rule to the log if it isn't there yet.
+    fn rule_index(&mut self, code: &DiagnosticId) -> usize {
+        let id = match code {
+            DiagnosticId::Error(code) => code,
+            DiagnosticId::Lint { name, .. } => name,
+        };
+        if let Some(index) = self.rules.get_index_of(id) {
+            return index;
+        }
+
+        let rule = match code {
+            DiagnosticId::Error(_) => Rule {
+                id: id.clone(),
+                full_description: self
+                    .registry
+                    .as_ref()
+                    .and_then(|registry| registry.try_find_description(id).ok())
+                    .map(|explanation| Message::new(explanation.trim())),
+                help_uri: Some(format!("https://doc.rust-lang.org/error_codes/{id}.html")),
+            },
+            DiagnosticId::Lint { .. } => {
+                Rule { id: id.clone(), full_description: None, help_uri: None }
+            }
+        };
+        self.rules.insert_full(id.clone(), rule).0
+    }
+
+    fn result_from_diagnostic(&mut self, diag: &crate::Diagnostic) -> Option<SarifResult> {
+        let level = match diag.level {
+            Level::Bug | Level::DelayedBug | Level::Fatal | Level::Error { .. } => "error",
+            Level::Warning(_) => "warning",
+            Level::Note | Level::OnceNote | Level::Help | Level::OnceHelp => "note",
+            // Failure notes only summarize the other diagnostics, like "aborting due to 2
+            // previous errors", so they aren't results of their own.
+            Level::FailureNote | Level::Allow | Level::Expect(_) => return None,
+        };
+        let args = to_fluent_args(diag.args());
+
+        let rule_index = diag.code.as_ref().map(|code| self.rule_index(code));
+        let rule_id = rule_index.map(|index| self.rules[index].id.clone());
+
+        let mut locations = Vec::new();
+        let mut related_locations = Vec::new();
+        for span_label in diag.span.span_labels() {
+            let message = span_label
+                .label
+                .as_ref()
+                .map(|label| self.translate_message(label, &args).map_err(Report::new).unwrap())
+                .map(Message::new);
+            let location =
+                Location { physical_location: self.physical_location(span_label.span), message };
+            if span_label.is_primary {
+                locations.push(location);
+            } else {
+                related_locations.push(location);
+            }
+        }
+        for child in &diag.children {
+            self.related_locations_from_sub_diagnostic(child, &args, &mut related_locations);
+        }
+
+        let fixes = diag
+            .suggestions
+            .iter()
+            .flatten()
+            .flat_map(|sugg| self.fixes_from_suggestion(sugg, &args))
+            .collect();
+
+        Some(SarifResult {
+            rule_id,
+            rule_index,
+            level,
+            message: Message::new(self.translate_messages(&diag.message, &args)),
+            locations,
+            related_locations,
+            fixes,
+        })
+    }
+
+    fn related_locations_from_sub_diagnostic(
+        &self,
+        diag: &SubDiagnostic,
+        args: &FluentArgs<'_>,
+        related_locations: &mut Vec<Location>,
+    ) {
+        let message =
+            format!("{}: {}", diag.level.to_str(), self.translate_messages(&diag.message, args));
+        let span = diag.render_span.as_ref().unwrap_or(&diag.span);
+        if span.primary_spans().is_empty() {
+            related_locations
+                .push(Location { physical_location: None, message: Some(Message::new(message)) });
+            return;
+        }
+        for &span in span.primary_spans() {
+            related_locations.push(Location {
+                physical_location: self.physical_location(span),
+                message: Some(Message::new(message.clone())),
+            });
+        }
+    }
+
+    /// Creates a fix for every alternative substitution of the suggestion.
+    fn fixes_from_suggestion(&self, sugg: &CodeSuggestion, args: &FluentArgs<'_>) -> Vec<Fix> {
+        let description =
+            self.translate_message(&sugg.msg, args).map_err(Report::new).unwrap().to_string();
+        sugg.substitutions
+            .iter()
+            .filter_map(|substitution| {
+                let mut artifact_changes: Vec<ArtifactChange> = Vec::new();
+                for part in &substitution.parts {
+                    let Some(PhysicalLocation { artifact_location, region }) =
+                        self.physical_location(part.span)
+                    else {
+                        continue;
+                    };
+                    let replacement = Replacement {
+                        deleted_region: region,
+                        inserted_content: ArtifactContent { text: part.snippet.clone() },
+                    };
+                    match artifact_changes
+                        .iter_mut()
+                        .find(|change| change.artifact_location == artifact_location)
+                    {
+                        Some(change) => change.replacements.push(replacement),
+                        None => artifact_changes.push(ArtifactChange {
+                            artifact_location,
+                            replacements: vec![replacement],
+                        }),
+                    }
+                }
+                (!artifact_changes.is_empty()).then(|| Fix {
+                    description: Message::new(description.clone()),
+                    artifact_changes,
+                    properties: FixProperties { applicability: sugg.applicability },
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the location of `span` in its source file, or `None` if it doesn't point into a
+    /// file on disk.
+    fn physical_location(&self, span: Span) -> Option<PhysicalLocation> {
+        if span.is_dummy() {
+            return None;
+        }
+        let start = self.sm.lookup_char_pos(span.lo());
+        let end = self.sm.lookup_char_pos(span.hi());
+        if !matches!(start.file.name, FileName::Real(_)) {
+            return None;
+        }
+
+        let byte_start = start.file.original_relative_byte_pos(span.lo()).0;
+        let byte_end = start.file.original_relative_byte_pos(span.hi()).0;
+        Some(PhysicalLocation {
+            artifact_location: ArtifactLocation {
+                uri: path_to_uri(&self.sm.filename_for_diagnostics(&start.file.name).to_string()),
+            },
+            region: Region {
+                start_line: start.line,
+                start_column: start.col.0 + 1,
+                end_line: end.line,
+                end_column: end.col.0 + 1,
+                byte_offset: byte_start,
+                byte_length: byte_end - byte_start,
+            },
+        })
+    }
+
+    fn write_log(&mut self) -> io::Result<()> {
+        let log = SarifLog {
+            schema: SCHEMA,
+            version: VERSION,
+            runs: [Run {
+                tool: Tool {
+                    driver: ToolComponent {
+                        name: "rustc",
+                        information_uri: "https://www.rust-lang.org/",
+                        rules: self.rules.values().collect(),
+                    },
+                },
+                results: &self.results,
+                column_kind: "unicodeCodePoints",
+            }],
+        };
+        writeln!(&mut self.dst, "{}", serde_json::to_string(&log).unwrap())?;
+        self.dst.flush()
+    }
+}
+
+impl Translate for SarifEmitter {
+    fn fluent_bundle(&self) -> Option<&Lrc<FluentBundle>> {
+        self.fluent_bundle.as_ref()
+    }
+
+    fn fallback_fluent_bundle(&self) -> &FluentBundle {
+        &self.fallback_bundle
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: &crate::Diagnostic) {
+        if let Some(result) = self.result_from_diagnostic(diag) {
+            self.results.push(result);
+        }
+    }
+
+    fn source_map(&self) -> Option<&Lrc<SourceMap>> {
+        Some(&self.sm)
+    }
+
+    fn should_show_explain(&self) -> bool {
+        // The explanations are part of the rules instead.
+        false
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        if self.results.is_empty() && !self.emit_empty_log {
+            return;
+        }
+        // Panicking in `drop` would abort if the emitter is dropped while unwinding, and lose
+        // whatever the panic was about. That includes `eprintln!`, which panics if stderr is
+        // closed, and stderr is where the log goes by default.
+        if let Err(e) = self.write_log() {
+            let _ = writeln!(io::stderr(), "failed to print diagnostics: {e:?}");
+        }
+    }
+}
+
+// The following data types are provided just for serialisation. Their field names follow the
+// SARIF specification.
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [Run<'a>; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run<'a> {
+    tool: Tool<'a>,
+    results: &'a [SarifResult],
+    /// What the columns of the regions count. rustc counts chars.
+    column_kind: &'static str,
+}
+
+#[derive(Serialize)]
+struct Tool<'a> {
+    driver: ToolComponent<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent<'a> {
+    name: &'static str,
+    information_uri: &'static str,
+    rules: Vec<&'a Rule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    /// The error code or lint name.
+    id: String,
+    /// The long explanation of an error code, in Markdown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_description: Option<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+impl Message {
+    fn new(text: impl Into<String>) -> Message {
+        Message { text: text.into() }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    /// The index of the rule in the `rules` of the tool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    /// "error", "warning" or "note".
+    level: &'static str,
+    message: Message,
+    /// The primary spans of the diagnostic.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+    /// The secondary spans of the diagnostic, and its notes and helps.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fixes: Vec<Fix>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    physical_location: Option<PhysicalLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize, PartialEq)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    /// 1-based.
+    start_line: usize,
+    /// 1-based, character offset.
+    start_column: usize,
+    end_line: usize,
+    /// 1-based, character offset of the character after the region.
+    end_column: usize,
+    byte_offset: u32,
+    byte_length: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Fix {
+    description: Message,
+    artifact_changes: Vec<ArtifactChange>,
+    properties: FixProperties,
+}
+
+#[derive(Serialize)]
+struct FixProperties {
+    /// How confident rustc is that the fix is correct.
+    applicability: Applicability,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactChange {
+    artifact_location: ArtifactLocation,
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Replacement {
+    deleted_region: Region,
+    inserted_content: ArtifactContent,
+}
+
+#[derive(Serialize)]
+struct ArtifactContent {
+    text: String,
+}
@@ -0,0 +1,243 @@
+use super::*;
+
+use crate::{DiagnosticId, Handler};
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::BytePos;
+
+use std::str;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+
+struct Shared<T> {
+    data: Arc<Mutex<T>>,
+}
+
+impl<T: Write> Write for Shared<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.data.lock().unwrap().flush()
+    }
+}
+
+/// Emits diagnostics for `code` in a file called `test.rs` using `f`, and returns the SARIF log.
+fn test_log(code: &str, emit_empty_log: bool, f: impl FnOnce(&Handler)) -> Option<Value> {
+    rustc_span::create_default_session_globals_then(|| {
+        let sm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        sm.new_source_file(Path::new("test.rs").to_owned().into(), code.to_owned());
+        let fallback_bundle =
+            crate::fallback_fluent_bundle(vec![crate::DEFAULT_LOCALE_RESOURCE], false);
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let se = SarifEmitter::new(
+            Box::new(Shared { data: output.clone() }),
+            Some(Registry::new(&[("E0001", "Some explanation.\n")])),
+            sm,
+            None,
+            fallback_bundle,
+        )
+        .emit_empty_log(emit_empty_log);
+
+        let handler = Handler::with_emitter(Box::new(se));
+        f(&handler);
+        drop(handler);
+
+        let bytes = output.lock().unwrap();
+        let actual_output = str::from_utf8(&bytes).unwrap();
+        if actual_output.is_empty() {
+            return None;
+        }
+        assert_eq!(actual_output.lines().count(), 1);
+        Some(serde_json::from_str(actual_output).unwrap())
+    })
+}
+
+fn span(lo: u32, hi: u32) -> Span {
+    Span::with_root_ctxt(BytePos(lo), BytePos(hi))
+}
+
+/// Test the span yields correct regions in SARIF.
+fn test_region(code: &str, span: Span, expected_region: Value) {
+    let log = test_log(code, true, |handler| {
+        handler.span_err(span, "foo");
+    })
+    .unwrap();
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["locations"][0]["physicalLocation"]["region"], expected_region);
+}
+
+#[test]
+fn empty_log() {
+    let log = test_log("", true, |_| {}).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(log["runs"][0]["tool"]["driver"]["name"], "rustc");
+    assert_eq!(log["runs"][0]["tool"]["driver"]["rules"], json!([]));
+    assert_eq!(log["runs"][0]["results"], json!([]));
+
+    assert_eq!(test_log("", false, |_| {}), None);
+}
+
+#[test]
+fn result() {
+    let log = test_log("fn main() {}\n", false, |handler| {
+        handler.span_warn(span(3, 7), "foo");
+    })
+    .unwrap();
+    assert_eq!(
+        log["runs"][0]["results"],
+        json!([{
+            "level": "warning",
+            "message": { "text": "foo" },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": "test.rs" },
+                    "region": {
+                        "startLine": 1,
+                        "startColumn": 4,
+                        "endLine": 1,
+                        "endColumn": 8,
+                        "byteOffset": 3,
+                        "byteLength": 4,
+                    },
+                },
+            }],
+        }])
+    );
+}
+
+#[test]
+fn rules() {
+    let log = test_log("fn main() {}\n", false, |handler| {
+        handler.struct_span_err(span(0, 2), "foo").code(DiagnosticId::Error("E0001".into())).emit();
+        handler
+            .struct_span_warn(span(3, 7), "bar")
+            .code(DiagnosticId::Lint {
+                name: "some_lint".into(),
+                has_future_breakage: false,
+                is_force_warn: false,
+            })
+            .emit();
+        handler
+            .struct_span_err(span(8, 10), "baz")
+            .code(DiagnosticId::Error("E0001".into()))
+            .emit();
+        // Failure notes aren't results.
+        handler.emit_diagnostic(&mut crate::Diagnostic::new(
+            Level::FailureNote,
+            "aborting due to 2 previous errors",
+        ));
+    })
+    .unwrap();
+    assert_eq!(
+        log["runs"][0]["tool"]["driver"]["rules"],
+        json!([
+            {
+                "id": "E0001",
+                "fullDescription": { "text": "Some explanation." },
+                "helpUri": "https://doc.rust-lang.org/error_codes/E0001.html",
+            },
+            { "id": "some_lint" },
+        ])
+    );
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!((&results[0]["ruleId"], &results[0]["ruleIndex"]), (&json!("E0001"), &json!(0)));
+    assert_eq!((&results[1]["ruleId"], &results[1]["ruleIndex"]), (&json!("some_lint"), &json!(1)));
+    assert_eq!((&results[2]["ruleId"], &results[2]["ruleIndex"]), (&json!("E0001"), &json!(0)));
+}
+
+#[test]
+fn related_locations() {
+    let log = test_log("fn main() {}\n", false, |handler| {
+        let mut diag = handler.struct_span_err(span(3, 7), "foo");
+        diag.span_label(span(3, 7), "primary label");
+        diag.span_label(span(0, 2), "secondary label");
+        diag.span_note(span(10, 12), "a note");
+        diag.help("a help");
+        diag.emit();
+    })
+    .unwrap();
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["locations"][0]["message"]["text"], "primary label");
+    let related_locations = result["relatedLocations"].as_array().unwrap();
+    assert_eq!(related_locations.len(), 3);
+    assert_eq!(related_locations[0]["message"]["text"], "secondary label");
+    assert_eq!(related_locations[0]["physicalLocation"]["region"]["byteOffset"], 0);
+    assert_eq!(related_locations[1]["message"]["text"], "note: a note");
+    assert_eq!(related_locations[1]["physicalLocation"]["region"]["byteOffset"], 10);
+    assert_eq!(related_locations[2], json!({ "message": { "text": "help: a help" } }));
+}
+
+#[test]
+fn fixes() {
+    let log = test_log("fn mian() {}\n", false, |handler| {
+        handler
+            .struct_span_err(span(3, 7), "foo")
+            .span_suggestion(span(3, 7), "rename it", "main", Applicability::MachineApplicable)
+            .emit();
+    })
+    .unwrap();
+    assert_eq!(
+        log["runs"][0]["results"][0]["fixes"],
+        json!([{
+            "description": { "text": "rename it" },
+            "artifactChanges": [{
+                "artifactLocation": { "uri": "test.rs" },
+                "replacements": [{
+                    "deletedRegion": {
+                        "startLine": 1,
+                        "startColumn": 4,
+                        "endLine": 1,
+                        "endColumn": 8,
+                        "byteOffset": 3,
+                        "byteLength": 4,
+                    },
+                    "insertedContent": { "text": "main" },
+                }],
+            }],
+            "properties": { "applicability": "MachineApplicable" },
+        }])
+    );
+}
+
+#[test]
+fn multibyte_columns() {
+    test_region(
+        "é = ü",
+        span(5, 7),
+        json!({
+            "startLine": 1,
+            "startColumn": 5,
+            "endLine": 1,
+            "endColumn": 6,
+            "byteOffset": 5,
+            "byteLength": 2,
+        }),
+    )
+}
+
+#[test]
+fn crlf_newlines_with_bom() {
+    test_region(
+        "\u{feff}\r\nmod foo;\r\nmod bar;\r\n",
+        span(5, 12),
+        json!({
+            "startLine": 2,
+            "startColumn": 5,
+            "endLine": 3,
+            "endColumn": 3,
+            "byteOffset": 9,
+            "byteLength": 8,
+        }),
+    )
+}
+
+#[test]
+fn uris() {
+    assert_eq!(path_to_uri("src/main.rs"), "src/main.rs");
+    assert_eq!(path_to_uri("src/my file#1.rs"), "src/my%20file%231.rs");
+    assert_eq!(path_to_uri("/home/ferris/src/lib.rs"), "file:///home/ferris/src/lib.rs");
+}
@@ -26,6 +26,7 @@
 pub use persist::save_dep_graph;
 pub use persist::save_work_product_index;
 pub use persist::setup_dep_graph;
+pub use persist::IncrCompCache;
 pub use persist::LoadResult;
 
 use rustc_errors::{DiagnosticMessage, SubdiagnosticMessage};
@@ -0,0 +1,122 @@
+//! Keeps the files of finished incremental sessions decoded in memory, for the next session of a
+//! process that runs many compilations.
+
+use super::file_format;
+use super::fs::{in_incr_comp_dir, DEP_GRAPH_FILENAME, QUERY_CACHE_FILENAME};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_fs_util::FileStamp;
+use rustc_middle::dep_graph::{DepsType, SerializedDepGraph};
+use rustc_middle::query::on_disk_cache::{Footer, OnDiskCache};
+use rustc_serialize::opaque::MemDecoder;
+use rustc_serialize::Decodable;
+use rustc_session::Session;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// The dep graph and the footer of the query result cache that the latest finished session of
+/// each incremental directory wrote, decoded ahead of the next session that loads them.
+///
+/// Processes that run many compilations, like `rustc --server`, keep one of these alive across
+/// them. A new session directory starts out with hard links to the files of the previous session,
+/// so the [`FileStamp`] of a file tells whether it's the one that was decoded.
+#[derive(Default)]
+pub struct IncrCompCache {
+    /// The directories of the finished sessions that haven't been decoded yet, by the incremental
+    /// directory they belong to.
+    pending: Mutex<FxHashMap<PathBuf, FinishedSession>>,
+    decoded: Mutex<FxHashMap<PathBuf, DecodedSession>>,
+}
+
+struct FinishedSession {
+    session_dir: PathBuf,
+    is_nightly_build: bool,
+    cfg_version: &'static str,
+}
+
+struct DecodedSession {
+    is_nightly_build: bool,
+    /// The dep graph, with the hash of the command line that it was built with.
+    dep_graph: Option<(FileStamp, u64, SerializedDepGraph)>,
+    query_cache_footer: Option<(FileStamp, Footer)>,
+}
+
+impl IncrCompCache {
+    /// Remembers the session directory of `sess`, if it finished an incremental session, for
+    /// [`IncrCompCache::decode_pending`] to decode its files.
+    pub fn record_session(&self, sess: &Session) {
+        let (Some(incr_dir), Some(session_dir)) =
+            (&sess.opts.incremental, sess.finalized_incr_comp_session_dir())
+        else {
+            return;
+        };
+        let session = FinishedSession {
+            session_dir,
+            is_nightly_build: sess.is_nightly_build(),
+            cfg_version: sess.cfg_version,
+        };
+        self.pending.lock().unwrap().insert(incr_dir.clone(), session);
+    }
+
+    /// Decodes the files of the sessions recorded since the last call. This is meant to run while
+    /// the process waits for its next compilation.
+    pub fn decode_pending(&self) {
+        let pending = mem::take(&mut *self.pending.lock().unwrap());
+        for (incr_dir, session) in pending {
+            let decoded = session.decode();
+            self.decoded.lock().unwrap().insert(incr_dir, decoded);
+        }
+    }
+
+    /// Returns the dep graph stored in the file at `path`, if it was decoded and built with the
+    /// command line hash `expected_hash`.
+    pub(crate) fn dep_graph(
+        &self,
+        sess: &Session,
+        path: &Path,
+        expected_hash: u64,
+    ) -> Option<SerializedDepGraph> {
+        let stamp = FileStamp::of(path).ok()?;
+        let decoded = self.decoded.lock().unwrap();
+        let session = decoded.get(sess.opts.incremental.as_ref()?)?;
+        let (decoded_stamp, hash, dep_graph) = session.dep_graph.as_ref()?;
+        (session.is_nightly_build == sess.is_nightly_build()
+            && *decoded_stamp == stamp
+            && *hash == expected_hash)
+            .then(|| dep_graph.clone())
+    }
+
+    /// Returns the footer of the query result cache stored in the file at `path`, if it was
+    /// decoded.
+    pub(crate) fn query_cache_footer(&self, sess: &Session, path: &Path) -> Option<Footer> {
+        let stamp = FileStamp::of(path).ok()?;
+        let decoded = self.decoded.lock().unwrap();
+        let session = decoded.get(sess.opts.incremental.as_ref()?)?;
+        let (decoded_stamp, footer) = session.query_cache_footer.as_ref()?;
+        (session.is_nightly_build == sess.is_nightly_build() && *decoded_stamp == stamp)
+            .then(|| footer.clone())
+    }
+}
+
+impl FinishedSession {
+    fn decode(&self) -> DecodedSession {
+        let read = |file_name| {
+            let path = in_incr_comp_dir(&self.session_dir, file_name);
+            let stamp = FileStamp::of(&path).ok()?;
+            let (data, start_pos) =
+                file_format::read_file(&path, false, self.is_nightly_build, self.cfg_version)
+                    .ok()??;
+            Some((stamp, data, start_pos))
+        };
+
+        let dep_graph = read(DEP_GRAPH_FILENAME).map(|(stamp, data, start_pos)| {
+            let mut decoder = MemDecoder::new(&data, start_pos);
+            let hash = u64::decode(&mut decoder);
+            (stamp, hash, SerializedDepGraph::decode::<DepsType>(&mut decoder))
+        });
+        let query_cache_footer = read(QUERY_CACHE_FILENAME)
+            .map(|(stamp, data, start_pos)| (stamp, OnDiskCache::decode_footer(&data, start_pos)));
+
+        DecodedSession { is_nightly_build: self.is_nightly_build, dep_graph, query_cache_footer }
+    }
+}
@@ -12,6 +12,7 @@
 use rustc_span::{ErrorGuaranteed, Symbol};
 use std::path::{Path, PathBuf};
 
+use super::cache::IncrCompCache;
 use super::data::*;
 use super::file_format;
 use super::fs::*;
@@ -95,7 +96,10 @@ fn delete_dirty_work_product(sess: &Session, swp: SerializedWorkProduct) {
     work_product::delete_workproduct_files(sess, &swp.work_product);
 }
 
-fn load_dep_graph(sess: &Session) -> LoadResult<(SerializedDepGraph, WorkProductMap)> {
+fn load_dep_graph(
+    sess: &Session,
+    cache: Option<&IncrCompCache>,
+) -> LoadResult<(SerializedDepGraph, WorkProductMap)> {
     let prof = sess.prof.clone();
 
     if sess.opts.incremental.is_none() {
@@ -147,6 +151,10 @@ fn load_dep_graph(sess: &Session) -> LoadResult<(SerializedDepGraph, WorkProduct
 
     let _prof_timer = prof.generic_activity("incr_comp_load_dep_graph");
 
+    if let Some(dep_graph) = cache.and_then(|cache| cache.dep_graph(sess, &path, expected_hash)) {
+        return LoadResult::Ok { data: (dep_graph, prev_work_products) };
+    }
+
     match load_data(&path, sess) {
         LoadResult::DataOutOfDate => LoadResult::DataOutOfDate,
         LoadResult::LoadDepGraph(path, err) => LoadResult::LoadDepGraph(path, err),
@@ -180,33 +188,42 @@ fn load_dep_graph(sess: &Session) -> LoadResult<(SerializedDepGraph, WorkProduct
 ///
 /// If we are not in incremental compilation mode, returns `None`.
 /// Otherwise, tries to load the query result cache from disk,
-/// creating an empty cache if it could not be loaded.
-pub fn load_query_result_cache(sess: &Session) -> Option<OnDiskCache<'_>> {
+/// creating an empty cache if it could not be loaded. Its footer
+/// is taken from `cache` if it was decoded there.
+pub fn load_query_result_cache<'sess>(
+    sess: &'sess Session,
+    cache: Option<&IncrCompCache>,
+) -> Option<OnDiskCache<'sess>> {
     if sess.opts.incremental.is_none() {
         return None;
     }
 
     let _prof_timer = sess.prof.generic_activity("incr_comp_load_query_result_cache");
 
-    match load_data(&query_cache_path(sess), sess) {
+    let path = query_cache_path(sess);
+    match load_data(&path, sess) {
         LoadResult::Ok { data: (bytes, start_pos) } => {
-            Some(OnDiskCache::new(sess, bytes, start_pos))
+            match cache.and_then(|cache| cache.query_cache_footer(sess, &path)) {
+                Some(footer) => Some(OnDiskCache::with_footer(sess, bytes, footer)),
+                None => Some(OnDiskCache::new(sess, bytes, start_pos)),
+            }
         }
         _ => Some(OnDiskCache::new_empty(sess.source_map())),
     }
 }
 
-/// Setups the dependency graph by loading an existing graph from disk and set up streaming of a
-/// new graph to an incremental session directory.
+/// Setups the dependency graph by loading an existing graph from disk, or from `cache` if it was
+/// decoded there, and set up streaming of a new graph to an incremental session directory.
 pub fn setup_dep_graph(
     sess: &Session,
     crate_name: Symbol,
     stable_crate_id: StableCrateId,
+    cache: Option<&IncrCompCache>,
 ) -> Result<DepGraph, ErrorGuaranteed> {
     // `load_dep_graph` can only be called after `prepare_session_directory`.
     prepare_session_directory(sess, crate_name, stable_crate_id)?;
 
-    let res = sess.opts.build_dep_graph().then(|| load_dep_graph(sess));
+    let res = sess.opts.build_dep_graph().then(|| load_dep_graph(sess, cache));
 
     if sess.opts.incremental.is_some() {
         sess.time("incr_comp_garbage_collect_session_directories", || {
@@ -2,6 +2,7 @@
 //! into the given directory. At the same time, it also hashes the
 //! various HIR nodes.
 
+mod cache;
 mod data;
 mod dirty_clean;
 mod file_format;
@@ -10,6 +11,7 @@
 mod save;
 mod work_product;
 
+pub use cache::IncrCompCache;
 pub use fs::finalize_session_directory;
 pub use fs::in_incr_comp_dir;
 pub use fs::in_incr_comp_dir_sess;
@@ -0,0 +1,74 @@
+use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::owned_slice::OwnedSlice;
+use rustc_fs_util::FileStamp;
+use rustc_incremental::IncrCompCache;
+use rustc_session::cstore::{MetadataLoader, MetadataLoaderDyn};
+use rustc_target::spec::Target;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Data that doesn't belong to any session, kept by a process that runs many compilations, like
+/// `rustc --server`, for the next compilation to reuse. Hand it to every compilation through
+/// [`Config::caches`](crate::Config::caches).
+#[derive(Default)]
+pub struct Caches {
+    /// The dep graphs and query result caches that the latest sessions of incremental
+    /// directories wrote.
+    pub incr_comp: IncrCompCache,
+    /// The metadata of rlibs and dylibs, by the path of the file and whether it's a dylib.
+    metadata: Mutex<FxHashMap<(PathBuf, bool), (FileStamp, OwnedSlice)>>,
+}
+
+impl Caches {
+    /// Does the work that prepares the caches for the next compilation, like decoding the
+    /// files that the last one wrote. This is meant to run while the process waits for it.
+    pub fn prepare_next_compilation(&self) {
+        self.incr_comp.decode_pending();
+    }
+
+    fn metadata(
+        &self,
+        filename: &Path,
+        is_dylib: bool,
+        load: impl FnOnce() -> Result<OwnedSlice, String>,
+    ) -> Result<OwnedSlice, String> {
+        let Ok(stamp) = FileStamp::of(filename) else { return load() };
+        let key = (filename.to_owned(), is_dylib);
+        if let Some((cached_stamp, metadata)) = self.metadata.lock().unwrap().get(&key)
+            && *cached_stamp == stamp
+        {
+            return Ok(metadata.clone());
+        }
+
+        let metadata = load()?;
+        self.metadata.lock().unwrap().insert(key, (stamp, metadata.clone()));
+        Ok(metadata)
+    }
+}
+
+impl fmt::Debug for Caches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Caches").finish_non_exhaustive()
+    }
+}
+
+/// Reads the metadata of crates from [`Caches`], and reads the files that aren't in it, or were
+/// written again since they were cached, with the metadata loader of the codegen backend.
+#[derive(Debug)]
+pub(crate) struct CachingMetadataLoader {
+    pub(crate) metadata_loader: Box<MetadataLoaderDyn>,
+    pub(crate) caches: Arc<Caches>,
+}
+
+impl MetadataLoader for CachingMetadataLoader {
+    fn get_rlib_metadata(&self, target: &Target, filename: &Path) -> Result<OwnedSlice, String> {
+        self.caches
+            .metadata(filename, false, || self.metadata_loader.get_rlib_metadata(target, filename))
+    }
+
+    fn get_dylib_metadata(&self, target: &Target, filename: &Path) -> Result<OwnedSlice, String> {
+        self.caches
+            .metadata(filename, true, || self.metadata_loader.get_dylib_metadata(target, filename))
+    }
+}
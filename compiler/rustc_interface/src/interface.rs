@@ -1,4 +1,6 @@
+use crate::caches::CachingMetadataLoader;
 use crate::util;
+use crate::Caches;
 
 use rustc_ast::token;
 use rustc_ast::{self as ast, LitKind, MetaItemKind};
@@ -18,6 +20,7 @@
 use rustc_session::config::{
     self, Cfg, CheckCfg, ExpectedValues, Input, OutFileName, OutputFilenames,
 };
+use rustc_session::cstore::MetadataLoaderDyn;
 use rustc_session::parse::ParseSess;
 use rustc_session::CompilerIO;
 use rustc_session::Session;
@@ -42,6 +45,7 @@ pub struct Compiler {
     codegen_backend: Lrc<dyn CodegenBackend>,
     pub(crate) register_lints: Option<Box<dyn Fn(&Session, &mut LintStore) + Send + Sync>>,
     pub(crate) override_queries: Option<fn(&Session, &mut Providers)>,
+    pub(crate) caches: Option<Arc<Caches>>,
 }
 
 impl Compiler {
@@ -54,6 +58,17 @@ pub fn codegen_backend(&self) -> &Lrc<dyn CodegenBackend> {
     pub fn register_lints(&self) -> &Option<Box<dyn Fn(&Session, &mut LintStore) + Send + Sync>> {
         &self.register_lints
     }
+    /// Returns the metadata loader of the codegen backend, reading through the caches of the
+    /// compilation if it has any.
+    pub fn metadata_loader(&self) -> Box<MetadataLoaderDyn> {
+        let metadata_loader = self.codegen_backend.metadata_loader();
+        match &self.caches {
+            Some(caches) => {
+                Box::new(CachingMetadataLoader { metadata_loader, caches: caches.clone() })
+            }
+            None => metadata_loader,
+        }
+    }
     pub fn build_output_filenames(
         &self,
         sess: &Session,
@@ -339,6 +354,11 @@ pub struct Config {
     pub output_dir: Option<PathBuf>,
     pub output_file: Option<OutFileName>,
     pub ice_file: Option<PathBuf>,
+    /// Reads the source files of the crate: the input file, modules and the files of `include!`,
+    /// `include_str!` and `include_bytes!`. Use [`InMemoryFileLoader`] to compile sources that
+    /// aren't on disk.
+    ///
+    /// [`InMemoryFileLoader`]: rustc_span::source_map::InMemoryFileLoader
     pub file_loader: Option<Box<dyn FileLoader + Send + Sync>>,
     pub locale_resources: &'static [&'static str],
 
@@ -382,6 +402,10 @@ pub struct Config {
     /// This is mainly useful for other tools that reads that debuginfo to figure out
     /// how to call the compiler with the same arguments.
     pub expanded_args: Vec<String>,
+
+    /// Data that outlives this compilation, to reuse what the previous compilations in the same
+    /// process left there, and leave it for the next ones.
+    pub caches: Option<Arc<Caches>>,
 }
 
 // JUSTIFICATION: before session exists, only config
@@ -439,6 +463,7 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
                 codegen_backend: Lrc::from(codegen_backend),
                 register_lints: config.register_lints,
                 override_queries: config.override_queries,
+                caches: config.caches,
             };
 
             rustc_span::set_source_map(compiler.sess.parse_sess.clone_source_map(), move || {
@@ -450,6 +475,10 @@ pub fn run_compiler<R: Send>(config: Config, f: impl FnOnce(&Compiler) -> R + Se
                     f(&compiler)
                 };
 
+                if let Some(caches) = &compiler.caches {
+                    caches.incr_comp.record_session(&compiler.sess);
+                }
+
                 let prof = compiler.sess.prof.clone();
 
                 prof.generic_activity("drop_compiler").run(move || drop(compiler));
@@ -16,6 +16,7 @@
 use rustc_errors::{DiagnosticMessage, SubdiagnosticMessage};
 use rustc_fluent_macro::fluent_messages;
 
+mod caches;
 mod callbacks;
 mod errors;
 pub mod interface;
@@ -24,6 +25,7 @@
 mod queries;
 pub mod util;
 
+pub use caches::Caches;
 pub use callbacks::setup_callbacks;
 pub use interface::{run_compiler, Config};
 pub use passes::DEFAULT_QUERY_PROVIDERS;
@@ -438,12 +438,18 @@ fn write_out_deps(tcx: TyCtxt<'_>, outputs: &OutputFilenames, out_filenames: &[P
     let result: io::Result<()> = try {
         // Build a list of files used to compile the output and
         // write Makefile-compatible dependency rules
-        let mut files: Vec<String> = sess
-            .source_map()
+        let source_map = sess.source_map();
+        let mut files: Vec<String> = source_map
             .files()
             .iter()
             .filter(|fmap| fmap.is_real_file())
             .filter(|fmap| !fmap.is_imported())
+            .filter(|fmap| match &fmap.name {
+                FileName::Real(name) => {
+                    !name.local_path().is_some_and(|path| source_map.is_virtual_file(path))
+                }
+                _ => true,
+            })
             .map(|fmap| escape_dep_filename(&fmap.name.prefer_local().to_string()))
             .collect();
 
@@ -468,9 +474,17 @@ fn write_out_deps(tcx: TyCtxt<'_>, outputs: &OutputFilenames, out_filenames: &[P
             files.push(normalize_path(profile_sample.as_path().to_path_buf()));
         }
 
+        // And the `-Z lint-config` file
+        if let Some(ref lint_config) = sess.opts.unstable_opts.lint_config {
+            files.push(normalize_path(lint_config.as_path().to_path_buf()));
+        }
+
         // Debugger visualizer files
         for debugger_visualizer in tcx.debugger_visualizers(LOCAL_CRATE) {
-            files.push(normalize_path(debugger_visualizer.path.clone().unwrap()));
+            let path = debugger_visualizer.path.clone().unwrap();
+            if !source_map.is_virtual_file(&path) {
+                files.push(normalize_path(path));
+            }
         }
 
         if sess.binary_dep_depinfo() {
@@ -498,6 +512,7 @@ fn write_out_deps(tcx: TyCtxt<'_>, outputs: &OutputFilenames, out_filenames: &[P
 
         let write_deps_to_file = |file: &mut dyn Write| -> io::Result<()> {
             for path in out_filenames {
+                let path = sess.opts.display_output_path(path);
                 writeln!(file, "{}: {}\n", path.display(), files.join(" "))?;
             }
 
@@ -544,9 +559,10 @@ fn write_out_deps(tcx: TyCtxt<'_>, outputs: &OutputFilenames, out_filenames: &[P
     match result {
         Ok(_) => {
             if sess.opts.json_artifact_notifications {
-                sess.parse_sess
-                    .span_diagnostic
-                    .emit_artifact_notification(&deps_filename, "dep-info");
+                sess.parse_sess.span_diagnostic.emit_artifact_notification(
+                    &sess.opts.display_output_path(deps_filename),
+                    "dep-info",
+                );
             }
         }
         Err(error) => {
@@ -591,7 +607,7 @@ fn output_filenames(tcx: TyCtxt<'_>, (): ()) -> Arc<OutputFilenames> {
     // Ensure the source file isn't accidentally overwritten during compilation.
     if let Some(ref input_path) = sess.io.input.opt_path() {
         if sess.opts.will_create_output_file() {
-            if output_contains_path(&output_paths, input_path) {
+            if output_contains_path(&output_paths, &sess.opts.resolve_path(input_path)) {
                 sess.emit_fatal(errors::InputFileWouldBeOverWritten { path: input_path });
             }
             if let Some(ref dir_path) = output_conflicts_with_dir(&output_paths) {
@@ -672,7 +688,9 @@ pub fn create_global_ctxt<'tcx>(
     dep_graph.assert_ignored();
 
     let sess = &compiler.session();
-    let query_result_on_disk_cache = rustc_incremental::load_query_result_cache(sess);
+    let incr_comp_cache = compiler.caches.as_ref().map(|caches| &caches.incr_comp);
+    let query_result_on_disk_cache =
+        rustc_incremental::load_query_result_cache(sess, incr_comp_cache);
 
     let codegen_backend = compiler.codegen_backend();
     let mut providers = *DEFAULT_QUERY_PROVIDERS;
@@ -146,16 +146,17 @@ pub fn global_ctxt(&'tcx self) -> Result<QueryResult<'_, &'tcx GlobalCtxt<'tcx>>
                 sess.opts.cg.metadata.clone(),
                 sess.cfg_version,
             );
-            let dep_graph = setup_dep_graph(sess, crate_name, stable_crate_id)?;
+            let incr_comp_cache = self.compiler.caches.as_ref().map(|caches| &caches.incr_comp);
+            let dep_graph = setup_dep_graph(sess, crate_name, stable_crate_id, incr_comp_cache)?;
 
             let lint_store = Lrc::new(passes::create_lint_store(
                 sess,
-                &*self.codegen_backend().metadata_loader(),
+                &*self.compiler.metadata_loader(),
                 self.compiler.register_lints.as_deref(),
                 &pre_configured_attrs,
             ));
             let cstore = FreezeLock::new(Box::new(CStore::new(
-                self.codegen_backend().metadata_loader(),
+                self.compiler.metadata_loader(),
                 stable_crate_id,
             )) as _);
             let definitions = FreezeLock::new(Definitions::new(stable_crate_id));
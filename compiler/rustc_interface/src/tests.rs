@@ -2,13 +2,15 @@
 use crate::interface::parse_cfg;
 use rustc_data_structures::profiling::TimePassesFormat;
 use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
+use rustc_session::config::lint_config::LintConfig;
 use rustc_session::config::{
-    build_configuration, build_session_options, rustc_optgroups, BranchProtection, CFGuard, Cfg,
-    DebugInfo, DumpMonoStatsFormat, ErrorOutputType, ExternEntry, ExternLocation, Externs, Input,
-    InstrumentCoverage, InstrumentXRay, LinkSelfContained, LinkerPluginLto, LocationDetail, LtoCli,
-    MirSpanview, OomStrategy, Options, OutFileName, OutputType, OutputTypes, PAuthKey, PacRet,
-    Passes, Polonius, ProcMacroExecutionStrategy, Strip, SwitchWithOptPath, SymbolManglingVersion,
-    TraitSolver, WasiExecModel,
+    build_configuration, build_session_options, build_session_options_in, rustc_optgroups,
+    BranchProtection, CFGuard, Cfg, DebugInfo, DumpMonoStatsFormat, ErrorOutputType, ExternEntry,
+    ExternLocation, Externs, Input, InstrumentCoverage, InstrumentXRay, LinkSelfContained,
+    LinkerPluginLto, LocationDetail, LtoCli, MirSpanview, OomStrategy, Options, OutFileName,
+    OutputType, OutputTypes, PAuthKey, PacRet, Passes, Polonius, PrintSizesFormat,
+    ProcMacroExecutionStrategy, Strip, SwitchWithOptPath, SymbolManglingVersion, TraitSolver,
+    WasiExecModel,
 };
 use rustc_session::lint::Level;
 use rustc_session::search_paths::SearchPath;
@@ -636,6 +638,10 @@ macro_rules! tracked {
 
     // Make sure that changing a [TRACKED_NO_CRATE_HASH] option leaves the crate hash unchanged but changes the incremental hash.
     // tidy-alphabetical-start
+    tracked!(
+        lint_config,
+        Some(LintConfig::parse("/home/bors/rust".into(), "[a]\ndeny = [\"unsafe_code\"]").unwrap())
+    );
     tracked!(
         real_rust_source_base_dir,
         Some("/home/bors/rust/.rustup/toolchains/nightly/lib/rustlib/src/rust".into())
@@ -661,6 +667,7 @@ macro_rules! untracked {
     // tidy-alphabetical-start
     untracked!(assert_incr_state, Some(String::from("loaded")));
     untracked!(deduplicate_diagnostics, false);
+    untracked!(diagnostic_baseline, Some(PathBuf::from("baseline.json")));
     untracked!(dont_buffer_diagnostics, true);
     untracked!(dump_dep_graph, true);
     untracked!(dump_mir, Some(String::from("abc")));
@@ -681,6 +688,7 @@ macro_rules! untracked {
     untracked!(input_stats, true);
     untracked!(keep_hygiene_data, true);
     untracked!(link_native_libraries, false);
+    untracked!(lint_config, Some(PathBuf::from("lint-config.toml")));
     untracked!(llvm_time_trace, true);
     untracked!(ls, vec!["all".to_owned()]);
     untracked!(macro_backtrace, true);
@@ -697,11 +705,13 @@ macro_rules! untracked {
     untracked!(print_codegen_stats, true);
     untracked!(print_llvm_passes, true);
     untracked!(print_mono_items, Some(String::from("abc")));
+    untracked!(print_sizes_format, PrintSizesFormat::Json);
     untracked!(print_type_sizes, true);
     untracked!(proc_macro_backtrace, true);
     untracked!(proc_macro_execution_strategy, ProcMacroExecutionStrategy::CrossThread);
     untracked!(profile_closures, true);
     untracked!(query_dep_graph, true);
+    untracked!(reduce_ice, true);
     untracked!(self_profile, SwitchWithOptPath::Enabled(None));
     untracked!(self_profile_events, Some(vec![String::new()]));
     untracked!(span_debug, true);
@@ -719,6 +729,7 @@ macro_rules! untracked {
     untracked!(unstable_options, true);
     untracked!(validate_mir, true);
     untracked!(verbose, true);
+    untracked!(write_baseline, true);
     untracked!(write_long_types_to_disk, false);
     // tidy-alphabetical-end
 
@@ -859,3 +870,30 @@ fn test_edition_parsing() {
     let sessopts = build_session_options(&mut handler, &matches);
     assert!(sessopts.edition == Edition::Edition2018)
 }
+
+#[test]
+fn test_working_dir_paths() {
+    let mut handler = EarlyErrorHandler::new(ErrorOutputType::default());
+    let working_dir = std::env::temp_dir().join("working-dir");
+
+    let args = ["--emit=link,metadata=out/x.rmeta", "-L", "deps", "-C", "incremental=incr"];
+    let matches = optgroups().parse(&args.map(String::from)).unwrap();
+    let mut sessopts = build_session_options_in(&mut handler, &matches, Some(working_dir.clone()));
+    assert_eq!(
+        sessopts.output_types.get(&OutputType::Metadata),
+        Some(&Some(OutFileName::Real(working_dir.join("out/x.rmeta"))))
+    );
+    assert_eq!(sessopts.search_paths[0].dir, working_dir.join("deps"));
+    assert_eq!(sessopts.incremental, Some(working_dir.join("incr")));
+
+    let (mut output_dir, mut output_file) = (None, None);
+    sessopts.resolve_output_paths(&mut output_dir, &mut output_file);
+    assert_eq!(output_dir.as_deref(), Some(working_dir.as_path()));
+    assert_eq!(output_file, None);
+
+    // Outputs are named the way they were given.
+    let metadata = working_dir.join("out/x.rmeta");
+    assert_eq!(sessopts.display_output_path(&metadata), Path::new("out/x.rmeta"));
+    let rlib = working_dir.join("libfoo.rlib");
+    assert_eq!(sessopts.display_output_path(&rlib), Path::new("libfoo.rlib"));
+}
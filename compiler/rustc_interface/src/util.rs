@@ -82,11 +82,13 @@ pub fn create_session(
     // target_override is documented to be called before init(), so this is okay
     let target_override = codegen_backend.target_override(&sopts);
 
+    let additional_ftl =
+        sopts.unstable_opts.translate_additional_ftl.as_deref().map(|path| sopts.resolve_path(path));
     let bundle = match rustc_errors::fluent_bundle(
         sopts.maybe_sysroot.clone(),
         sysroot_candidates().to_vec(),
         sopts.unstable_opts.translate_lang.clone(),
-        sopts.unstable_opts.translate_additional_ftl.as_deref(),
+        additional_ftl.as_deref(),
         sopts.unstable_opts.translate_directionality_markers,
     ) {
         Ok(bundle) => bundle,
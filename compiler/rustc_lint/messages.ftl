@@ -325,6 +325,8 @@ lint_invalid_reference_casting_note_book = for more information, visit <https://
 
 lint_invalid_reference_casting_note_ty_has_interior_mutability = even for types with interior mutability, the only legal way to obtain a mutable pointer from a shared reference is through `UnsafeCell::get`
 
+lint_lint_config_source = `forbid` lint level was set by the `-Z lint-config` file for `{$pattern}`
+
 lint_lintpass_by_hand = implementing `LintPass` by hand
     .help = try using `declare_lint_pass!` or `impl_lint_pass!` instead
 
@@ -492,6 +494,8 @@ lint_renamed_lint = lint `{$name}` has been renamed to `{$replace}`
     .suggestion = use the new name
     .help = use the new name `{$replace}`
 
+lint_requested_in_lint_config = requested by the `-Z lint-config` file for `{$pattern}`
+
 lint_requested_level = requested on the command line with `{$level} {$lint_name}`
 
 lint_span_use_eq_ctxt = use `.eq_ctxt()` instead of `.ctxt() == .ctxt()`
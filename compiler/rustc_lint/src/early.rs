@@ -90,11 +90,24 @@ fn visit_param(&mut self, param: &'a ast::Param) {
     }
 
     fn visit_item(&mut self, it: &'a ast::Item) {
+        // Modules loaded from their own file get the levels that `-Z lint-config` sets for the
+        // file, which their attributes override.
+        let lint_config = match &it.kind {
+            ast::ItemKind::Mod(_, ast::ModKind::Loaded(_, ast::Inline::No, spans))
+                if self.context.sess().opts.lint_config.is_some() =>
+            {
+                Some(self.context.builder.push_lint_config(spans.inner_span))
+            }
+            _ => None,
+        };
         self.with_lint_attrs(it.id, &it.attrs, |cx| {
             lint_callback!(cx, check_item, it);
             ast_visit::walk_item(cx, it);
             lint_callback!(cx, check_item_post, it);
-        })
+        });
+        if let Some(push) = lint_config {
+            self.context.builder.pop(push);
+        }
     }
 
     fn visit_foreign_item(&mut self, it: &'a ast::ForeignItem) {
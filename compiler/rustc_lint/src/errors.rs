@@ -21,6 +21,7 @@ pub enum OverruledAttributeSub {
     DefaultSource { id: String },
     NodeSource { span: Span, reason: Option<Symbol> },
     CommandLineSource,
+    LintConfigSource { pattern: Symbol },
 }
 
 impl AddToDiagnostic for OverruledAttributeSub {
@@ -43,6 +44,10 @@ fn add_to_diagnostic_with<F>(self, diag: &mut Diagnostic, _: F)
             OverruledAttributeSub::CommandLineSource => {
                 diag.note(fluent::lint_command_line_source);
             }
+            OverruledAttributeSub::LintConfigSource { pattern } => {
+                diag.note(fluent::lint_lint_config_source);
+                diag.set_arg("pattern", pattern);
+            }
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::errors::{CheckNameUnknownTool, RequestedLevel, UnsupportedGroup};
 use crate::lints::{
     DeprecatedLintNameFromCommandLine, RemovedLintFromCommandLine, RenamedLintFromCommandLine,
-    UnknownLintFromCommandLine,
+    UnknownLintFromCommandLine, UnknownLintFromLintConfig,
 };
 use crate::{
     builtin::MISSING_DOCS,
@@ -39,7 +39,8 @@
 use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Symbol};
-use rustc_span::{Span, DUMMY_SP};
+use rustc_span::{FileName, Span, DUMMY_SP};
+use std::path::Path;
 
 use crate::errors::{
     MalformedAttribute, MalformedAttributeSub, OverruledAttribute, OverruledAttributeSub,
@@ -174,6 +175,22 @@ fn shallow_lint_levels_on(tcx: TyCtxt<'_>, owner: hir::OwnerId) -> ShallowLintLe
         levels.add_command_line();
     }
 
+    // The crate root and the modules loaded from their own file get the levels that
+    // `-Z lint-config` sets for their file.
+    if tcx.sess.opts.lint_config.is_some() {
+        let source_map = tcx.sess.source_map();
+        match tcx.hir().owner(owner) {
+            hir::OwnerNode::Crate(mod_) => levels.add_lint_config(mod_.spans.inner_span),
+            hir::OwnerNode::Item(hir::Item { kind: hir::ItemKind::Mod(mod_), span, .. })
+                if source_map.span_to_filename(mod_.spans.inner_span)
+                    != source_map.span_to_filename(*span) =>
+            {
+                levels.add_lint_config(mod_.spans.inner_span)
+            }
+            _ => {}
+        }
+    }
+
     match attrs.map.range(..) {
         // There is only something to do if there are attributes at all.
         [] => {}
@@ -489,6 +506,9 @@ fn process_command_line(&mut self) {
             .list
             .push(LintSet { specs: FxHashMap::default(), parent: COMMAND_LINE });
         self.add_command_line();
+        if let Some(path) = self.sess.io.input.opt_path() {
+            self.add_lint_config_for_file(path);
+        }
     }
 
     /// Pushes a list of AST lint attributes onto this context.
@@ -511,11 +531,24 @@ pub(crate) fn push(
         is_crate_node: bool,
         source_hir_id: Option<HirId>,
     ) -> BuilderPush {
+        self.push_with(|builder| builder.add(attrs, is_crate_node, source_hir_id))
+    }
+
+    /// Pushes the lint levels that the `-Z lint-config` file sets for the module whose contents
+    /// are `inner_span`. This must be done before pushing the attributes of the module, which
+    /// override them.
+    ///
+    /// Don't forget to call `pop`!
+    pub(crate) fn push_lint_config(&mut self, inner_span: Span) -> BuilderPush {
+        self.push_with(|builder| builder.add_lint_config(inner_span))
+    }
+
+    fn push_with(&mut self, add: impl FnOnce(&mut Self)) -> BuilderPush {
         let prev = self.provider.cur;
         self.provider.cur =
             self.provider.sets.list.push(LintSet { specs: FxHashMap::default(), parent: prev });
 
-        self.add(attrs, is_crate_node, source_hir_id);
+        add(self);
 
         if self.provider.current_specs().is_empty() {
             self.provider.sets.list.pop();
@@ -629,6 +662,40 @@ fn add_command_line(&mut self) {
         }
     }
 
+    /// Adds the lint levels that the `-Z lint-config` file sets for the module whose contents are
+    /// `inner_span`.
+    fn add_lint_config(&mut self, inner_span: Span) {
+        if let FileName::Real(name) = self.sess.source_map().span_to_filename(inner_span)
+            && let Some(path) = name.local_path()
+        {
+            self.add_lint_config_for_file(path);
+        }
+    }
+
+    fn add_lint_config_for_file(&mut self, path: &Path) {
+        let sess = self.sess;
+        let Some(config) = &sess.opts.lint_config else {
+            return;
+        };
+        for rule in config.rules_for(path, sess.opts.working_dir.local_path_if_available()) {
+            let pattern = Symbol::intern(&rule.pattern);
+            for &(ref lint_name, level) in &rule.levels {
+                let Ok(ids) = self.store.find_lints(lint_name) else {
+                    let lint =
+                        UnknownLintFromLintConfig { name: lint_name, pattern: &rule.pattern };
+                    self.emit_lint(UNKNOWN_LINTS, lint);
+                    continue;
+                };
+                let src = LintLevelSource::LintConfig { name: Symbol::intern(lint_name), pattern };
+                for id in ids {
+                    if self.check_gated_lint(id, DUMMY_SP, true) {
+                        self.insert_spec(id, (level, src));
+                    }
+                }
+            }
+        }
+    }
+
     /// Attempts to insert the `id` to `level_src` map entry. If unsuccessful
     /// (e.g. if a forbid was already inserted on the same scope), then emits a
     /// diagnostic with no change to `specs`.
@@ -658,6 +725,7 @@ fn insert_spec(&mut self, id: LintId, (mut level, src): LevelAndSource) {
                     LintLevelSource::Default => false,
                     LintLevelSource::Node { name, .. } => self.store.is_lint_group(name),
                     LintLevelSource::CommandLine(symbol, _) => self.store.is_lint_group(symbol),
+                    LintLevelSource::LintConfig { name, .. } => self.store.is_lint_group(name),
                 };
                 debug!(
                     "fcw_warning={:?}, specs.get(&id) = {:?}, old_src={:?}, id_name={:?}",
@@ -674,6 +742,9 @@ fn insert_spec(&mut self, id: LintId, (mut level, src): LevelAndSource) {
                         OverruledAttributeSub::NodeSource { span, reason }
                     }
                     LintLevelSource::CommandLine(_, _) => OverruledAttributeSub::CommandLineSource,
+                    LintLevelSource::LintConfig { pattern, .. } => {
+                        OverruledAttributeSub::LintConfigSource { pattern }
+                    }
                 };
                 if !fcw_warning {
                     self.sess.emit_err(OverruledAttribute {
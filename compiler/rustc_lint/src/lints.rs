@@ -1107,6 +1107,14 @@ pub struct UnknownLintFromCommandLine<'a> {
     pub requested_level: RequestedLevel<'a>,
 }
 
+#[derive(LintDiagnostic)]
+#[diag(lint_unknown_lint, code = "E0602")]
+#[note(lint_requested_in_lint_config)]
+pub struct UnknownLintFromLintConfig<'a> {
+    pub name: &'a str,
+    pub pattern: &'a str,
+}
+
 #[derive(LintDiagnostic)]
 #[diag(lint_ignored_unless_crate_specified)]
 pub struct IgnoredUnlessCrateSpecified<'a> {
@@ -34,6 +34,10 @@ pub enum LintLevelSource {
     /// The provided `Level` is the level specified on the command line.
     /// (The actual level may be lower due to `--cap-lints`.)
     CommandLine(Symbol, Level),
+
+    /// Lint level was set by the `-Z lint-config` file, for the module of a file that matches
+    /// `pattern`.
+    LintConfig { name: Symbol, pattern: Symbol },
 }
 
 impl LintLevelSource {
@@ -42,6 +46,7 @@ pub fn name(&self) -> Symbol {
             LintLevelSource::Default => symbol::kw::Default,
             LintLevelSource::Node { name, .. } => name,
             LintLevelSource::CommandLine(name, _) => name,
+            LintLevelSource::LintConfig { name, .. } => name,
         }
     }
 
@@ -50,6 +55,7 @@ pub fn span(&self) -> Span {
             LintLevelSource::Default => DUMMY_SP,
             LintLevelSource::Node { span, .. } => span,
             LintLevelSource::CommandLine(_, _) => DUMMY_SP,
+            LintLevelSource::LintConfig { .. } => DUMMY_SP,
         }
     }
 }
@@ -230,6 +236,18 @@ pub fn explain_lint_level_source(
                 ));
             }
         }
+        LintLevelSource::LintConfig { name: lint_config_name, pattern } => {
+            let level_str = level.as_str();
+            err.note_once(format!(
+                "`{level_str} = [\"{lint_config_name}\"]` set by the `-Z lint-config` file for \
+                 `{pattern}`"
+            ));
+            if lint_config_name.as_str() != name {
+                err.note_once(format!(
+                    "`#[{level_str}({name})]` implied by `#[{level_str}({lint_config_name})]`"
+                ));
+            }
+        }
         LintLevelSource::Node { name: lint_attr_name, span, reason, .. } => {
             if let Some(rationale) = reason {
                 err.note(rationale.to_string());
@@ -97,9 +97,11 @@ pub struct OnDiskCache<'sess> {
     foreign_expn_data: UnhashMap<ExpnHash, u32>,
 }
 
-// This type is used only for serialization and deserialization.
-#[derive(Encodable, Decodable)]
-struct Footer {
+/// The lookup tables at the end of a serialized `OnDiskCache`. They don't refer to anything in a
+/// session, so a process that compiles the same crate again can decode them ahead of time and
+/// hand them to [`OnDiskCache::with_footer`].
+#[derive(Clone, Encodable, Decodable)]
+pub struct Footer {
     file_index_to_stable_id: FxHashMap<SourceFileIndex, EncodedSourceFileId>,
     query_result_index: EncodedDepNodeIndex,
     side_effects_index: EncodedDepNodeIndex,
@@ -165,21 +167,28 @@ fn new(tcx: TyCtxt<'_>, file: &SourceFile) -> EncodedSourceFileId {
 impl<'sess> OnDiskCache<'sess> {
     /// Creates a new `OnDiskCache` instance from the serialized data in `data`.
     pub fn new(sess: &'sess Session, data: Mmap, start_pos: usize) -> Self {
-        debug_assert!(sess.opts.incremental.is_some());
+        let footer = Self::decode_footer(&data, start_pos);
+        Self::with_footer(sess, data, footer)
+    }
 
-        // Wrap in a scope so we can borrow `data`.
-        let footer: Footer = {
-            let mut decoder = MemDecoder::new(&data, start_pos);
+    /// Decodes the footer of the serialized data in `data`, which holds the lookup tables.
+    pub fn decode_footer(data: &[u8], start_pos: usize) -> Footer {
+        let mut decoder = MemDecoder::new(data, start_pos);
 
-            // Decode the *position* of the footer, which can be found in the
-            // last 8 bytes of the file.
-            let footer_pos = decoder
-                .with_position(decoder.len() - IntEncodedWithFixedSize::ENCODED_SIZE, |decoder| {
-                    IntEncodedWithFixedSize::decode(decoder).0 as usize
-                });
-            // Decode the file footer, which contains all the lookup tables, etc.
-            decoder.with_position(footer_pos, |decoder| decode_tagged(decoder, TAG_FILE_FOOTER))
-        };
+        // Decode the *position* of the footer, which can be found in the
+        // last 8 bytes of the file.
+        let footer_pos = decoder
+            .with_position(decoder.len() - IntEncodedWithFixedSize::ENCODED_SIZE, |decoder| {
+                IntEncodedWithFixedSize::decode(decoder).0 as usize
+            });
+        // Decode the file footer, which contains all the lookup tables, etc.
+        decoder.with_position(footer_pos, |decoder| decode_tagged(decoder, TAG_FILE_FOOTER))
+    }
+
+    /// Creates a new `OnDiskCache` instance from the serialized data in `data`, whose footer has
+    /// already been decoded into `footer`.
+    pub fn with_footer(sess: &'sess Session, data: Mmap, footer: Footer) -> Self {
+        debug_assert!(sess.opts.incremental.is_some());
 
         Self {
             serialized_data: RwLock::new(Some(data)),
@@ -0,0 +1,463 @@
+use std::assert_matches::assert_matches;
+use std::collections::hash_map::Entry;
+use std::collections::VecDeque;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::mir::coverage::{
+    BlockMarkerId, BranchSpan, ConditionId, ConditionInfo, CoverageKind, MCDCBranchSpan,
+    MCDCDecisionSpan,
+};
+use rustc_middle::mir::{self, BasicBlock, UnOp};
+use rustc_middle::thir::{ExprId, ExprKind, LogicalOp, Thir};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::def_id::LocalDefId;
+use rustc_span::Span;
+
+use crate::build::Builder;
+use crate::errors::MCDCExceedsConditionNumLimit;
+
+/// The maximum number of conditions in a decision that LLVM can instrument
+/// for MC/DC. Decisions with more conditions are instrumented as if they were
+/// made of independent branches.
+const MAX_CONDITIONS_NUM_IN_DECISION: usize = 6;
+
+pub(crate) struct BranchInfoBuilder {
+    /// Maps condition expressions to their enclosing `!`, for better instrumentation.
+    nots: FxHashMap<ExprId, NotInfo>,
+
+    num_block_markers: usize,
+    branch_spans: Vec<BranchSpan>,
+
+    /// Only present if MC/DC coverage is enabled.
+    mcdc_state: Option<MCDCState>,
+    mcdc_branch_spans: Vec<MCDCBranchSpan>,
+    mcdc_decision_spans: Vec<MCDCDecisionSpan>,
+}
+
+#[derive(Clone, Copy)]
+struct NotInfo {
+    /// When visiting the associated expression as a branch condition, treat this
+    /// enclosing `!` as the branch condition instead.
+    enclosing_not: ExprId,
+    /// True if the associated expression is nested within an odd number of `!`
+    /// expressions relative to `enclosing_not` (inclusive of `enclosing_not`).
+    is_flipped: bool,
+}
+
+impl BranchInfoBuilder {
+    /// Creates a new branch info builder, but only if branch coverage instrumentation
+    /// is enabled and `def_id` represents a function that is eligible for coverage.
+    pub(crate) fn new_if_enabled(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Option<Self> {
+        if !tcx.sess.instrument_coverage_branch()
+            || !tcx.hir().body_owner_kind(def_id).is_fn_or_closure()
+        {
+            return None;
+        }
+
+        Some(Self {
+            nots: FxHashMap::default(),
+            num_block_markers: 0,
+            branch_spans: vec![],
+            mcdc_state: tcx.sess.instrument_coverage_mcdc().then(MCDCState::default),
+            mcdc_branch_spans: vec![],
+            mcdc_decision_spans: vec![],
+        })
+    }
+
+    /// Unary `!` expressions inside an `if` condition are lowered by lowering
+    /// their argument instead, and then reversing the then/else arms of that `if`.
+    ///
+    /// That's awkward for branch coverage instrumentation, so to work around that
+    /// we pre-emptively visit any affected `!` expressions, and record extra
+    /// information that [`Builder::visit_coverage_branch_condition`] can use to
+    /// synthesize branch instrumentation for the enclosing `!`.
+    pub(crate) fn visit_unary_not(&mut self, thir: &Thir<'_>, unary_not: ExprId) {
+        assert_matches!(thir[unary_not].kind, ExprKind::Unary { op: UnOp::Not, .. });
+
+        self.visit_with_not_info(
+            thir,
+            unary_not,
+            // Set `is_flipped: false` for the `!` itself, so that its enclosed
+            // expression will have `is_flipped: true`.
+            NotInfo { enclosing_not: unary_not, is_flipped: false },
+        );
+    }
+
+    fn visit_with_not_info(&mut self, thir: &Thir<'_>, expr_id: ExprId, not_info: NotInfo) {
+        match self.nots.entry(expr_id) {
+            // This expression has already been marked by an enclosing `!`.
+            Entry::Occupied(_) => return,
+            Entry::Vacant(entry) => entry.insert(not_info),
+        };
+
+        match thir[expr_id].kind {
+            ExprKind::Unary { op: UnOp::Not, arg } => {
+                let not_info = NotInfo { is_flipped: !not_info.is_flipped, ..not_info };
+                self.visit_with_not_info(thir, arg, not_info);
+            }
+            ExprKind::Scope { value, .. } => self.visit_with_not_info(thir, value, not_info),
+            ExprKind::Use { source } => self.visit_with_not_info(thir, source, not_info),
+            // All other expressions (including `&&` and `||`) don't need any
+            // special handling of their contents, so stop visiting.
+            _ => {}
+        }
+    }
+
+    fn next_block_marker_id(&mut self) -> BlockMarkerId {
+        let id = BlockMarkerId::from_usize(self.num_block_markers);
+        self.num_block_markers += 1;
+        id
+    }
+
+    fn record_branch(
+        &mut self,
+        tcx: TyCtxt<'_>,
+        span: Span,
+        true_marker: BlockMarkerId,
+        false_marker: BlockMarkerId,
+    ) {
+        let Some(mcdc_state) = self.mcdc_state.as_mut() else {
+            self.branch_spans.push(BranchSpan { span, true_marker, false_marker });
+            return;
+        };
+
+        let Some(condition_info) = mcdc_state.take_condition(true_marker, false_marker) else {
+            // This condition doesn't belong to a decision that is tracked for MC/DC.
+            self.mcdc_branch_spans.push(MCDCBranchSpan {
+                span,
+                condition_info: None,
+                true_marker,
+                false_marker,
+            });
+            return;
+        };
+
+        let ctx = mcdc_state.current_ctx_mut().expect("a condition was taken from this context");
+        ctx.branch_spans.push(MCDCBranchSpan {
+            span,
+            condition_info: Some(condition_info),
+            true_marker,
+            false_marker,
+        });
+        if ctx.decision_stack.is_empty() {
+            // All the conditions of the decision have been evaluated.
+            let (decision, branch_spans, is_supported) = ctx.take_decision();
+            self.append_mcdc_decision(tcx, decision, branch_spans, is_supported);
+        }
+    }
+
+    fn append_mcdc_decision(
+        &mut self,
+        tcx: TyCtxt<'_>,
+        decision: MCDCDecisionSpan,
+        mut branch_spans: Vec<MCDCBranchSpan>,
+        is_supported: bool,
+    ) {
+        if is_supported && decision.conditions_num > MAX_CONDITIONS_NUM_IN_DECISION {
+            tcx.sess.emit_warning(MCDCExceedsConditionNumLimit {
+                span: decision.span,
+                conditions_num: decision.conditions_num,
+                max_conditions_num: MAX_CONDITIONS_NUM_IN_DECISION,
+            });
+        }
+        if is_supported && decision.conditions_num <= MAX_CONDITIONS_NUM_IN_DECISION {
+            self.mcdc_decision_spans.push(decision);
+        } else {
+            // Instrument the conditions of the decision as independent branches.
+            for branch in &mut branch_spans {
+                branch.condition_info = None;
+            }
+        }
+        self.mcdc_branch_spans.extend(branch_spans);
+    }
+
+    pub(crate) fn into_done(self) -> Option<Box<mir::coverage::BranchInfo>> {
+        let Self {
+            nots: _,
+            num_block_markers,
+            branch_spans,
+            mcdc_state: _,
+            mcdc_branch_spans,
+            mcdc_decision_spans,
+        } = self;
+
+        if num_block_markers == 0 {
+            assert!(branch_spans.is_empty());
+            assert!(mcdc_branch_spans.is_empty());
+            return None;
+        }
+
+        Some(Box::new(mir::coverage::BranchInfo {
+            num_block_markers,
+            branch_spans,
+            mcdc_branch_spans,
+            mcdc_decision_spans,
+        }))
+    }
+}
+
+/// Tracks the decisions and conditions that are being lowered, to assign
+/// condition IDs for MC/DC.
+#[derive(Default)]
+struct MCDCState {
+    /// One entry for each condition that is currently being lowered, e.g. the
+    /// condition of an `if` nested within the condition of another `if`. Only
+    /// the outermost `if` condition is tracked as a decision, because LLVM
+    /// can't record the test vectors of nested decisions.
+    decision_ctx_stack: Vec<Option<MCDCDecisionCtx>>,
+}
+
+#[derive(Default)]
+struct MCDCDecisionCtx {
+    /// The conditions that remain to be visited, with the next one on top.
+    decision_stack: VecDeque<ConditionInfo>,
+    processing_decision: Option<MCDCDecisionSpan>,
+    /// The conditions of the decision that have been visited so far.
+    branch_spans: Vec<MCDCBranchSpan>,
+    /// Set if the decision contains a `let` expression, which can't be
+    /// instrumented as a condition.
+    has_let: bool,
+}
+
+impl MCDCDecisionCtx {
+    fn take_decision(&mut self) -> (MCDCDecisionSpan, Vec<MCDCBranchSpan>, bool) {
+        let decision = self.processing_decision.take().expect("conditions belong to a decision");
+        let branch_spans = std::mem::take(&mut self.branch_spans);
+        (decision, branch_spans, !std::mem::take(&mut self.has_let))
+    }
+}
+
+impl MCDCState {
+    fn current_ctx_mut(&mut self) -> Option<&mut MCDCDecisionCtx> {
+        self.decision_ctx_stack.last_mut().and_then(Option::as_mut)
+    }
+
+    // At first we assign ConditionIds for each sub expression.
+    // If the sub expression is composite, re-assign its ConditionId to its LHS
+    // and generate a new ConditionId for its RHS.
+    //
+    // Example: "x = (A && B) || (C && D) || (D && F)"
+    //
+    //      Visit Depth1:
+    //              (A && B) || (C && D) || (D && F)
+    //              ^-------LHS--------^    ^-RHS--^
+    //                      ID=1              ID=2
+    //
+    //      Visit LHS-Depth2:
+    //              (A && B) || (C && D)
+    //              ^-LHS--^    ^-RHS--^
+    //                ID=1        ID=3
+    //
+    //      Visit LHS-Depth3:
+    //               (A && B)
+    //               LHS   RHS
+    //               ID=1  ID=4
+    //
+    //      Visit RHS-Depth3:
+    //                         (C && D)
+    //                         LHS   RHS
+    //                         ID=3  ID=5
+    //
+    //      Visit RHS-Depth2:              (D && F)
+    //                                     LHS   RHS
+    //                                     ID=2  ID=6
+    //
+    //      Visit Depth1:
+    //              (A && B)  || (C && D)  || (D && F)
+    //              ID=1  ID=4   ID=3  ID=5   ID=2  ID=6
+    //
+    // A node ID of '0' always means MC/DC isn't being tracked.
+    //
+    // If a "next" ID is '0', it means it's the end of the test vector.
+    //
+    // As the compiler tracks expressions in pre-order, we can ensure that the
+    // condition info of parents is always assigned when their children are visited.
+    // - If the op is AND, the "false_next" of LHS and RHS should be the parent's
+    //   "false_next". While the "true_next" of the LHS is the RHS, the "true_next"
+    //   of the RHS is the parent's "true_next".
+    // - If the op is OR, the "true_next" of LHS and RHS should be the parent's
+    //   "true_next". While the "false_next" of the LHS is the RHS, the "false_next"
+    //   of the RHS is the parent's "false_next".
+    fn record_conditions(&mut self, op: LogicalOp, span: Span) {
+        let Some(ctx) = self.current_ctx_mut() else { return };
+
+        let decision = match ctx.processing_decision.as_mut() {
+            Some(decision) => {
+                decision.span = decision.span.to(span);
+                decision
+            }
+            None => ctx.processing_decision.insert(MCDCDecisionSpan {
+                span,
+                conditions_num: 0,
+                end_markers: vec![],
+            }),
+        };
+
+        let parent_condition = ctx.decision_stack.pop_back().unwrap_or_default();
+        let lhs_id = if parent_condition.condition_id == ConditionId::NONE {
+            decision.conditions_num += 1;
+            ConditionId::from_usize(decision.conditions_num)
+        } else {
+            parent_condition.condition_id
+        };
+
+        decision.conditions_num += 1;
+        let rhs_condition_id = ConditionId::from_usize(decision.conditions_num);
+
+        let (lhs, rhs) = match op {
+            LogicalOp::And => {
+                let lhs = ConditionInfo {
+                    condition_id: lhs_id,
+                    true_next_id: rhs_condition_id,
+                    false_next_id: parent_condition.false_next_id,
+                };
+                let rhs = ConditionInfo {
+                    condition_id: rhs_condition_id,
+                    true_next_id: parent_condition.true_next_id,
+                    false_next_id: parent_condition.false_next_id,
+                };
+                (lhs, rhs)
+            }
+            LogicalOp::Or => {
+                let lhs = ConditionInfo {
+                    condition_id: lhs_id,
+                    true_next_id: parent_condition.true_next_id,
+                    false_next_id: rhs_condition_id,
+                };
+                let rhs = ConditionInfo {
+                    condition_id: rhs_condition_id,
+                    true_next_id: parent_condition.true_next_id,
+                    false_next_id: parent_condition.false_next_id,
+                };
+                (lhs, rhs)
+            }
+        };
+        // We visit the expression tree in pre-order, so place the left-hand side on top.
+        ctx.decision_stack.push_back(rhs);
+        ctx.decision_stack.push_back(lhs);
+    }
+
+    fn take_condition(
+        &mut self,
+        true_marker: BlockMarkerId,
+        false_marker: BlockMarkerId,
+    ) -> Option<ConditionInfo> {
+        let ctx = self.current_ctx_mut()?;
+        let condition_info = ctx.decision_stack.pop_back()?;
+        let decision = ctx
+            .processing_decision
+            .as_mut()
+            .expect("processing decision should have been created before any conditions");
+        if condition_info.true_next_id == ConditionId::NONE {
+            decision.end_markers.push(true_marker);
+        }
+        if condition_info.false_next_id == ConditionId::NONE {
+            decision.end_markers.push(false_marker);
+        }
+        Some(condition_info)
+    }
+}
+
+impl Builder<'_, '_> {
+    /// If branch coverage is enabled, inject marker statements into `then_block`
+    /// and `else_block`, and record their IDs in the table of branch spans.
+    pub(crate) fn visit_coverage_branch_condition(
+        &mut self,
+        mut expr_id: ExprId,
+        mut then_block: BasicBlock,
+        mut else_block: BasicBlock,
+    ) {
+        // Bail out if branch coverage is not enabled for this function.
+        let Some(branch_info) = self.coverage_branch_info.as_ref() else { return };
+
+        // If this condition expression is nested within one or more `!` expressions,
+        // replace it with the enclosing `!` collected by `visit_unary_not`.
+        if let Some(&NotInfo { enclosing_not, is_flipped }) = branch_info.nots.get(&expr_id) {
+            expr_id = enclosing_not;
+            if is_flipped {
+                std::mem::swap(&mut then_block, &mut else_block);
+            }
+        }
+        let source_info = self.source_info(self.thir[expr_id].span);
+
+        // Now that we have `source_info`, we can upgrade to a &mut reference.
+        let branch_info = self.coverage_branch_info.as_mut().expect("upgrading & to &mut");
+
+        let mut inject_branch_marker = |block: BasicBlock| {
+            let id = branch_info.next_block_marker_id();
+
+            let marker_statement = mir::Statement {
+                source_info,
+                kind: mir::StatementKind::Coverage(Box::new(mir::Coverage {
+                    kind: CoverageKind::BlockMarker { id },
+                })),
+            };
+            self.cfg.push(block, marker_statement);
+
+            id
+        };
+
+        let true_marker = inject_branch_marker(then_block);
+        let false_marker = inject_branch_marker(else_block);
+
+        branch_info.record_branch(self.tcx, source_info.span, true_marker, false_marker);
+    }
+
+    /// If MC/DC coverage is enabled, assign condition IDs to the operands of
+    /// a `&&` or `||` expression that is part of the decision being lowered.
+    pub(crate) fn visit_coverage_branch_operation(&mut self, logical_op: LogicalOp, span: Span) {
+        if let Some(branch_info) = self.coverage_branch_info.as_mut()
+            && let Some(mcdc_state) = branch_info.mcdc_state.as_mut()
+        {
+            mcdc_state.record_conditions(logical_op, span);
+        }
+    }
+
+    /// If MC/DC coverage is enabled, note that a `let` expression took the place
+    /// of a condition in the decision being lowered, so that the decision is
+    /// instrumented as independent branches instead.
+    pub(crate) fn visit_coverage_let_condition(&mut self) {
+        if let Some(branch_info) = self.coverage_branch_info.as_mut()
+            && let Some(mcdc_state) = branch_info.mcdc_state.as_mut()
+            && let Some(ctx) = mcdc_state.current_ctx_mut()
+            && ctx.decision_stack.pop_back().is_some()
+        {
+            ctx.has_let = true;
+        }
+    }
+
+    /// If MC/DC coverage is enabled, start tracking the conditions of a boolean
+    /// expression that is about to be lowered. Only the condition of an `if`
+    /// that isn't nested within another condition is tracked as a decision.
+    pub(crate) fn mcdc_enter_condition_if_enabled(&mut self, is_if_condition: bool) {
+        if let Some(branch_info) = self.coverage_branch_info.as_mut()
+            && let Some(mcdc_state) = branch_info.mcdc_state.as_mut()
+        {
+            let is_decision = is_if_condition && mcdc_state.decision_ctx_stack.is_empty();
+            mcdc_state.decision_ctx_stack.push(is_decision.then(MCDCDecisionCtx::default));
+        }
+    }
+
+    /// If MC/DC coverage is enabled, stop tracking the conditions of the boolean
+    /// expression that was being lowered.
+    pub(crate) fn mcdc_exit_condition_if_enabled(&mut self) {
+        if let Some(branch_info) = self.coverage_branch_info.as_mut()
+            && let Some(mcdc_state) = branch_info.mcdc_state.as_mut()
+        {
+            let ctx = mcdc_state.decision_ctx_stack.pop().expect("condition was entered");
+            if let Some(mut ctx) = ctx {
+                assert!(ctx.decision_stack.is_empty(), "all conditions should have been visited");
+                // The decision is still open if its last condition was a `let`.
+                if ctx.processing_decision.is_some() {
+                    let (decision, branch_spans, is_supported) = ctx.take_decision();
+                    branch_info.append_mcdc_decision(
+                        self.tcx,
+                        decision,
+                        branch_spans,
+                        is_supported,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -74,13 +74,15 @@ pub(crate) fn expr_into_dest(
                             };
                             let (then_block, else_block) =
                                 this.in_if_then_scope(condition_scope, then_expr.span, |this| {
+                                    this.mcdc_enter_condition_if_enabled(true);
                                     let then_blk = unpack!(this.then_else_break(
                                         block,
-                                        &this.thir[cond],
+                                        cond,
                                         Some(condition_scope),
                                         condition_scope,
                                         source_info
                                     ));
+                                    this.mcdc_exit_condition_if_enabled();
 
                                     this.expr_into_dest(destination, then_blk, then_expr)
                                 });
@@ -164,13 +166,16 @@ pub(crate) fn expr_into_dest(
                 // We first evaluate the left-hand side of the predicate ...
                 let (then_block, else_block) =
                     this.in_if_then_scope(condition_scope, expr.span, |this| {
-                        this.then_else_break(
+                        this.mcdc_enter_condition_if_enabled(false);
+                        let lhs_block = this.then_else_break(
                             block,
-                            &this.thir[lhs],
+                            lhs,
                             Some(condition_scope),
                             condition_scope,
                             source_info,
-                        )
+                        );
+                        this.mcdc_exit_condition_if_enabled();
+                        lhs_block
                     });
                 let (short_circuit, continuation, constant) = match op {
                     LogicalOp::And => (else_block, then_block, false),
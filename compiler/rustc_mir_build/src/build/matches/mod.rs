@@ -36,19 +36,21 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
     pub(crate) fn then_else_break(
         &mut self,
         mut block: BasicBlock,
-        expr: &Expr<'tcx>,
+        expr_id: ExprId,
         temp_scope_override: Option<region::Scope>,
         break_scope: region::Scope,
         variable_source_info: SourceInfo,
     ) -> BlockAnd<()> {
         let this = self;
+        let expr = &this.thir[expr_id];
         let expr_span = expr.span;
 
         match expr.kind {
             ExprKind::LogicalOp { op: LogicalOp::And, lhs, rhs } => {
+                this.visit_coverage_branch_operation(LogicalOp::And, expr_span);
                 let lhs_then_block = unpack!(this.then_else_break(
                     block,
-                    &this.thir[lhs],
+                    lhs,
                     temp_scope_override,
                     break_scope,
                     variable_source_info,
@@ -56,7 +58,7 @@ pub(crate) fn then_else_break(
 
                 let rhs_then_block = unpack!(this.then_else_break(
                     lhs_then_block,
-                    &this.thir[rhs],
+                    rhs,
                     temp_scope_override,
                     break_scope,
                     variable_source_info,
@@ -65,12 +67,13 @@ pub(crate) fn then_else_break(
                 rhs_then_block.unit()
             }
             ExprKind::LogicalOp { op: LogicalOp::Or, lhs, rhs } => {
+                this.visit_coverage_branch_operation(LogicalOp::Or, expr_span);
                 let local_scope = this.local_scope();
                 let (lhs_success_block, failure_block) =
                     this.in_if_then_scope(local_scope, expr_span, |this| {
                         this.then_else_break(
                             block,
-                            &this.thir[lhs],
+                            lhs,
                             temp_scope_override,
                             local_scope,
                             variable_source_info,
@@ -78,21 +81,40 @@ pub(crate) fn then_else_break(
                     });
                 let rhs_success_block = unpack!(this.then_else_break(
                     failure_block,
-                    &this.thir[rhs],
+                    rhs,
                     temp_scope_override,
                     break_scope,
                     variable_source_info,
                 ));
-                this.cfg.goto(lhs_success_block, variable_source_info, rhs_success_block);
-                rhs_success_block.unit()
+
+                // With branch coverage, `rhs_success_block` starts with the
+                // marker of the RHS true arm, which must not be counted on the
+                // LHS path, so make both success arms converge to a new block.
+                // Otherwise, the LHS can just go to the RHS success block.
+                if this.coverage_branch_info.is_some() {
+                    let success_block = this.cfg.start_new_block();
+                    this.cfg.goto(lhs_success_block, variable_source_info, success_block);
+                    this.cfg.goto(rhs_success_block, variable_source_info, success_block);
+                    success_block.unit()
+                } else {
+                    this.cfg.goto(lhs_success_block, variable_source_info, rhs_success_block);
+                    rhs_success_block.unit()
+                }
             }
             ExprKind::Unary { op: UnOp::Not, arg } => {
+                // Improve branch coverage instrumentation by noting conditions
+                // nested within one or more `!` expressions.
+                // (Skipped if branch coverage is not enabled.)
+                if let Some(branch_info) = this.coverage_branch_info.as_mut() {
+                    branch_info.visit_unary_not(this.thir, expr_id);
+                }
+
                 let local_scope = this.local_scope();
                 let (success_block, failure_block) =
                     this.in_if_then_scope(local_scope, expr_span, |this| {
                         this.then_else_break(
                             block,
-                            &this.thir[arg],
+                            arg,
                             temp_scope_override,
                             local_scope,
                             variable_source_info,
@@ -106,7 +128,7 @@ pub(crate) fn then_else_break(
                 this.in_scope(region_scope, lint_level, |this| {
                     this.then_else_break(
                         block,
-                        &this.thir[value],
+                        value,
                         temp_scope_override,
                         break_scope,
                         variable_source_info,
@@ -115,20 +137,23 @@ pub(crate) fn then_else_break(
             }
             ExprKind::Use { source } => this.then_else_break(
                 block,
-                &this.thir[source],
+                source,
                 temp_scope_override,
                 break_scope,
                 variable_source_info,
             ),
-            ExprKind::Let { expr, ref pat } => this.lower_let_expr(
-                block,
-                &this.thir[expr],
-                pat,
-                break_scope,
-                Some(variable_source_info.scope),
-                variable_source_info.span,
-                true,
-            ),
+            ExprKind::Let { expr, ref pat } => {
+                this.visit_coverage_let_condition();
+                this.lower_let_expr(
+                    block,
+                    &this.thir[expr],
+                    pat,
+                    break_scope,
+                    Some(variable_source_info.scope),
+                    variable_source_info.span,
+                    true,
+                )
+            }
             _ => {
                 let temp_scope = temp_scope_override.unwrap_or_else(|| this.local_scope());
                 let mutability = Mutability::Mut;
@@ -140,6 +165,10 @@ pub(crate) fn then_else_break(
                 let else_block = this.cfg.start_new_block();
                 let term = TerminatorKind::if_(operand, then_block, else_block);
 
+                // Record branch coverage info for this condition.
+                // (Does nothing if branch coverage is not enabled.)
+                this.visit_coverage_branch_condition(expr_id, then_block, else_block);
+
                 let source_info = this.source_info(expr_span);
                 this.cfg.terminate(block, source_info, term);
                 this.break_for_else(else_block, break_scope, source_info);
@@ -2030,15 +2059,17 @@ fn bind_and_guard_matched_candidate<'pat>(
             let (post_guard_block, otherwise_post_guard_block) =
                 self.in_if_then_scope(match_scope, guard_span, |this| match *guard {
                     Guard::If(e) => {
-                        let e = &this.thir[e];
-                        guard_span = e.span;
-                        this.then_else_break(
+                        guard_span = this.thir[e].span;
+                        this.mcdc_enter_condition_if_enabled(false);
+                        let guard_block = this.then_else_break(
                             block,
                             e,
                             None,
                             match_scope,
                             this.source_info(arm.span),
-                        )
+                        );
+                        this.mcdc_exit_condition_if_enabled();
+                        guard_block
                     }
                     Guard::IfLet(ref pat, scrutinee) => {
                         let s = &this.thir[scrutinee];
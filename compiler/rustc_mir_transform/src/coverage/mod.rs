@@ -8,12 +8,14 @@
 mod tests;
 
 use self::counters::{BcbCounter, CoverageCounters};
-use self::graph::CoverageGraph;
+use self::graph::{BasicCoverageBlock, CoverageGraph};
 use self::spans::CoverageSpans;
 
 use crate::MirPass;
 
+use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::sync::Lrc;
+use rustc_index::IndexVec;
 use rustc_middle::hir;
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::mir::coverage::*;
@@ -153,15 +155,29 @@ fn inject_counters(&'a mut self) {
         // every coverage span has a `Counter` or `Expression` assigned to its `BasicCoverageBlock`
         // and all `Expression` dependencies (operands) are also generated, for any other
         // `BasicCoverageBlock`s not already associated with a coverage span.
-        let bcb_has_coverage_spans = |bcb| coverage_spans.bcb_has_coverage_spans(bcb);
+        //
+        // Branch mappings need counters for both of their arms, so the BCBs of
+        // those arms are treated as if they had coverage spans.
+        let branch_mappings =
+            extract_branch_mappings(self.mir_body, body_span, &self.basic_coverage_blocks);
+        let bcb_has_coverage_spans = |bcb| {
+            coverage_spans.bcb_has_coverage_spans(bcb) || branch_mappings.branch_bcbs.contains(&bcb)
+        };
         self.coverage_counters
             .make_bcb_counters(&self.basic_coverage_blocks, bcb_has_coverage_spans);
 
-        let mappings = self.create_mappings_and_inject_coverage_statements(&coverage_spans);
+        let mut mappings = self.create_mappings_and_inject_coverage_statements(
+            &coverage_spans,
+            &branch_mappings.branch_bcbs,
+        );
+        mappings.extend(self.create_branch_mappings(&branch_mappings));
+        let mcdc_bitmap_bytes =
+            self.create_mcdc_mappings_and_inject_statements(&branch_mappings, &mut mappings);
 
         self.mir_body.function_coverage_info = Some(Box::new(FunctionCoverageInfo {
             function_source_hash: self.function_source_hash,
             num_counters: self.coverage_counters.num_counters(),
+            mcdc_bitmap_bytes,
             expressions: self.coverage_counters.take_expressions(),
             mappings,
         }));
@@ -173,6 +189,7 @@ fn inject_counters(&'a mut self) {
     fn create_mappings_and_inject_coverage_statements(
         &mut self,
         coverage_spans: &CoverageSpans,
+        branch_bcbs: &FxHashSet<BasicCoverageBlock>,
     ) -> Vec<Mapping> {
         let source_map = self.tcx.sess.source_map();
         let body_span = self.body_span;
@@ -186,17 +203,15 @@ fn create_mappings_and_inject_coverage_statements(
         // Process the counters and spans associated with BCB nodes.
         for (bcb, counter_kind) in self.coverage_counters.bcb_node_counters() {
             let spans = coverage_spans.spans_for_bcb(bcb);
-            let has_mappings = !spans.is_empty();
+            let has_mappings = !spans.is_empty() || branch_bcbs.contains(&bcb);
 
             // If this BCB has any coverage spans, add corresponding mappings to
             // the mappings table.
-            if has_mappings {
-                let term = counter_kind.as_term();
-                mappings.extend(spans.iter().map(|&span| {
-                    let code_region = make_code_region(source_map, file_name, span, body_span);
-                    Mapping { code_region, term }
-                }));
-            }
+            let term = counter_kind.as_term();
+            mappings.extend(spans.iter().map(|&span| {
+                let code_region = make_code_region(source_map, file_name, span, body_span);
+                Mapping { kind: MappingKind::Code(term), code_region }
+            }));
 
             let do_inject = match counter_kind {
                 // Counter-increment statements always need to be injected.
@@ -246,6 +261,97 @@ fn create_mappings_and_inject_coverage_statements(
         mappings
     }
 
+    /// Creates a mapping for each branch that was recorded during MIR building,
+    /// using the counters of the BCBs of its true and false arms.
+    fn create_branch_mappings(&self, branch_mappings: &ExtractedBranchMappings) -> Vec<Mapping> {
+        let source_map = self.tcx.sess.source_map();
+        let body_span = self.body_span;
+
+        use rustc_session::RemapFileNameExt;
+        let file_name =
+            Symbol::intern(&self.source_file.name.for_codegen(self.tcx.sess).to_string_lossy());
+
+        let bcb_term = |bcb| {
+            self.coverage_counters
+                .bcb_counter(bcb)
+                .expect("all BCBs with branch mappings have been given a counter")
+                .as_term()
+        };
+
+        branch_mappings
+            .branch_pairs
+            .iter()
+            .map(|pair| {
+                let true_term = bcb_term(pair.true_bcb);
+                let false_term = bcb_term(pair.false_bcb);
+                let kind = match pair.condition_info {
+                    None => MappingKind::Branch { true_term, false_term },
+                    Some(mcdc_params) => {
+                        MappingKind::MCDCBranch { true_term, false_term, mcdc_params }
+                    }
+                };
+                let code_region = make_code_region(source_map, file_name, pair.span, body_span);
+                Mapping { kind, code_region }
+            })
+            .collect()
+    }
+
+    /// Creates a mapping for each MC/DC decision, and injects the statements
+    /// that record its conditions and test vectors into MIR. Returns the number
+    /// of bytes needed for the test vector bitmaps of all decisions.
+    fn create_mcdc_mappings_and_inject_statements(
+        &mut self,
+        branch_mappings: &ExtractedBranchMappings,
+        mappings: &mut Vec<Mapping>,
+    ) -> u32 {
+        let source_map = self.tcx.sess.source_map();
+        let body_span = self.body_span;
+
+        use rustc_session::RemapFileNameExt;
+        let file_name =
+            Symbol::intern(&self.source_file.name.for_codegen(self.tcx.sess).to_string_lossy());
+
+        // Each decision gets its own range of the bitmap, with one bit for
+        // each possible test vector.
+        let mut next_bitmap_idx = 0u32;
+        for decision in &branch_mappings.decisions {
+            let bitmap_idx = next_bitmap_idx;
+            next_bitmap_idx += (1_u32 << decision.conditions_num).div_ceil(8);
+
+            for &end_bcb in &decision.end_bcbs {
+                inject_statement(
+                    self.mir_body,
+                    CoverageKind::TestVectorBitmapUpdate { bitmap_idx },
+                    self.basic_coverage_blocks[end_bcb].leader_bb(),
+                );
+            }
+
+            let code_region = make_code_region(source_map, file_name, decision.span, body_span);
+            let kind = MappingKind::MCDCDecision(DecisionInfo {
+                bitmap_idx,
+                conditions_num: decision.conditions_num,
+            });
+            mappings.push(Mapping { kind, code_region });
+        }
+
+        // The condition bitmap must be updated before the test vector bitmap
+        // in blocks that end a decision, so these statements are inserted
+        // in front of the ones above.
+        for pair in &branch_mappings.branch_pairs {
+            let Some(condition_info) = pair.condition_info else { continue };
+            let id = condition_info.condition_id;
+            for (bcb, value) in [(pair.true_bcb, true), (pair.false_bcb, false)] {
+                inject_statement(
+                    self.mir_body,
+                    CoverageKind::CondBitmapUpdate { id, value },
+                    self.basic_coverage_blocks[bcb].leader_bb(),
+                );
+            }
+        }
+
+        next_bitmap_idx
+    }
+
     fn make_mir_coverage_kind(&self, counter_kind: &BcbCounter) -> CoverageKind {
         match *counter_kind {
             BcbCounter::Counter { id } => CoverageKind::CounterIncrement { id },
@@ -254,6 +360,115 @@ fn make_mir_coverage_kind(&self, counter_kind: &BcbCounter) -> CoverageKind {
     }
 }
 
+/// A branch whose true and false arms have been resolved to the BCBs that
+/// contain their block markers.
+struct BcbBranchPair {
+    span: Span,
+    true_bcb: BasicCoverageBlock,
+    false_bcb: BasicCoverageBlock,
+    /// Only present for conditions of a decision that is instrumented for MC/DC.
+    condition_info: Option<ConditionInfo>,
+}
+
+struct BcbMCDCDecision {
+    span: Span,
+    conditions_num: u16,
+    end_bcbs: Vec<BasicCoverageBlock>,
+}
+
+#[derive(Default)]
+struct ExtractedBranchMappings {
+    branch_pairs: Vec<BcbBranchPair>,
+    decisions: Vec<BcbMCDCDecision>,
+    /// The BCBs of all branch arms, which need counters even if they have no
+    /// coverage spans of their own.
+    branch_bcbs: FxHashSet<BasicCoverageBlock>,
+}
+
+/// Resolves the branch information recorded during MIR building to BCBs, via
+/// the [`CoverageKind::BlockMarker`] statements in the MIR body. Branches whose
+/// spans are outside the function body, or whose marker blocks have been
+/// removed as unreachable, are discarded.
+fn extract_branch_mappings(
+    mir_body: &mir::Body<'_>,
+    body_span: Span,
+    basic_coverage_blocks: &CoverageGraph,
+) -> ExtractedBranchMappings {
+    let mut extracted = ExtractedBranchMappings::default();
+    let Some(branch_info) = mir_body.coverage_branch_info.as_deref() else { return extracted };
+
+    let mut block_markers = IndexVec::<BlockMarkerId, Option<BasicBlock>>::from_elem_n(
+        None,
+        branch_info.num_block_markers,
+    );
+
+    // Fill out the mapping from block marker IDs to their enclosing blocks.
+    for (bb, data) in mir_body.basic_blocks.iter_enumerated() {
+        for statement in &data.statements {
+            if let StatementKind::Coverage(box mir::Coverage {
+                kind: CoverageKind::BlockMarker { id },
+            }) = statement.kind
+            {
+                block_markers[id] = Some(bb);
+            }
+        }
+    }
+
+    let bcb_from_marker =
+        |marker: BlockMarkerId| basic_coverage_blocks.bcb_from_bb(block_markers[marker]?);
+    let is_in_body = |span: Span| span.eq_ctxt(body_span) && body_span.contains(span);
+
+    let plain_branches = branch_info
+        .branch_spans
+        .iter()
+        .map(|branch| (branch.span, None, branch.true_marker, branch.false_marker));
+    let mcdc_branches = branch_info.mcdc_branch_spans.iter().map(|branch| {
+        (branch.span, branch.condition_info, branch.true_marker, branch.false_marker)
+    });
+    // MC/DC is only reported if every decision and every one of its conditions
+    // can still be mapped; otherwise all conditions are reported as branches.
+    let mut mcdc_is_valid = true;
+    for (span, condition_info, true_marker, false_marker) in plain_branches.chain(mcdc_branches) {
+        let (Some(true_bcb), Some(false_bcb)) =
+            (bcb_from_marker(true_marker), bcb_from_marker(false_marker))
+        else {
+            mcdc_is_valid &= condition_info.is_none();
+            continue;
+        };
+        if !is_in_body(span) {
+            mcdc_is_valid &= condition_info.is_none();
+            continue;
+        }
+
+        extracted.branch_bcbs.extend([true_bcb, false_bcb]);
+        extracted.branch_pairs.push(BcbBranchPair { span, true_bcb, false_bcb, condition_info });
+    }
+
+    for decision in &branch_info.mcdc_decision_spans {
+        let end_bcbs: Option<Vec<_>> =
+            decision.end_markers.iter().map(|&marker| bcb_from_marker(marker)).collect();
+        let Some(end_bcbs) = end_bcbs.filter(|_| is_in_body(decision.span)) else {
+            mcdc_is_valid = false;
+            continue;
+        };
+
+        extracted.decisions.push(BcbMCDCDecision {
+            span: decision.span,
+            conditions_num: decision.conditions_num as u16,
+            end_bcbs,
+        });
+    }
+
+    if !mcdc_is_valid {
+        extracted.decisions.clear();
+        for pair in &mut extracted.branch_pairs {
+            pair.condition_info = None;
+        }
+    }
+
+    extracted
+}
+
 fn inject_edge_counter_basic_block(
     mir_body: &mut mir::Body<'_>,
     from_bb: BasicBlock,
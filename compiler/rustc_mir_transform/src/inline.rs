@@ -462,6 +462,13 @@ fn check_mir_body(
     ) -> Result<(), &'static str> {
         let tcx = self.tcx;
 
+        // The MC/DC bitmaps of a function can't be moved into its callers.
+        if let Some(coverage_info) = &callee_body.function_coverage_info
+            && coverage_info.mcdc_bitmap_bytes > 0
+        {
+            return Err("callee has MC/DC coverage bitmaps");
+        }
+
         let mut threshold = if cross_crate_inlinable {
             self.tcx.sess.opts.unstable_opts.inline_mir_hint_threshold.unwrap_or(100)
         } else {
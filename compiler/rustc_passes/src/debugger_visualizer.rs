@@ -1,7 +1,6 @@
 //! Detecting usage of the `#[debugger_visualizer]` attribute.
 
 use rustc_ast::Attribute;
-use rustc_data_structures::sync::Lrc;
 use rustc_expand::base::resolve_path;
 use rustc_middle::{
     middle::debugger_visualizer::{DebuggerVisualizerFile, DebuggerVisualizerType},
@@ -54,10 +53,10 @@ fn check_for_debugger_visualizer(&mut self, attr: &Attribute) {
                     }
                 };
 
-            match std::fs::read(&file) {
+            match self.sess.source_map().read_binary_file(&file) {
                 Ok(contents) => {
                     self.visualizers.push(DebuggerVisualizerFile::new(
-                        Lrc::from(contents),
+                        contents,
                         visualizer_type,
                         file,
                     ));
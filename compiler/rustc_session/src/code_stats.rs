@@ -1,8 +1,10 @@
+use crate::config::PrintSizesFormat;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lock;
 use rustc_span::def_id::DefId;
 use rustc_span::Symbol;
 use rustc_target::abi::{Align, Size};
+use serde::Serialize;
 use std::cmp;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -27,16 +29,22 @@ pub enum FieldKind {
     CoroutineLocal,
 }
 
-impl std::fmt::Display for FieldKind {
-    fn fmt(&self, w: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl FieldKind {
+    fn as_str(self) -> &'static str {
         match self {
-            FieldKind::AdtField => write!(w, "field"),
-            FieldKind::Upvar => write!(w, "upvar"),
-            FieldKind::CoroutineLocal => write!(w, "local"),
+            FieldKind::AdtField => "field",
+            FieldKind::Upvar => "upvar",
+            FieldKind::CoroutineLocal => "local",
         }
     }
 }
 
+impl std::fmt::Display for FieldKind {
+    fn fmt(&self, w: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        w.write_str(self.as_str())
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct FieldInfo {
     pub kind: FieldKind,
@@ -46,6 +54,18 @@ pub struct FieldInfo {
     pub align: u64,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize)]
+pub struct NicheInfo {
+    /// Offset of the niche from the start of the type.
+    pub offset: u64,
+    pub size: u64,
+    /// The valid values of the niche, which may wrap around.
+    pub valid_range_start: u128,
+    pub valid_range_end: u128,
+    /// The number of invalid values, which can be used to encode enum discriminants.
+    pub available: u128,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum DataTypeKind {
     Struct,
@@ -55,6 +75,18 @@ pub enum DataTypeKind {
     Coroutine,
 }
 
+impl DataTypeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            DataTypeKind::Struct => "struct",
+            DataTypeKind::Union => "union",
+            DataTypeKind::Enum => "enum",
+            DataTypeKind::Closure => "closure",
+            DataTypeKind::Coroutine => "coroutine",
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Debug)]
 pub struct TypeSizeInfo {
     pub kind: DataTypeKind,
@@ -64,6 +96,7 @@ pub struct TypeSizeInfo {
     pub packed: bool,
     pub opt_discr_size: Option<u64>,
     pub variants: Vec<VariantInfo>,
+    pub largest_niche: Option<NicheInfo>,
 }
 
 pub struct VTableSizeInfo {
@@ -101,6 +134,7 @@ pub fn record_type_size<S: ToString>(
         packed: bool,
         opt_discr_size: Option<Size>,
         mut variants: Vec<VariantInfo>,
+        largest_niche: Option<NicheInfo>,
     ) {
         // Sort variants so the largest ones are shown first. A stable sort is
         // used here so that source code order is preserved for all variants
@@ -118,6 +152,7 @@ pub fn record_type_size<S: ToString>(
             packed,
             opt_discr_size: opt_discr_size.map(|s| s.bytes()),
             variants,
+            largest_niche,
         };
         self.type_sizes.borrow_mut().insert(info);
     }
@@ -130,7 +165,7 @@ pub fn record_vtable_size(&self, trait_did: DefId, trait_name: &str, info: VTabl
         );
     }
 
-    pub fn print_type_sizes(&self) {
+    pub fn print_type_sizes(&self, format: PrintSizesFormat) {
         let type_sizes = self.type_sizes.borrow();
         let mut sorted: Vec<_> = type_sizes.iter().collect();
 
@@ -138,6 +173,13 @@ pub fn print_type_sizes(&self) {
         // Secondary sort: description (dictionary order)
         sorted.sort_by_key(|info| (cmp::Reverse(info.overall_size), &info.type_description));
 
+        if format == PrintSizesFormat::Json {
+            for info in sorted {
+                println!("{}", serde_json::to_string(&TypeSizeJson::new(info)).unwrap());
+            }
+            return;
+        }
+
         for info in sorted {
             let TypeSizeInfo { type_description, overall_size, align, kind, variants, .. } = info;
             println!(
@@ -226,7 +268,7 @@ pub fn print_type_sizes(&self) {
         }
     }
 
-    pub fn print_vtable_sizes(&self, crate_name: &str) {
+    pub fn print_vtable_sizes(&self, crate_name: &str, format: PrintSizesFormat) {
         let mut infos =
             std::mem::take(&mut *self.vtable_sizes.lock()).into_values().collect::<Vec<_>>();
 
@@ -247,9 +289,144 @@ pub fn print_vtable_sizes(&self, crate_name: &str) {
             upcasting_cost_percent,
         } in infos
         {
-            println!(
-                r#"print-vtable-sizes {{ "crate_name": "{crate_name}", "trait_name": "{trait_name}", "entries": "{entries}", "entries_ignoring_upcasting": "{entries_ignoring_upcasting}", "entries_for_upcasting": "{entries_for_upcasting}", "upcasting_cost_percent": "{upcasting_cost_percent}" }}"#
-            );
+            match format {
+                PrintSizesFormat::Text => println!(
+                    r#"print-vtable-sizes {{ "crate_name": "{crate_name}", "trait_name": "{trait_name}", "entries": "{entries}", "entries_ignoring_upcasting": "{entries_ignoring_upcasting}", "entries_for_upcasting": "{entries_for_upcasting}", "upcasting_cost_percent": "{upcasting_cost_percent}" }}"#
+                ),
+                PrintSizesFormat::Json => {
+                    let info = VTableSizeJson {
+                        crate_name,
+                        trait_name: &trait_name,
+                        entries,
+                        entries_ignoring_upcasting,
+                        entries_for_upcasting,
+                        upcasting_cost_percent,
+                    };
+                    println!("{}", serde_json::to_string(&info).unwrap());
+                }
+            }
+        }
+    }
+}
+
+// The following data types are provided just for serialisation.
+
+#[derive(Serialize)]
+struct TypeSizeJson<'a> {
+    #[serde(rename = "type")]
+    type_description: &'a str,
+    /// "struct", "union", "enum", "closure" or "coroutine".
+    kind: &'static str,
+    size: u64,
+    align: u64,
+    packed: bool,
+    discriminant_size: Option<u64>,
+    /// Struct-like types have a single variant without a name.
+    variants: Vec<VariantJson>,
+    /// Padding after the end of the largest variant.
+    end_padding: u64,
+    largest_niche: Option<NicheInfo>,
+}
+
+#[derive(Serialize)]
+struct VariantJson {
+    name: Option<String>,
+    /// The offset of the end of the last field, which includes the discriminant.
+    size: u64,
+    /// Whether the variant is unsized, in which case `size` is its minimum size.
+    is_unsized: bool,
+    align: u64,
+    /// Sorted by offset.
+    fields: Vec<FieldJson>,
+    /// The gaps between the discriminant and the fields, sorted by offset.
+    padding: Vec<PaddingJson>,
+}
+
+#[derive(Serialize)]
+struct FieldJson {
+    /// "field", "upvar" or "local".
+    kind: &'static str,
+    name: String,
+    offset: u64,
+    size: u64,
+    align: u64,
+}
+
+#[derive(Serialize)]
+struct PaddingJson {
+    offset: u64,
+    size: u64,
+}
+
+#[derive(Serialize)]
+struct VTableSizeJson<'a> {
+    crate_name: &'a str,
+    trait_name: &'a str,
+    entries: usize,
+    entries_ignoring_upcasting: usize,
+    entries_for_upcasting: usize,
+    upcasting_cost_percent: f64,
+}
+
+impl<'a> TypeSizeJson<'a> {
+    fn new(info: &'a TypeSizeInfo) -> TypeSizeJson<'a> {
+        let discr_size = info.opt_discr_size.unwrap_or(0);
+        let max_variant_size =
+            info.variants.iter().map(|variant| variant.size).fold(discr_size, cmp::max);
+        let struct_like = match info.kind {
+            DataTypeKind::Struct | DataTypeKind::Closure => true,
+            DataTypeKind::Enum | DataTypeKind::Union | DataTypeKind::Coroutine => false,
+        };
+        TypeSizeJson {
+            type_description: &info.type_description,
+            kind: info.kind.as_str(),
+            size: info.overall_size,
+            align: info.align,
+            packed: info.packed,
+            discriminant_size: info.opt_discr_size,
+            variants: info
+                .variants
+                .iter()
+                .map(|variant| VariantJson::new(variant, discr_size, struct_like))
+                .collect(),
+            end_padding: info.overall_size.saturating_sub(max_variant_size),
+            largest_niche: info.largest_niche,
+        }
+    }
+}
+
+impl VariantJson {
+    fn new(variant: &VariantInfo, discr_size: u64, struct_like: bool) -> VariantJson {
+        // Zero-sized fields go before the other fields at the same offset, like in the text
+        // output, so that they don't hide any padding.
+        let mut fields = variant.fields.clone();
+        fields.sort_by_key(|f| (f.offset, f.size));
+
+        let mut padding = Vec::new();
+        let mut min_offset = discr_size;
+        for field in &fields {
+            if field.offset > min_offset {
+                padding.push(PaddingJson { offset: min_offset, size: field.offset - min_offset });
+            }
+            min_offset = field.offset + field.size;
+        }
+
+        VariantJson {
+            name: if struct_like { None } else { variant.name.map(|name| name.to_string()) },
+            size: variant.size,
+            is_unsized: variant.kind == SizeKind::Min,
+            align: variant.align,
+            fields: fields
+                .into_iter()
+                .map(|field| FieldJson {
+                    kind: field.kind.as_str(),
+                    name: field.name.to_string(),
+                    offset: field.offset,
+                    size: field.size,
+                    align: field.align,
+                })
+                .collect(),
+            padding,
         }
     }
 }
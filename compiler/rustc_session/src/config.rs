@@ -3,6 +3,7 @@
 
 pub use crate::options::*;
 
+use crate::config::lint_config::LintConfig;
 use crate::errors::FileWriteFail;
 use crate::search_paths::SearchPath;
 use crate::utils::{CanonicalizedPath, NativeLib, NativeLibKind};
@@ -26,6 +27,7 @@
 use rustc_errors::emitter::HumanReadableErrorType;
 use rustc_errors::{ColorConfig, DiagnosticArgValue, HandlerFlags, IntoDiagnosticArg};
 
+use std::borrow::Cow;
 use std::collections::btree_map::{
     Iter as BTreeMapIter, Keys as BTreeMapKeysIter, Values as BTreeMapValuesIter,
 };
@@ -39,6 +41,7 @@
 use std::str::{self, FromStr};
 use std::sync::LazyLock;
 
+pub mod lint_config;
 pub mod sigpipe;
 
 /// The different settings that the `-C strip` flag can have.
@@ -171,6 +174,10 @@ pub enum InstrumentCoverage {
     /// Additionally, instrument branches and output branch coverage.
     /// `-Zunstable-options -C instrument-coverage=branch`
     Branch,
+    /// Additionally, instrument branches and the conditions of boolean decisions,
+    /// and output branch and MC/DC coverage.
+    /// `-Zunstable-options -C instrument-coverage=mcdc`
+    Mcdc,
     /// `-Zunstable-options -C instrument-coverage=except-unused-generics`
     ExceptUnusedGenerics,
     /// `-Zunstable-options -C instrument-coverage=except-unused-functions`
@@ -438,6 +445,7 @@ pub enum OutputType {
     Object,
     Exe,
     DepInfo,
+    StableMir,
 }
 
 // Safety: Trivial C-Style enums have a stable sort order across compilation sessions.
@@ -461,7 +469,8 @@ fn is_compatible_with_codegen_units_and_single_output_file(&self) -> bool {
             | OutputType::Assembly
             | OutputType::LlvmAssembly
             | OutputType::Mir
-            | OutputType::Object => false,
+            | OutputType::Object
+            | OutputType::StableMir => false,
         }
     }
 
@@ -475,6 +484,7 @@ pub fn shorthand(&self) -> &'static str {
             OutputType::Metadata => "metadata",
             OutputType::Exe => "link",
             OutputType::DepInfo => "dep-info",
+            OutputType::StableMir => "stable-mir",
         }
     }
 
@@ -488,13 +498,14 @@ fn from_shorthand(shorthand: &str) -> Option<Self> {
             "metadata" => OutputType::Metadata,
             "link" => OutputType::Exe,
             "dep-info" => OutputType::DepInfo,
+            "stable-mir" => OutputType::StableMir,
             _ => return None,
         })
     }
 
     fn shorthands_display() -> String {
         format!(
-            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
+            "`{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`, `{}`",
             OutputType::Bitcode.shorthand(),
             OutputType::Assembly.shorthand(),
             OutputType::LlvmAssembly.shorthand(),
@@ -503,6 +514,7 @@ fn shorthands_display() -> String {
             OutputType::Metadata.shorthand(),
             OutputType::Exe.shorthand(),
             OutputType::DepInfo.shorthand(),
+            OutputType::StableMir.shorthand(),
         )
     }
 
@@ -516,6 +528,7 @@ pub fn extension(&self) -> &'static str {
             OutputType::Metadata => "rmeta",
             OutputType::DepInfo => "d",
             OutputType::Exe => "",
+            OutputType::StableMir => "smir.json",
         }
     }
 
@@ -524,7 +537,8 @@ pub fn is_text_output(&self) -> bool {
             OutputType::Assembly
             | OutputType::LlvmAssembly
             | OutputType::Mir
-            | OutputType::DepInfo => true,
+            | OutputType::DepInfo
+            | OutputType::StableMir => true,
             OutputType::Bitcode | OutputType::Object | OutputType::Metadata | OutputType::Exe => {
                 false
             }
@@ -545,6 +559,9 @@ pub enum ErrorOutputType {
         /// human output.
         json_rendered: HumanReadableErrorType,
     },
+    /// Output in the SARIF format that's consumed by static analysis tools, written out as a
+    /// single log once compilation is done.
+    Sarif,
 }
 
 impl Default for ErrorOutputType {
@@ -621,7 +638,8 @@ pub fn should_codegen(&self) -> bool {
             | OutputType::LlvmAssembly
             | OutputType::Mir
             | OutputType::Object
-            | OutputType::Exe => true,
+            | OutputType::Exe
+            | OutputType::StableMir => true,
             OutputType::Metadata | OutputType::DepInfo => false,
         })
     }
@@ -635,7 +653,8 @@ pub fn should_link(&self) -> bool {
             | OutputType::Mir
             | OutputType::Metadata
             | OutputType::Object
-            | OutputType::DepInfo => false,
+            | OutputType::DepInfo
+            | OutputType::StableMir => false,
             OutputType::Exe => true,
         })
     }
@@ -739,6 +758,7 @@ pub enum PrintKind {
     Sysroot,
     TargetLibdir,
     CrateName,
+    CrateInfo,
     Cfg,
     CallingConventions,
     TargetList,
@@ -1084,6 +1104,7 @@ fn default() -> Options {
             debuginfo_compression: DebugInfoCompression::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            lint_config: None,
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -1116,6 +1137,8 @@ fn default() -> Options {
             pretty: None,
             working_dir: RealFileName::LocalPath(std::env::current_dir().unwrap()),
             color: ColorConfig::Auto,
+            logical_env: None,
+            resolved_output_dirs: Vec::new(),
         }
     }
 }
@@ -1152,6 +1175,139 @@ pub fn share_generics(&self) -> bool {
     pub fn get_symbol_mangling_version(&self) -> SymbolManglingVersion {
         self.cg.symbol_mangling_version.unwrap_or(SymbolManglingVersion::Legacy)
     }
+
+    /// Returns `path` resolved against the working directory of the compilation, which is not
+    /// the working directory of the process for options built by [`build_session_options_in`].
+    pub fn resolve_path(&self, path: &Path) -> PathBuf {
+        self.working_dir.local_path_if_available().join(path)
+    }
+
+    /// Resolves the relative paths of the options that are opened or written by the compiler
+    /// against the working directory of the compilation.
+    ///
+    /// The inputs that are part of the command line hash, like `-C profile-use`, are kept the way
+    /// they were given, so that they show up like that in dep-info files, and are resolved with
+    /// [`Options::resolve_path`] where they are opened. Paths that are only seen by the linker or
+    /// by the compiled program, like `-C link-arg` or `-C profile-generate`, are left alone, since
+    /// those processes run in the working directory of the compilation.
+    fn resolve_paths(&mut self) {
+        let working_dir = self.working_dir.local_path_if_available().to_owned();
+        let resolve = |path: &mut PathBuf| *path = working_dir.join(&*path);
+        let resolve_string = |path: &mut String| {
+            if let Ok(resolved) = working_dir.join(&*path).into_os_string().into_string() {
+                *path = resolved;
+            }
+        };
+        // A program without a directory is looked up in `PATH`.
+        let resolve_program = |path: &mut PathBuf| {
+            if path.parent().is_some_and(|dir| !dir.as_os_str().is_empty()) {
+                resolve(path);
+            }
+        };
+
+        let mut output_dirs = Vec::new();
+        for output in self.output_types.0.values_mut().flatten() {
+            if let OutFileName::Real(path) = output {
+                if let Some(dir) = path.parent() {
+                    output_dirs.push((working_dir.join(dir), dir.to_owned()));
+                }
+                resolve(path);
+            }
+        }
+        self.resolved_output_dirs.extend(output_dirs);
+        for print in &mut self.prints {
+            if let OutFileName::Real(path) = &mut print.out {
+                resolve(path);
+            }
+        }
+
+        for search_path in &mut self.search_paths {
+            if search_path.dir.is_relative() {
+                *search_path =
+                    SearchPath::new(search_path.kind, working_dir.join(&search_path.dir));
+            }
+        }
+        for entry in self.externs.0.values_mut() {
+            if let ExternLocation::ExactPaths(paths) = &mut entry.location {
+                *paths = paths
+                    .iter()
+                    .map(|path| CanonicalizedPath::new(&working_dir.join(path.original())))
+                    .collect();
+            }
+        }
+        if let Some(path) = &mut self.maybe_sysroot {
+            resolve(path);
+        }
+        if let Some(path) = &mut self.incremental {
+            resolve(path);
+        }
+
+        if let Some(path) = &mut self.cg.linker {
+            resolve_program(path);
+        }
+        if let Some(path) = &mut self.cg.dlltool {
+            resolve_program(path);
+        }
+
+        let unstable_opts = &mut self.unstable_opts;
+        for switch in [&mut unstable_opts.self_profile, &mut unstable_opts.dump_mono_stats] {
+            // Both are written to the working directory by default.
+            if let SwitchWithOptPath::Enabled(path) = switch {
+                *path = Some(
+                    path.take().map_or_else(|| working_dir.clone(), |path| working_dir.join(path)),
+                );
+            }
+        }
+        resolve_string(&mut unstable_opts.dump_mir_dir);
+        resolve_string(&mut unstable_opts.nll_facts_dir);
+        if let Some(path) = &mut unstable_opts.temps_dir {
+            resolve_string(path);
+        }
+        if let Some(path) = &mut unstable_opts.diagnostic_baseline {
+            resolve(path);
+        }
+        if let Some(path) = &mut unstable_opts.remark_dir {
+            resolve(path);
+        }
+    }
+
+    /// Resolves `--out-dir` and `-o` against the working directory of the compilation, like
+    /// [`build_session_options_in`] resolves the paths of the options. Without either, the
+    /// outputs are written to the working directory of the compilation.
+    pub fn resolve_output_paths(
+        &mut self,
+        output_dir: &mut Option<PathBuf>,
+        output_file: &mut Option<OutFileName>,
+    ) {
+        let working_dir = self.working_dir.local_path_if_available().to_owned();
+        if let Some(OutFileName::Real(path)) = output_file {
+            if let Some(dir) = path.parent() {
+                self.resolved_output_dirs.push((working_dir.join(dir), dir.to_owned()));
+            }
+            *path = working_dir.join(&*path);
+        } else if let Some(dir) = output_dir {
+            self.resolved_output_dirs.push((working_dir.join(&*dir), dir.clone()));
+            *dir = working_dir.join(&*dir);
+        } else if output_file.is_none() {
+            self.resolved_output_dirs.push((working_dir.clone(), PathBuf::new()));
+            *output_dir = Some(working_dir);
+        }
+    }
+
+    /// Returns the path of an output the way it would be written if the paths of the outputs
+    /// hadn't been resolved against another working directory, for example in dep-info files.
+    pub fn display_output_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        let unresolved = self
+            .resolved_output_dirs
+            .iter()
+            .filter_map(|(dir, given_dir)| Some((path.strip_prefix(dir).ok()?, given_dir)))
+            .min_by_key(|(file, _)| file.components().count());
+        match unresolved {
+            Some((file, given_dir)) if file.as_os_str().is_empty() => Cow::Owned(given_dir.clone()),
+            Some((file, given_dir)) => Cow::Owned(given_dir.join(file)),
+            None => Cow::Borrowed(path),
+        }
+    }
 }
 
 impl UnstableOptions {
@@ -1814,6 +1970,12 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
             "Remap source names in all output (compiler messages and output files)",
             "FROM=TO",
         ),
+        opt::opt(
+            "",
+            "server",
+            "Serve compilation requests on a Unix domain socket instead of compiling",
+            "SOCKET",
+        ),
     ]);
     opts
 }
@@ -1960,8 +2122,12 @@ pub fn parse_error_format(
             Some("human-annotate-rs") => {
                 ErrorOutputType::HumanReadable(HumanReadableErrorType::AnnotateSnippet(color))
             }
+            Some("human-unicode") => {
+                ErrorOutputType::HumanReadable(HumanReadableErrorType::Unicode(color))
+            }
             Some("json") => ErrorOutputType::Json { pretty: false, json_rendered },
             Some("pretty-json") => ErrorOutputType::Json { pretty: true, json_rendered },
+            Some("sarif") => ErrorOutputType::Sarif,
             Some("short") => ErrorOutputType::HumanReadable(HumanReadableErrorType::Short(color)),
 
             Some(arg) => {
@@ -2043,6 +2209,20 @@ fn check_error_format_stability(
             });
             handler.early_error("`--error-format=human-annotate-rs` is unstable");
         }
+        if let ErrorOutputType::HumanReadable(HumanReadableErrorType::Unicode(_)) = error_format {
+            handler.abort_if_error_and_set_error_format(ErrorOutputType::Json {
+                pretty: false,
+                json_rendered,
+            });
+            handler.early_error("`--error-format=human-unicode` is unstable");
+        }
+        if let ErrorOutputType::Sarif = error_format {
+            handler.abort_if_error_and_set_error_format(ErrorOutputType::Json {
+                pretty: false,
+                json_rendered,
+            });
+            handler.early_error("`--error-format=sarif` is unstable");
+        }
     }
 }
 
@@ -2062,6 +2242,9 @@ fn parse_output_types(
                         display = OutputType::shorthands_display(),
                     ))
                 });
+                if output_type == OutputType::StableMir && !unstable_opts.unstable_options {
+                    handler.early_error("`--emit=stable-mir` requires `-Z unstable-options`");
+                }
                 output_types.insert(output_type, path);
             }
         }
@@ -2133,6 +2316,10 @@ fn check_thread_count(handler: &EarlyErrorHandler, unstable_opts: &UnstableOptio
     if unstable_opts.threads > 1 && unstable_opts.fuel.is_some() {
         handler.early_error("optimization fuel is incompatible with multiple threads");
     }
+
+    if unstable_opts.write_baseline && unstable_opts.diagnostic_baseline.is_none() {
+        handler.early_error("`-Z write-baseline` requires `-Z diagnostic-baseline`");
+    }
 }
 
 fn collect_print_requests(
@@ -2153,6 +2340,7 @@ fn collect_print_requests(
 
     const PRINT_KINDS: &[(&str, PrintKind)] = &[
         ("crate-name", PrintKind::CrateName),
+        ("crate-info", PrintKind::CrateInfo),
         ("file-names", PrintKind::FileNames),
         ("sysroot", PrintKind::Sysroot),
         ("target-libdir", PrintKind::TargetLibdir),
@@ -2193,6 +2381,16 @@ fn collect_print_requests(
                     );
                 }
             }
+            Some((_, PrintKind::CrateInfo)) => {
+                if unstable_opts.unstable_options {
+                    PrintKind::CrateInfo
+                } else {
+                    handler.early_error(
+                        "the `-Z unstable-options` flag must also be passed to \
+                         enable the crate-info print option",
+                    );
+                }
+            }
             Some((_, PrintKind::AllTargetSpecs)) => {
                 if unstable_opts.unstable_options {
                     PrintKind::AllTargetSpecs
@@ -2233,11 +2431,19 @@ fn collect_print_requests(
 pub fn parse_target_triple(
     handler: &EarlyErrorHandler,
     matches: &getopts::Matches,
+) -> TargetTriple {
+    parse_target_triple_in(handler, matches, Path::new(""))
+}
+
+fn parse_target_triple_in(
+    handler: &EarlyErrorHandler,
+    matches: &getopts::Matches,
+    working_dir: &Path,
 ) -> TargetTriple {
     match matches.opt_str("target") {
         Some(target) if target.ends_with(".json") => {
             let path = Path::new(&target);
-            TargetTriple::from_path(path).unwrap_or_else(|_| {
+            TargetTriple::from_path(&working_dir.join(path)).unwrap_or_else(|_| {
                 handler.early_error(format!("target file {path:?} does not exist"))
             })
         }
@@ -2578,6 +2784,7 @@ fn parse_remap_path_prefix(
     handler: &EarlyErrorHandler,
     matches: &getopts::Matches,
     unstable_opts: &UnstableOptions,
+    working_dir: &Path,
 ) -> Vec<(PathBuf, PathBuf)> {
     let mut mapping: Vec<(PathBuf, PathBuf)> = matches
         .opt_strs("remap-path-prefix")
@@ -2587,22 +2794,36 @@ fn parse_remap_path_prefix(
             Some((from, to)) => (PathBuf::from(from), PathBuf::from(to)),
         })
         .collect();
-    match &unstable_opts.remap_cwd_prefix {
-        Some(to) => match std::env::current_dir() {
-            Ok(cwd) => mapping.push((cwd, to.clone())),
-            Err(_) => (),
-        },
-        None => (),
-    };
+    if let Some(to) = &unstable_opts.remap_cwd_prefix {
+        mapping.push((working_dir.to_owned(), to.clone()));
+    }
     mapping
 }
 
+pub fn build_session_options(
+    handler: &mut EarlyErrorHandler,
+    matches: &getopts::Matches,
+) -> Options {
+    build_session_options_in(handler, matches, None)
+}
+
+/// Like [`build_session_options`], for a compilation that runs in `working_dir` rather than in
+/// the working directory of the process, which is left alone. The relative paths of the options
+/// that the compiler opens or writes are resolved against `working_dir`.
 // JUSTIFICATION: before wrapper fn is available
 #[allow(rustc::bad_opt_access)]
-pub fn build_session_options(
+pub fn build_session_options_in(
     handler: &mut EarlyErrorHandler,
     matches: &getopts::Matches,
+    working_dir: Option<PathBuf>,
 ) -> Options {
+    let resolve_paths = working_dir.is_some();
+    let working_dir = working_dir.unwrap_or_else(|| {
+        std::env::current_dir().unwrap_or_else(|e| {
+            handler.early_error(format!("Current directory is invalid: {e}"));
+        })
+    });
+
     let color = parse_color(handler, matches);
 
     let edition = parse_crate_edition(handler, matches);
@@ -2705,12 +2926,13 @@ pub fn build_session_options(
         InstrumentCoverage::All | InstrumentCoverage::Off => {}
         // Unstable values:
         InstrumentCoverage::Branch
+        | InstrumentCoverage::Mcdc
         | InstrumentCoverage::ExceptUnusedFunctions
         | InstrumentCoverage::ExceptUnusedGenerics => {
             if !unstable_opts.unstable_options {
                 handler.early_error(
-                    "`-C instrument-coverage=branch` and `-C instrument-coverage=except-*` \
-                    require `-Z unstable-options`",
+                    "`-C instrument-coverage=branch`, `-C instrument-coverage=mcdc` and \
+                    `-C instrument-coverage=except-*` require `-Z unstable-options`",
                 );
             }
         }
@@ -2795,7 +3017,7 @@ pub fn build_session_options(
     let cg = cg;
 
     let sysroot_opt = matches.opt_str("sysroot").map(|m| PathBuf::from(&m));
-    let target_triple = parse_target_triple(handler, matches);
+    let target_triple = parse_target_triple_in(handler, matches, &working_dir);
     let opt_level = parse_opt_level(handler, matches, &cg);
     // The `-g` and `-C debuginfo` flags specify the same setting, so we want to be able
     // to use them interchangeably. See the note above (regarding `-O` and `-C opt-level`)
@@ -2826,7 +3048,7 @@ pub fn build_session_options(
 
     let crate_name = matches.opt_str("crate-name");
 
-    let remap_path_prefix = parse_remap_path_prefix(handler, matches, &unstable_opts);
+    let remap_path_prefix = parse_remap_path_prefix(handler, matches, &unstable_opts, &working_dir);
 
     let pretty = parse_pretty(handler, &unstable_opts);
 
@@ -2837,13 +3059,10 @@ pub fn build_session_options(
 
     // Try to find a directory containing the Rust `src`, for more details see
     // the doc comment on the `real_rust_source_base_dir` field.
-    let tmp_buf;
     let sysroot = match &sysroot_opt {
-        Some(s) => s,
-        None => {
-            tmp_buf = crate::filesearch::get_or_default_sysroot().expect("Failed finding sysroot");
-            &tmp_buf
-        }
+        Some(s) if resolve_paths => working_dir.join(s),
+        Some(s) => s.clone(),
+        None => crate::filesearch::get_or_default_sysroot().expect("Failed finding sysroot"),
     };
     let real_rust_source_base_dir = {
         // This is the location used by the `rust-src` `rustup` component.
@@ -2863,10 +3082,6 @@ pub fn build_session_options(
         candidate.join("library/std/src/lib.rs").is_file().then_some(candidate)
     };
 
-    let working_dir = std::env::current_dir().unwrap_or_else(|e| {
-        handler.early_error(format!("Current directory is invalid: {e}"));
-    });
-
     let remap = file_path_mapping(remap_path_prefix.clone(), &unstable_opts);
     let (path, remapped) = remap.map_prefix(&working_dir);
     let working_dir = if remapped {
@@ -2875,7 +3090,16 @@ pub fn build_session_options(
         RealFileName::LocalPath(path.into_owned())
     };
 
-    Options {
+    let lint_config = unstable_opts.lint_config.as_deref().map(|path| {
+        LintConfig::load(path, working_dir.local_path_if_available()).unwrap_or_else(|e| {
+            handler.early_error(format!(
+                "failed to load the lint configuration `{}`: {e}",
+                path.display()
+            ))
+        })
+    });
+
+    let mut options = Options {
         assert_incr_state,
         crate_types,
         optimize: opt_level,
@@ -2883,6 +3107,7 @@ pub fn build_session_options(
         debuginfo_compression,
         lint_opts,
         lint_cap,
+        lint_config,
         describe_lints,
         output_types,
         search_paths,
@@ -2915,7 +3140,15 @@ pub fn build_session_options(
         pretty,
         working_dir,
         color,
+        logical_env: None,
+        resolved_output_dirs: Vec::new(),
+    };
+
+    if resolve_paths {
+        options.resolve_paths();
     }
+
+    options
 }
 
 fn parse_pretty(handler: &EarlyErrorHandler, unstable_opts: &UnstableOptions) -> Option<PpMode> {
@@ -3171,10 +3404,10 @@ pub fn needs_analysis(&self) -> bool {
 pub(crate) mod dep_tracking {
     use super::{
         BranchProtection, CFGuard, CFProtection, CrateType, DebugInfo, DebugInfoCompression,
-        ErrorOutputType, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LocationDetail,
-        LtoCli, OomStrategy, OptLevel, OutFileName, OutputType, OutputTypes, Polonius,
-        RemapPathScopeComponents, ResolveDocLinks, SourceFileHashAlgorithm, SplitDwarfKind,
-        SwitchWithOptPath, SymbolManglingVersion, TraitSolver, TrimmedDefPaths,
+        ErrorOutputType, InstrumentCoverage, InstrumentXRay, LinkerPluginLto, LintConfig,
+        LocationDetail, LtoCli, OomStrategy, OptLevel, OutFileName, OutputType, OutputTypes,
+        Polonius, RemapPathScopeComponents, ResolveDocLinks, SourceFileHashAlgorithm,
+        SplitDwarfKind, SwitchWithOptPath, SymbolManglingVersion, TraitSolver, TrimmedDefPaths,
     };
     use crate::lint;
     use crate::options::WasiExecModel;
@@ -3239,6 +3472,7 @@ fn hash(
         String,
         PathBuf,
         lint::Level,
+        LintConfig,
         WasiExecModel,
         u32,
         RelocModel,
@@ -3419,6 +3653,15 @@ pub fn extension(self) -> &'static str {
     }
 }
 
+/// Which format to use for `-Z print-type-sizes` and `-Z print-vtable-sizes`
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum PrintSizesFormat {
+    /// Human-readable lines, prefixed with the name of the flag
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
 /// `-Zpolonius` values, enabling the borrow checker polonius analysis, and which version: legacy,
 /// or future prototype.
 #[derive(Clone, Copy, PartialEq, Hash, Debug, Default)]
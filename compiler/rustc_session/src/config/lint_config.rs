@@ -0,0 +1,274 @@
+//! The `-Z lint-config` file, which sets lint levels for the modules of the source files that
+//! match some path globs.
+//!
+//! The file is written in a subset of TOML: each table is named after a glob, and lists the lints
+//! to set to each level for the files that match it.
+//!
+//! ```toml
+//! # Deny unsafe code everywhere, except in the FFI bindings.
+//! ["**"]
+//! deny = ["unsafe_code"]
+//!
+//! ["src/ffi/**"]
+//! allow = ["unsafe_code"]
+//! ```
+//!
+//! Globs are relative to the directory containing the file. `*` matches any part of a path
+//! component, `?` matches any single character, and `**` matches any number of components.
+//!
+//! The levels apply to the module that a file defines as if they were inner attributes at the
+//! top of that file, in the order they are written: later tables override earlier ones, and the
+//! attributes in the source code override all of them.
+
+use crate::lint;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+#[cfg(test)]
+mod tests;
+
+/// The parsed contents of a `-Z lint-config` file.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct LintConfig {
+    /// The absolute path of the directory that the globs are relative to.
+    pub root: PathBuf,
+    pub rules: Vec<LintConfigRule>,
+}
+
+/// The lint levels that a table of the file sets.
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub struct LintConfigRule {
+    pub pattern: String,
+    /// The lints and their levels, in the order they are written in.
+    pub levels: Vec<(String, lint::Level)>,
+}
+
+impl LintConfig {
+    /// Loads the file at `path`, which is relative to `working_dir` if it isn't absolute.
+    pub fn load(path: &Path, working_dir: &Path) -> Result<LintConfig, String> {
+        let path = working_dir.join(path);
+        let src = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        LintConfig::parse(root, &src)
+    }
+
+    pub fn parse(root: PathBuf, src: &str) -> Result<LintConfig, String> {
+        let rules = Parser { src, pos: 0 }.parse()?;
+        Ok(LintConfig { root, rules })
+    }
+
+    /// The rules that apply to `file`, which is relative to `working_dir` if it isn't absolute,
+    /// in the order they are written in.
+    pub fn rules_for<'a>(
+        &'a self,
+        file: &Path,
+        working_dir: &Path,
+    ) -> impl Iterator<Item = &'a LintConfigRule> {
+        let relative = working_dir.join(file).strip_prefix(&self.root).ok().map(normalize);
+        self.rules.iter().filter(move |rule| {
+            relative.as_ref().is_some_and(|components| {
+                let pattern: Vec<_> = rule.pattern.split('/').filter(|c| !c.is_empty()).collect();
+                glob_matches(&pattern, components)
+            })
+        })
+    }
+}
+
+/// The components of `path`, with `.` and `..` resolved.
+fn normalize(path: &Path) -> Vec<String> {
+    let mut components = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                components.pop();
+            }
+            component => components.push(component.as_os_str().to_string_lossy().into_owned()),
+        }
+    }
+    components
+}
+
+fn glob_matches(pattern: &[&str], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| glob_matches(rest, &path[skip..])),
+        Some((component, rest)) => match path.split_first() {
+            Some((first, path)) => {
+                component_matches(component.as_bytes(), first.as_bytes())
+                    && glob_matches(rest, path)
+            }
+            None => false,
+        },
+    }
+}
+
+fn component_matches(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            (0..=name.len()).any(|skip| component_matches(rest, &name[skip..]))
+        }
+        (Some((b'?', rest)), Some((_, name))) => component_matches(rest, name),
+        (Some((c, rest)), Some((n, name))) => c == n && component_matches(rest, name),
+        _ => false,
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(mut self) -> Result<Vec<LintConfigRule>, String> {
+        let mut rules: Vec<LintConfigRule> = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Ok(rules),
+                Some('[') => {
+                    self.pos += 1;
+                    self.skip_spaces();
+                    let pattern = self.key()?;
+                    self.skip_spaces();
+                    self.expect(']')?;
+                    self.end_of_line()?;
+                    rules.push(LintConfigRule { pattern, levels: Vec::new() });
+                }
+                Some(_) => {
+                    let key = self.key()?;
+                    let Some(level) = lint::Level::from_str(&key) else {
+                        return Err(self.error(format!(
+                            "unknown lint level `{key}`, expected `allow`, `warn`, `deny` or \
+                             `forbid`"
+                        )));
+                    };
+                    let Some(rule) = rules.last_mut() else {
+                        return Err(self.error("lint levels must be inside a table".to_owned()));
+                    };
+                    self.skip_spaces();
+                    self.expect('=')?;
+                    self.skip_spaces();
+                    for lint_name in self.array()? {
+                        rule.levels.push((lint_name.replace('-', "_"), level));
+                    }
+                    self.end_of_line()?;
+                }
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn error(&self, msg: String) -> String {
+        let line = self.src[..self.pos].lines().count().max(1);
+        format!("line {line}: {msg}")
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{c}`")))
+        }
+    }
+
+    /// Skips spaces and comments, but not newlines.
+    fn skip_spaces(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' => self.pos += 1,
+                '#' => {
+                    self.pos += self.src[self.pos..].find('\n').unwrap_or(self.src.len() - self.pos)
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Skips spaces, comments and newlines.
+    fn skip_whitespace(&mut self) {
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                Some('\n') => self.pos += 1,
+                Some('\r') if self.src[self.pos..].starts_with("\r\n") => self.pos += 2,
+                _ => break,
+            }
+        }
+    }
+
+    fn end_of_line(&mut self) -> Result<(), String> {
+        self.skip_spaces();
+        match self.peek() {
+            None | Some('\n') => Ok(()),
+            Some('\r') if self.src[self.pos..].starts_with("\r\n") => Ok(()),
+            Some(_) => Err(self.error("expected a newline".to_owned())),
+        }
+    }
+
+    /// Parses a bare or quoted key.
+    fn key(&mut self) -> Result<String, String> {
+        if self.peek() == Some('"') {
+            return self.string();
+        }
+        let len = self.src[self.pos..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(self.src.len() - self.pos);
+        if len == 0 {
+            return Err(self.error("expected a key".to_owned()));
+        }
+        let key = self.src[self.pos..self.pos + len].to_owned();
+        self.pos += len;
+        Ok(key)
+    }
+
+    /// Parses a basic string, without escapes other than `\"` and `\\`.
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut s = String::new();
+        let mut chars = self.src[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(s);
+                }
+                '\\' => match chars.next() {
+                    Some((_, c @ ('"' | '\\'))) => s.push(c),
+                    _ => {
+                        self.pos += i;
+                        return Err(self.error("unsupported escape sequence".to_owned()));
+                    }
+                },
+                '\n' => break,
+                c => s.push(c),
+            }
+        }
+        Err(self.error("unterminated string".to_owned()))
+    }
+
+    /// Parses an array of strings, which may span several lines.
+    fn array(&mut self) -> Result<Vec<String>, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(']') {
+                self.pos += 1;
+                return Ok(items);
+            }
+            items.push(self.string()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {}
+                _ => return Err(self.error("expected `,` or `]`".to_owned())),
+            }
+        }
+    }
+}
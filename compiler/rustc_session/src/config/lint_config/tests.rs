@@ -0,0 +1,140 @@
+use super::*;
+use crate::lint::Level;
+
+fn parse(src: &str) -> Result<LintConfig, String> {
+    LintConfig::parse(PathBuf::from("/project"), src)
+}
+
+fn matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<_> = pattern.split('/').filter(|c| !c.is_empty()).collect();
+    glob_matches(&pattern, &normalize(Path::new(path)))
+}
+
+/// The patterns of the rules that apply to `file`, for a working directory of `working_dir`.
+fn patterns<'a>(config: &'a LintConfig, file: &str, working_dir: &str) -> Vec<&'a str> {
+    config
+        .rules_for(Path::new(file), Path::new(working_dir))
+        .map(|rule| rule.pattern.as_str())
+        .collect()
+}
+
+#[test]
+fn test_parse() {
+    let config = parse(
+        "# Deny unsafe code everywhere.\n\
+         [\"**\"]\n\
+         deny = [\"unsafe_code\"] # except below\n\
+         \n\
+         [ ffi ]\n\
+         allow = [\n\
+         \x20   \"unsafe-code\",\n\
+         \x20   \"dead_code\",\n\
+         ]\n\
+         warn = []\n",
+    )
+    .unwrap();
+    assert_eq!(config.root, PathBuf::from("/project"));
+    assert_eq!(
+        config.rules,
+        [
+            LintConfigRule {
+                pattern: "**".to_owned(),
+                levels: vec![("unsafe_code".to_owned(), Level::Deny)],
+            },
+            LintConfigRule {
+                pattern: "ffi".to_owned(),
+                levels: vec![
+                    ("unsafe_code".to_owned(), Level::Allow),
+                    ("dead_code".to_owned(), Level::Allow),
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_parse_crlf() {
+    let config = parse("[\"src/*.rs\"]\r\nforbid = [\"unsafe_code\"]\r\n").unwrap();
+    assert_eq!(config.rules[0].pattern, "src/*.rs");
+    assert_eq!(config.rules[0].levels, [("unsafe_code".to_owned(), Level::Forbid)]);
+}
+
+#[test]
+fn test_parse_quoting() {
+    let config = parse(r#"["src/\"odd\"/a\\b.rs"]"#).unwrap();
+    assert_eq!(config.rules[0].pattern, r#"src/"odd"/a\b.rs"#);
+
+    let config = parse("[\"src/#1.rs\"] # not part of the key\n").unwrap();
+    assert_eq!(config.rules[0].pattern, "src/#1.rs");
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = |src| parse(src).unwrap_err();
+    assert_eq!(error("deny = [\"unsafe_code\"]"), "line 1: lint levels must be inside a table");
+    assert_eq!(
+        error("[\"**\"]\nignore = [\"unsafe_code\"]"),
+        "line 2: unknown lint level `ignore`, expected `allow`, `warn`, `deny` or `forbid`"
+    );
+    assert_eq!(error("[\"**\"\n"), "line 1: expected `]`");
+    assert_eq!(error("[\"src/**]\n"), "line 1: unterminated string");
+    assert_eq!(error(r#"["src\n"]"#), "line 1: unsupported escape sequence");
+    assert_eq!(error("[\"**\"] deny = []"), "line 1: expected a newline");
+    assert_eq!(error("[\"**\"]\ndeny = [\"a\" \"b\"]"), "line 2: expected `,` or `]`");
+    assert_eq!(error("[\"**\"]\ndeny [\"a\"]"), "line 2: expected `=`");
+    assert_eq!(error("[]"), "line 1: expected a key");
+}
+
+#[test]
+fn test_star() {
+    assert!(matches("*", "lib.rs"));
+    assert!(!matches("*", "src/lib.rs"));
+    assert!(matches("src/*.rs", "src/lib.rs"));
+    assert!(matches("src/*.rs", "src/.rs"));
+    assert!(!matches("src/*.rs", "src/lib.txt"));
+    assert!(!matches("src/*.rs", "src/ffi/mod.rs"));
+    assert!(matches("src/f*i/*", "src/ffi/mod.rs"));
+    assert!(matches("src/*f*/mod.rs", "src/ffi/mod.rs"));
+    assert!(!matches("src/*", "src"));
+    assert!(matches("src/?.rs", "src/a.rs"));
+    assert!(!matches("src/?.rs", "src/ab.rs"));
+}
+
+#[test]
+fn test_double_star() {
+    assert!(matches("**", "lib.rs"));
+    assert!(matches("**", "src/a/b/c.rs"));
+    assert!(matches("src/**", "src/lib.rs"));
+    assert!(matches("src/**", "src/ffi/mod.rs"));
+    assert!(!matches("src/**", "tests/lib.rs"));
+    assert!(matches("**/mod.rs", "mod.rs"));
+    assert!(matches("**/mod.rs", "src/ffi/mod.rs"));
+    assert!(!matches("**/mod.rs", "src/ffi/lib.rs"));
+    assert!(matches("src/**/b.rs", "src/b.rs"));
+    assert!(matches("src/**/b.rs", "src/a/x/b.rs"));
+    assert!(matches("src/**/*.rs", "src/a/x/b.rs"));
+    assert!(!matches("src/**/b.rs", "b.rs"));
+    // `.` and `..` are resolved before matching.
+    assert!(matches("src/*.rs", "src/./ffi/../lib.rs"));
+}
+
+#[test]
+fn test_rules_for() {
+    let config = parse(
+        "[\"**\"]\n\
+         deny = [\"unsafe_code\"]\n\
+         [\"src/ffi/**\"]\n\
+         allow = [\"unsafe_code\"]\n\
+         [\"src/*.rs\"]\n\
+         warn = [\"missing_docs\"]\n",
+    )
+    .unwrap();
+    // Later tables override earlier ones, so the rules are returned in the order they are written.
+    assert_eq!(patterns(&config, "src/ffi/mod.rs", "/project"), ["**", "src/ffi/**"]);
+    assert_eq!(patterns(&config, "src/lib.rs", "/project"), ["**", "src/*.rs"]);
+    // Relative paths are relative to the working directory, absolute ones aren't.
+    assert_eq!(patterns(&config, "ffi/mod.rs", "/project/src"), ["**", "src/ffi/**"]);
+    assert_eq!(patterns(&config, "/project/src/lib.rs", "/elsewhere"), ["**", "src/*.rs"]);
+    // Files outside of the directory of the configuration match nothing, not even `**`.
+    assert!(patterns(&config, "lib.rs", "/elsewhere").is_empty());
+}
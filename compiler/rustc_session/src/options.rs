@@ -1,3 +1,4 @@
+use crate::config::lint_config::LintConfig;
 use crate::config::*;
 
 use crate::search_paths::SearchPath;
@@ -143,6 +144,8 @@ pub struct Options {
         debuginfo_compression: DebugInfoCompression [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED_NO_CRATE_HASH],
         lint_cap: Option<lint::Level> [TRACKED_NO_CRATE_HASH],
+        /// The contents of the `-Z lint-config` file.
+        lint_config: Option<LintConfig> [TRACKED_NO_CRATE_HASH],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -220,6 +223,16 @@ pub struct Options {
         /// The (potentially remapped) working directory
         working_dir: RealFileName [TRACKED],
         color: ColorConfig [UNTRACKED],
+
+        /// If `Some`, the environment that `env!` and `option_env!` read, instead
+        /// of the environment of the process. Macros are expanded again in every
+        /// session, so the values they read are tracked through the HIR.
+        logical_env: Option<BTreeMap<String, String>> [UNTRACKED],
+
+        /// The directories of the outputs, resolved against a working directory
+        /// other than the one of the process, with the paths they were given as.
+        /// See `Options::display_output_path`.
+        resolved_output_dirs: Vec<(PathBuf, PathBuf)> [UNTRACKED],
     }
 );
 
@@ -388,8 +401,9 @@ mod desc {
     pub const parse_optimization_fuel: &str = "crate=integer";
     pub const parse_mir_spanview: &str = "`statement` (default), `terminator`, or `block`";
     pub const parse_dump_mono_stats: &str = "`markdown` (default) or `json`";
-    pub const parse_instrument_coverage: &str =
-        "`all` (default), `branch`, `except-unused-generics`, `except-unused-functions`, or `off`";
+    pub const parse_print_sizes_format: &str = "`text` (default) or `json`";
+    pub const parse_instrument_coverage: &str = "`all` (default), `branch`, `mcdc`, \
+        `except-unused-generics`, `except-unused-functions`, or `off`";
     pub const parse_instrument_xray: &str = "either a boolean (`yes`, `no`, `on`, `off`, etc), or a comma separated list of settings: `always` or `never` (mutually exclusive), `ignore-loops`, `instruction-threshold=N`, `skip-entry`, `skip-exit`";
     pub const parse_unpretty: &str = "`string` or `string=string`";
     pub const parse_treat_err_as_bug: &str = "either no value or a non-negative number";
@@ -912,6 +926,21 @@ pub(crate) fn parse_dump_mono_stats(slot: &mut DumpMonoStatsFormat, v: Option<&s
         }
     }
 
+    pub(crate) fn parse_print_sizes_format(slot: &mut PrintSizesFormat, v: Option<&str>) -> bool {
+        match v {
+            None => true,
+            Some("json") => {
+                *slot = PrintSizesFormat::Json;
+                true
+            }
+            Some("text") => {
+                *slot = PrintSizesFormat::Text;
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
     pub(crate) fn parse_instrument_coverage(
         slot: &mut InstrumentCoverage,
         v: Option<&str>,
@@ -932,6 +961,7 @@ pub(crate) fn parse_instrument_coverage(
         *slot = match v {
             "all" => InstrumentCoverage::All,
             "branch" => InstrumentCoverage::Branch,
+            "mcdc" => InstrumentCoverage::Mcdc,
             "except-unused-generics" | "except_unused_generics" => {
                 InstrumentCoverage::ExceptUnusedGenerics
             }
@@ -1492,6 +1522,8 @@ pub(crate) fn parse_dump_solver_proof_tree(
     dep_info_omit_d_target: bool = (false, parse_bool, [TRACKED],
         "in dep-info output, omit targets for tracking dependencies of the dep-info files \
         themselves (default: no)"),
+    diagnostic_baseline: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "suppress the lint diagnostics listed in a baseline file"),
     dont_buffer_diagnostics: bool = (false, parse_bool, [UNTRACKED],
         "emit diagnostics rather than buffering (breaks NLL error downgrading, sorting) \
         (default: no)"),
@@ -1612,6 +1644,8 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    lint_config: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "set lint levels for the modules of the source files matching path globs, from a TOML file"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
@@ -1718,6 +1752,8 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "print the LLVM optimization passes being run (default: no)"),
     print_mono_items: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "print the result of the monomorphization collection pass"),
+    print_sizes_format: PrintSizesFormat = (PrintSizesFormat::Text, parse_print_sizes_format, [UNTRACKED],
+        "the format to use for -Z print-type-sizes and -Z print-vtable-sizes (`text` (default) or `json`)"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
     print_vtable_sizes: bool = (false, parse_bool, [UNTRACKED],
@@ -1742,6 +1778,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "enable queries of the dependency graph for regression testing (default: no)"),
     randomize_layout: bool = (false, parse_bool, [TRACKED],
         "randomize the layout of types (default: no)"),
+    reduce_ice: bool = (false, parse_bool, [UNTRACKED],
+        "when the compiler panics, reduce the crate to a smaller program that triggers the same \
+        ICE, and write it next to the ICE report (default: no)"),
     relax_elf_relocations: Option<bool> = (None, parse_opt_bool, [TRACKED],
         "whether ELF relocations can be relaxed"),
     relro_level: Option<RelroLevel> = (None, parse_relro_level, [TRACKED],
@@ -1923,6 +1962,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         Requires `-Clto[=[fat,yes]]`"),
     wasi_exec_model: Option<WasiExecModel> = (None, parse_wasi_exec_model, [TRACKED],
         "whether to build a wasi command or reactor"),
+    write_baseline: bool = (false, parse_bool, [UNTRACKED],
+        "record the lint diagnostics of this session in the `-Z diagnostic-baseline` file \
+        instead of suppressing them (default: no)"),
     write_long_types_to_disk: bool = (true, parse_bool, [UNTRACKED],
         "whether long type names should be written to files instead of being printed in errors"),
     // tidy-alphabetical-end
@@ -1,5 +1,5 @@
 use crate::code_stats::CodeStats;
-pub use crate::code_stats::{DataTypeKind, FieldInfo, FieldKind, SizeKind, VariantInfo};
+pub use crate::code_stats::{DataTypeKind, FieldInfo, FieldKind, NicheInfo, SizeKind, VariantInfo};
 use crate::config::{
     self, CrateType, InstrumentCoverage, OptLevel, OutFileName, OutputType,
     RemapPathScopeComponents, SwitchWithOptPath,
@@ -20,9 +20,11 @@
     AtomicU64, AtomicUsize, Lock, Lrc, OneThread, Ordering, Ordering::SeqCst,
 };
 use rustc_errors::annotate_snippet_emitter_writer::AnnotateSnippetEmitterWriter;
+use rustc_errors::baseline::DiagnosticBaseline;
 use rustc_errors::emitter::{DynEmitter, EmitterWriter, HumanReadableErrorType};
 use rustc_errors::json::JsonEmitter;
 use rustc_errors::registry::Registry;
+use rustc_errors::sarif::SarifEmitter;
 use rustc_errors::{
     error_code, fallback_fluent_bundle, DiagnosticBuilder, DiagnosticId, DiagnosticMessage,
     ErrorGuaranteed, FluentBundle, Handler, IntoDiagnostic, LazyFallbackBundle, MultiSpan, Noted,
@@ -41,7 +43,9 @@
 
 use std::cell::{self, RefCell};
 use std::env;
+use std::ffi::OsString;
 use std::fmt;
+use std::io::{self, IsTerminal};
 use std::ops::{Div, Mul};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
@@ -297,6 +301,7 @@ fn check_miri_unleashed_features(&self) {
     /// Invoked all the way at the end to finish off diagnostics printing.
     pub fn finish_diagnostics(&self, registry: &Registry) {
         self.check_miri_unleashed_features();
+        self.diagnostic().write_baseline();
         self.diagnostic().print_error_count(registry);
         self.emit_future_breakage();
     }
@@ -650,6 +655,7 @@ pub fn delay_good_path_bug(&self, msg: impl Into<DiagnosticMessage>) {
             || self.opts.unstable_opts.dump_mir.is_some()
             || self.opts.unstable_opts.unpretty.is_some()
             || self.opts.output_types.contains_key(&OutputType::Mir)
+            || self.opts.output_types.contains_key(&OutputType::StableMir)
             || std::env::var_os("RUSTC_LOG").is_some()
         {
             return;
@@ -709,7 +715,14 @@ pub fn instrument_coverage(&self) -> bool {
     }
 
     pub fn instrument_coverage_branch(&self) -> bool {
-        self.opts.cg.instrument_coverage() == InstrumentCoverage::Branch
+        matches!(
+            self.opts.cg.instrument_coverage(),
+            InstrumentCoverage::Branch | InstrumentCoverage::Mcdc
+        )
+    }
+
+    pub fn instrument_coverage_mcdc(&self) -> bool {
+        self.opts.cg.instrument_coverage() == InstrumentCoverage::Mcdc
     }
 
     pub fn instrument_coverage_except_unused_generics(&self) -> bool {
@@ -811,6 +824,15 @@ pub fn host_filesearch(&self, kind: PathKind) -> filesearch::FileSearch<'_> {
         )
     }
 
+    /// Reads `key` from the logical environment of the session if it has one, and from the
+    /// environment of the process otherwise.
+    pub fn env_var_os(&self, key: &str) -> Option<OsString> {
+        match &self.opts.logical_env {
+            Some(logical_env) => logical_env.get(key).map(OsString::from),
+            None => env::var_os(key),
+        }
+    }
+
     /// Returns a list of directories where target-specific tool binaries are located.
     pub fn get_tools_search_paths(&self, self_contained: bool) -> Vec<PathBuf> {
         let rustlib_path = rustc_target::target_rustlib_path(&self.sysroot, &config::host_triple());
@@ -883,6 +905,15 @@ pub fn incr_comp_session_dir_opt(&self) -> Option<cell::Ref<'_, PathBuf>> {
         self.opts.incremental.as_ref().map(|_| self.incr_comp_session_dir())
     }
 
+    /// Returns the session directory once it has been finalized, i.e. once the files in it are
+    /// the ones that the next session will load.
+    pub fn finalized_incr_comp_session_dir(&self) -> Option<PathBuf> {
+        match *self.incr_comp_session.borrow() {
+            IncrCompSession::Finalized { ref session_directory } => Some(session_directory.clone()),
+            _ => None,
+        }
+    }
+
     pub fn print_perf_stats(&self) {
         eprintln!(
             "Total time spent computing symbol hashes:      {}",
@@ -1326,6 +1357,15 @@ fn default_emitter(
                 {
                     TerminalUrl::Yes
                 }
+                // The Unicode output is opt-in, so it can link whenever the output goes to a
+                // terminal, as terminals that don't support hyperlinks ignore them.
+                _ if matches!(
+                    sopts.error_format,
+                    config::ErrorOutputType::HumanReadable(HumanReadableErrorType::Unicode(_))
+                ) && io::stderr().is_terminal() =>
+                {
+                    TerminalUrl::Yes
+                }
                 _ => TerminalUrl::No,
             }
         }
@@ -1349,11 +1389,13 @@ fn default_emitter(
                     .fluent_bundle(bundle)
                     .sm(Some(source_map))
                     .short_message(short)
+                    .theme(kind.theme())
                     .teach(sopts.unstable_opts.teach)
                     .diagnostic_width(sopts.diagnostic_width)
                     .macro_backtrace(macro_backtrace)
                     .track_diagnostics(track_diagnostics)
                     .terminal_url(terminal_url)
+                    .working_dir(Some(sopts.working_dir.local_path_if_available().to_path_buf()))
                     .ignored_directories_in_source_blocks(
                         sopts.unstable_opts.ignore_directory_in_diagnostics_source_blocks.clone(),
                     );
@@ -1378,6 +1420,9 @@ fn default_emitter(
                 sopts.unstable_opts.ignore_directory_in_diagnostics_source_blocks.clone(),
             ),
         ),
+        config::ErrorOutputType::Sarif => {
+            Box::new(SarifEmitter::stderr(Some(registry), source_map, bundle, fallback_bundle))
+        }
     }
 }
 
@@ -1447,6 +1492,19 @@ pub fn build_session(
     if let Some(ice_file) = ice_file {
         span_diagnostic = span_diagnostic.with_ice_file(ice_file);
     }
+    if let Some(path) = &sopts.unstable_opts.diagnostic_baseline {
+        let baseline = if sopts.unstable_opts.write_baseline {
+            DiagnosticBaseline::record(path.clone())
+        } else {
+            DiagnosticBaseline::load(path).unwrap_or_else(|e| {
+                handler.early_error(format!(
+                    "failed to read diagnostic baseline `{}`: {e}",
+                    path.display()
+                ))
+            })
+        };
+        span_diagnostic = span_diagnostic.with_baseline(baseline);
+    }
 
     let self_profiler = if let SwitchWithOptPath::Enabled(ref d) = sopts.unstable_opts.self_profile
     {
@@ -1565,14 +1623,14 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
     // Make sure that any given profiling data actually exists so LLVM can't
     // decide to silently skip PGO.
     if let Some(ref path) = sess.opts.cg.profile_use {
-        if !path.exists() {
+        if !sess.opts.resolve_path(path).exists() {
             sess.emit_err(errors::ProfileUseFileDoesNotExist { path });
         }
     }
 
     // Do the same for sample profile data.
     if let Some(ref path) = sess.opts.unstable_opts.profile_sample_use {
-        if !path.exists() {
+        if !sess.opts.resolve_path(path).exists() {
             sess.emit_err(errors::ProfileSampleUseFileDoesNotExist { path });
         }
     }
@@ -1801,7 +1859,11 @@ fn mk_emitter(output: ErrorOutputType) -> Box<DynEmitter> {
     let emitter: Box<DynEmitter> = match output {
         config::ErrorOutputType::HumanReadable(kind) => {
             let (short, color_config) = kind.unzip();
-            Box::new(EmitterWriter::stderr(color_config, fallback_bundle).short_message(short))
+            Box::new(
+                EmitterWriter::stderr(color_config, fallback_bundle)
+                    .short_message(short)
+                    .theme(kind.theme()),
+            )
         }
         config::ErrorOutputType::Json { pretty, json_rendered } => Box::new(JsonEmitter::basic(
             pretty,
@@ -1813,6 +1875,11 @@ fn mk_emitter(output: ErrorOutputType) -> Box<DynEmitter> {
             false,
             TerminalUrl::No,
         )),
+        // The session writes the log of the compilation, so only write a log for early errors
+        // if there are any.
+        config::ErrorOutputType::Sarif => {
+            Box::new(SarifEmitter::basic(None, fallback_bundle).emit_empty_log(false))
+        }
     };
     emitter
 }
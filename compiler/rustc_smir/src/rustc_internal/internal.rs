@@ -6,8 +6,9 @@
 // Prefer importing stable_mir over internal rustc constructs to make this file more readable.
 use crate::rustc_smir::Tables;
 use rustc_middle::ty::{self as rustc_ty, Ty as InternalTy};
-use stable_mir::ty::{Const, GenericArgKind, GenericArgs, Region, Ty};
-use stable_mir::DefId;
+use stable_mir::abi::Layout;
+use stable_mir::ty::{AdtDef, Const, GenericArgKind, GenericArgs, Region, Ty, VariantDef};
+use stable_mir::{AllocId, DefId};
 
 use super::RustcInternal;
 
@@ -18,6 +19,21 @@ fn internal(&self, tables: &mut Tables<'tcx>) -> Self::T {
     }
 }
 
+impl<'tcx> RustcInternal<'tcx> for AdtDef {
+    type T = rustc_ty::AdtDef<'tcx>;
+    fn internal(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        tables.tcx.adt_def(self.0.internal(tables))
+    }
+}
+
+impl<'tcx> RustcInternal<'tcx> for VariantDef {
+    type T = &'tcx rustc_ty::VariantDef;
+    fn internal(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        let idx = rustc_target::abi::VariantIdx::from_usize(self.idx());
+        self.adt_def().internal(tables).variant(idx)
+    }
+}
+
 impl<'tcx> RustcInternal<'tcx> for GenericArgs {
     type T = rustc_ty::GenericArgsRef<'tcx>;
     fn internal(&self, tables: &mut Tables<'tcx>) -> Self::T {
@@ -65,3 +81,17 @@ fn internal(&self, tables: &mut Tables<'tcx>) -> Self::T {
         tables.constants[self.id]
     }
 }
+
+impl<'tcx> RustcInternal<'tcx> for AllocId {
+    type T = rustc_middle::mir::interpret::AllocId;
+    fn internal(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        tables.alloc_ids[*self]
+    }
+}
+
+impl<'tcx> RustcInternal<'tcx> for Layout {
+    type T = rustc_target::abi::Layout<'tcx>;
+    fn internal(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        tables.layouts[*self]
+    }
+}
@@ -0,0 +1,138 @@
+//! Serialization of the Stable MIR of a crate to JSON, for `--emit=stable-mir`.
+//!
+//! The output is a single JSON object with the body of every local item that has MIR. Types,
+//! definitions and spans are referenced by their index in the tables that come with it, so that
+//! each of them is only written once:
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "crate_name": "foo",
+//!   "items": [{ "def_id": 0, "name": "main", "kind": "Fn", "span": 0, "body": { .. } }],
+//!   "types": [{ "id": 0, "kind": { "RigidTy": { "Tuple": [] } } }],
+//!   "def_ids": [{ "id": 0, "name": "main" }],
+//!   "spans": [{ "id": 0, "file": "foo.rs", "lines": { "start_line": 1, .. } }]
+//! }
+//! ```
+//!
+//! Everything else follows the data types of `stable_mir`, as serialized by `serde`.
+
+use crate::rustc_internal::{run, with_tables};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::{OutFileName, OutputType};
+use serde::Serialize;
+use stable_mir::mir::Body;
+use stable_mir::ty::{IndexedVal, LineInfo, Span, Ty, TyKind};
+use stable_mir::{DefId, DefKind, Filename};
+use std::fs::File;
+use std::io::{self, Write};
+
+/// The version of the JSON schema, which is bumped whenever the output changes in a way that
+/// could break existing consumers.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct CrateJson {
+    schema_version: u32,
+    crate_name: String,
+    items: Vec<ItemJson>,
+    types: Vec<TypeJson>,
+    def_ids: Vec<DefIdJson>,
+    spans: Vec<SpanJson>,
+}
+
+#[derive(Serialize)]
+struct ItemJson {
+    def_id: DefId,
+    name: String,
+    kind: DefKind,
+    span: Span,
+    body: Body,
+}
+
+#[derive(Serialize)]
+struct TypeJson {
+    id: Ty,
+    kind: TyKind,
+}
+
+#[derive(Serialize)]
+struct DefIdJson {
+    id: DefId,
+    name: String,
+}
+
+#[derive(Serialize)]
+struct SpanJson {
+    id: Span,
+    file: Filename,
+    lines: LineInfo,
+}
+
+/// Writes the Stable MIR of the local crate to the `stable-mir` output file.
+pub fn emit_stable_mir(tcx: TyCtxt<'_>) -> io::Result<()> {
+    match tcx.output_filenames(()).path(OutputType::StableMir) {
+        OutFileName::Stdout => {
+            let mut f = io::stdout();
+            write_smir_json(tcx, &mut f)?;
+        }
+        OutFileName::Real(path) => {
+            let mut f = io::BufWriter::new(File::create(&path)?);
+            write_smir_json(tcx, &mut f)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the Stable MIR of the local crate as JSON, followed by a newline.
+pub fn write_smir_json<W: Write>(tcx: TyCtxt<'_>, w: &mut W) -> io::Result<()> {
+    let mut krate = None;
+    run(tcx, || krate = Some(collect_crate()));
+    serde_json::to_writer(&mut *w, &krate.unwrap())?;
+    writeln!(w)?;
+    w.flush()
+}
+
+fn collect_crate() -> CrateJson {
+    let items = stable_mir::all_local_items()
+        .into_iter()
+        .map(|item| ItemJson {
+            def_id: item.0,
+            name: item.name(),
+            kind: item.kind(),
+            span: item.span(),
+            body: item.body(),
+        })
+        .collect();
+
+    // Converting the kind of a type can create new types, e.g. for its fields or generic
+    // arguments, so keep going until every type that was created has been written.
+    let mut types = Vec::new();
+    while types.len() < with_tables(|tables| tables.types.len()) {
+        let ty = Ty::to_val(types.len());
+        types.push(TypeJson { id: ty, kind: ty.kind() });
+    }
+
+    let def_ids = (0..with_tables(|tables| tables.def_ids.len()))
+        .map(|index| {
+            let def_id = DefId::to_val(index);
+            DefIdJson { id: def_id, name: stable_mir::with(|cx| cx.name_of_def_id(def_id)) }
+        })
+        .collect();
+
+    let spans = (0..with_tables(|tables| tables.spans.len()))
+        .map(|index| {
+            let span = Span::to_val(index);
+            SpanJson { id: span, file: span.get_filename(), lines: span.get_lines() }
+        })
+        .collect();
+
+    CrateJson {
+        schema_version: SCHEMA_VERSION,
+        crate_name: stable_mir::local_crate().name,
+        items,
+        types,
+        def_ids,
+        spans,
+    }
+}
@@ -18,8 +18,10 @@
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::ops::Index;
+use std::path::Path;
 
 mod internal;
+pub mod json;
 
 pub fn stable<'tcx, S: Stable<'tcx>>(item: &S) -> S::T {
     with_tables(|tables| item.stable(tables))
@@ -170,6 +172,8 @@ pub fn run(tcx: TyCtxt<'_>, f: impl FnOnce()) {
         types: IndexMap::default(),
         instances: IndexMap::default(),
         constants: IndexMap::default(),
+        layouts: IndexMap::default(),
+        mono_items: None,
     }));
     stable_mir::run(&tables, || init(&tables, f));
 }
@@ -251,6 +255,90 @@ fn after_analysis<'tcx>(
     }};
 }
 
+/// Flags that are added to every compilation performed by [`run_driver!`](crate::run_driver),
+/// so that stable MIR can inspect the body of items defined in other crates of the workspace.
+pub const DRIVER_FLAGS: &[&str] = &["-Zalways-encode-mir"];
+
+/// Prepare the arguments that a [`run_driver!`](crate::run_driver) tool was invoked with to be
+/// given to the compiler.
+///
+/// When the tool is used as `RUSTC_WRAPPER` or `RUSTC_WORKSPACE_WRAPPER`, Cargo passes the path
+/// to `rustc` as the first argument, which is dropped here. Returns the arguments to run the
+/// compiler with, and whether the crate being compiled should be analyzed. Invocations that only
+/// query information (e.g. `rustc -vV`), and compilations of dependencies or build scripts, are
+/// passed through to the compiler without running the analysis.
+pub fn driver_args(mut args: Vec<String>) -> (Vec<String>, bool) {
+    if args.get(1).is_some_and(|arg| Path::new(arg).file_stem() == Some("rustc".as_ref())) {
+        args.remove(1);
+    }
+    let is_query = args.iter().skip(1).any(|arg| {
+        matches!(arg.as_str(), "-V" | "-vV" | "--version") || arg.starts_with("--print")
+    });
+    if is_query {
+        return (args, false);
+    }
+    // Cargo caps the lints of crates that don't belong to the workspace.
+    let is_dependency = args.windows(2).any(|pair| pair[0] == "--cap-lints" && pair[1] == "allow")
+        || args.iter().any(|arg| arg == "--cap-lints=allow");
+    let is_build_script = args
+        .windows(2)
+        .any(|pair| pair[0] == "--crate-name" && pair[1].starts_with("build_script_"));
+    for flag in DRIVER_FLAGS {
+        if !args.iter().any(|arg| arg == flag) {
+            args.push(flag.to_string());
+        }
+    }
+    (args, !is_dependency && !is_build_script)
+}
+
+/// Run the compiler with the given arguments, and invoke the callback after analysis if the
+/// crate being compiled should be analyzed. This can be used to implement the `main` function
+/// of a tool that is invoked by Cargo as `RUSTC_WORKSPACE_WRAPPER`:
+///
+/// ```ignore (illustrative)
+/// fn main() {
+///     let args = std::env::args().collect();
+///     let result = run_driver!(args, tcx, analyze(tcx));
+///     let code = match result {
+///         Ok(()) | Err(CompilerError::Skipped) => 0,
+///         Err(_) => 1,
+///     };
+///     std::process::exit(code)
+/// }
+/// ```
+///
+/// See [`driver_args`] for how the arguments are processed. Compilations that are passed through
+/// to the compiler without analysis return [`stable_mir::CompilerError::Skipped`] on success.
+#[macro_export]
+macro_rules! run_driver {
+    ($args:expr, $callback:expr) => {
+        run_driver!($args, tcx, $callback)
+    };
+    ($args:expr, $tcx:ident, $callback:expr) => {{
+        use rustc_driver::{Callbacks, RunCompiler};
+        use stable_mir::CompilerError;
+
+        let (args, analyze) = rustc_internal::driver_args($args);
+        if analyze {
+            $crate::run!(args, $tcx, $callback)
+        } else {
+            /// Runs the compiler without any custom behavior.
+            pub struct PassThrough;
+
+            impl Callbacks for PassThrough {}
+
+            let compiler_result = rustc_driver::catch_fatal_errors(|| {
+                RunCompiler::new(&args, &mut PassThrough).run()
+            });
+            match compiler_result {
+                Ok(Ok(())) => Err(CompilerError::Skipped),
+                Ok(Err(_)) => Err(CompilerError::CompilationFailed),
+                Err(_) => Err(CompilerError::ICE),
+            }
+        }
+    }};
+}
+
 /// Simmilar to rustc's `FxIndexMap`, `IndexMap` with extra
 /// safety features added.
 pub struct IndexMap<K, V> {
@@ -269,6 +357,11 @@ pub fn create_or_fetch(&mut self, key: K) -> V {
         let v = self.index_map.entry(key).or_insert(V::to_val(len));
         *v
     }
+
+    /// The number of values created so far, whose indices are `0..len`.
+    pub fn len(&self) -> usize {
+        self.index_map.len()
+    }
 }
 
 impl<K: PartialEq + Hash + Eq, V: Copy + Debug + PartialEq + IndexedVal> Index<V>
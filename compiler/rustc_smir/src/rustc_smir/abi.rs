@@ -0,0 +1,257 @@
+//! Conversion of the layouts and calling conventions computed by the compiler.
+
+use crate::rustc_smir::{Stable, Tables};
+use rustc_middle::ty::Ty;
+use rustc_target::abi::call::{ArgAbi, FnAbi, PassMode};
+use rustc_target::abi::{
+    Abi, AddressSpace, FieldIdx, FieldsShape, Integer, IntegerType, LayoutS, Niche, Primitive,
+    ReprOptions, Scalar, TagEncoding, VariantIdx, Variants, WrappingRange,
+};
+use stable_mir::abi;
+use stable_mir::opaque;
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::Layout<'tcx> {
+    type T = abi::Layout;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        tables.layouts.create_or_fetch(*self)
+    }
+}
+
+impl<'tcx> Stable<'tcx> for LayoutS<FieldIdx, VariantIdx> {
+    type T = abi::LayoutShape;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        abi::LayoutShape {
+            fields: self.fields.stable(tables),
+            variants: self.variants.stable(tables),
+            abi: self.abi.stable(tables),
+            largest_niche: self.largest_niche.as_ref().map(|niche| niche.stable(tables)),
+            abi_align: self.align.abi.bytes(),
+            size: self.size.bytes_usize(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for FieldsShape<FieldIdx> {
+    type T = abi::FieldsShape;
+
+    fn stable(&self, _tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            FieldsShape::Primitive => abi::FieldsShape::Primitive,
+            FieldsShape::Union(count) => abi::FieldsShape::Union(count.get()),
+            FieldsShape::Array { stride, count } => {
+                abi::FieldsShape::Array { stride: stride.bytes_usize(), count: *count }
+            }
+            FieldsShape::Arbitrary { offsets, .. } => abi::FieldsShape::Arbitrary {
+                offsets: offsets.iter().map(|offset| offset.bytes_usize()).collect(),
+            },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Variants<FieldIdx, VariantIdx> {
+    type T = abi::VariantsShape;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            Variants::Single { index } => {
+                abi::VariantsShape::Single { index: index.stable(tables) }
+            }
+            Variants::Multiple { tag, tag_encoding, tag_field, variants } => {
+                abi::VariantsShape::Multiple {
+                    tag: tag.stable(tables),
+                    tag_encoding: tag_encoding.stable(tables),
+                    tag_field: *tag_field,
+                    variants: variants.iter().map(|variant| variant.stable(tables)).collect(),
+                }
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for TagEncoding<VariantIdx> {
+    type T = abi::TagEncoding;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            TagEncoding::Direct => abi::TagEncoding::Direct,
+            TagEncoding::Niche { untagged_variant, niche_variants, niche_start } => {
+                abi::TagEncoding::Niche {
+                    untagged_variant: untagged_variant.stable(tables),
+                    niche_variants: niche_variants.start().stable(tables)
+                        ..=niche_variants.end().stable(tables),
+                    niche_start: *niche_start,
+                }
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Abi {
+    type T = abi::ValueAbi;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match *self {
+            Abi::Uninhabited => abi::ValueAbi::Uninhabited,
+            Abi::Scalar(scalar) => abi::ValueAbi::Scalar(scalar.stable(tables)),
+            Abi::ScalarPair(first, second) => {
+                abi::ValueAbi::ScalarPair(first.stable(tables), second.stable(tables))
+            }
+            Abi::Vector { element, count } => {
+                abi::ValueAbi::Vector { element: element.stable(tables), count }
+            }
+            Abi::Aggregate { sized } => abi::ValueAbi::Aggregate { sized },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Scalar {
+    type T = abi::Scalar;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match *self {
+            Scalar::Initialized { value, valid_range } => abi::Scalar::Initialized {
+                value: value.stable(tables),
+                valid_range: valid_range.stable(tables),
+            },
+            Scalar::Union { value } => abi::Scalar::Union { value: value.stable(tables) },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Primitive {
+    type T = abi::Primitive;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match *self {
+            Primitive::Int(length, signed) => {
+                abi::Primitive::Int { length: length.stable(tables), signed }
+            }
+            Primitive::F32 => abi::Primitive::Float { length: abi::FloatLength::F32 },
+            Primitive::F64 => abi::Primitive::Float { length: abi::FloatLength::F64 },
+            Primitive::Pointer(AddressSpace(space)) => {
+                abi::Primitive::Pointer(abi::AddressSpace(space))
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Integer {
+    type T = abi::IntegerLength;
+
+    fn stable(&self, _tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            Integer::I8 => abi::IntegerLength::I8,
+            Integer::I16 => abi::IntegerLength::I16,
+            Integer::I32 => abi::IntegerLength::I32,
+            Integer::I64 => abi::IntegerLength::I64,
+            Integer::I128 => abi::IntegerLength::I128,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for IntegerType {
+    type T = abi::IntegerType;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match *self {
+            IntegerType::Pointer(is_signed) => abi::IntegerType::Pointer { is_signed },
+            IntegerType::Fixed(length, is_signed) => {
+                abi::IntegerType::Fixed { length: length.stable(tables), is_signed }
+            }
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ReprOptions {
+    type T = abi::ReprOptions;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        abi::ReprOptions {
+            int: self.int.map(|int| int.stable(tables)),
+            align: self.align.map(|align| align.bytes()),
+            pack: self.pack.map(|pack| pack.bytes()),
+            is_c: self.c(),
+            is_simd: self.simd(),
+            is_transparent: self.transparent(),
+            is_linear: self.linear(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for WrappingRange {
+    type T = abi::WrappingRange;
+
+    fn stable(&self, _tables: &mut Tables<'tcx>) -> Self::T {
+        abi::WrappingRange { start: self.start, end: self.end }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for Niche {
+    type T = abi::Niche;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        abi::Niche {
+            offset: self.offset.bytes_usize(),
+            value: self.value.stable(tables),
+            valid_range: self.valid_range.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for FnAbi<'tcx, Ty<'tcx>> {
+    type T = abi::FnAbi;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        abi::FnAbi {
+            args: self.args.iter().map(|arg| arg.stable(tables)).collect(),
+            ret: self.ret.stable(tables),
+            fixed_count: self.fixed_count,
+            c_variadic: self.c_variadic,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ArgAbi<'tcx, Ty<'tcx>> {
+    type T = abi::ArgAbi;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        abi::ArgAbi {
+            ty: self.layout.ty.stable(tables),
+            layout: self.layout.layout.stable(tables),
+            mode: self.mode.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for PassMode {
+    type T = abi::PassMode;
+
+    fn stable(&self, _tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            PassMode::Ignore => abi::PassMode::Ignore,
+            PassMode::Direct(attrs) => abi::PassMode::Direct(opaque(attrs)),
+            PassMode::Pair(first, second) => abi::PassMode::Pair(opaque(first), opaque(second)),
+            PassMode::Cast { pad_i32, cast } => {
+                abi::PassMode::Cast { pad_i32: *pad_i32, cast: opaque(cast) }
+            }
+            PassMode::Indirect { attrs, meta_attrs, on_stack } => abi::PassMode::Indirect {
+                attrs: opaque(attrs),
+                meta_attrs: opaque(meta_attrs),
+                on_stack: *on_stack,
+            },
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::abi::Endian {
+    type T = abi::Endian;
+
+    fn stable(&self, _tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            rustc_target::abi::Endian::Little => abi::Endian::Little,
+            rustc_target::abi::Endian::Big => abi::Endian::Big,
+        }
+    }
+}
@@ -10,23 +10,31 @@
 use crate::rustc_internal::{IndexMap, RustcInternal};
 use crate::rustc_smir::hir::def::DefKind;
 use crate::rustc_smir::stable_mir::ty::{BoundRegion, EarlyBoundRegion, Region};
+use rustc_data_structures::fx::FxIndexSet;
+use rustc_data_structures::unord::{UnordMap, UnordSet};
 use rustc_hir as hir;
 use rustc_middle::mir;
 use rustc_middle::mir::interpret::{alloc_range, AllocId};
 use rustc_middle::mir::mono::MonoItem;
 use rustc_middle::ty::{self, Instance, ParamEnv, Ty, TyCtxt, Variance};
+use rustc_monomorphize::{collect_crate_mono_items, MonoItemCollectionMode};
 use rustc_span::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_span::symbol::{sym, Symbol};
 use rustc_target::abi::FieldIdx;
-use stable_mir::mir::mono::InstanceDef;
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
+use stable_mir::abi::MachineInfo;
+use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::mono::{InstanceDef, MonoItemEdge, MonoItemGraph, StaticDef};
 use stable_mir::mir::{Body, CopyNonOverlapping, Statement, UserTypeProjection, VariantIdx};
 use stable_mir::ty::{
-    Const, ConstId, ConstantKind, FloatTy, GenericParamDef, IntTy, LineInfo, Movability, RigidTy,
-    Span, TyKind, UintTy,
+    AdtDef, AdtKind, Const, ConstId, ConstantKind, FieldDef, FloatTy, GenericParamDef, IntTy,
+    LineInfo, Movability, RigidTy, Span, TyKind, UintTy, VariantDef,
 };
 use stable_mir::{self, opaque, Context, Filename};
 use std::cell::RefCell;
 use tracing::debug;
 
+mod abi;
 mod alloc;
 mod builder;
 
@@ -151,6 +159,65 @@ fn ty_kind(&self, ty: stable_mir::ty::Ty) -> TyKind {
         tables.types[ty].kind().stable(&mut *tables)
     }
 
+    fn def_ty(&self, item: stable_mir::DefId) -> stable_mir::ty::Ty {
+        let mut tables = self.0.borrow_mut();
+        let def_id = tables[item];
+        tables.tcx.type_of(def_id).instantiate_identity().stable(&mut *tables)
+    }
+
+    fn def_ty_with_args(
+        &self,
+        item: stable_mir::DefId,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> stable_mir::ty::Ty {
+        let mut tables = self.0.borrow_mut();
+        let def_id = tables[item];
+        let args = args.internal(&mut *tables);
+        let ty = tables.tcx.type_of(def_id).instantiate(tables.tcx, args);
+        tables.tcx.erase_regions(ty).stable(&mut *tables)
+    }
+
+    fn adt_kind(&self, def: AdtDef) -> AdtKind {
+        let mut tables = self.0.borrow_mut();
+        def.internal(&mut *tables).adt_kind().stable(&mut *tables)
+    }
+
+    fn adt_is_box(&self, def: AdtDef) -> bool {
+        let mut tables = self.0.borrow_mut();
+        def.internal(&mut *tables).is_box()
+    }
+
+    fn adt_is_phantom_data(&self, def: AdtDef) -> bool {
+        let mut tables = self.0.borrow_mut();
+        def.internal(&mut *tables).is_phantom_data()
+    }
+
+    fn adt_is_unsafe_cell(&self, def: AdtDef) -> bool {
+        let mut tables = self.0.borrow_mut();
+        def.internal(&mut *tables).is_unsafe_cell()
+    }
+
+    fn adt_repr(&self, def: AdtDef) -> stable_mir::abi::ReprOptions {
+        let mut tables = self.0.borrow_mut();
+        def.internal(&mut *tables).repr().stable(&mut *tables)
+    }
+
+    fn adt_variants_len(&self, def: AdtDef) -> usize {
+        let mut tables = self.0.borrow_mut();
+        def.internal(&mut *tables).variants().len()
+    }
+
+    fn variant_name(&self, def: VariantDef) -> stable_mir::Symbol {
+        let mut tables = self.0.borrow_mut();
+        def.internal(&mut *tables).name.to_string()
+    }
+
+    fn variant_fields(&self, def: VariantDef) -> Vec<FieldDef> {
+        let mut tables = self.0.borrow_mut();
+        let variant = def.internal(&mut *tables);
+        variant.fields.iter().map(|field| field.stable(&mut *tables)).collect()
+    }
+
     fn generics_of(&self, def_id: stable_mir::DefId) -> stable_mir::ty::Generics {
         let mut tables = self.0.borrow_mut();
         let def_id = tables[def_id];
@@ -243,6 +310,231 @@ fn resolve_instance(
             Ok(None) | Err(_) => None,
         }
     }
+
+    fn ty_layout(
+        &self,
+        ty: stable_mir::ty::Ty,
+    ) -> Result<stable_mir::abi::Layout, stable_mir::Error> {
+        let mut tables = self.0.borrow_mut();
+        let ty = ty.internal(&mut *tables);
+        let layout = tables.tcx.layout_of(ParamEnv::reveal_all().and(ty)).map_err(|err| {
+            stable_mir::Error::new(format!("Failed to get layout of `{ty}`: {err}"))
+        })?;
+        Ok(layout.layout.stable(&mut *tables))
+    }
+
+    fn layout_shape(&self, layout: stable_mir::abi::Layout) -> stable_mir::abi::LayoutShape {
+        let mut tables = self.0.borrow_mut();
+        let layout = tables.layouts[layout];
+        layout.0.0.stable(&mut *tables)
+    }
+
+    fn resolve_for_vtable(
+        &self,
+        def: stable_mir::ty::FnDef,
+        args: &stable_mir::ty::GenericArgs,
+    ) -> Option<stable_mir::mir::mono::Instance> {
+        let mut tables = self.0.borrow_mut();
+        let def_id = def.0.internal(&mut *tables);
+        let args_ref = args.internal(&mut *tables);
+        Instance::resolve_for_vtable(tables.tcx, ParamEnv::reveal_all(), def_id, args_ref)
+            .map(|instance| instance.stable(&mut *tables))
+    }
+
+    fn resolve_drop_in_place(&self, ty: stable_mir::ty::Ty) -> stable_mir::mir::mono::Instance {
+        let mut tables = self.0.borrow_mut();
+        let internal_ty = ty.internal(&mut *tables);
+        Instance::resolve_drop_in_place(tables.tcx, internal_ty).stable(&mut *tables)
+    }
+
+    fn ty_implements_trait(
+        &self,
+        ty: stable_mir::ty::Ty,
+        trait_def: stable_mir::ty::TraitDef,
+        args: &stable_mir::ty::GenericArgs,
+        item: stable_mir::DefId,
+    ) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let ty = ty.internal(&mut *tables);
+        let trait_def_id = trait_def.0.internal(&mut *tables);
+        let args = args.internal(&mut *tables);
+        let param_env = tables.tcx.param_env_reveal_all_normalized(tables[item]);
+        let params = std::iter::once(ty.into()).chain(args.iter());
+        tables
+            .tcx
+            .infer_ctxt()
+            .build()
+            .type_implements_trait(trait_def_id, params, param_env)
+            .must_apply_modulo_regions()
+    }
+
+    fn normalize_ty(
+        &self,
+        ty: stable_mir::ty::Ty,
+        item: stable_mir::DefId,
+    ) -> Result<stable_mir::ty::Ty, stable_mir::Error> {
+        let mut tables = self.0.borrow_mut();
+        let ty = ty.internal(&mut *tables);
+        let param_env = tables.tcx.param_env_reveal_all_normalized(tables[item]);
+        let normalized = tables
+            .tcx
+            .try_normalize_erasing_regions(param_env, ty)
+            .map_err(|_| stable_mir::Error::new(format!("Failed to normalize `{ty}`")))?;
+        Ok(normalized.stable(&mut *tables))
+    }
+
+    fn instance_mangled_name(&self, def: InstanceDef) -> String {
+        let tables = self.0.borrow();
+        let instance = tables.instances[def];
+        tables.tcx.symbol_name(instance).name.to_string()
+    }
+
+    fn collect_mono_items(&self) -> Result<MonoItemGraph, stable_mir::Error> {
+        // The collection is expensive and emits its errors again every time it runs, so it is
+        // only done once per session. The tables aren't borrowed while the collector runs, since
+        // it evaluates constants and may take a while.
+        let tcx = self.0.borrow().tcx;
+        if self.0.borrow().mono_items.is_none() {
+            let mode = if tcx.sess.link_dead_code() {
+                MonoItemCollectionMode::Eager
+            } else {
+                MonoItemCollectionMode::Lazy
+            };
+            let err_count = tcx.sess.diagnostic().err_count();
+            let (items, usage_map) = collect_crate_mono_items(tcx, mode);
+            let graph = if tcx.sess.diagnostic().err_count() > err_count {
+                Err("Errors were emitted while collecting mono items".to_string())
+            } else {
+                // The collector returns a hash set, so order the items by their symbol names,
+                // which are distinct, to build the same graph in every run.
+                let items: UnordMap<&str, MonoItem<'_>> = UnordSet::from(items)
+                    .into_items()
+                    .map(|item| (item.symbol_name(tcx).name, item))
+                    .collect();
+                let items: FxIndexSet<_> =
+                    items.to_sorted_stable_ord().into_iter().map(|(_, item)| *item).collect();
+                let mut edges = Vec::new();
+                for (user, item) in items.iter().enumerate() {
+                    let mut used: Vec<_> = usage_map
+                        .get_used_items(*item)
+                        .iter()
+                        .filter_map(|used| items.get_index_of(used))
+                        .collect();
+                    used.sort_unstable();
+                    edges.extend(used.into_iter().map(|used| MonoItemEdge { user, used }));
+                }
+                let mut tables = self.0.borrow_mut();
+                let items = items.iter().map(|item| item.stable(&mut *tables)).collect();
+                Ok(MonoItemGraph { items, edges })
+            };
+            self.0.borrow_mut().mono_items = Some(graph);
+        }
+        self.0.borrow().mono_items.clone().unwrap().map_err(stable_mir::Error::new)
+    }
+
+    fn instance_abi(&self, def: InstanceDef) -> Result<stable_mir::abi::FnAbi, stable_mir::Error> {
+        let mut tables = self.0.borrow_mut();
+        let instance = tables.instances[def];
+        let fn_abi = tables
+            .tcx
+            .fn_abi_of_instance(ParamEnv::reveal_all().and((instance, ty::List::empty())))
+            .map_err(|err| {
+                stable_mir::Error::new(format!("Failed to get ABI of `{instance}`: {err:?}"))
+            })?;
+        Ok(fn_abi.stable(&mut *tables))
+    }
+
+    fn eval_const(&self, cnst: &Const) -> Result<stable_mir::ty::Allocation, stable_mir::Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let cnst = cnst.internal(&mut *tables);
+        let value = cnst
+            .eval(tcx, ParamEnv::reveal_all(), None)
+            .map_err(|_| stable_mir::Error::new(format!("Failed to evaluate constant `{cnst}`")))?;
+        Ok(alloc::new_allocation(cnst.ty(), value, &mut *tables))
+    }
+
+    fn eval_static_initializer(
+        &self,
+        def: StaticDef,
+    ) -> Result<stable_mir::ty::Allocation, stable_mir::Error> {
+        let mut tables = self.0.borrow_mut();
+        let def_id = def.0.internal(&mut *tables);
+        if tables.tcx.is_foreign_item(def_id) {
+            return Err(stable_mir::Error::new(format!(
+                "Foreign static `{def_id:?}` has no initializer"
+            )));
+        }
+        let alloc = tables.tcx.eval_static_initializer(def_id).map_err(|_| {
+            stable_mir::Error::new(format!("Failed to evaluate static `{def_id:?}`"))
+        })?;
+        Ok(alloc.inner().stable(&mut *tables))
+    }
+
+    fn global_alloc(&self, id: stable_mir::AllocId) -> GlobalAlloc {
+        let mut tables = self.0.borrow_mut();
+        let alloc_id = id.internal(&mut *tables);
+        tables.tcx.global_alloc(alloc_id).stable(&mut *tables)
+    }
+
+    fn target_info(&self) -> MachineInfo {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        MachineInfo {
+            endian: tcx.data_layout.endian.stable(&mut *tables),
+            pointer_size: tcx.data_layout.pointer_size.bytes_usize(),
+        }
+    }
+
+    fn attrs_by_path(
+        &self,
+        def_id: stable_mir::DefId,
+        attr: &[stable_mir::Symbol],
+    ) -> Vec<stable_mir::Attribute> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let did = tables[def_id];
+        let attr_name: Vec<_> = attr.iter().map(|seg| Symbol::intern(seg)).collect();
+        // Doc comments don't have a path, but they are equivalent to `#[doc = "..."]`.
+        let is_doc = attr_name == [sym::doc];
+        let attrs = if let Some(did) = did.as_local() {
+            tcx.hir().attrs(tcx.hir().local_def_id_to_hir_id(did))
+        } else {
+            tcx.item_attrs(did)
+        };
+        attrs
+            .iter()
+            .filter(|a| a.path_matches(&attr_name) || (is_doc && a.is_doc_comment()))
+            .map(|a| {
+                let value = rustc_ast_pretty::pprust::attribute_to_string(a);
+                stable_mir::Attribute::new(value, a.span.stable(&mut *tables))
+            })
+            .collect()
+    }
+
+    fn visibility(&self, def_id: stable_mir::DefId) -> stable_mir::Visibility {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let def_id = tables[def_id];
+        let visibility = match tcx.def_kind(def_id) {
+            // Anonymous and inline constants, e.g. array lengths and enum discriminants, have no
+            // visibility of their own. Like closures, they are visible in their parent module.
+            // Parent modules are only known locally; otherwise use the parent item's visibility.
+            DefKind::AnonConst | DefKind::InlineConst => match def_id.as_local() {
+                Some(local) => {
+                    ty::Visibility::Restricted(tcx.parent_module_from_def_id(local).to_def_id())
+                }
+                None => tcx.visibility(tcx.parent(def_id)),
+            },
+            _ => tcx.visibility(def_id),
+        };
+        visibility.stable(&mut *tables)
+    }
+
+    fn source_text(&self, span: Span) -> Option<String> {
+        let tables = self.0.borrow();
+        tables.tcx.sess.source_map().span_to_snippet(tables[span]).ok()
+    }
 }
 
 pub(crate) struct TablesWrapper<'tcx>(pub(crate) RefCell<Tables<'tcx>>);
@@ -255,6 +547,9 @@ pub struct Tables<'tcx> {
     pub(crate) types: IndexMap<Ty<'tcx>, stable_mir::ty::Ty>,
     pub(crate) instances: IndexMap<ty::Instance<'tcx>, InstanceDef>,
     pub(crate) constants: IndexMap<mir::Const<'tcx>, ConstId>,
+    pub(crate) layouts: IndexMap<rustc_target::abi::Layout<'tcx>, stable_mir::abi::Layout>,
+    /// The result of the mono item collection, once it ran.
+    pub(crate) mono_items: Option<Result<MonoItemGraph, String>>,
 }
 
 impl<'tcx> Tables<'tcx> {
@@ -304,9 +599,19 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
                 .map(|decl| stable_mir::mir::LocalDecl {
                     ty: decl.ty.stable(tables),
                     span: decl.source_info.span.stable(tables),
+                    scope: decl.source_info.scope.as_usize(),
+                    mutability: decl.mutability.stable(tables),
                 })
                 .collect(),
             self.arg_count,
+            self.var_debug_info.iter().map(|info| info.stable(tables)).collect(),
+            self.source_scopes
+                .iter()
+                .map(|scope| stable_mir::mir::SourceScopeData {
+                    span: scope.span.stable(tables),
+                    parent_scope: scope.parent_scope.map(|parent| parent.as_usize()),
+                })
+                .collect(),
         )
     }
 }
@@ -432,6 +737,18 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
     }
 }
 
+impl<'tcx> Stable<'tcx> for ty::Visibility<DefId> {
+    type T = stable_mir::Visibility;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            ty::Visibility::Public => stable_mir::Visibility::Public,
+            ty::Visibility::Restricted(module) => {
+                stable_mir::Visibility::Restricted(tables.create_def_id(*module))
+            }
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for mir::Mutability {
     type T = stable_mir::mir::Mutability;
     fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
@@ -669,10 +986,55 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
 
 impl<'tcx> Stable<'tcx> for mir::Place<'tcx> {
     type T = stable_mir::mir::Place;
-    fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
         stable_mir::mir::Place {
             local: self.local.as_usize(),
-            projection: format!("{:?}", self.projection),
+            projection: self.projection.iter().map(|elem| elem.stable(tables)).collect(),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::PlaceElem<'tcx> {
+    type T = stable_mir::mir::ProjectionElem;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        use mir::ProjectionElem::*;
+        use stable_mir::mir::ProjectionElem;
+        match self {
+            Deref => ProjectionElem::Deref,
+            Field(idx, ty) => ProjectionElem::Field(idx.stable(tables), ty.stable(tables)),
+            Index(local) => ProjectionElem::Index(local.as_usize()),
+            ConstantIndex { offset, min_length, from_end } => ProjectionElem::ConstantIndex {
+                offset: *offset,
+                min_length: *min_length,
+                from_end: *from_end,
+            },
+            Subslice { from, to, from_end } => {
+                ProjectionElem::Subslice { from: *from, to: *to, from_end: *from_end }
+            }
+            Downcast(_, idx) => ProjectionElem::Downcast(idx.stable(tables)),
+            OpaqueCast(ty) => ProjectionElem::OpaqueCast(ty.stable(tables)),
+            Subtype(ty) => ProjectionElem::Subtype(ty.stable(tables)),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for mir::VarDebugInfo<'tcx> {
+    type T = stable_mir::mir::VarDebugInfo;
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        stable_mir::mir::VarDebugInfo {
+            name: self.name.to_string(),
+            source_info: stable_mir::mir::SourceInfo {
+                span: self.source_info.span.stable(tables),
+                scope: self.source_info.scope.as_usize(),
+            },
+            value: match &self.value {
+                mir::VarDebugInfoContents::Place(place) => {
+                    stable_mir::mir::VarDebugInfoContents::Place(place.stable(tables))
+                }
+                mir::VarDebugInfoContents::Const(constant) => {
+                    stable_mir::mir::VarDebugInfoContents::Const(constant.stable(tables))
+                }
+            },
         }
     }
 }
@@ -1331,6 +1693,26 @@ fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
     }
 }
 
+impl<'tcx> Stable<'tcx> for ty::AdtKind {
+    type T = AdtKind;
+
+    fn stable(&self, _tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            ty::AdtKind::Struct => AdtKind::Struct,
+            ty::AdtKind::Union => AdtKind::Union,
+            ty::AdtKind::Enum => AdtKind::Enum,
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for ty::FieldDef {
+    type T = FieldDef;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        FieldDef { def: tables.create_def_id(self.did), name: self.name.to_string() }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for ty::ParamTy {
     type T = stable_mir::ty::ParamTy;
     fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
@@ -1359,6 +1741,28 @@ fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
     }
 }
 
+impl<'tcx> Stable<'tcx> for mir::interpret::GlobalAlloc<'tcx> {
+    type T = GlobalAlloc;
+
+    fn stable(&self, tables: &mut Tables<'tcx>) -> Self::T {
+        match self {
+            mir::interpret::GlobalAlloc::Function(instance) => {
+                GlobalAlloc::Function(instance.stable(tables))
+            }
+            mir::interpret::GlobalAlloc::VTable(ty, trait_ref) => GlobalAlloc::VTable(
+                ty.stable(tables),
+                trait_ref.map(|trait_ref| trait_ref.stable(tables)),
+            ),
+            mir::interpret::GlobalAlloc::Static(def_id) => {
+                GlobalAlloc::Static(tables.static_def(*def_id))
+            }
+            mir::interpret::GlobalAlloc::Memory(alloc) => {
+                GlobalAlloc::Memory(alloc.inner().stable(tables))
+            }
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for ty::trait_def::TraitSpecializationKind {
     type T = stable_mir::ty::TraitSpecializationKind;
     fn stable(&self, _: &mut Tables<'tcx>) -> Self::T {
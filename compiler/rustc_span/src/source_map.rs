@@ -107,6 +107,13 @@ pub trait FileLoader {
     /// Read the contents of a potentially non-UTF-8 file into memory.
     /// We don't normalize binary files, so we can start in an Lrc.
     fn read_binary_file(&self, path: &Path) -> io::Result<Lrc<[u8]>>;
+
+    /// Query whether a file only exists in the loader, and not on disk.
+    /// Such files are left out of the dep-info, since build systems can't
+    /// track them.
+    fn is_virtual_file(&self, _path: &Path) -> bool {
+        false
+    }
 }
 
 /// A FileLoader that uses std::fs to load real files.
@@ -163,6 +170,75 @@ fn read_binary_file(&self, path: &Path) -> io::Result<Lrc<[u8]>> {
     }
 }
 
+/// A FileLoader that serves files from memory, for tools that compile sources
+/// that don't exist on disk, such as generated code.
+///
+/// Paths are compared after resolving their `.` and `..` components, so that a
+/// file is found no matter how `mod` items and `#[path]` attributes lead to it.
+/// Files that weren't added to the loader are read from disk, which is where
+/// the sources of the standard library come from when diagnostics need them.
+#[derive(Default)]
+pub struct InMemoryFileLoader {
+    files: FxHashMap<PathBuf, Vec<u8>>,
+}
+
+impl InMemoryFileLoader {
+    pub fn new() -> InMemoryFileLoader {
+        InMemoryFileLoader::default()
+    }
+
+    /// Adds a file, replacing any file that was previously added at the same path.
+    pub fn add_file(&mut self, path: impl AsRef<Path>, contents: impl Into<Vec<u8>>) {
+        self.files.insert(normalize_path(path.as_ref()), contents.into());
+    }
+
+    fn get(&self, path: &Path) -> Option<&Vec<u8>> {
+        self.files.get(&normalize_path(path))
+    }
+}
+
+impl FileLoader for InMemoryFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.get(path).is_some() || RealFileLoader.file_exists(path)
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        match self.get(path) {
+            Some(contents) => String::from_utf8(contents.clone())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            None => RealFileLoader.read_file(path),
+        }
+    }
+
+    fn read_binary_file(&self, path: &Path) -> io::Result<Lrc<[u8]>> {
+        match self.get(path) {
+            Some(contents) => Ok(Lrc::from(&contents[..])),
+            None => RealFileLoader.read_binary_file(path),
+        }
+    }
+
+    fn is_virtual_file(&self, path: &Path) -> bool {
+        self.get(path).is_some()
+    }
+}
+
+/// Resolves the `.` and `..` components of a path without looking at the file
+/// system, like `mod` resolution does.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            path::Component::CurDir => {}
+            // `file_name` is `None` for a root, or for a path that ends with `..`.
+            path::Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
 /// This is a [SourceFile] identifier that is used to correlate source files between
 /// subsequent compilation sessions (which is something we need to do during
 /// incremental compilation).
@@ -253,6 +329,11 @@ pub fn file_exists(&self, path: &Path) -> bool {
         self.file_loader.file_exists(path)
     }
 
+    /// Whether `path` is only known to the file loader, and doesn't exist on disk.
+    pub fn is_virtual_file(&self, path: &Path) -> bool {
+        self.file_loader.is_virtual_file(path)
+    }
+
     pub fn load_file(&self, path: &Path) -> io::Result<Lrc<SourceFile>> {
         let src = self.file_loader.read_file(path)?;
         let filename = path.to_owned().into();
@@ -276,6 +357,12 @@ pub fn load_binary_file(&self, path: &Path) -> io::Result<Lrc<[u8]>> {
         Ok(bytes)
     }
 
+    /// Reads a file as a binary blob through the file loader, without adding it to the
+    /// `SourceMap`. Callers are responsible for listing it in the dep-info.
+    pub fn read_binary_file(&self, path: &Path) -> io::Result<Lrc<[u8]>> {
+        self.file_loader.read_binary_file(path)
+    }
+
     // By returning a `MonotonicVec`, we ensure that consumers cannot invalidate
     // any existing indices pointing into `files`.
     pub fn files(&self) -> MappedReadGuard<'_, monotonic::MonotonicVec<Lrc<SourceFile>>> {
@@ -610,3 +610,39 @@ fn read_binary_file_handles_lying_stat() {
     let bin = RealFileLoader.read_binary_file(kernel_max).unwrap();
     assert_eq!(&real[..], &bin[..]);
 }
+
+#[test]
+fn in_memory_file_loader() {
+    let mut loader = InMemoryFileLoader::new();
+    loader.add_file("src/lib.rs", "mod foo;");
+    loader.add_file("./src/data.bin", [0xff, 0x00]);
+
+    // Paths are found no matter how they are spelled.
+    assert!(loader.file_exists(Path::new("src/lib.rs")));
+    assert!(loader.file_exists(Path::new("src/foo/../lib.rs")));
+    assert!(loader.is_virtual_file(Path::new("./src/lib.rs")));
+    assert!(!loader.file_exists(Path::new("src/foo.rs")));
+    assert!(!loader.is_virtual_file(Path::new("src/foo.rs")));
+
+    assert_eq!(loader.read_file(Path::new("src/lib.rs")).unwrap(), "mod foo;");
+    assert_eq!(&loader.read_binary_file(Path::new("src/data.bin")).unwrap()[..], [0xff, 0x00]);
+    assert_eq!(
+        loader.read_file(Path::new("src/data.bin")).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert_eq!(
+        loader.read_file(Path::new("src/foo.rs")).unwrap_err().kind(),
+        io::ErrorKind::NotFound
+    );
+
+    // The source map reads and tracks files through its loader.
+    let sm = SourceMap::with_file_loader_and_hash_kind(
+        Box::new(loader),
+        FilePathMapping::empty(),
+        SourceFileHashAlgorithm::Md5,
+    );
+    let file = sm.load_file(Path::new("src/lib.rs")).unwrap();
+    assert_eq!(file.src.as_deref().map(|src| &src[..]), Some("mod foo;"));
+    assert!(sm.is_virtual_file(Path::new("src/lib.rs")));
+    assert!(!sm.is_virtual_file(Path::new("src/foo.rs")));
+}
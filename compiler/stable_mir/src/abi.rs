@@ -0,0 +1,257 @@
+//! The layout of types in memory, and how values are passed to functions.
+
+use crate::mir::VariantIdx;
+use crate::ty::{Align, IndexedVal, Size, Ty};
+use crate::{with, Opaque};
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// The layout of a type in memory.
+///
+/// Layouts are interned by the compiler: use [`Layout::shape`] to get their description.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Layout(usize);
+
+impl Layout {
+    pub fn shape(self) -> LayoutShape {
+        with(|cx| cx.layout_shape(self))
+    }
+}
+
+impl IndexedVal for Layout {
+    fn to_val(index: usize) -> Self {
+        Layout(index)
+    }
+    fn to_index(&self) -> usize {
+        self.0
+    }
+}
+
+/// The description of a layout.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct LayoutShape {
+    /// Where the fields are located within the layout.
+    pub fields: FieldsShape,
+
+    /// How the variants of an enum are encoded. Structs, tuples, unions and all the other types
+    /// that aren't enums have a single variant.
+    ///
+    /// The fields of each variant are located in the layout of that variant, while `fields` holds
+    /// the fields shared by all the variants, such as the tag.
+    pub variants: VariantsShape,
+
+    /// How values of this layout are passed between functions, and their valid values.
+    pub abi: ValueAbi,
+
+    /// The leaf scalar with the largest number of invalid values, if any.
+    pub largest_niche: Option<Niche>,
+
+    /// The alignment required by the ABI, in bytes.
+    pub abi_align: Align,
+
+    /// The size of the layout in bytes. For unsized types, this is the size of their sized
+    /// prefix.
+    pub size: Size,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum FieldsShape {
+    /// Scalar primitives and `!`, which never have fields.
+    Primitive,
+
+    /// All the fields start at offset 0. The `usize` is the number of fields.
+    Union(usize),
+
+    /// Array-like placement, with all fields of identical types.
+    Array { stride: Size, count: u64 },
+
+    /// Struct-like placement.
+    Arbitrary {
+        /// The offset of each field in bytes, in the order the fields are declared in. The
+        /// offsets aren't necessarily increasing, as fields can be reordered.
+        offsets: Vec<Size>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum VariantsShape {
+    /// Types with a single variant, including enums with a single inhabited variant.
+    Single { index: VariantIdx },
+
+    /// Enums with more than one inhabited variant, whose discriminant is encoded as a tag.
+    Multiple {
+        tag: Scalar,
+        tag_encoding: TagEncoding,
+        /// The index of the tag among the fields of the enum layout.
+        tag_field: usize,
+        /// The layout of each variant.
+        variants: Vec<LayoutShape>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum TagEncoding {
+    /// The tag directly stores the discriminant, possibly in a smaller integer.
+    Direct,
+
+    /// The discriminant is encoded in values that are invalid for a field of `untagged_variant`.
+    /// For a variant with the discriminant `d`, the tag is set to
+    /// `(d - niche_variants.start).wrapping_add(niche_start)`.
+    Niche {
+        untagged_variant: VariantIdx,
+        niche_variants: RangeInclusive<VariantIdx>,
+        niche_start: u128,
+    },
+}
+
+/// How values are passed between functions, in terms of the categories of C types that ABIs have
+/// rules for.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum ValueAbi {
+    Uninhabited,
+    Scalar(Scalar),
+    ScalarPair(Scalar, Scalar),
+    Vector {
+        element: Scalar,
+        count: u64,
+    },
+    Aggregate {
+        /// Whether the size is exact, rather than the lower bound of an unsized type.
+        sized: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum Scalar {
+    Initialized {
+        value: Primitive,
+        valid_range: WrappingRange,
+    },
+    /// A scalar in a union, which has no invalid values and can be uninitialized.
+    Union {
+        value: Primitive,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum Primitive {
+    Int { length: IntegerLength, signed: bool },
+    Float { length: FloatLength },
+    Pointer(AddressSpace),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum IntegerLength {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+}
+
+/// The integer type used to store the discriminant of an enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum IntegerType {
+    /// `isize` or `usize`.
+    Pointer {
+        is_signed: bool,
+    },
+    Fixed {
+        length: IntegerLength,
+        is_signed: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum FloatLength {
+    F32,
+    F64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct AddressSpace(pub u32);
+
+/// An inclusive range of valid values, which wraps around the maximum value of the scalar when
+/// `start > end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct WrappingRange {
+    pub start: u128,
+    pub end: u128,
+}
+
+/// A scalar field whose invalid values can encode the discriminant of an enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Niche {
+    /// The offset of the scalar in bytes.
+    pub offset: Size,
+    pub value: Primitive,
+    pub valid_range: WrappingRange,
+}
+
+/// The options set with the `#[repr]` attribute of an ADT.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct ReprOptions {
+    /// The discriminant type set with `#[repr(u8)]` and similar attributes.
+    pub int: Option<IntegerType>,
+    /// The alignment set with `#[repr(align(N))]`, in bytes.
+    pub align: Option<Align>,
+    /// The alignment set with `#[repr(packed(N))]`, in bytes.
+    pub pack: Option<Align>,
+    pub is_c: bool,
+    pub is_simd: bool,
+    pub is_transparent: bool,
+    /// Whether the fields must keep their declaration order, e.g. due to `#[repr(C)]`.
+    pub is_linear: bool,
+}
+
+/// How a function is called: how its arguments are passed, and how it returns.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FnAbi {
+    pub args: Vec<ArgAbi>,
+    pub ret: ArgAbi,
+    /// The number of arguments that aren't variadic.
+    pub fixed_count: u32,
+    pub c_variadic: bool,
+}
+
+/// How an argument or a return value is passed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ArgAbi {
+    pub ty: Ty,
+    pub layout: Layout,
+    pub mode: PassMode,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum PassMode {
+    /// Ignored, as it is a ZST, or an uninhabited return value.
+    Ignore,
+    /// Passed directly, as a single immediate value.
+    Direct(Opaque),
+    /// Passed as a pair of immediate values.
+    Pair(Opaque, Opaque),
+    /// Passed after being cast to another type.
+    Cast { pad_i32: bool, cast: Opaque },
+    /// Passed by reference.
+    Indirect { attrs: Opaque, meta_attrs: Opaque, on_stack: bool },
+}
+
+/// Information about the machine that the crate is compiled for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MachineInfo {
+    pub endian: Endian,
+    /// The size of pointers, in bytes.
+    pub pointer_size: Size,
+}
+
+impl MachineInfo {
+    pub fn target() -> MachineInfo {
+        with(|cx| cx.target_info())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum Endian {
+    Little,
+    Big,
+}
@@ -18,7 +18,7 @@ pub enum CompilerError<T> {
     /// Compilation was interrupted.
     Interrupted(T),
     /// Compilation skipped. This happens when users invoke rustc to retrieve information such as
-    /// --version.
+    /// --version, or when a driver passes the compilation of a dependency through to rustc.
     Skipped,
 }
 
@@ -27,7 +27,9 @@ pub enum CompilerError<T> {
 pub struct Error(String);
 
 impl Error {
-    pub(crate) fn new(msg: String) -> Self {
+    /// Create an error with the given message. This is meant to be used by the compiler when it
+    /// can't fulfill a request.
+    pub fn new(msg: String) -> Self {
         Self(msg)
     }
 }
@@ -17,20 +17,24 @@
 //! The goal is to eventually be published on
 //! [crates.io](https://crates.io).
 
-use crate::mir::mono::InstanceDef;
+use crate::abi::{FnAbi, Layout, LayoutShape, MachineInfo, ReprOptions};
+use crate::mir::alloc::GlobalAlloc;
+use crate::mir::mono::{InstanceDef, MonoItemGraph, StaticDef};
 use crate::mir::Body;
+use serde::Serialize;
 use std::cell::Cell;
 use std::fmt;
 use std::fmt::Debug;
 
 use self::ty::{
-    GenericPredicates, Generics, ImplDef, ImplTrait, IndexedVal, LineInfo, Span, TraitDecl,
-    TraitDef, Ty, TyKind,
+    AdtDef, AdtKind, Allocation, Const, FieldDef, GenericPredicates, Generics, ImplDef, ImplTrait,
+    IndexedVal, LineInfo, Span, TraitDecl, TraitDef, Ty, TyKind, VariantDef,
 };
 
 #[macro_use]
 extern crate scoped_tls;
 
+pub mod abi;
 pub mod error;
 pub mod mir;
 pub mod ty;
@@ -47,7 +51,7 @@
 pub type CrateNum = usize;
 
 /// A unique identification number for each item accessible for the current compilation unit.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct DefId(usize);
 
 impl Debug for DefId {
@@ -70,7 +74,7 @@ fn to_index(&self) -> usize {
 }
 
 /// A unique identification number for each provenance
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct AllocId(usize);
 
 impl IndexedVal for AllocId {
@@ -92,7 +96,7 @@ fn to_index(&self) -> usize {
 pub type ImplTraitDecls = Vec<ImplDef>;
 
 /// Holds information about a crate.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
 pub struct Crate {
     pub id: CrateNum,
     pub name: Symbol,
@@ -105,7 +109,7 @@ pub struct Crate {
 /// Holds information about an item in the crate.
 /// For now, it only stores the item DefId. Use functions inside `rustc_internal` module to
 /// use this item.
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize)]
 pub struct CrateItem(pub DefId);
 
 impl CrateItem {
@@ -128,6 +132,54 @@ pub fn kind(&self) -> DefKind {
     pub fn requires_monomorphization(&self) -> bool {
         with(|cx| cx.requires_monomorphization(self.0))
     }
+
+    /// Retrieve the attributes of this item whose path matches `attr`, e.g. `["inline"]` or
+    /// `["my_tool", "verify"]`. Doc comments are returned as attributes of path `["doc"]`.
+    pub fn attrs_by_path(&self, attr: &[Symbol]) -> Vec<Attribute> {
+        with(|cx| cx.attrs_by_path(self.0, attr))
+    }
+
+    /// Retrieve the visibility of this item.
+    ///
+    /// Items without a visibility of their own, like closures and anonymous constants, are
+    /// visible in the module that contains them.
+    pub fn visibility(&self) -> Visibility {
+        with(|cx| cx.visibility(self.0))
+    }
+}
+
+/// An attribute of an item, as it is written in the source code.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
+pub struct Attribute {
+    value: String,
+    span: Span,
+}
+
+impl Attribute {
+    /// Create an attribute with the given representation. This is meant to be used by the
+    /// compiler.
+    pub fn new(value: String, span: Span) -> Self {
+        Self { value, span }
+    }
+
+    /// The textual representation of this attribute, e.g. `#[inline]` or `/// Documentation`.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// The span of this attribute.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Where an item can be named from.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+pub enum Visibility {
+    /// The item is visible everywhere, e.g. `pub`.
+    Public,
+    /// The item is only visible within the given module, e.g. `pub(crate)` or private items.
+    Restricted(DefId),
 }
 
 /// Return the function where execution starts if the current
@@ -214,6 +266,36 @@ pub trait Context {
     /// Obtain the representation of a type.
     fn ty_kind(&self, ty: Ty) -> TyKind;
 
+    /// Get the type of an item, without substituting its generic parameters.
+    fn def_ty(&self, item: DefId) -> Ty;
+
+    /// Get the type of an item, with its generic parameters replaced by `args`.
+    fn def_ty_with_args(&self, item: DefId, args: &GenericArgs) -> Ty;
+
+    /// Returns whether the ADT is a struct, an enum or a union.
+    fn adt_kind(&self, def: AdtDef) -> AdtKind;
+
+    /// Returns whether the ADT is `Box`.
+    fn adt_is_box(&self, def: AdtDef) -> bool;
+
+    /// Returns whether the ADT is `PhantomData`.
+    fn adt_is_phantom_data(&self, def: AdtDef) -> bool;
+
+    /// Returns whether the ADT is `UnsafeCell`.
+    fn adt_is_unsafe_cell(&self, def: AdtDef) -> bool;
+
+    /// Get the `#[repr]` options of an ADT.
+    fn adt_repr(&self, def: AdtDef) -> ReprOptions;
+
+    /// The number of variants of an ADT.
+    fn adt_variants_len(&self, def: AdtDef) -> usize;
+
+    /// The name of a variant.
+    fn variant_name(&self, def: VariantDef) -> Symbol;
+
+    /// The fields of a variant.
+    fn variant_fields(&self, def: VariantDef) -> Vec<FieldDef>;
+
     /// Get the body of an Instance.
     /// FIXME: Monomorphize the body.
     fn instance_body(&self, instance: InstanceDef) -> Body;
@@ -233,6 +315,61 @@ pub trait Context {
 
     /// Resolve an instance from the given function definition and generic arguments.
     fn resolve_instance(&self, def: FnDef, args: &GenericArgs) -> Option<Instance>;
+
+    /// Get the layout of a type.
+    fn ty_layout(&self, ty: Ty) -> Result<Layout, Error>;
+
+    /// Get the description of a layout.
+    fn layout_shape(&self, layout: Layout) -> LayoutShape;
+
+    /// Get how an instance is called.
+    fn instance_abi(&self, def: InstanceDef) -> Result<FnAbi, Error>;
+
+    /// Resolve the instance called through a vtable for the given method and generic arguments.
+    fn resolve_for_vtable(&self, def: FnDef, args: &GenericArgs) -> Option<Instance>;
+
+    /// Resolve the drop glue of a type.
+    fn resolve_drop_in_place(&self, ty: Ty) -> Instance;
+
+    /// Whether a type implements a trait with the given generic arguments, excluding `Self`,
+    /// assuming the where clauses of `item`.
+    fn ty_implements_trait(
+        &self,
+        ty: Ty,
+        trait_def: TraitDef,
+        args: &GenericArgs,
+        item: DefId,
+    ) -> bool;
+
+    /// Normalize a type and erase its regions, assuming the where clauses of `item`.
+    fn normalize_ty(&self, ty: Ty, item: DefId) -> Result<Ty, Error>;
+
+    /// Get the symbol name of an instance.
+    fn instance_mangled_name(&self, def: InstanceDef) -> String;
+
+    /// Collect the mono items of the local crate, and the edges between them.
+    fn collect_mono_items(&self) -> Result<MonoItemGraph, Error>;
+
+    /// Evaluate a constant.
+    fn eval_const(&self, cnst: &Const) -> Result<Allocation, Error>;
+
+    /// Evaluate the initializer of a static.
+    fn eval_static_initializer(&self, def: StaticDef) -> Result<Allocation, Error>;
+
+    /// Get the global allocation that an allocation id refers to.
+    fn global_alloc(&self, id: AllocId) -> GlobalAlloc;
+
+    /// Get information about the target machine.
+    fn target_info(&self) -> MachineInfo;
+
+    /// Retrieve the attributes of an item whose path matches `attr`.
+    fn attrs_by_path(&self, def_id: DefId, attr: &[Symbol]) -> Vec<Attribute>;
+
+    /// Get the visibility of an item.
+    fn visibility(&self, def_id: DefId) -> Visibility;
+
+    /// Return the source code that a `Span` refers to, if it is available.
+    fn source_text(&self, span: Span) -> Option<String>;
 }
 
 // A thread local variable that stores a pointer to the tables mapping between TyCtxt
@@ -259,7 +396,7 @@ pub fn with<R>(f: impl FnOnce(&dyn Context) -> R) -> R {
 }
 
 /// A type that provides internal information but that can still be used for debug purpose.
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq, Serialize)]
 pub struct Opaque(String);
 
 impl std::fmt::Display for Opaque {
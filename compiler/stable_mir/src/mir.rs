@@ -1,4 +1,7 @@
+pub mod alloc;
 mod body;
 pub mod mono;
+mod pretty;
+pub mod visit;
 
 pub use body::*;
@@ -0,0 +1,25 @@
+//! The global allocations that constants and statics can point to.
+
+use crate::mir::mono::{Instance, StaticDef};
+use crate::ty::{Allocation, Binder, ExistentialTraitRef, Ty};
+use crate::{with, AllocId};
+use serde::Serialize;
+
+/// The allocation that a pointer points to, as recorded in the provenance of an [`Allocation`].
+#[derive(Clone, Debug, Serialize)]
+pub enum GlobalAlloc {
+    /// The pointer is a function pointer.
+    Function(Instance),
+    /// The pointer points to the vtable of a type, for the given principal trait, if any.
+    VTable(Ty, Option<Binder<ExistentialTraitRef>>),
+    /// The pointer points to a static. Use [`StaticDef::eval_initializer`] to get its memory.
+    Static(StaticDef),
+    /// The pointer points to memory, e.g. a promoted constant or a string literal.
+    Memory(Allocation),
+}
+
+impl From<AllocId> for GlobalAlloc {
+    fn from(value: AllocId) -> Self {
+        with(|cx| cx.global_alloc(value))
+    }
+}
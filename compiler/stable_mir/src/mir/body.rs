@@ -1,9 +1,12 @@
+use crate::mir::pretty;
 use crate::ty::{AdtDef, ClosureDef, Const, CoroutineDef, GenericArgs, Movability, Region};
 use crate::Opaque;
-use crate::{ty::Ty, Span};
+use crate::{ty::Ty, Span, Symbol};
+use serde::Serialize;
+use std::io;
 
 /// The SMIR representation of a single function.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Body {
     pub blocks: Vec<BasicBlock>,
 
@@ -12,10 +15,16 @@ pub struct Body {
     // The first local is the return value pointer, followed by `arg_count`
     // locals for the function arguments, followed by any user-declared
     // variables and temporaries.
-    locals: LocalDecls,
+    pub(super) locals: LocalDecls,
 
     // The number of arguments this function takes.
-    arg_count: usize,
+    pub(super) arg_count: usize,
+
+    /// Debug information of the user variables, including the arguments and the captures.
+    pub var_debug_info: Vec<VarDebugInfo>,
+
+    /// The lexical scopes of the function, indexed by `SourceScope`.
+    pub source_scopes: Vec<SourceScopeData>,
 }
 
 impl Body {
@@ -23,14 +32,20 @@ impl Body {
     ///
     /// A constructor is required to build a `Body` from outside the crate
     /// because the `arg_count` and `locals` fields are private.
-    pub fn new(blocks: Vec<BasicBlock>, locals: LocalDecls, arg_count: usize) -> Self {
+    pub fn new(
+        blocks: Vec<BasicBlock>,
+        locals: LocalDecls,
+        arg_count: usize,
+        var_debug_info: Vec<VarDebugInfo>,
+        source_scopes: Vec<SourceScopeData>,
+    ) -> Self {
         // If locals doesn't contain enough entries, it can lead to panics in
         // `ret_local`, `arg_locals`, and `inner_locals`.
         assert!(
             locals.len() > arg_count,
             "A Body must contain at least a local for the return value and each of the function's arguments"
         );
-        Self { blocks, locals, arg_count }
+        Self { blocks, locals, arg_count, var_debug_info, source_scopes }
     }
 
     /// Return local that holds this function's return value.
@@ -56,29 +71,69 @@ pub fn inner_locals(&self) -> &[LocalDecl] {
     pub fn locals(&self) -> &[LocalDecl] {
         &self.locals
     }
+
+    /// Write a textual representation of this body, in the same syntax as the MIR dumps of the
+    /// compiler, using `fn_name` as the name of the function.
+    pub fn dump<W: io::Write>(&self, w: &mut W, fn_name: &str) -> io::Result<()> {
+        pretty::write_body(self, w, fn_name)
+    }
 }
 
 type LocalDecls = Vec<LocalDecl>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct LocalDecl {
     pub ty: Ty,
     pub span: Span,
+    /// The scope in which the local is declared.
+    pub scope: SourceScope,
+    pub mutability: Mutability,
+}
+
+/// Debug information of a user variable: where its value can be found, and the scope in which it
+/// is visible.
+#[derive(Clone, Debug, Serialize)]
+pub struct VarDebugInfo {
+    pub name: Symbol,
+    pub source_info: SourceInfo,
+    pub value: VarDebugInfoContents,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub enum VarDebugInfoContents {
+    Place(Place),
+    Const(Constant),
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SourceInfo {
+    pub span: Span,
+    pub scope: SourceScope,
+}
+
+/// A lexical scope of a body. The outermost scope, which holds the arguments, is `0`.
+pub type SourceScope = usize;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SourceScopeData {
+    pub span: Span,
+    /// The scope that contains this one, or `None` for the outermost scope.
+    pub parent_scope: Option<SourceScope>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct BasicBlock {
     pub statements: Vec<Statement>,
     pub terminator: Terminator,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Terminator {
     pub kind: TerminatorKind,
     pub span: Span,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum TerminatorKind {
     Goto {
         target: usize,
@@ -122,7 +177,7 @@ pub enum TerminatorKind {
     },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct InlineAsmOperand {
     pub in_value: Option<Operand>,
     pub out_place: Option<Place>,
@@ -131,7 +186,7 @@ pub struct InlineAsmOperand {
     pub raw_rpr: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum UnwindAction {
     Continue,
     Unreachable,
@@ -139,7 +194,7 @@ pub enum UnwindAction {
     Cleanup(usize),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum AssertMessage {
     BoundsCheck { len: Operand, index: Operand },
     Overflow(BinOp, Operand, Operand),
@@ -151,7 +206,7 @@ pub enum AssertMessage {
     MisalignedPointerDereference { required: Operand, found: Operand },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum BinOp {
     Add,
     AddUnchecked,
@@ -177,20 +232,20 @@ pub enum BinOp {
     Offset,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum UnOp {
     Not,
     Neg,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum CoroutineKind {
     Async(CoroutineSource),
     Coroutine,
     Gen(CoroutineSource),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum CoroutineSource {
     Block,
     Closure,
@@ -204,7 +259,7 @@ pub enum CoroutineSource {
 pub(crate) type Coverage = Opaque;
 
 /// The FakeReadCause describes the type of pattern why a FakeRead statement exists.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum FakeReadCause {
     ForMatchGuard,
     ForMatchedPlace(LocalDefId),
@@ -214,7 +269,7 @@ pub enum FakeReadCause {
 }
 
 /// Describes what kind of retag is to be performed
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum RetagKind {
     FnEntry,
     TwoPhase,
@@ -222,7 +277,7 @@ pub enum RetagKind {
     Default,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum Variance {
     Covariant,
     Invariant,
@@ -230,26 +285,26 @@ pub enum Variance {
     Bivariant,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct CopyNonOverlapping {
     pub src: Operand,
     pub dst: Operand,
     pub count: Operand,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum NonDivergingIntrinsic {
     Assume(Operand),
     CopyNonOverlapping(CopyNonOverlapping),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Statement {
     pub kind: StatementKind,
     pub span: Span,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum StatementKind {
     Assign(Place, Rvalue),
     FakeRead(FakeReadCause, Place),
@@ -266,7 +321,7 @@ pub enum StatementKind {
     Nop,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum Rvalue {
     /// Creates a pointer with the indicated mutability to the place.
     ///
@@ -378,7 +433,7 @@ pub enum Rvalue {
     Use(Operand),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum AggregateKind {
     Array(Ty),
     Tuple,
@@ -387,21 +442,52 @@ pub enum AggregateKind {
     Coroutine(CoroutineDef, GenericArgs, Movability),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum Operand {
     Copy(Place),
     Move(Place),
     Constant(Constant),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Place {
     pub local: Local,
     /// projection out of a place (access a field, deref a pointer, etc)
-    pub projection: String,
+    pub projection: Vec<ProjectionElem>,
 }
 
-#[derive(Clone, Debug)]
+/// An access to a part of a place, applied on the result of the previous projections.
+#[derive(Clone, Debug, Serialize)]
+pub enum ProjectionElem {
+    /// Dereference the place, which must be a reference, a raw pointer or a `Box`.
+    Deref,
+
+    /// A field of a struct, a tuple, a union, a closure or an enum variant downcast to with
+    /// `Downcast`, with the type of the field.
+    Field(FieldIdx, Ty),
+
+    /// An element of an array or a slice, with the index in the given local.
+    Index(Local),
+
+    /// An element of an array or a slice, at a constant offset from the start, or from the end if
+    /// `from_end` is set. The array or slice is at least `min_length` elements long.
+    ConstantIndex { offset: u64, min_length: u64, from_end: bool },
+
+    /// A subslice from `from` to `to`. If `from_end` is set, `to` is counted from the end, e.g.
+    /// `[2:-1]` for `[_, _, rest @ .., _]`.
+    Subslice { from: u64, to: u64, from_end: bool },
+
+    /// Access the fields of the given variant of an enum.
+    Downcast(VariantIdx),
+
+    /// Use the place as the hidden type of an opaque type.
+    OpaqueCast(Ty),
+
+    /// Use the place as a subtype of its type, e.g. when it has a less general lifetime.
+    Subtype(Ty),
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub struct UserTypeProjection {
     pub base: UserTypeAnnotationIndex,
     pub projection: String,
@@ -409,6 +495,9 @@ pub struct UserTypeProjection {
 
 pub type Local = usize;
 
+/// The local that holds the return value of a function.
+pub const RETURN_LOCAL: Local = 0;
+
 type FieldIdx = usize;
 
 /// The source-order index of a variant in a type.
@@ -416,20 +505,20 @@ pub struct UserTypeProjection {
 
 type UserTypeAnnotationIndex = usize;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Constant {
     pub span: Span,
     pub user_ty: Option<UserTypeAnnotationIndex>,
     pub literal: Const,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct SwitchTarget {
     pub value: u128,
     pub target: usize,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum BorrowKind {
     /// Data must be immutable and is aliasable.
     Shared,
@@ -446,26 +535,26 @@ pub enum BorrowKind {
     },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum MutBorrowKind {
     Default,
     TwoPhaseBorrow,
     ClosureCapture,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum Mutability {
     Not,
     Mut,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Serialize)]
 pub enum Safety {
     Unsafe,
     Normal,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum PointerCoercion {
     /// Go from a fn-item type to a fn-pointer type.
     ReifyFnPointer,
@@ -492,7 +581,7 @@ pub enum PointerCoercion {
     Unsize,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum CastKind {
     PointerExposeAddress,
     PointerFromExposedAddress,
@@ -507,7 +596,7 @@ pub enum CastKind {
     Transmute,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum NullOp {
     /// Returns the size of a value of that type.
     SizeOf,
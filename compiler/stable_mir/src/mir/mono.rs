@@ -1,16 +1,18 @@
+use crate::abi::FnAbi;
 use crate::mir::Body;
-use crate::ty::{FnDef, GenericArgs, IndexedVal, Ty};
+use crate::ty::{Allocation, FnDef, GenericArgs, IndexedVal, Ty};
 use crate::{with, CrateItem, DefId, Error, Opaque};
+use serde::Serialize;
 use std::fmt::Debug;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum MonoItem {
     Fn(Instance),
     Static(StaticDef),
     GlobalAsm(Opaque),
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Serialize)]
 pub struct Instance {
     /// The type of instance.
     pub kind: InstanceKind,
@@ -19,7 +21,7 @@ pub struct Instance {
     pub def: InstanceDef,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
 pub enum InstanceKind {
     /// A user defined item.
     Item,
@@ -42,6 +44,33 @@ pub fn ty(&self) -> Ty {
         with(|context| context.instance_ty(self.def))
     }
 
+    /// Get how this instance is called: the layouts of its arguments and return value, and how
+    /// they are passed.
+    pub fn fn_abi(&self) -> Result<FnAbi, Error> {
+        with(|context| context.instance_abi(self.def))
+    }
+
+    /// Resolve the instance that a vtable calls for the method `def` with the generic arguments
+    /// `args`, which can be a shim that passes the receiver by value.
+    pub fn resolve_for_vtable(def: FnDef, args: &GenericArgs) -> Result<Instance, crate::Error> {
+        with(|context| {
+            context.resolve_for_vtable(def, args).ok_or_else(|| {
+                crate::Error::new(format!("Failed to resolve `{def:?}` with `{args:?}`"))
+            })
+        })
+    }
+
+    /// Resolve the drop glue of the type `ty`, which is a no-op shim for types that don't need to
+    /// be dropped.
+    pub fn resolve_drop_in_place(ty: Ty) -> Instance {
+        with(|context| context.resolve_drop_in_place(ty))
+    }
+
+    /// Get the symbol name of this instance, as emitted in the object files.
+    pub fn mangled_name(&self) -> String {
+        with(|context| context.instance_mangled_name(self.def))
+    }
+
     /// Resolve an instance starting from a function definition and generic arguments.
     pub fn resolve(def: FnDef, args: &GenericArgs) -> Result<Instance, crate::Error> {
         with(|context| {
@@ -82,12 +111,60 @@ fn try_from(value: Instance) -> Result<Self, Self::Error> {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// The mono items that codegen emits for the local crate, and the edges between them.
+#[derive(Clone, Debug, Serialize)]
+pub struct MonoItemGraph {
+    /// All the items reachable from the roots of the crate, sorted by their symbol names.
+    pub items: Vec<MonoItem>,
+    /// The edges between items, from each item to the items it uses, e.g. the functions it calls,
+    /// the drop glue it runs, the statics it accesses or the vtable methods it creates. They are
+    /// sorted by the indices of their users, then of the items they use.
+    pub edges: Vec<MonoItemEdge>,
+}
+
+/// An edge of the mono item graph, as indices into [`MonoItemGraph::items`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct MonoItemEdge {
+    pub user: usize,
+    pub used: usize,
+}
+
+impl MonoItemGraph {
+    /// The items used by the item at index `user`.
+    pub fn used_items(&self, user: usize) -> impl Iterator<Item = &MonoItem> {
+        self.edges.iter().filter(move |edge| edge.user == user).map(|edge| &self.items[edge.used])
+    }
+
+    /// The items that use the item at index `used`.
+    pub fn user_items(&self, used: usize) -> impl Iterator<Item = &MonoItem> {
+        self.edges.iter().filter(move |edge| edge.used == used).map(|edge| &self.items[edge.user])
+    }
+}
+
+/// Collect the mono items that codegen emits for the local crate, starting from its roots: the
+/// entry function, the non-generic items, and, with `-C link-dead-code`, all the items that can
+/// be instantiated. This includes drop glue, vtable shims, statics and global assembly.
+///
+/// This fails if an error is emitted during the collection, e.g. when evaluating a constant.
+pub fn collect_items() -> Result<MonoItemGraph, Error> {
+    with(|cx| cx.collect_mono_items())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub struct InstanceDef(usize);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct StaticDef(pub DefId);
 
+impl StaticDef {
+    /// Evaluate the initializer of this static, returning the memory of the static.
+    ///
+    /// This fails if the static is defined in an `extern` block, or if its evaluation fails.
+    pub fn eval_initializer(&self) -> Result<Allocation, Error> {
+        with(|cx| cx.eval_static_initializer(*self))
+    }
+}
+
 impl IndexedVal for InstanceDef {
     fn to_val(index: usize) -> Self {
         InstanceDef(index)
@@ -0,0 +1,635 @@
+//! Textual representation of stable MIR bodies, in the same syntax as the MIR dumps of the
+//! compiler, e.g.:
+//!
+//! ```text
+//! fn sum(_1: u8, _2: u8) -> u8 {
+//!     debug left => _1;
+//!     debug right => _2;
+//!     let mut _0: u8;
+//!
+//!     bb0: {
+//!         _0 = Add(_1, _2);
+//!         return;
+//!     }
+//! }
+//! ```
+
+use crate::mir::alloc::GlobalAlloc;
+use crate::mir::*;
+use crate::ty::{
+    Abi, AdtKind, Allocation, BoundTyKind, Const, ConstantKind, DynKind, ExistentialPredicate,
+    FloatTy, GenericArgKind, GenericArgs, IntTy, RegionKind, RigidTy, TermKind, Ty, TyKind, UintTy,
+};
+use crate::{with, DefId};
+use std::io::{self, Write};
+
+const INDENT: &str = "    ";
+
+pub(super) fn write_body<W: Write>(body: &Body, w: &mut W, fn_name: &str) -> io::Result<()> {
+    let args: Vec<_> = body
+        .arg_locals()
+        .iter()
+        .enumerate()
+        .map(|(idx, decl)| format!("_{}: {}", idx + 1, pretty_ty(decl.ty)))
+        .collect();
+    writeln!(w, "fn {fn_name}({}) -> {} {{", args.join(", "), pretty_ty(body.ret_local().ty))?;
+
+    write_scope_tree(body, w, 0, 1)?;
+
+    for (idx, block) in body.blocks.iter().enumerate() {
+        writeln!(w)?;
+        writeln!(w, "{INDENT}bb{idx}: {{")?;
+        for statement in &block.statements {
+            writeln!(w, "{INDENT}{INDENT}{};", pretty_statement(&statement.kind))?;
+        }
+        writeln!(w, "{INDENT}{INDENT}{};", pretty_terminator(&block.terminator.kind))?;
+        writeln!(w, "{INDENT}}}")?;
+    }
+    writeln!(w, "}}")
+}
+
+/// Write the variables declared in `parent`, followed by its child scopes, which are nested
+/// within it.
+fn write_scope_tree<W: Write>(
+    body: &Body,
+    w: &mut W,
+    parent: SourceScope,
+    depth: usize,
+) -> io::Result<()> {
+    let indent = INDENT.repeat(depth);
+
+    for info in body.var_debug_info.iter().filter(|info| info.source_info.scope == parent) {
+        let value = match &info.value {
+            VarDebugInfoContents::Place(place) => pretty_place(place),
+            VarDebugInfoContents::Const(constant) => pretty_constant(constant),
+        };
+        writeln!(w, "{indent}debug {} => {value};", info.name)?;
+    }
+
+    for (local, decl) in body.locals().iter().enumerate() {
+        if (1..body.arg_locals().len() + 1).contains(&local) {
+            // Skip over argument locals, they're printed in the signature.
+            continue;
+        }
+        if decl.scope != parent {
+            // Not declared in this scope.
+            continue;
+        }
+        let mut_str = if let Mutability::Mut = decl.mutability { "mut " } else { "" };
+        writeln!(w, "{indent}let {mut_str}_{local}: {};", pretty_ty(decl.ty))?;
+    }
+
+    for (child, _) in body
+        .source_scopes
+        .iter()
+        .enumerate()
+        .filter(|(_, scope)| scope.parent_scope == Some(parent))
+    {
+        writeln!(w, "{indent}scope {child} {{")?;
+        write_scope_tree(body, w, child, depth + 1)?;
+        writeln!(w, "{indent}}}")?;
+    }
+    Ok(())
+}
+
+fn pretty_statement(statement: &StatementKind) -> String {
+    match statement {
+        StatementKind::Assign(place, rvalue) => {
+            format!("{} = {}", pretty_place(place), pretty_rvalue(rvalue))
+        }
+        StatementKind::FakeRead(cause, place) => {
+            format!("FakeRead({cause:?}, {})", pretty_place(place))
+        }
+        StatementKind::SetDiscriminant { place, variant_index } => {
+            format!("discriminant({}) = {variant_index}", pretty_place(place))
+        }
+        StatementKind::Deinit(place) => format!("Deinit({})", pretty_place(place)),
+        StatementKind::StorageLive(local) => format!("StorageLive(_{local})"),
+        StatementKind::StorageDead(local) => format!("StorageDead(_{local})"),
+        StatementKind::Retag(kind, place) => {
+            let kind = match kind {
+                RetagKind::FnEntry => "[fn entry] ",
+                RetagKind::TwoPhase => "[2phase] ",
+                RetagKind::Raw => "[raw] ",
+                RetagKind::Default => "",
+            };
+            format!("Retag({kind}{})", pretty_place(place))
+        }
+        StatementKind::PlaceMention(place) => format!("PlaceMention({})", pretty_place(place)),
+        StatementKind::AscribeUserType { place, projections, variance } => {
+            format!("AscribeUserType({}, {variance:?}, {projections:?})", pretty_place(place))
+        }
+        StatementKind::Coverage(coverage) => format!("Coverage({coverage})"),
+        StatementKind::Intrinsic(NonDivergingIntrinsic::Assume(op)) => {
+            format!("assume({})", pretty_operand(op))
+        }
+        StatementKind::Intrinsic(NonDivergingIntrinsic::CopyNonOverlapping(copy)) => format!(
+            "copy_nonoverlapping(dst = {}, src = {}, count = {})",
+            pretty_operand(&copy.dst),
+            pretty_operand(&copy.src),
+            pretty_operand(&copy.count)
+        ),
+        StatementKind::ConstEvalCounter => "ConstEvalCounter".to_string(),
+        StatementKind::Nop => "nop".to_string(),
+    }
+}
+
+fn pretty_terminator(terminator: &TerminatorKind) -> String {
+    match terminator {
+        TerminatorKind::Goto { target } => format!("goto -> bb{target}"),
+        TerminatorKind::SwitchInt { discr, targets, otherwise } => {
+            let mut successors: Vec<_> =
+                targets.iter().map(|target| (target.value.to_string(), target.target)).collect();
+            successors.push(("otherwise".to_string(), *otherwise));
+            let head = format!("switchInt({})", pretty_operand(discr));
+            head + &pretty_successors(&successors, None)
+        }
+        TerminatorKind::Resume => "resume".to_string(),
+        TerminatorKind::Abort => "terminate".to_string(),
+        TerminatorKind::Return => "return".to_string(),
+        TerminatorKind::Unreachable => "unreachable".to_string(),
+        TerminatorKind::Drop { place, target, unwind } => {
+            let successors = [("return".to_string(), *target)];
+            format!("drop({})", pretty_place(place)) + &pretty_successors(&successors, Some(unwind))
+        }
+        TerminatorKind::Call { func, args, destination, target, unwind } => {
+            let args: Vec<_> = args.iter().map(pretty_operand).collect();
+            let head = format!(
+                "{} = {}({})",
+                pretty_place(destination),
+                pretty_operand(func),
+                args.join(", ")
+            );
+            let successors: Vec<_> =
+                target.iter().map(|target| ("return".to_string(), *target)).collect();
+            head + &pretty_successors(&successors, Some(unwind))
+        }
+        TerminatorKind::Assert { cond, expected, msg, target, unwind } => {
+            let not = if *expected { "" } else { "!" };
+            let head = format!("assert({not}{}, {})", pretty_operand(cond), pretty_assert_msg(msg));
+            let successors = [("success".to_string(), *target)];
+            head + &pretty_successors(&successors, Some(unwind))
+        }
+        TerminatorKind::CoroutineDrop => "coroutine_drop".to_string(),
+        TerminatorKind::InlineAsm { template, operands, options, destination, unwind, .. } => {
+            let mut head = format!("asm!({template}");
+            for operand in operands {
+                head.push_str(&format!(", {}", operand.raw_rpr));
+            }
+            head.push_str(&format!(", options({options}))"));
+            let successors: Vec<_> =
+                destination.iter().map(|target| ("return".to_string(), *target)).collect();
+            head + &pretty_successors(&successors, Some(unwind))
+        }
+    }
+}
+
+/// Format the successors of a terminator, with their labels. The cleanup block is a successor,
+/// while the other unwind actions are only printed when they apply.
+fn pretty_successors(successors: &[(String, usize)], unwind: Option<&UnwindAction>) -> String {
+    let mut successors = successors.to_vec();
+    let unwind = match unwind {
+        Some(UnwindAction::Cleanup(target)) => {
+            successors.push(("unwind".to_string(), *target));
+            None
+        }
+        Some(UnwindAction::Continue) => Some("unwind continue"),
+        Some(UnwindAction::Unreachable) => Some("unwind unreachable"),
+        Some(UnwindAction::Terminate) => Some("unwind terminate"),
+        None => None,
+    };
+    match (&successors[..], unwind) {
+        ([], None) => String::new(),
+        ([], Some(unwind)) => format!(" -> {unwind}"),
+        ([(_, target)], None) => format!(" -> bb{target}"),
+        _ => {
+            let mut labels: Vec<_> =
+                successors.iter().map(|(label, target)| format!("{label}: bb{target}")).collect();
+            labels.extend(unwind.map(str::to_string));
+            format!(" -> [{}]", labels.join(", "))
+        }
+    }
+}
+
+fn pretty_assert_msg(msg: &AssertMessage) -> String {
+    match msg {
+        AssertMessage::BoundsCheck { len, index } => format!(
+            "\"index out of bounds: the length is {{}} but the index is {{}}\", {}, {}",
+            pretty_operand(len),
+            pretty_operand(index)
+        ),
+        AssertMessage::Overflow(op, l, r) => {
+            let (l, r) = (pretty_operand(l), pretty_operand(r));
+            match op {
+                BinOp::Add => {
+                    format!("\"attempt to compute `{{}} + {{}}`, which would overflow\", {l}, {r}")
+                }
+                BinOp::Sub => {
+                    format!("\"attempt to compute `{{}} - {{}}`, which would overflow\", {l}, {r}")
+                }
+                BinOp::Mul => {
+                    format!("\"attempt to compute `{{}} * {{}}`, which would overflow\", {l}, {r}")
+                }
+                BinOp::Div => {
+                    format!("\"attempt to compute `{{}} / {{}}`, which would overflow\", {l}, {r}")
+                }
+                BinOp::Rem => format!(
+                    "\"attempt to compute the remainder of `{{}} % {{}}`, which would overflow\", \
+                     {l}, {r}"
+                ),
+                BinOp::Shr => {
+                    format!("\"attempt to shift right by `{{}}`, which would overflow\", {r}")
+                }
+                BinOp::Shl => {
+                    format!("\"attempt to shift left by `{{}}`, which would overflow\", {r}")
+                }
+                op => format!("\"attempt to compute `{op:?}` with overflow\", {l}, {r}"),
+            }
+        }
+        AssertMessage::OverflowNeg(op) => {
+            format!("\"attempt to negate `{{}}`, which would overflow\", {}", pretty_operand(op))
+        }
+        AssertMessage::DivisionByZero(op) => {
+            format!("\"attempt to divide `{{}}` by zero\", {}", pretty_operand(op))
+        }
+        AssertMessage::RemainderByZero(op) => format!(
+            "\"attempt to calculate the remainder of `{{}}` with a divisor of zero\", {}",
+            pretty_operand(op)
+        ),
+        AssertMessage::ResumedAfterReturn(kind) => {
+            let msg = match kind {
+                CoroutineKind::Coroutine => "coroutine resumed after completion",
+                CoroutineKind::Async(_) => "`async fn` resumed after completion",
+                CoroutineKind::Gen(_) => {
+                    "`gen fn` should just keep returning `None` after completion"
+                }
+            };
+            format!("\"{msg}\"")
+        }
+        AssertMessage::ResumedAfterPanic(kind) => {
+            let msg = match kind {
+                CoroutineKind::Coroutine => "coroutine resumed after panicking",
+                CoroutineKind::Async(_) => "`async fn` resumed after panicking",
+                CoroutineKind::Gen(_) => {
+                    "`gen fn` should just keep returning `None` after panicking"
+                }
+            };
+            format!("\"{msg}\"")
+        }
+        AssertMessage::MisalignedPointerDereference { required, found } => format!(
+            "\"misaligned pointer dereference: address must be a multiple of {{}} but is {{}}\", \
+             {}, {}",
+            pretty_operand(required),
+            pretty_operand(found)
+        ),
+    }
+}
+
+fn pretty_rvalue(rvalue: &Rvalue) -> String {
+    match rvalue {
+        Rvalue::AddressOf(mutability, place) => {
+            let kind = match mutability {
+                Mutability::Not => "const",
+                Mutability::Mut => "mut",
+            };
+            format!("&raw {kind} {}", pretty_place(place))
+        }
+        Rvalue::Aggregate(kind, operands) => pretty_aggregate(kind, operands),
+        Rvalue::BinaryOp(op, l, r) => {
+            format!("{op:?}({}, {})", pretty_operand(l), pretty_operand(r))
+        }
+        Rvalue::Cast(kind, op, ty) => {
+            format!("{} as {} ({kind:?})", pretty_operand(op), pretty_ty(*ty))
+        }
+        Rvalue::CheckedBinaryOp(op, l, r) => {
+            format!("Checked{op:?}({}, {})", pretty_operand(l), pretty_operand(r))
+        }
+        Rvalue::CopyForDeref(place) => format!("deref_copy {}", pretty_place(place)),
+        Rvalue::Discriminant(place) => format!("discriminant({})", pretty_place(place)),
+        Rvalue::Len(place) => format!("Len({})", pretty_place(place)),
+        Rvalue::Ref(_, kind, place) => {
+            let kind = match kind {
+                BorrowKind::Shared => "",
+                BorrowKind::Shallow => "shallow ",
+                BorrowKind::Mut { .. } => "mut ",
+            };
+            format!("&{kind}{}", pretty_place(place))
+        }
+        Rvalue::Repeat(op, count) => {
+            format!("[{}; {}]", pretty_operand(op), pretty_const_value(count, false))
+        }
+        Rvalue::ShallowInitBox(op, ty) => {
+            format!("ShallowInitBox({}, {})", pretty_operand(op), pretty_ty(*ty))
+        }
+        Rvalue::ThreadLocalRef(item) => format!("&/*tls*/ {}", item.name()),
+        Rvalue::NullaryOp(op, ty) => match op {
+            NullOp::SizeOf => format!("SizeOf({})", pretty_ty(*ty)),
+            NullOp::AlignOf => format!("AlignOf({})", pretty_ty(*ty)),
+            NullOp::OffsetOf(fields) => format!("OffsetOf({}, {fields:?})", pretty_ty(*ty)),
+        },
+        Rvalue::UnaryOp(op, operand) => format!("{op:?}({})", pretty_operand(operand)),
+        Rvalue::Use(operand) => pretty_operand(operand),
+    }
+}
+
+fn pretty_aggregate(kind: &AggregateKind, operands: &[Operand]) -> String {
+    let operands: Vec<_> = operands.iter().map(pretty_operand).collect();
+    let numbered_fields = || -> Vec<_> {
+        operands.iter().enumerate().map(|(idx, op)| format!("{idx}: {op}")).collect()
+    };
+    let with_fields = |name: String, fields: Vec<String>| {
+        if fields.is_empty() {
+            name
+        } else {
+            format!("{name} {{ {} }}", fields.join(", "))
+        }
+    };
+    match kind {
+        AggregateKind::Array(_) => format!("[{}]", operands.join(", ")),
+        AggregateKind::Tuple => match &operands[..] {
+            [] => "()".to_string(),
+            [op] => format!("({op},)"),
+            _ => format!("({})", operands.join(", ")),
+        },
+        AggregateKind::Adt(def, variant_idx, args, _, active_field) => {
+            let variant = def.variant(*variant_idx).unwrap();
+            let mut name = def_name(def.0) + &pretty_args(args, true);
+            if let AdtKind::Enum = def.kind() {
+                name = format!("{name}::{}", variant.name());
+            }
+            let fields = variant.fields();
+            if let Some(field) = active_field {
+                // Unions are built with their active field only.
+                with_fields(name, vec![format!("{}: {}", fields[*field].name, operands[0])])
+            } else if fields.first().is_some_and(|field| field.name == "0") {
+                format!("{name}({})", operands.join(", "))
+            } else {
+                let fields = fields
+                    .iter()
+                    .zip(&operands)
+                    .map(|(field, op)| format!("{}: {op}", field.name))
+                    .collect();
+                with_fields(name, fields)
+            }
+        }
+        AggregateKind::Closure(def, _) => {
+            with_fields(format!("{{closure@{}}}", def_name(def.0)), numbered_fields())
+        }
+        AggregateKind::Coroutine(def, _, _) => {
+            with_fields(format!("{{coroutine@{}}}", def_name(def.0)), numbered_fields())
+        }
+    }
+}
+
+fn pretty_operand(operand: &Operand) -> String {
+    match operand {
+        Operand::Copy(place) => pretty_place(place),
+        Operand::Move(place) => format!("move {}", pretty_place(place)),
+        Operand::Constant(constant) => pretty_constant(constant),
+    }
+}
+
+fn pretty_place(place: &Place) -> String {
+    let mut pretty = String::new();
+    for elem in place.projection.iter().rev() {
+        match elem {
+            ProjectionElem::Deref => pretty.push_str("(*"),
+            ProjectionElem::Field(..)
+            | ProjectionElem::Downcast(_)
+            | ProjectionElem::OpaqueCast(_)
+            | ProjectionElem::Subtype(_) => pretty.push('('),
+            ProjectionElem::Index(_)
+            | ProjectionElem::ConstantIndex { .. }
+            | ProjectionElem::Subslice { .. } => {}
+        }
+    }
+    pretty.push_str(&format!("_{}", place.local));
+    for elem in &place.projection {
+        let elem = match elem {
+            ProjectionElem::Deref => ")".to_string(),
+            ProjectionElem::Field(idx, ty) => format!(".{idx}: {})", pretty_ty(*ty)),
+            ProjectionElem::Index(local) => format!("[_{local}]"),
+            ProjectionElem::ConstantIndex { offset, min_length, from_end: false } => {
+                format!("[{offset} of {min_length}]")
+            }
+            ProjectionElem::ConstantIndex { offset, min_length, from_end: true } => {
+                format!("[-{offset} of {min_length}]")
+            }
+            ProjectionElem::Subslice { from, to: 0, from_end: true } => format!("[{from}:]"),
+            ProjectionElem::Subslice { from: 0, to, from_end: true } => format!("[:-{to}]"),
+            ProjectionElem::Subslice { from, to, from_end: true } => format!("[{from}:-{to}]"),
+            ProjectionElem::Subslice { from, to, from_end: false } => format!("[{from}..{to}]"),
+            ProjectionElem::Downcast(idx) => format!(" as variant#{idx})"),
+            ProjectionElem::OpaqueCast(ty) => format!(" as {})", pretty_ty(*ty)),
+            ProjectionElem::Subtype(ty) => format!(" as subtype {})", pretty_ty(*ty)),
+        };
+        pretty.push_str(&elem);
+    }
+    pretty
+}
+
+/// Function items are printed as a path, and the other constants with a `const` prefix.
+fn pretty_constant(constant: &Constant) -> String {
+    match constant.ty().kind() {
+        TyKind::RigidTy(RigidTy::FnDef(..)) => pretty_const_value(&constant.literal, true),
+        _ => format!("const {}", pretty_const_value(&constant.literal, true)),
+    }
+}
+
+/// Format the value of a constant. Integers get the suffix of their type if `suffix` is set,
+/// e.g. `3_usize`.
+fn pretty_const_value(constant: &Const, suffix: bool) -> String {
+    let ty = constant.ty();
+    match constant.kind() {
+        ConstantKind::Param(param) => param.name.clone(),
+        ConstantKind::Unevaluated(uneval) => {
+            let name = def_name(uneval.def.0) + &pretty_args(&uneval.args, true);
+            match uneval.promoted {
+                Some(promoted) => format!("{name}::promoted[{promoted}]"),
+                None => name,
+            }
+        }
+        ConstantKind::ZeroSized => match ty.kind() {
+            TyKind::RigidTy(RigidTy::FnDef(def, args)) => {
+                def_name(def.0) + &pretty_args(&args, true)
+            }
+            TyKind::RigidTy(RigidTy::Tuple(tys)) if tys.is_empty() => "()".to_string(),
+            TyKind::RigidTy(RigidTy::Adt(def, args)) => def_name(def.0) + &pretty_args(&args, true),
+            _ => format!("ZeroSized: {}", pretty_ty(ty)),
+        },
+        ConstantKind::Allocated(alloc) => pretty_allocation(alloc, ty, suffix)
+            .unwrap_or_else(|| format!("{{alloc: {}}}", pretty_ty(ty))),
+    }
+}
+
+/// Format the scalars and the string literals like the compiler does. This returns `None` for the
+/// values that can't be read, and for the other types.
+fn pretty_allocation(alloc: &Allocation, ty: Ty, suffix: bool) -> Option<String> {
+    let with_suffix = |value: String, ty: String| {
+        if suffix {
+            format!("{value}_{ty}")
+        } else {
+            value
+        }
+    };
+    let pretty = match ty.kind() {
+        TyKind::RigidTy(RigidTy::Bool) => alloc.read_bool().ok()?.to_string(),
+        TyKind::RigidTy(RigidTy::Char) => {
+            format!("{:?}", char::from_u32(alloc.read_uint().ok()? as u32)?)
+        }
+        TyKind::RigidTy(RigidTy::Int(_)) => {
+            with_suffix(alloc.read_int().ok()?.to_string(), pretty_ty(ty))
+        }
+        TyKind::RigidTy(RigidTy::Uint(_)) => {
+            with_suffix(alloc.read_uint().ok()?.to_string(), pretty_ty(ty))
+        }
+        TyKind::RigidTy(RigidTy::Float(FloatTy::F32)) => {
+            format!("{}f32", f32::from_bits(alloc.read_uint().ok()? as u32))
+        }
+        TyKind::RigidTy(RigidTy::Float(FloatTy::F64)) => {
+            format!("{}f64", f64::from_bits(alloc.read_uint().ok()? as u64))
+        }
+        TyKind::RigidTy(RigidTy::Tuple(tys)) if tys.is_empty() => "()".to_string(),
+        TyKind::RigidTy(RigidTy::Ref(_, pointee, _))
+            if matches!(pointee.kind(), TyKind::RigidTy(RigidTy::Str)) =>
+        {
+            let (GlobalAlloc::Memory(literal), offset) = alloc.read_pointer(0).ok()?? else {
+                return None;
+            };
+            let bytes = literal.raw_bytes().ok()?;
+            // The length follows the pointer to the literal.
+            let len = alloc.read_partial_uint(alloc.bytes.len() / 2..alloc.bytes.len()).ok()?;
+            let len = len as usize;
+            format!("{:?}", std::str::from_utf8(bytes.get(offset..offset + len)?).ok()?)
+        }
+        _ => return None,
+    };
+    Some(pretty)
+}
+
+fn pretty_ty(ty: Ty) -> String {
+    match ty.kind() {
+        TyKind::RigidTy(rigid) => pretty_rigid_ty(rigid),
+        TyKind::Alias(_, alias) => def_name(alias.def_id.0) + &pretty_args(&alias.args, false),
+        TyKind::Param(param) => param.name,
+        TyKind::Bound(_, bound) => match bound.kind {
+            BoundTyKind::Param(_, name) => name,
+            BoundTyKind::Anon => format!("^{}", bound.var),
+        },
+    }
+}
+
+fn pretty_rigid_ty(ty: RigidTy) -> String {
+    match ty {
+        RigidTy::Bool => "bool".to_string(),
+        RigidTy::Char => "char".to_string(),
+        RigidTy::Int(int) => match int {
+            IntTy::Isize => "isize",
+            IntTy::I8 => "i8",
+            IntTy::I16 => "i16",
+            IntTy::I32 => "i32",
+            IntTy::I64 => "i64",
+            IntTy::I128 => "i128",
+        }
+        .to_string(),
+        RigidTy::Uint(uint) => match uint {
+            UintTy::Usize => "usize",
+            UintTy::U8 => "u8",
+            UintTy::U16 => "u16",
+            UintTy::U32 => "u32",
+            UintTy::U64 => "u64",
+            UintTy::U128 => "u128",
+        }
+        .to_string(),
+        RigidTy::Float(FloatTy::F32) => "f32".to_string(),
+        RigidTy::Float(FloatTy::F64) => "f64".to_string(),
+        RigidTy::Adt(def, args) => def_name(def.0) + &pretty_args(&args, false),
+        RigidTy::Foreign(def) => def_name(def.0),
+        RigidTy::Str => "str".to_string(),
+        RigidTy::Array(ty, len) => {
+            format!("[{}; {}]", pretty_ty(ty), pretty_const_value(&len, false))
+        }
+        RigidTy::Slice(ty) => format!("[{}]", pretty_ty(ty)),
+        RigidTy::RawPtr(ty, Mutability::Not) => format!("*const {}", pretty_ty(ty)),
+        RigidTy::RawPtr(ty, Mutability::Mut) => format!("*mut {}", pretty_ty(ty)),
+        RigidTy::Ref(region, ty, mutability) => {
+            let region = match region.kind {
+                RegionKind::ReStatic => "'static ".to_string(),
+                RegionKind::ReEarlyBound(region) => format!("{} ", region.name),
+                _ => String::new(),
+            };
+            let mutability = if let Mutability::Mut = mutability { "mut " } else { "" };
+            format!("&{region}{mutability}{}", pretty_ty(ty))
+        }
+        RigidTy::FnDef(def, args) => def_name(def.0) + &pretty_args(&args, true),
+        RigidTy::FnPtr(sig) => {
+            let sig = sig.value;
+            let unsafety = if let Safety::Unsafe = sig.unsafety { "unsafe " } else { "" };
+            let abi =
+                if sig.abi == Abi::Rust { String::new() } else { format!("extern {:?} ", sig.abi) };
+            let (output, inputs) = sig.inputs_and_output.split_last().unwrap();
+            let mut inputs: Vec<_> = inputs.iter().map(|ty| pretty_ty(*ty)).collect();
+            if sig.c_variadic {
+                inputs.push("...".to_string());
+            }
+            let output = match output.kind() {
+                TyKind::RigidTy(RigidTy::Tuple(tys)) if tys.is_empty() => String::new(),
+                _ => format!(" -> {}", pretty_ty(*output)),
+            };
+            format!("{unsafety}{abi}fn({}){output}", inputs.join(", "))
+        }
+        RigidTy::Closure(def, _) => format!("{{closure@{}}}", def_name(def.0)),
+        RigidTy::Coroutine(def, _, _) => format!("{{coroutine@{}}}", def_name(def.0)),
+        RigidTy::Dynamic(predicates, _, kind) => {
+            let bounds: Vec<_> = predicates
+                .iter()
+                .map(|predicate| match &predicate.value {
+                    ExistentialPredicate::Trait(trait_ref) => {
+                        def_name(trait_ref.def_id.0) + &pretty_args(&trait_ref.generic_args, false)
+                    }
+                    ExistentialPredicate::Projection(projection) => {
+                        let term = match &projection.term {
+                            TermKind::Type(ty) => pretty_ty(*ty),
+                            TermKind::Const(cnst) => pretty_const_value(cnst, false),
+                        };
+                        format!("{} = {term}", def_name(projection.def_id.0))
+                    }
+                    ExistentialPredicate::AutoTrait(def) => def_name(def.0),
+                })
+                .collect();
+            let dyn_kind = if let DynKind::DynStar = kind { "dyn*" } else { "dyn" };
+            format!("{dyn_kind} {}", bounds.join(" + "))
+        }
+        RigidTy::Never => "!".to_string(),
+        RigidTy::Tuple(tys) => match &tys[..] {
+            [ty] => format!("({},)", pretty_ty(*ty)),
+            _ => {
+                let tys: Vec<_> = tys.iter().map(|ty| pretty_ty(*ty)).collect();
+                format!("({})", tys.join(", "))
+            }
+        },
+    }
+}
+
+/// Format the type and const arguments, with a turbofish for the paths to values. Regions are
+/// erased in bodies, so they are not printed.
+fn pretty_args(args: &GenericArgs, turbofish: bool) -> String {
+    let args: Vec<_> = args
+        .0
+        .iter()
+        .filter_map(|arg| match arg {
+            GenericArgKind::Lifetime(_) => None,
+            GenericArgKind::Type(ty) => Some(pretty_ty(*ty)),
+            GenericArgKind::Const(cnst) => Some(pretty_const_value(cnst, false)),
+        })
+        .collect();
+    match (args.is_empty(), turbofish) {
+        (true, _) => String::new(),
+        (false, true) => format!("::<{}>", args.join(", ")),
+        (false, false) => format!("<{}>", args.join(", ")),
+    }
+}
+
+fn def_name(def: DefId) -> String {
+    with(|cx| cx.name_of_def_id(def))
+}
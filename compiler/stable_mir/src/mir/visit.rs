@@ -0,0 +1,467 @@
+//! # The StableMIR Visitor
+//!
+//! ## Overview
+//!
+//! We currently provide two flavors of visitors for MIR bodies: [MirVisitor], which only gets
+//! shared references, and [MutMirVisitor], which gets mutable references and can be used to
+//! modify a body, e.g. to build an instrumented copy of it. They mirror the MIR visitors of the
+//! compiler.
+//!
+//! Each `visit_foo` method has a default implementation that calls `super_foo`, which walks the
+//! components of `foo`. To customize the traversal, override `visit_foo`, and call `super_foo`
+//! from it to keep visiting the components of `foo`:
+//!
+//! ```ignore (illustrative)
+//! fn visit_operand(&mut self, operand: &Operand, location: Location) {
+//!     // ... do something with `operand` ...
+//!     self.super_operand(operand, location);
+//! }
+//! ```
+//!
+//! Places are visited with a [PlaceContext], which tells whether the place is read, written or
+//! only mentioned at that point.
+
+use crate::mir::*;
+use crate::ty::{Const, GenericArgKind, GenericArgs, Region, Span, Ty};
+
+/// A position in a body: the statement at `statement_index` in the basic block `block`, or its
+/// terminator if `statement_index` is the number of statements in the block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Location {
+    pub block: usize,
+    pub statement_index: usize,
+}
+
+/// How a place is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceContext {
+    /// The place is read, moved out of, or borrowed immutably.
+    NonMutatingUse,
+    /// The place is written to, or borrowed mutably.
+    MutatingUse,
+    /// The place is neither read nor written, e.g. in `StorageLive` or `PlaceMention`.
+    NonUse,
+}
+
+impl PlaceContext {
+    pub fn is_mutating(&self) -> bool {
+        matches!(self, PlaceContext::MutatingUse)
+    }
+
+    pub fn is_use(&self) -> bool {
+        !matches!(self, PlaceContext::NonUse)
+    }
+}
+
+/// Where a type is visited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TyContext {
+    /// The type of a local, including the return place and the arguments.
+    LocalDecl(Local),
+    /// A type that appears in a statement or a terminator.
+    Location(Location),
+}
+
+macro_rules! make_mir_visitor {
+    ($visitor_trait_name:ident, $($mutability:ident)?) => {
+        pub trait $visitor_trait_name {
+            fn visit_body(&mut self, body: & $($mutability)? Body) {
+                self.super_body(body)
+            }
+
+            fn visit_basic_block(&mut self, block: usize, data: & $($mutability)? BasicBlock) {
+                self.super_basic_block(block, data)
+            }
+
+            fn visit_local_decl(&mut self, local: Local, decl: & $($mutability)? LocalDecl) {
+                self.super_local_decl(local, decl)
+            }
+
+            fn visit_statement(
+                &mut self,
+                statement: & $($mutability)? Statement,
+                location: Location,
+            ) {
+                self.super_statement(statement, location)
+            }
+
+            fn visit_terminator(
+                &mut self,
+                terminator: & $($mutability)? Terminator,
+                location: Location,
+            ) {
+                self.super_terminator(terminator, location)
+            }
+
+            fn visit_assert_msg(
+                &mut self,
+                msg: & $($mutability)? AssertMessage,
+                location: Location,
+            ) {
+                self.super_assert_msg(msg, location)
+            }
+
+            fn visit_rvalue(&mut self, rvalue: & $($mutability)? Rvalue, location: Location) {
+                self.super_rvalue(rvalue, location)
+            }
+
+            fn visit_operand(&mut self, operand: & $($mutability)? Operand, location: Location) {
+                self.super_operand(operand, location)
+            }
+
+            fn visit_place(
+                &mut self,
+                place: & $($mutability)? Place,
+                context: PlaceContext,
+                location: Location,
+            ) {
+                self.super_place(place, context, location)
+            }
+
+            fn visit_local(
+                &mut self,
+                _local: & $($mutability)? Local,
+                _context: PlaceContext,
+                _location: Location,
+            ) {
+            }
+
+            fn visit_projection_elem(
+                &mut self,
+                elem: & $($mutability)? ProjectionElem,
+                context: PlaceContext,
+                location: Location,
+            ) {
+                self.super_projection_elem(elem, context, location)
+            }
+
+            fn visit_var_debug_info(&mut self, var_debug_info: & $($mutability)? VarDebugInfo) {
+                self.super_var_debug_info(var_debug_info)
+            }
+
+            fn visit_constant(
+                &mut self,
+                constant: & $($mutability)? Constant,
+                location: Location,
+            ) {
+                self.super_constant(constant, location)
+            }
+
+            fn visit_span(&mut self, _span: & $($mutability)? Span) {}
+
+            fn visit_ty(&mut self, _ty: & $($mutability)? Ty, _context: TyContext) {}
+
+            fn visit_const(&mut self, _constant: & $($mutability)? Const, _location: Location) {}
+
+            fn visit_region(&mut self, _region: & $($mutability)? Region, _location: Location) {}
+
+            fn visit_args(&mut self, args: & $($mutability)? GenericArgs, location: Location) {
+                self.super_args(args, location)
+            }
+
+            // The `super_xxx` methods comprise the default behavior and are not meant to be
+            // overridden.
+
+            fn super_body(&mut self, body: & $($mutability)? Body) {
+                let Body { blocks, locals, arg_count: _, var_debug_info, source_scopes } = body;
+                for (block, data) in blocks.into_iter().enumerate() {
+                    self.visit_basic_block(block, data);
+                }
+                for (local, decl) in locals.into_iter().enumerate() {
+                    self.visit_local_decl(local, decl);
+                }
+                for info in var_debug_info {
+                    self.visit_var_debug_info(info);
+                }
+                for SourceScopeData { span, parent_scope: _ } in source_scopes {
+                    self.visit_span(span);
+                }
+            }
+
+            fn super_basic_block(&mut self, block: usize, data: & $($mutability)? BasicBlock) {
+                let BasicBlock { statements, terminator } = data;
+                let mut statement_index = 0;
+                for statement in statements {
+                    self.visit_statement(statement, Location { block, statement_index });
+                    statement_index += 1;
+                }
+                self.visit_terminator(terminator, Location { block, statement_index });
+            }
+
+            fn super_local_decl(&mut self, local: Local, decl: & $($mutability)? LocalDecl) {
+                let LocalDecl { ty, span, scope: _, mutability: _ } = decl;
+                self.visit_ty(ty, TyContext::LocalDecl(local));
+                self.visit_span(span);
+            }
+
+            fn super_statement(
+                &mut self,
+                statement: & $($mutability)? Statement,
+                location: Location,
+            ) {
+                let Statement { kind, span } = statement;
+                self.visit_span(span);
+                match kind {
+                    StatementKind::Assign(place, rvalue) => {
+                        self.visit_place(place, PlaceContext::MutatingUse, location);
+                        self.visit_rvalue(rvalue, location);
+                    }
+                    StatementKind::FakeRead(_, place) => {
+                        self.visit_place(place, PlaceContext::NonMutatingUse, location);
+                    }
+                    StatementKind::SetDiscriminant { place, .. }
+                    | StatementKind::Deinit(place)
+                    | StatementKind::Retag(_, place) => {
+                        self.visit_place(place, PlaceContext::MutatingUse, location);
+                    }
+                    StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => {
+                        self.visit_local(local, PlaceContext::NonUse, location);
+                    }
+                    StatementKind::PlaceMention(place)
+                    | StatementKind::AscribeUserType { place, .. } => {
+                        self.visit_place(place, PlaceContext::NonUse, location);
+                    }
+                    StatementKind::Intrinsic(NonDivergingIntrinsic::Assume(operand)) => {
+                        self.visit_operand(operand, location);
+                    }
+                    StatementKind::Intrinsic(NonDivergingIntrinsic::CopyNonOverlapping(
+                        CopyNonOverlapping { src, dst, count },
+                    )) => {
+                        self.visit_operand(src, location);
+                        self.visit_operand(dst, location);
+                        self.visit_operand(count, location);
+                    }
+                    StatementKind::Coverage(_)
+                    | StatementKind::ConstEvalCounter
+                    | StatementKind::Nop => {}
+                }
+            }
+
+            fn super_terminator(
+                &mut self,
+                terminator: & $($mutability)? Terminator,
+                location: Location,
+            ) {
+                let Terminator { kind, span } = terminator;
+                self.visit_span(span);
+                match kind {
+                    TerminatorKind::Goto { .. }
+                    | TerminatorKind::Resume
+                    | TerminatorKind::Abort
+                    | TerminatorKind::Unreachable
+                    | TerminatorKind::CoroutineDrop => {}
+                    TerminatorKind::Return => {
+                        // The return value is read from the return place.
+                        let $($mutability)? local = RETURN_LOCAL;
+                        let context = PlaceContext::NonMutatingUse;
+                        self.visit_local(& $($mutability)? local, context, location);
+                    }
+                    TerminatorKind::SwitchInt { discr, .. } => {
+                        self.visit_operand(discr, location);
+                    }
+                    TerminatorKind::Drop { place, .. } => {
+                        self.visit_place(place, PlaceContext::MutatingUse, location);
+                    }
+                    TerminatorKind::Call { func, args, destination, .. } => {
+                        self.visit_operand(func, location);
+                        for arg in args {
+                            self.visit_operand(arg, location);
+                        }
+                        self.visit_place(destination, PlaceContext::MutatingUse, location);
+                    }
+                    TerminatorKind::Assert { cond, msg, .. } => {
+                        self.visit_operand(cond, location);
+                        self.visit_assert_msg(msg, location);
+                    }
+                    TerminatorKind::InlineAsm { operands, .. } => {
+                        for InlineAsmOperand { in_value, out_place, .. } in operands {
+                            if let Some(operand) = in_value {
+                                self.visit_operand(operand, location);
+                            }
+                            if let Some(place) = out_place {
+                                self.visit_place(place, PlaceContext::MutatingUse, location);
+                            }
+                        }
+                    }
+                }
+            }
+
+            fn super_assert_msg(
+                &mut self,
+                msg: & $($mutability)? AssertMessage,
+                location: Location,
+            ) {
+                match msg {
+                    AssertMessage::BoundsCheck { len, index } => {
+                        self.visit_operand(len, location);
+                        self.visit_operand(index, location);
+                    }
+                    AssertMessage::Overflow(_, left, right) => {
+                        self.visit_operand(left, location);
+                        self.visit_operand(right, location);
+                    }
+                    AssertMessage::OverflowNeg(operand)
+                    | AssertMessage::DivisionByZero(operand)
+                    | AssertMessage::RemainderByZero(operand) => {
+                        self.visit_operand(operand, location);
+                    }
+                    AssertMessage::ResumedAfterReturn(_) | AssertMessage::ResumedAfterPanic(_) => {}
+                    AssertMessage::MisalignedPointerDereference { required, found } => {
+                        self.visit_operand(required, location);
+                        self.visit_operand(found, location);
+                    }
+                }
+            }
+
+            fn super_rvalue(&mut self, rvalue: & $($mutability)? Rvalue, location: Location) {
+                match rvalue {
+                    Rvalue::AddressOf(mutability, place) => {
+                        let context = match mutability {
+                            Mutability::Mut => PlaceContext::MutatingUse,
+                            Mutability::Not => PlaceContext::NonMutatingUse,
+                        };
+                        self.visit_place(place, context, location);
+                    }
+                    Rvalue::Aggregate(kind, operands) => {
+                        match kind {
+                            AggregateKind::Array(ty) => {
+                                self.visit_ty(ty, TyContext::Location(location));
+                            }
+                            AggregateKind::Tuple => {}
+                            AggregateKind::Adt(_, _, args, _, _)
+                            | AggregateKind::Closure(_, args)
+                            | AggregateKind::Coroutine(_, args, _) => {
+                                self.visit_args(args, location);
+                            }
+                        }
+                        for operand in operands {
+                            self.visit_operand(operand, location);
+                        }
+                    }
+                    Rvalue::BinaryOp(_, left, right) | Rvalue::CheckedBinaryOp(_, left, right) => {
+                        self.visit_operand(left, location);
+                        self.visit_operand(right, location);
+                    }
+                    Rvalue::Cast(_, operand, ty) | Rvalue::ShallowInitBox(operand, ty) => {
+                        self.visit_operand(operand, location);
+                        self.visit_ty(ty, TyContext::Location(location));
+                    }
+                    Rvalue::CopyForDeref(place)
+                    | Rvalue::Discriminant(place)
+                    | Rvalue::Len(place) => {
+                        self.visit_place(place, PlaceContext::NonMutatingUse, location);
+                    }
+                    Rvalue::Ref(region, kind, place) => {
+                        self.visit_region(region, location);
+                        let context = match kind {
+                            BorrowKind::Mut { .. } => PlaceContext::MutatingUse,
+                            BorrowKind::Shared | BorrowKind::Shallow => {
+                                PlaceContext::NonMutatingUse
+                            }
+                        };
+                        self.visit_place(place, context, location);
+                    }
+                    Rvalue::Repeat(operand, count) => {
+                        self.visit_operand(operand, location);
+                        self.visit_const(count, location);
+                    }
+                    Rvalue::ThreadLocalRef(_) => {}
+                    Rvalue::NullaryOp(_, ty) => {
+                        self.visit_ty(ty, TyContext::Location(location));
+                    }
+                    Rvalue::UnaryOp(_, operand) | Rvalue::Use(operand) => {
+                        self.visit_operand(operand, location);
+                    }
+                }
+            }
+
+            fn super_operand(&mut self, operand: & $($mutability)? Operand, location: Location) {
+                match operand {
+                    Operand::Copy(place) | Operand::Move(place) => {
+                        self.visit_place(place, PlaceContext::NonMutatingUse, location);
+                    }
+                    Operand::Constant(constant) => {
+                        self.visit_constant(constant, location);
+                    }
+                }
+            }
+
+            fn super_place(
+                &mut self,
+                place: & $($mutability)? Place,
+                context: PlaceContext,
+                location: Location,
+            ) {
+                let Place { local, projection } = place;
+                self.visit_local(local, context, location);
+                for elem in projection {
+                    self.visit_projection_elem(elem, context, location);
+                }
+            }
+
+            fn super_projection_elem(
+                &mut self,
+                elem: & $($mutability)? ProjectionElem,
+                _context: PlaceContext,
+                location: Location,
+            ) {
+                match elem {
+                    ProjectionElem::Deref
+                    | ProjectionElem::ConstantIndex { .. }
+                    | ProjectionElem::Subslice { .. }
+                    | ProjectionElem::Downcast(_) => {}
+                    ProjectionElem::Field(_, ty)
+                    | ProjectionElem::OpaqueCast(ty)
+                    | ProjectionElem::Subtype(ty) => {
+                        self.visit_ty(ty, TyContext::Location(location))
+                    }
+                    ProjectionElem::Index(local) => {
+                        self.visit_local(local, PlaceContext::NonMutatingUse, location)
+                    }
+                }
+            }
+
+            fn super_var_debug_info(&mut self, var_debug_info: & $($mutability)? VarDebugInfo) {
+                let VarDebugInfo { name: _, source_info, value } = var_debug_info;
+                let SourceInfo { span, scope: _ } = source_info;
+                self.visit_span(span);
+                // Like in the compiler, debug information is visited at the start of the body.
+                let location = Location { block: 0, statement_index: 0 };
+                match value {
+                    VarDebugInfoContents::Place(place) => {
+                        self.visit_place(place, PlaceContext::NonUse, location)
+                    }
+                    VarDebugInfoContents::Const(constant) => {
+                        self.visit_constant(constant, location)
+                    }
+                }
+            }
+
+            fn super_constant(
+                &mut self,
+                constant: & $($mutability)? Constant,
+                location: Location,
+            ) {
+                let Constant { span, user_ty: _, literal } = constant;
+                self.visit_span(span);
+                self.visit_const(literal, location);
+            }
+
+            fn super_args(&mut self, args: & $($mutability)? GenericArgs, location: Location) {
+                let GenericArgs(args) = args;
+                for arg in args {
+                    match arg {
+                        GenericArgKind::Lifetime(region) => self.visit_region(region, location),
+                        GenericArgKind::Type(ty) => {
+                            self.visit_ty(ty, TyContext::Location(location))
+                        }
+                        GenericArgKind::Const(constant) => self.visit_const(constant, location),
+                    }
+                }
+            }
+        }
+    };
+}
+
+make_mir_visitor!(MirVisitor,);
+make_mir_visitor!(MutMirVisitor, mut);
@@ -1,12 +1,16 @@
 use super::{
     mir::Safety,
-    mir::{Body, Mutability},
-    with, AllocId, DefId, Symbol,
+    mir::{Body, Mutability, VariantIdx},
+    with, AllocId, DefId, Error, Symbol,
 };
+use crate::abi::{Endian, Layout, MachineInfo, ReprOptions};
+use crate::mir::alloc::GlobalAlloc;
 use crate::{Filename, Opaque};
+use serde::Serialize;
 use std::fmt::{self, Debug, Formatter};
+use std::ops::Range;
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize)]
 pub struct Ty(pub usize);
 
 impl Debug for Ty {
@@ -19,10 +23,31 @@ impl Ty {
     pub fn kind(&self) -> TyKind {
         with(|context| context.ty_kind(*self))
     }
+
+    /// Get the layout of this type. This fails if the type is generic or unsized in a way that
+    /// prevents computing its layout, e.g. if it's too big for the target.
+    pub fn layout(&self) -> Result<Layout, Error> {
+        with(|context| context.ty_layout(*self))
+    }
+
+    /// Whether this type implements the trait `trait_def` with the generic arguments `args`,
+    /// which don't include the `Self` type. The trait is solved in the environment of `item`, so
+    /// its where clauses are assumed to hold, and with all the opaque types revealed.
+    pub fn implements_trait(&self, trait_def: TraitDef, args: &GenericArgs, item: DefId) -> bool {
+        with(|context| context.ty_implements_trait(*self, trait_def, args, item))
+    }
+
+    /// Normalize the associated types and the opaque types in this type, and erase its regions.
+    /// The type is normalized in the environment of `item`, which must be the item this type
+    /// comes from if it refers to generic parameters. This fails if a projection can't be
+    /// normalized in that environment.
+    pub fn normalize(&self, item: DefId) -> Result<Ty, Error> {
+        with(|context| context.normalize_ty(*self, item))
+    }
 }
 
 /// Represents a constant in MIR or from the Type system.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Const {
     /// The constant kind.
     kind: ConstantKind,
@@ -47,19 +72,26 @@ pub fn kind(&self) -> &ConstantKind {
     pub fn ty(&self) -> Ty {
         self.ty
     }
+
+    /// Evaluate this constant, returning its memory.
+    ///
+    /// This fails if the constant depends on generic parameters, or if its evaluation fails.
+    pub fn eval(&self) -> Result<Allocation, Error> {
+        with(|cx| cx.eval_const(self))
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub struct ConstId(pub usize);
 
 type Ident = Opaque;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Region {
     pub kind: RegionKind,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub enum RegionKind {
     ReEarlyBound(EarlyBoundRegion),
     ReLateBound(DebruijnIndex, BoundRegion),
@@ -70,7 +102,7 @@ pub enum RegionKind {
 
 pub(crate) type DebruijnIndex = u32;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct EarlyBoundRegion {
     pub def_id: RegionDef,
     pub index: u32,
@@ -79,7 +111,7 @@ pub struct EarlyBoundRegion {
 
 pub(crate) type BoundVar = u32;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BoundRegion {
     pub var: BoundVar,
     pub kind: BoundRegionKind,
@@ -87,13 +119,13 @@ pub struct BoundRegion {
 
 pub(crate) type UniverseIndex = u32;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Placeholder<T> {
     pub universe: UniverseIndex,
     pub bound: T,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct Span(usize);
 
 impl Debug for Span {
@@ -115,9 +147,15 @@ pub fn get_filename(&self) -> Filename {
     pub fn get_lines(&self) -> LineInfo {
         with(|c| c.get_lines(&self))
     }
+
+    /// Return the source code of this `Span`, if it is available. This is usually not the case
+    /// for spans that come from other crates, or from macro expansions of other crates.
+    pub fn source_text(&self) -> Option<String> {
+        with(|c| c.source_text(*self))
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize)]
 /// Information you get from `Span` in a struct form.
 /// Line and col start from 1.
 pub struct LineInfo {
@@ -127,7 +165,7 @@ pub struct LineInfo {
     pub end_col: usize,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum TyKind {
     RigidTy(RigidTy),
     Alias(AliasKind, AliasTy),
@@ -135,7 +173,7 @@ pub enum TyKind {
     Bound(usize, BoundTy),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum RigidTy {
     Bool,
     Char,
@@ -158,7 +196,7 @@ pub enum RigidTy {
     Tuple(Vec<Ty>),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum IntTy {
     Isize,
     I8,
@@ -168,7 +206,7 @@ pub enum IntTy {
     I128,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum UintTy {
     Usize,
     U8,
@@ -178,22 +216,22 @@ pub enum UintTy {
     U128,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum FloatTy {
     F32,
     F64,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum Movability {
     Static,
     Movable,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct ForeignDef(pub DefId);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct FnDef(pub DefId);
 
 impl FnDef {
@@ -202,41 +240,135 @@ pub fn body(&self) -> Body {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct ClosureDef(pub DefId);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct CoroutineDef(pub DefId);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct ParamDef(pub DefId);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct BrNamedDef(pub DefId);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct AdtDef(pub DefId);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+impl AdtDef {
+    pub fn kind(&self) -> AdtKind {
+        with(|cx| cx.adt_kind(*self))
+    }
+
+    /// Whether this is `Box`, which has special meaning for the compiler.
+    pub fn is_box(&self) -> bool {
+        with(|cx| cx.adt_is_box(*self))
+    }
+
+    pub fn is_phantom_data(&self) -> bool {
+        with(|cx| cx.adt_is_phantom_data(*self))
+    }
+
+    pub fn is_unsafe_cell(&self) -> bool {
+        with(|cx| cx.adt_is_unsafe_cell(*self))
+    }
+
+    /// The `#[repr]` options of this ADT.
+    pub fn repr(&self) -> ReprOptions {
+        with(|cx| cx.adt_repr(*self))
+    }
+
+    /// The number of variants of this ADT. Structs and unions have exactly one variant.
+    pub fn num_variants(&self) -> usize {
+        with(|cx| cx.adt_variants_len(*self))
+    }
+
+    /// The variants of this ADT, in the order they are declared in.
+    pub fn variants(&self) -> Vec<VariantDef> {
+        (0..self.num_variants()).map(|idx| VariantDef { idx, adt_def: *self }).collect()
+    }
+
+    /// The variant with the given index, if it exists.
+    pub fn variant(&self, idx: VariantIdx) -> Option<VariantDef> {
+        (idx < self.num_variants()).then_some(VariantDef { idx, adt_def: *self })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum AdtKind {
+    Enum,
+    Union,
+    Struct,
+}
+
+/// A variant of an ADT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct VariantDef {
+    idx: VariantIdx,
+    adt_def: AdtDef,
+}
+
+impl VariantDef {
+    /// The index of this variant in its ADT.
+    pub fn idx(&self) -> VariantIdx {
+        self.idx
+    }
+
+    /// The ADT that this variant belongs to.
+    pub fn adt_def(&self) -> AdtDef {
+        self.adt_def
+    }
+
+    pub fn name(&self) -> Symbol {
+        with(|cx| cx.variant_name(*self))
+    }
+
+    /// The fields of this variant, in the order they are declared in.
+    pub fn fields(&self) -> Vec<FieldDef> {
+        with(|cx| cx.variant_fields(*self))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FieldDef {
+    /// The definition of this field, which can be used to retrieve its type.
+    pub def: DefId,
+    /// The name of this field, which is its index for tuple structs and tuple variants.
+    pub name: Symbol,
+}
+
+impl FieldDef {
+    /// The type of this field, with the generic parameters of its ADT replaced by `args`.
+    pub fn ty_with_args(&self, args: &GenericArgs) -> Ty {
+        with(|cx| cx.def_ty_with_args(self.def, args))
+    }
+
+    /// The declared type of this field, which may refer to the generic parameters of its ADT.
+    pub fn ty(&self) -> Ty {
+        with(|cx| cx.def_ty(self.def))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct AliasDef(pub DefId);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct TraitDef(pub DefId);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct GenericDef(pub DefId);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct ConstDef(pub DefId);
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
 pub struct ImplDef(pub DefId);
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
 pub struct RegionDef(pub DefId);
 
 /// A list of generic arguments.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct GenericArgs(pub Vec<GenericArgKind>);
 
 impl std::ops::Index<ParamTy> for GenericArgs {
@@ -255,7 +387,7 @@ fn index(&self, index: ParamConst) -> &Self::Output {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum GenericArgKind {
     Lifetime(Region),
     Type(Ty),
@@ -284,13 +416,13 @@ pub fn expect_const(&self) -> &Const {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum TermKind {
     Type(Ty),
     Const(Const),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum AliasKind {
     Projection,
     Inherent,
@@ -298,7 +430,7 @@ pub enum AliasKind {
     Weak,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct AliasTy {
     pub def_id: AliasDef,
     pub args: GenericArgs,
@@ -306,7 +438,7 @@ pub struct AliasTy {
 
 pub type PolyFnSig = Binder<FnSig>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct FnSig {
     pub inputs_and_output: Vec<Ty>,
     pub c_variadic: bool,
@@ -314,7 +446,7 @@ pub struct FnSig {
     pub abi: Abi,
 }
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
 pub enum Abi {
     Rust,
     C { unwind: bool },
@@ -345,70 +477,70 @@ pub enum Abi {
     RiscvInterruptS,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Binder<T> {
     pub value: T,
     pub bound_vars: Vec<BoundVariableKind>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct EarlyBinder<T> {
     pub value: T,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum BoundVariableKind {
     Ty(BoundTyKind),
     Region(BoundRegionKind),
     Const,
 }
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize)]
 pub enum BoundTyKind {
     Anon,
     Param(ParamDef, String),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum BoundRegionKind {
     BrAnon,
     BrNamed(BrNamedDef, String),
     BrEnv,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum DynKind {
     Dyn,
     DynStar,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum ExistentialPredicate {
     Trait(ExistentialTraitRef),
     Projection(ExistentialProjection),
     AutoTrait(TraitDef),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ExistentialTraitRef {
     pub def_id: TraitDef,
     pub generic_args: GenericArgs,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ExistentialProjection {
     pub def_id: TraitDef,
     pub generic_args: GenericArgs,
     pub term: TermKind,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ParamTy {
     pub index: u32,
     pub name: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct BoundTy {
     pub var: usize,
     pub kind: BoundTyKind,
@@ -417,21 +549,21 @@ pub struct BoundTy {
 pub type Bytes = Vec<Option<u8>>;
 pub type Size = usize;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
 pub struct Prov(pub AllocId);
 pub type Align = u64;
 pub type Promoted = u32;
 pub type InitMaskMaterialized = Vec<u64>;
 
 /// Stores the provenance information of pointers stored in memory.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ProvenanceMap {
     /// Provenance in this map applies from the given offset for an entire pointer-size worth of
     /// bytes. Two entries in this map are always at least a pointer size apart.
     pub ptrs: Vec<(Size, Prov)>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Allocation {
     pub bytes: Bytes,
     pub provenance: ProvenanceMap,
@@ -439,7 +571,107 @@ pub struct Allocation {
     pub mutability: Mutability,
 }
 
-#[derive(Clone, Debug)]
+impl Allocation {
+    /// Get the bytes of this allocation. This fails if any of them is uninitialized.
+    pub fn raw_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.bytes
+            .iter()
+            .copied()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| Error::new(format!("Found uninitialized bytes: `{:?}`", self.bytes)))
+    }
+
+    /// Read the unsigned integer stored in the bytes of `range`, with the endianness of the
+    /// target.
+    ///
+    /// This fails if the range is empty, larger than 16 bytes or out of bounds, or if it holds
+    /// uninitialized bytes or a part of a pointer.
+    pub fn read_partial_uint(&self, range: Range<Size>) -> Result<u128, Error> {
+        if range.is_empty() || range.len() > 16 || range.end > self.bytes.len() {
+            return Err(Error::new(format!(
+                "Cannot read an integer from range `{range:?}` of an allocation of {} bytes",
+                self.bytes.len()
+            )));
+        }
+        let machine = MachineInfo::target();
+        if self
+            .provenance
+            .ptrs
+            .iter()
+            .any(|(offset, _)| *offset < range.end && range.start < *offset + machine.pointer_size)
+        {
+            return Err(Error::new(format!("Cannot read a pointer as an integer in `{range:?}`")));
+        }
+        let bytes = self.bytes[range.clone()].iter().copied().collect::<Option<Vec<_>>>();
+        let bytes = bytes.ok_or_else(|| {
+            Error::new(format!("Found uninitialized bytes in `{range:?}`: `{:?}`", self.bytes))
+        })?;
+        Ok(read_target_uint(machine.endian, &bytes))
+    }
+
+    /// Read this allocation as an unsigned integer.
+    pub fn read_uint(&self) -> Result<u128, Error> {
+        self.read_partial_uint(0..self.bytes.len())
+    }
+
+    /// Read this allocation as a signed integer.
+    pub fn read_int(&self) -> Result<i128, Error> {
+        let shift = 128 - self.bytes.len().min(16) * 8;
+        Ok(((self.read_uint()? << shift) as i128) >> shift)
+    }
+
+    /// Read this allocation as a `bool`, failing if it isn't `0` or `1`.
+    pub fn read_bool(&self) -> Result<bool, Error> {
+        match self.read_uint()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            val => Err(Error::new(format!("Unexpected value for bool: `{val}`"))),
+        }
+    }
+
+    /// Read the pointer stored at `offset`: the allocation it points to, and the offset within
+    /// that allocation.
+    ///
+    /// Returns `None` if the pointer has no provenance, e.g. a null pointer or a pointer built
+    /// from an integer. This fails if the bytes of the pointer are uninitialized or out of
+    /// bounds, or if they only hold a part of a pointer.
+    pub fn read_pointer(&self, offset: Size) -> Result<Option<(GlobalAlloc, Size)>, Error> {
+        let machine = MachineInfo::target();
+        let range = offset..offset + machine.pointer_size;
+        let prov = self.provenance.ptrs.iter().find(|(ptr_offset, _)| *ptr_offset == offset);
+        let Some((_, prov)) = prov else {
+            return self.read_partial_uint(range).map(|_| None);
+        };
+        let bytes = self
+            .bytes
+            .get(range.clone())
+            .and_then(|bytes| bytes.iter().copied().collect::<Option<Vec<_>>>());
+        let bytes = bytes.ok_or_else(|| {
+            Error::new(format!(
+                "Found uninitialized or missing bytes for the pointer in `{range:?}`"
+            ))
+        })?;
+        let ptr_offset = read_target_uint(machine.endian, &bytes) as Size;
+        Ok(Some((GlobalAlloc::from(prov.0), ptr_offset)))
+    }
+}
+
+/// Read an unsigned integer of at most 16 bytes, with the given endianness.
+fn read_target_uint(endian: Endian, bytes: &[u8]) -> u128 {
+    let mut buf = [0u8; 16];
+    match endian {
+        Endian::Little => {
+            buf[..bytes.len()].copy_from_slice(bytes);
+            u128::from_le_bytes(buf)
+        }
+        Endian::Big => {
+            buf[16 - bytes.len()..].copy_from_slice(bytes);
+            u128::from_be_bytes(buf)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub enum ConstantKind {
     Allocated(Allocation),
     Unevaluated(UnevaluatedConst),
@@ -449,27 +681,27 @@ pub enum ConstantKind {
     ZeroSized,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ParamConst {
     pub index: u32,
     pub name: String,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct UnevaluatedConst {
     pub def: ConstDef,
     pub args: GenericArgs,
     pub promoted: Option<Promoted>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum TraitSpecializationKind {
     None,
     Marker,
     AlwaysApplicable,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct TraitDecl {
     pub def_id: TraitDef,
     pub unsafety: Safety,
@@ -500,13 +732,13 @@ pub fn explicit_predicates_of(&self) -> GenericPredicates {
 
 pub type ImplTrait = EarlyBinder<TraitRef>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct TraitRef {
     pub def_id: TraitDef,
     pub args: GenericArgs,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct Generics {
     pub parent: Option<GenericDef>,
     pub parent_count: usize,
@@ -517,14 +749,14 @@ pub struct Generics {
     pub host_effect_index: Option<usize>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum GenericParamDefKind {
     Lifetime,
     Type { has_default: bool, synthetic: bool },
     Const { has_default: bool },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct GenericParamDef {
     pub name: super::Symbol,
     pub def_id: GenericDef,
@@ -533,12 +765,13 @@ pub struct GenericParamDef {
     pub kind: GenericParamDefKind,
 }
 
+#[derive(Serialize)]
 pub struct GenericPredicates {
     pub parent: Option<TraitDef>,
     pub predicates: Vec<(PredicateKind, Span)>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum PredicateKind {
     Clause(ClauseKind),
     ObjectSafe(TraitDef),
@@ -550,7 +783,7 @@ pub enum PredicateKind {
     AliasRelate(TermKind, TermKind, AliasRelationDirection),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum ClauseKind {
     Trait(TraitPredicate),
     RegionOutlives(RegionOutlivesPredicate),
@@ -561,50 +794,50 @@ pub enum ClauseKind {
     ConstEvaluatable(Const),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum ClosureKind {
     Fn,
     FnMut,
     FnOnce,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct SubtypePredicate {
     pub a: Ty,
     pub b: Ty,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct CoercePredicate {
     pub a: Ty,
     pub b: Ty,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum AliasRelationDirection {
     Equate,
     Subtype,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct TraitPredicate {
     pub trait_ref: TraitRef,
     pub polarity: ImplPolarity,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct OutlivesPredicate<A, B>(pub A, pub B);
 
 pub type RegionOutlivesPredicate = OutlivesPredicate<Region, Region>;
 pub type TypeOutlivesPredicate = OutlivesPredicate<Ty, Region>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ProjectionPredicate {
     pub projection_ty: AliasTy,
     pub term: TermKind,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize)]
 pub enum ImplPolarity {
     Positive,
     Negative,
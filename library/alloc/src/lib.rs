@@ -155,6 +155,7 @@
 #![feature(slice_range)]
 #![feature(std_internals)]
 #![feature(str_internals)]
+#![feature(str_segmentation)]
 #![feature(strict_provenance)]
 #![feature(trusted_len)]
 #![feature(trusted_random_access)]
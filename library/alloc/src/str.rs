@@ -38,6 +38,8 @@
 pub use core::str::{EscapeDebug, EscapeDefault, EscapeUnicode};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::str::{FromStr, Utf8Error};
+#[unstable(feature = "str_segmentation", issue = "none")]
+pub use core::str::{Graphemes, UnicodeWords};
 #[allow(deprecated)]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::str::{Lines, LinesAny};
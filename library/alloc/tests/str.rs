@@ -985,6 +985,56 @@ macro_rules! test {
     test!(b"A\xC3\xA9 \xF1\x80\x80 ", 4, Some(3));
 }
 
+#[test]
+fn from_utf8_error_long_inputs() {
+    // Long enough inputs are validated in blocks by SIMD implementations, so move the errors
+    // across all offsets of a block and put both ASCII and multi-byte characters around them.
+    let cases: &[(&[u8], usize, Option<usize>)] = &[
+        (b"\xFF ", 0, Some(1)),
+        (b"\x80 ", 0, Some(1)),
+        (b"\xC1 ", 0, Some(1)),
+        (b"\xC2 ", 0, Some(1)),
+        (b"\xC2\xC0", 0, Some(1)),
+        (b"\xE0\x9F", 0, Some(1)),
+        (b"\xE0\xA0\xC0", 0, Some(2)),
+        (b"\xED\xA0\x80 ", 0, Some(1)),
+        (b"\xF1\x80 ", 0, Some(2)),
+        (b"\xF1\x80\x80 ", 0, Some(3)),
+        (b"\xF4\x90\x80\x80", 0, Some(1)),
+        (b"\xF5\x80\x80\x80", 0, Some(1)),
+        (b"\xC3\xA9\x80", 2, Some(1)),
+        (b"\xF0\x9F\x98\x80\x80", 4, Some(1)),
+    ];
+    let ascii = "a".repeat(100);
+    let multi_byte = "é中😀".repeat(20);
+
+    for filler in [&ascii, &multi_byte] {
+        for prefix_len in 0..70 {
+            let prefix = &filler[..filler.floor_char_boundary(prefix_len)];
+            for &(bytes, valid_up_to, error_len) in cases {
+                let mut input = prefix.as_bytes().to_vec();
+                input.extend_from_slice(bytes);
+                input.extend_from_slice(filler.as_bytes());
+
+                let error = from_utf8(&input).unwrap_err();
+                assert_eq!(error.valid_up_to(), prefix.len() + valid_up_to);
+                assert_eq!(error.error_len(), error_len);
+            }
+
+            // Sequences cut off at the end of the input.
+            for bytes in [&b"\xC2"[..], b"\xE0\xA0", b"\xF1\x80\x80"] {
+                let mut input = filler.as_bytes().to_vec();
+                input.extend_from_slice(prefix.as_bytes());
+                input.extend_from_slice(bytes);
+
+                let error = from_utf8(&input).unwrap_err();
+                assert_eq!(error.valid_up_to(), filler.len() + prefix.len());
+                assert_eq!(error.error_len(), None);
+            }
+        }
+    }
+}
+
 #[test]
 fn test_as_bytes() {
     // no null
@@ -1499,6 +1549,55 @@ fn test_split_whitespace() {
     assert_eq!(words, ["Märy", "häd", "ä", "little", "lämb", "Little", "lämb"])
 }
 
+#[test]
+fn test_graphemes() {
+    fn t(data: &str, expected: &[&str]) {
+        let graphemes: Vec<&str> = data.graphemes().collect();
+        assert_eq!(graphemes, expected);
+    }
+    t("", &[]);
+    t("a\r\nb", &["a", "\r\n", "b"]);
+    t("e\u{301}\u{308}x", &["e\u{301}\u{308}", "x"]);
+    t("\u{7}\u{301}", &["\u{7}", "\u{301}"]);
+    // Hangul syllables made of conjoining jamo.
+    t("\u{1100}\u{1161}\u{11a8}\u{ac00}", &["\u{1100}\u{1161}\u{11a8}", "\u{ac00}"]);
+    // Regional indicators pair up into flags.
+    t("🇩🇪🇫🇷🇮", &["🇩🇪", "🇫🇷", "🇮"]);
+    // Emoji joined by ZWJ, and emoji modifiers.
+    t("👨\u{200d}👩\u{200d}👧a\u{200d}👧", &["👨\u{200d}👩\u{200d}👧", "a\u{200d}", "👧"]);
+    t("👍🏽", &["👍🏽"]);
+    // Prepend and SpacingMark.
+    t("\u{600}1\u{915}\u{93f}", &["\u{600}1", "\u{915}\u{93f}"]);
+}
+
+#[test]
+fn test_graphemes_as_str() {
+    let mut graphemes = "a\r\nb".graphemes();
+    assert_eq!(graphemes.next(), Some("a"));
+    assert_eq!(graphemes.as_str(), "\r\nb");
+    assert_eq!(graphemes.next(), Some("\r\n"));
+    assert_eq!(graphemes.as_str(), "b");
+    assert_eq!(graphemes.next(), Some("b"));
+    assert_eq!(graphemes.as_str(), "");
+    assert_eq!(graphemes.next(), None);
+}
+
+#[test]
+fn test_unicode_words() {
+    fn t(data: &str, expected: &[&str]) {
+        let words: Vec<&str> = data.unicode_words().collect();
+        assert_eq!(words, expected);
+    }
+    t("", &[]);
+    t(
+        "The quick (\"brown\") fox can't jump 32.3 feet, right?",
+        &["The", "quick", "brown", "fox", "can't", "jump", "32.3", "feet", "right"],
+    );
+    t("nai\u{308}ve café 🇩🇪 3,000.5kg", &["nai\u{308}ve", "café", "3,000.5kg"]);
+    t("snake_case_9 x", &["snake_case_9", "x"]);
+    t("צה\"ל", &["צה\"ל"]);
+}
+
 #[test]
 fn test_lines() {
     fn t(data: &str, expected: &[&str]) {
@@ -1,4 +1,6 @@
+use core::mem;
 use core::ptr::NonNull;
+use rand::Rng;
 use test::black_box;
 use test::Bencher;
 
@@ -171,3 +173,134 @@ fn fold_to_last(b: &mut Bencher) {
     let slice: &[i32] = &[0; 1024];
     b.iter(|| black_box(slice).iter().fold(None, |_, r| Some(NonNull::from(r))));
 }
+
+fn gen_ascending(len: usize) -> Vec<u64> {
+    (0..len as u64).collect()
+}
+
+fn gen_descending(len: usize) -> Vec<u64> {
+    (0..len as u64).rev().collect()
+}
+
+fn gen_random(len: usize) -> Vec<u64> {
+    let mut rng = crate::bench_rng();
+    (0..len).map(|_| rng.gen()).collect()
+}
+
+fn gen_random_low_cardinality(len: usize) -> Vec<u64> {
+    let mut rng = crate::bench_rng();
+    (0..len).map(|_| rng.gen_range(0..16)).collect()
+}
+
+fn gen_mostly_ascending(len: usize) -> Vec<u64> {
+    let mut rng = crate::bench_rng();
+    let mut v = gen_ascending(len);
+    for _ in (0usize..).take_while(|x| x * x <= len) {
+        let x = rng.gen_range(0..len);
+        let y = rng.gen_range(0..len);
+        v.swap(x, y);
+    }
+    v
+}
+
+fn gen_mostly_descending(len: usize) -> Vec<u64> {
+    let mut v = gen_mostly_ascending(len);
+    v.reverse();
+    v
+}
+
+fn gen_saw(len: usize) -> Vec<u64> {
+    // Ascending runs of a random length between 1 and 5% of the slice.
+    let mut rng = crate::bench_rng();
+    let max_run = len / 20 + 1;
+    let mut v = gen_random(len);
+    let mut start = 0;
+    while start < len {
+        let end = (start + rng.gen_range(1..=max_run)).min(len);
+        v[start..end].sort_unstable();
+        start = end;
+    }
+    v
+}
+
+fn gen_pipe_organ(len: usize) -> Vec<u64> {
+    let mut v = gen_random(len);
+    let mid = len / 2;
+    v[..mid].sort_unstable();
+    v[mid..].sort_unstable_by(|a, b| b.cmp(a));
+    v
+}
+
+fn gen_random_strings(len: usize) -> Vec<String> {
+    gen_random(len).into_iter().map(|x| format!("{x:x}")).collect()
+}
+
+fn gen_random_big(len: usize) -> Vec<[u64; 16]> {
+    gen_random(len).into_iter().map(|x| [x; 16]).collect()
+}
+
+macro_rules! sort {
+    ($f:ident, $name:ident, $gen:expr, $len:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let v = $gen($len);
+            b.iter(|| black_box(v.clone()).$f());
+            b.bytes = $len * mem::size_of_val(&$gen(1)[0]) as u64;
+        }
+    };
+}
+
+macro_rules! sort_comparisons {
+    ($f:ident, $name:ident, $gen:expr, $len:expr) => {
+        // Sorts with a comparison function that is expensive compared to moving the elements, so
+        // the number of comparisons dominates the result.
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let v = $gen($len);
+            b.iter(|| {
+                let mut v = v.clone();
+                let mut count = 0;
+                v.$f(|a: &u64, b: &u64| {
+                    count += 1;
+                    (*a as f64).cos().partial_cmp(&(*b as f64).cos()).unwrap()
+                });
+                black_box(count);
+            });
+            b.bytes = $len * mem::size_of::<u64>() as u64;
+        }
+    };
+}
+
+sort!(sort, sort_small_random, gen_random, 10);
+sort!(sort, sort_small_big, gen_random_big, 10);
+sort!(sort, sort_medium_random, gen_random, 100);
+sort!(sort, sort_medium_low_cardinality, gen_random_low_cardinality, 100);
+sort!(sort, sort_large_ascending, gen_ascending, 10_000);
+sort!(sort, sort_large_descending, gen_descending, 10_000);
+sort!(sort, sort_large_mostly_ascending, gen_mostly_ascending, 10_000);
+sort!(sort, sort_large_mostly_descending, gen_mostly_descending, 10_000);
+sort!(sort, sort_large_random, gen_random, 10_000);
+sort!(sort, sort_large_low_cardinality, gen_random_low_cardinality, 10_000);
+sort!(sort, sort_large_saw, gen_saw, 10_000);
+sort!(sort, sort_large_pipe_organ, gen_pipe_organ, 10_000);
+sort!(sort, sort_large_strings, gen_random_strings, 10_000);
+sort!(sort, sort_large_big, gen_random_big, 10_000);
+sort!(sort, sort_huge_random, gen_random, 1_000_000);
+sort_comparisons!(sort_by, sort_large_comparisons, gen_random, 10_000);
+
+sort!(sort_unstable, sort_unstable_small_random, gen_random, 10);
+sort!(sort_unstable, sort_unstable_small_big, gen_random_big, 10);
+sort!(sort_unstable, sort_unstable_medium_random, gen_random, 100);
+sort!(sort_unstable, sort_unstable_medium_low_cardinality, gen_random_low_cardinality, 100);
+sort!(sort_unstable, sort_unstable_large_ascending, gen_ascending, 10_000);
+sort!(sort_unstable, sort_unstable_large_descending, gen_descending, 10_000);
+sort!(sort_unstable, sort_unstable_large_mostly_ascending, gen_mostly_ascending, 10_000);
+sort!(sort_unstable, sort_unstable_large_mostly_descending, gen_mostly_descending, 10_000);
+sort!(sort_unstable, sort_unstable_large_random, gen_random, 10_000);
+sort!(sort_unstable, sort_unstable_large_low_cardinality, gen_random_low_cardinality, 10_000);
+sort!(sort_unstable, sort_unstable_large_saw, gen_saw, 10_000);
+sort!(sort_unstable, sort_unstable_large_pipe_organ, gen_pipe_organ, 10_000);
+sort!(sort_unstable, sort_unstable_large_strings, gen_random_strings, 10_000);
+sort!(sort_unstable, sort_unstable_large_big, gen_random_big, 10_000);
+sort!(sort_unstable, sort_unstable_huge_random, gen_random, 1_000_000);
+sort_comparisons!(sort_unstable_by, sort_unstable_large_comparisons, gen_random, 10_000);
@@ -8,3 +8,31 @@
 fn str_validate_emoji(b: &mut Bencher) {
     b.iter(|| str::from_utf8(black_box(corpora::emoji::LARGE.as_bytes())));
 }
+
+// The large corpora are long enough to be validated with SIMD instructions on x86 and AArch64,
+// while the tiny ones are always validated by the scalar implementation.
+
+#[bench]
+fn str_validate_en_large(b: &mut Bencher) {
+    b.iter(|| str::from_utf8(black_box(corpora::en::LARGE.as_bytes())));
+}
+
+#[bench]
+fn str_validate_en_tiny(b: &mut Bencher) {
+    b.iter(|| str::from_utf8(black_box(corpora::en::TINY.as_bytes())));
+}
+
+#[bench]
+fn str_validate_ru_large(b: &mut Bencher) {
+    b.iter(|| str::from_utf8(black_box(corpora::ru::LARGE.as_bytes())));
+}
+
+#[bench]
+fn str_validate_zh_large(b: &mut Bencher) {
+    b.iter(|| str::from_utf8(black_box(corpora::zh::LARGE.as_bytes())));
+}
+
+#[bench]
+fn str_validate_zh_tiny(b: &mut Bencher) {
+    b.iter(|| str::from_utf8(black_box(corpora::zh::TINY.as_bytes())));
+}
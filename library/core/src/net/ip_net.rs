@@ -0,0 +1,1168 @@
+use crate::fmt::{self, Write};
+use crate::iter::FusedIterator;
+use crate::ops::RangeInclusive;
+
+use super::display_buffer::DisplayBuffer;
+use super::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// An IP network, either IPv4 or IPv6.
+///
+/// This enum can contain either an [`Ipv4Net`] or an [`Ipv6Net`], see their
+/// respective documentation for more details.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ip_net)]
+/// use std::net::{IpAddr, IpNet, Ipv4Addr, Ipv6Addr};
+///
+/// let private: IpNet = "10.0.0.0/8".parse().unwrap();
+/// let documentation: IpNet = "2001:db8::/32".parse().unwrap();
+///
+/// assert!(private.contains(&IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))));
+/// assert!(documentation.contains(&IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))));
+/// assert!(!private.contains(&IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))));
+/// ```
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum IpNet {
+    /// An IPv4 network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    V4(#[unstable(feature = "ip_net", issue = "none")] Ipv4Net),
+    /// An IPv6 network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    V6(#[unstable(feature = "ip_net", issue = "none")] Ipv6Net),
+}
+
+/// An IPv4 network.
+///
+/// A network is made up of an [`Ipv4Addr`] and a prefix length, which is the number of leading
+/// bits of the address that identify the network. The remaining bits identify a host within the
+/// network. See [IETF RFC 4632] for details.
+///
+/// The address of an `Ipv4Net` is kept as it was given, so it may have some of its host bits set.
+/// Use [`network`] to get the first address of the network, or [`trunc`] to get a network with
+/// all of its host bits cleared.
+///
+/// See [`IpNet`] for a type encompassing both IPv4 and IPv6 networks.
+///
+/// [IETF RFC 4632]: https://tools.ietf.org/html/rfc4632
+/// [`network`]: Ipv4Net::network
+/// [`trunc`]: Ipv4Net::trunc
+///
+/// # Textual representation
+///
+/// `Ipv4Net` provides a [`FromStr`] implementation. A network is written as an [`Ipv4Addr`],
+/// followed by a `/` and the prefix length in decimal notation (this is called "CIDR notation").
+///
+/// [`FromStr`]: crate::str::FromStr
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ip_net)]
+/// use std::net::{Ipv4Addr, Ipv4Net};
+///
+/// let net = Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+/// assert_eq!("10.0.0.0/8".parse(), Ok(net));
+/// assert_eq!(net.to_string(), "10.0.0.0/8");
+/// assert_eq!(net.netmask(), Ipv4Addr::new(255, 0, 0, 0));
+/// assert!(net.contains(&Ipv4Addr::new(10, 1, 2, 3)));
+/// ```
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ipv4Net {
+    addr: Ipv4Addr,
+    prefix_len: u8,
+}
+
+/// An IPv6 network.
+///
+/// A network is made up of an [`Ipv6Addr`] and a prefix length, which is the number of leading
+/// bits of the address that identify the network. The remaining bits identify an interface
+/// within the network. See [IETF RFC 4291] for details.
+///
+/// The address of an `Ipv6Net` is kept as it was given, so it may have some of its host bits set.
+/// Use [`network`] to get the first address of the network, or [`trunc`] to get a network with
+/// all of its host bits cleared.
+///
+/// See [`IpNet`] for a type encompassing both IPv4 and IPv6 networks.
+///
+/// [IETF RFC 4291]: https://tools.ietf.org/html/rfc4291#section-2.3
+/// [`network`]: Ipv6Net::network
+/// [`trunc`]: Ipv6Net::trunc
+///
+/// # Textual representation
+///
+/// `Ipv6Net` provides a [`FromStr`] implementation. A network is written as an [`Ipv6Addr`],
+/// followed by a `/` and the prefix length in decimal notation.
+///
+/// [`FromStr`]: crate::str::FromStr
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ip_net)]
+/// use std::net::{Ipv6Addr, Ipv6Net};
+///
+/// let net = Ipv6Net::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 32).unwrap();
+/// assert_eq!("2001:db8::/32".parse(), Ok(net));
+/// assert_eq!(net.to_string(), "2001:db8::/32");
+/// assert!(net.contains(&Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)));
+/// ```
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ipv6Net {
+    addr: Ipv6Addr,
+    prefix_len: u8,
+}
+
+impl IpNet {
+    /// Returns the address of this network, as it was given when the network was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{IpAddr, IpNet, Ipv4Addr};
+    ///
+    /// let net: IpNet = "10.1.2.3/8".parse().unwrap();
+    /// assert_eq!(net.addr(), IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3)));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn addr(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.addr()),
+            IpNet::V6(net) => IpAddr::V6(net.addr()),
+        }
+    }
+
+    /// Returns the prefix length of this network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::IpNet;
+    ///
+    /// let net: IpNet = "2001:db8::/32".parse().unwrap();
+    /// assert_eq!(net.prefix_len(), 32);
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        match self {
+            IpNet::V4(net) => net.prefix_len(),
+            IpNet::V6(net) => net.prefix_len(),
+        }
+    }
+
+    /// Returns the network mask of this network.
+    ///
+    /// See [`Ipv4Net::netmask`] and [`Ipv6Net::netmask`] for details.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn netmask(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.netmask()),
+            IpNet::V6(net) => IpAddr::V6(net.netmask()),
+        }
+    }
+
+    /// Returns the host mask of this network.
+    ///
+    /// See [`Ipv4Net::hostmask`] and [`Ipv6Net::hostmask`] for details.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn hostmask(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.hostmask()),
+            IpNet::V6(net) => IpAddr::V6(net.hostmask()),
+        }
+    }
+
+    /// Returns the first address of this network.
+    ///
+    /// See [`Ipv4Net::network`] and [`Ipv6Net::network`] for details.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn network(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.network()),
+            IpNet::V6(net) => IpAddr::V6(net.network()),
+        }
+    }
+
+    /// Returns this network with all of the host bits of its address cleared.
+    ///
+    /// See [`Ipv4Net::trunc`] and [`Ipv6Net::trunc`] for details.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn trunc(&self) -> IpNet {
+        match self {
+            IpNet::V4(net) => IpNet::V4(net.trunc()),
+            IpNet::V6(net) => IpNet::V6(net.trunc()),
+        }
+    }
+
+    /// Returns the network with a prefix length one shorter than this network that contains it,
+    /// or [`None`] if the prefix length is already zero.
+    ///
+    /// See [`Ipv4Net::supernet`] and [`Ipv6Net::supernet`] for details.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn supernet(&self) -> Option<IpNet> {
+        match self {
+            IpNet::V4(net) => match net.supernet() {
+                Some(net) => Some(IpNet::V4(net)),
+                None => None,
+            },
+            IpNet::V6(net) => match net.supernet() {
+                Some(net) => Some(IpNet::V6(net)),
+                None => None,
+            },
+        }
+    }
+
+    /// Returns [`true`] if `addr` is part of this network.
+    ///
+    /// An IPv4 network never contains an IPv6 address and vice versa, not even an
+    /// [IPv4-mapped][Ipv4Addr::to_ipv6_mapped] one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{IpAddr, IpNet, Ipv4Addr};
+    ///
+    /// let net: IpNet = "192.168.0.0/16".parse().unwrap();
+    /// assert!(net.contains(&IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1))));
+    /// assert!(!net.contains(&IpAddr::V4(Ipv4Addr::new(192, 169, 1, 1))));
+    /// assert!(!net.contains(&IpAddr::V6(Ipv4Addr::new(192, 168, 1, 1).to_ipv6_mapped())));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, addr: &IpAddr) -> bool {
+        match (self, addr) {
+            (IpNet::V4(net), IpAddr::V4(addr)) => net.contains(addr),
+            (IpNet::V6(net), IpAddr::V6(addr)) => net.contains(addr),
+            _ => false,
+        }
+    }
+
+    /// Returns [`true`] if this is an IPv4 network, and [`false`] otherwise.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn is_ipv4(&self) -> bool {
+        matches!(self, IpNet::V4(_))
+    }
+
+    /// Returns [`true`] if this is an IPv6 network, and [`false`] otherwise.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn is_ipv6(&self) -> bool {
+        matches!(self, IpNet::V6(_))
+    }
+}
+
+impl Ipv4Net {
+    /// Creates a new IPv4 network from an address and a prefix length.
+    ///
+    /// Returns [`None`] if `prefix_len` is larger than [`Ipv4Addr::BITS`]. The host bits of
+    /// `addr` are kept, see [`trunc`](Ipv4Net::trunc) to clear them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// assert!(Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 8).is_some());
+    /// assert!(Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 33).is_none());
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn new(addr: Ipv4Addr, prefix_len: u8) -> Option<Ipv4Net> {
+        if prefix_len as u32 > Ipv4Addr::BITS { None } else { Some(Ipv4Net { addr, prefix_len }) }
+    }
+
+    /// Creates a new IPv4 network from an address and a network mask.
+    ///
+    /// Returns [`None`] if `netmask` is not a valid network mask, that is if its set bits are not
+    /// all at the front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let addr = Ipv4Addr::new(192, 168, 0, 0);
+    /// let net = Ipv4Net::with_netmask(addr, Ipv4Addr::new(255, 255, 240, 0)).unwrap();
+    /// assert_eq!(net.prefix_len(), 20);
+    ///
+    /// assert!(Ipv4Net::with_netmask(addr, Ipv4Addr::new(255, 0, 255, 0)).is_none());
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn with_netmask(addr: Ipv4Addr, netmask: Ipv4Addr) -> Option<Ipv4Net> {
+        let mask = netmask.to_bits();
+        let prefix_len = mask.leading_ones();
+        if prefix_len + mask.trailing_zeros() != Ipv4Addr::BITS {
+            None
+        } else {
+            Some(Ipv4Net { addr, prefix_len: prefix_len as u8 })
+        }
+    }
+
+    /// Returns the address of this network, as it was given when the network was created.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn addr(&self) -> Ipv4Addr {
+        self.addr
+    }
+
+    /// Returns the prefix length of this network.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    const fn mask(&self) -> u32 {
+        if self.prefix_len == 0 { 0 } else { u32::MAX << (Ipv4Addr::BITS - self.prefix_len as u32) }
+    }
+
+    /// Returns the network mask of this network, which has the leading [`prefix_len`] bits set.
+    ///
+    /// [`prefix_len`]: Ipv4Net::prefix_len
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "172.16.0.0/12".parse().unwrap();
+    /// assert_eq!(net.netmask(), Ipv4Addr::new(255, 240, 0, 0));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.mask())
+    }
+
+    /// Returns the host mask of this network, which is the inverse of the [`netmask`].
+    ///
+    /// [`netmask`]: Ipv4Net::netmask
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "172.16.0.0/12".parse().unwrap();
+    /// assert_eq!(net.hostmask(), Ipv4Addr::new(0, 15, 255, 255));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn hostmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(!self.mask())
+    }
+
+    /// Returns the first address of this network, which has all of its host bits cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "10.1.2.3/8".parse().unwrap();
+    /// assert_eq!(net.network(), Ipv4Addr::new(10, 0, 0, 0));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn network(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.addr.to_bits() & self.mask())
+    }
+
+    /// Returns the broadcast address of this network, which is its last address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "10.1.2.3/8".parse().unwrap();
+    /// assert_eq!(net.broadcast(), Ipv4Addr::new(10, 255, 255, 255));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn broadcast(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.addr.to_bits() | !self.mask())
+    }
+
+    /// Returns this network with all of the host bits of its address cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "10.1.2.3/8".parse().unwrap();
+    /// assert_eq!(net.trunc(), "10.0.0.0/8".parse().unwrap());
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn trunc(&self) -> Ipv4Net {
+        Ipv4Net { addr: self.network(), prefix_len: self.prefix_len }
+    }
+
+    /// Returns the network with a prefix length one shorter than this network that contains it,
+    /// or [`None`] if the prefix length is already zero.
+    ///
+    /// The address of the returned network has all of its host bits cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "10.1.0.0/16".parse().unwrap();
+    /// assert_eq!(net.supernet(), Some("10.0.0.0/15".parse().unwrap()));
+    /// assert_eq!("0.0.0.0/0".parse::<Ipv4Net>().unwrap().supernet(), None);
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn supernet(&self) -> Option<Ipv4Net> {
+        if self.prefix_len == 0 {
+            None
+        } else {
+            Some(Ipv4Net { addr: self.addr, prefix_len: self.prefix_len - 1 }.trunc())
+        }
+    }
+
+    /// Returns [`true`] if `addr` is part of this network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "192.168.0.0/24".parse().unwrap();
+    /// assert!(net.contains(&Ipv4Addr::new(192, 168, 0, 42)));
+    /// assert!(!net.contains(&Ipv4Addr::new(192, 168, 1, 42)));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, addr: &Ipv4Addr) -> bool {
+        addr.to_bits() & self.mask() == self.network().to_bits()
+    }
+
+    /// Returns an iterator over the usable host addresses of this network.
+    ///
+    /// The network address and the broadcast address are not usable by hosts, so they are skipped,
+    /// except for networks with a prefix length of 31 or 32 that don't have any other addresses
+    /// (see [IETF RFC 3021]).
+    ///
+    /// [IETF RFC 3021]: https://tools.ietf.org/html/rfc3021
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "10.0.0.0/30".parse().unwrap();
+    /// assert!(net.hosts().eq([Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 2)]));
+    ///
+    /// let net: Ipv4Net = "10.0.0.0/31".parse().unwrap();
+    /// assert!(net.hosts().eq([Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 0, 1)]));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[inline]
+    pub fn hosts(&self) -> Ipv4Hosts {
+        let (start, end) = (self.network().to_bits(), self.broadcast().to_bits());
+        let range = if self.prefix_len < 31 { start + 1..=end - 1 } else { start..=end };
+        Ipv4Hosts { range }
+    }
+
+    /// Returns an iterator over the subnets of this network with the given prefix length, in
+    /// ascending order.
+    ///
+    /// Returns [`None`] if `prefix_len` is shorter than the prefix length of this network or longer
+    /// than [`Ipv4Addr::BITS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "10.0.0.0/23".parse().unwrap();
+    /// let subnets: Vec<Ipv4Net> = net.subnets(25).unwrap().collect();
+    /// assert_eq!(subnets, [
+    ///     "10.0.0.0/25".parse().unwrap(),
+    ///     "10.0.0.128/25".parse().unwrap(),
+    ///     "10.0.1.0/25".parse().unwrap(),
+    ///     "10.0.1.128/25".parse().unwrap(),
+    /// ]);
+    ///
+    /// assert!(net.subnets(22).is_none());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[inline]
+    pub fn subnets(&self, prefix_len: u8) -> Option<Ipv4Subnets> {
+        if prefix_len < self.prefix_len || prefix_len as u32 > Ipv4Addr::BITS {
+            return None;
+        }
+        let count = (prefix_len - self.prefix_len) as u32;
+        let last = u32::MAX.checked_shr(u32::BITS - count).unwrap_or(0);
+        Some(Ipv4Subnets {
+            network: self.network().to_bits(),
+            shift: Ipv4Addr::BITS - prefix_len as u32,
+            prefix_len,
+            range: 0..=last,
+        })
+    }
+}
+
+impl Ipv6Net {
+    /// Creates a new IPv6 network from an address and a prefix length.
+    ///
+    /// Returns [`None`] if `prefix_len` is larger than [`Ipv6Addr::BITS`]. The host bits of
+    /// `addr` are kept, see [`trunc`](Ipv6Net::trunc) to clear them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// assert!(Ipv6Net::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 32).is_some());
+    /// assert!(Ipv6Net::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 129).is_none());
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn new(addr: Ipv6Addr, prefix_len: u8) -> Option<Ipv6Net> {
+        if prefix_len as u32 > Ipv6Addr::BITS { None } else { Some(Ipv6Net { addr, prefix_len }) }
+    }
+
+    /// Creates a new IPv6 network from an address and a network mask.
+    ///
+    /// Returns [`None`] if `netmask` is not a valid network mask, that is if its set bits are not
+    /// all at the front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let addr = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0);
+    /// let netmask = Ipv6Addr::new(0xffff, 0xffff, 0xffff, 0xffff, 0, 0, 0, 0);
+    /// assert_eq!(Ipv6Net::with_netmask(addr, netmask).unwrap().prefix_len(), 64);
+    ///
+    /// let netmask = Ipv6Addr::new(0xffff, 0, 0xffff, 0, 0, 0, 0, 0);
+    /// assert!(Ipv6Net::with_netmask(addr, netmask).is_none());
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn with_netmask(addr: Ipv6Addr, netmask: Ipv6Addr) -> Option<Ipv6Net> {
+        let mask = netmask.to_bits();
+        let prefix_len = mask.leading_ones();
+        if prefix_len + mask.trailing_zeros() != Ipv6Addr::BITS {
+            None
+        } else {
+            Some(Ipv6Net { addr, prefix_len: prefix_len as u8 })
+        }
+    }
+
+    /// Returns the address of this network, as it was given when the network was created.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn addr(&self) -> Ipv6Addr {
+        self.addr
+    }
+
+    /// Returns the prefix length of this network.
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    const fn mask(&self) -> u128 {
+        if self.prefix_len == 0 {
+            0
+        } else {
+            u128::MAX << (Ipv6Addr::BITS - self.prefix_len as u32)
+        }
+    }
+
+    /// Returns the network mask of this network, which has the leading [`prefix_len`] bits set.
+    ///
+    /// [`prefix_len`]: Ipv6Net::prefix_len
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8::/32".parse().unwrap();
+    /// assert_eq!(net.netmask(), Ipv6Addr::new(0xffff, 0xffff, 0, 0, 0, 0, 0, 0));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn netmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(self.mask())
+    }
+
+    /// Returns the host mask of this network, which is the inverse of the [`netmask`].
+    ///
+    /// [`netmask`]: Ipv6Net::netmask
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8::/112".parse().unwrap();
+    /// assert_eq!(net.hostmask(), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0xffff));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn hostmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(!self.mask())
+    }
+
+    /// Returns the first address of this network, which has all of its host bits cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8::1/32".parse().unwrap();
+    /// assert_eq!(net.network(), Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn network(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(self.addr.to_bits() & self.mask())
+    }
+
+    /// Returns the last address of this network, which has all of its host bits set.
+    ///
+    /// IPv6 has no broadcast addresses, so this is an ordinary address of the network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8::/112".parse().unwrap();
+    /// assert_eq!(net.last(), Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xffff));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn last(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(self.addr.to_bits() | !self.mask())
+    }
+
+    /// Returns this network with all of the host bits of its address cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv6Net;
+    ///
+    /// let net: Ipv6Net = "2001:db8::1/32".parse().unwrap();
+    /// assert_eq!(net.trunc(), "2001:db8::/32".parse().unwrap());
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn trunc(&self) -> Ipv6Net {
+        Ipv6Net { addr: self.network(), prefix_len: self.prefix_len }
+    }
+
+    /// Returns the network with a prefix length one shorter than this network that contains it,
+    /// or [`None`] if the prefix length is already zero.
+    ///
+    /// The address of the returned network has all of its host bits cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv6Net;
+    ///
+    /// let net: Ipv6Net = "2001:db9::/32".parse().unwrap();
+    /// assert_eq!(net.supernet(), Some("2001:db8::/31".parse().unwrap()));
+    /// assert_eq!("::/0".parse::<Ipv6Net>().unwrap().supernet(), None);
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn supernet(&self) -> Option<Ipv6Net> {
+        if self.prefix_len == 0 {
+            None
+        } else {
+            Some(Ipv6Net { addr: self.addr, prefix_len: self.prefix_len - 1 }.trunc())
+        }
+    }
+
+    /// Returns [`true`] if `addr` is part of this network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8::/32".parse().unwrap();
+    /// assert!(net.contains(&Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)));
+    /// assert!(!net.contains(&Ipv6Addr::new(0x2001, 0xdb9, 0, 0, 0, 0, 0, 1)));
+    /// ```
+    #[rustc_const_unstable(feature = "ip_net", issue = "none")]
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, addr: &Ipv6Addr) -> bool {
+        addr.to_bits() & self.mask() == self.network().to_bits()
+    }
+
+    /// Returns an iterator over the addresses of this network.
+    ///
+    /// Unlike [`Ipv4Net::hosts`], this includes the first and the last address, as IPv6 has no
+    /// broadcast addresses and the first address is only reserved as an anycast address for
+    /// routers (see [IETF RFC 4291 section 2.6.1]).
+    ///
+    /// [IETF RFC 4291 section 2.6.1]: https://tools.ietf.org/html/rfc4291#section-2.6.1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8::/127".parse().unwrap();
+    /// assert!(net.hosts().eq([
+    ///     Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0),
+    ///     Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+    /// ]));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[inline]
+    pub fn hosts(&self) -> Ipv6Hosts {
+        Ipv6Hosts { range: self.network().to_bits()..=self.last().to_bits() }
+    }
+
+    /// Returns an iterator over the subnets of this network with the given prefix length, in
+    /// ascending order.
+    ///
+    /// Returns [`None`] if `prefix_len` is shorter than the prefix length of this network or longer
+    /// than [`Ipv6Addr::BITS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv6Net;
+    ///
+    /// let net: Ipv6Net = "2001:db8::/47".parse().unwrap();
+    /// let subnets: Vec<Ipv6Net> = net.subnets(48).unwrap().collect();
+    /// assert_eq!(subnets, ["2001:db8::/48".parse().unwrap(), "2001:db8:1::/48".parse().unwrap()]);
+    ///
+    /// assert!(net.subnets(46).is_none());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[inline]
+    pub fn subnets(&self, prefix_len: u8) -> Option<Ipv6Subnets> {
+        if prefix_len < self.prefix_len || prefix_len as u32 > Ipv6Addr::BITS {
+            return None;
+        }
+        let count = (prefix_len - self.prefix_len) as u32;
+        let last = u128::MAX.checked_shr(u128::BITS - count).unwrap_or(0);
+        Some(Ipv6Subnets {
+            network: self.network().to_bits(),
+            shift: Ipv6Addr::BITS - prefix_len as u32,
+            prefix_len,
+            range: 0..=last,
+        })
+    }
+}
+
+/// An iterator over the host addresses of an [`Ipv4Net`].
+///
+/// This `struct` is created by [`Ipv4Net::hosts`]. See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct Ipv4Hosts {
+    range: RangeInclusive<u32>,
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv4Hosts {
+    type Item = Ipv4Addr;
+
+    #[inline]
+    fn next(&mut self) -> Option<Ipv4Addr> {
+        self.range.next().map(Ipv4Addr::from_bits)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Ipv4Addr> {
+        self.range.nth(n).map(Ipv4Addr::from_bits)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl DoubleEndedIterator for Ipv4Hosts {
+    #[inline]
+    fn next_back(&mut self) -> Option<Ipv4Addr> {
+        self.range.next_back().map(Ipv4Addr::from_bits)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv4Hosts {}
+
+/// An iterator over the addresses of an [`Ipv6Net`].
+///
+/// This `struct` is created by [`Ipv6Net::hosts`]. See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct Ipv6Hosts {
+    range: RangeInclusive<u128>,
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv6Hosts {
+    type Item = Ipv6Addr;
+
+    #[inline]
+    fn next(&mut self) -> Option<Ipv6Addr> {
+        self.range.next().map(Ipv6Addr::from_bits)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Ipv6Addr> {
+        self.range.nth(n).map(Ipv6Addr::from_bits)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl DoubleEndedIterator for Ipv6Hosts {
+    #[inline]
+    fn next_back(&mut self) -> Option<Ipv6Addr> {
+        self.range.next_back().map(Ipv6Addr::from_bits)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv6Hosts {}
+
+/// An iterator over the subnets of an [`Ipv4Net`].
+///
+/// This `struct` is created by [`Ipv4Net::subnets`]. See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct Ipv4Subnets {
+    network: u32,
+    shift: u32,
+    prefix_len: u8,
+    // The indices of the remaining subnets within the network.
+    range: RangeInclusive<u32>,
+}
+
+impl Ipv4Subnets {
+    #[inline]
+    fn subnet(&self, index: u32) -> Ipv4Net {
+        let addr = Ipv4Addr::from_bits(self.network | index.checked_shl(self.shift).unwrap_or(0));
+        Ipv4Net { addr, prefix_len: self.prefix_len }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv4Subnets {
+    type Item = Ipv4Net;
+
+    #[inline]
+    fn next(&mut self) -> Option<Ipv4Net> {
+        self.range.next().map(|index| self.subnet(index))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Ipv4Net> {
+        self.range.nth(n).map(|index| self.subnet(index))
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl DoubleEndedIterator for Ipv4Subnets {
+    #[inline]
+    fn next_back(&mut self) -> Option<Ipv4Net> {
+        self.range.next_back().map(|index| self.subnet(index))
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv4Subnets {}
+
+/// An iterator over the subnets of an [`Ipv6Net`].
+///
+/// This `struct` is created by [`Ipv6Net::subnets`]. See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct Ipv6Subnets {
+    network: u128,
+    shift: u32,
+    prefix_len: u8,
+    // The indices of the remaining subnets within the network.
+    range: RangeInclusive<u128>,
+}
+
+impl Ipv6Subnets {
+    #[inline]
+    fn subnet(&self, index: u128) -> Ipv6Net {
+        let addr = Ipv6Addr::from_bits(self.network | index.checked_shl(self.shift).unwrap_or(0));
+        Ipv6Net { addr, prefix_len: self.prefix_len }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv6Subnets {
+    type Item = Ipv6Net;
+
+    #[inline]
+    fn next(&mut self) -> Option<Ipv6Net> {
+        self.range.next().map(|index| self.subnet(index))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Ipv6Net> {
+        self.range.nth(n).map(|index| self.subnet(index))
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl DoubleEndedIterator for Ipv6Subnets {
+    #[inline]
+    fn next_back(&mut self) -> Option<Ipv6Net> {
+        self.range.next_back().map(|index| self.subnet(index))
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv6Subnets {}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for IpNet {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpNet::V4(net) => net.fmt(fmt),
+            IpNet::V6(net) => net.fmt(fmt),
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Debug for IpNet {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for Ipv4Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there are no alignment requirements, write the network directly to `f`.
+        // Otherwise, write it to a local buffer and then use `f.pad`.
+        if fmt.precision().is_none() && fmt.width().is_none() {
+            write!(fmt, "{}/{}", self.addr, self.prefix_len)
+        } else {
+            const LONGEST_IPV4_NET: &str = "255.255.255.255/32";
+
+            let mut buf = DisplayBuffer::<{ LONGEST_IPV4_NET.len() }>::new();
+            // Buffer is long enough for the longest possible IPv4 network, so this should never fail.
+            write!(buf, "{}/{}", self.addr, self.prefix_len).unwrap();
+
+            fmt.pad(buf.as_str())
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Debug for Ipv4Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for Ipv6Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there are no alignment requirements, write the network directly to `f`.
+        // Otherwise, write it to a local buffer and then use `f.pad`.
+        if fmt.precision().is_none() && fmt.width().is_none() {
+            write!(fmt, "{}/{}", self.addr, self.prefix_len)
+        } else {
+            const LONGEST_IPV6_NET: &str = "ffff:ffff:ffff:ffff:ffff:ffff:255.255.255.255/128";
+
+            let mut buf = DisplayBuffer::<{ LONGEST_IPV6_NET.len() }>::new();
+            // Buffer is long enough for the longest possible IPv6 network, so this should never fail.
+            write!(buf, "{}/{}", self.addr, self.prefix_len).unwrap();
+
+            fmt.pad(buf.as_str())
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Debug for Ipv6Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv4Net> for IpNet {
+    /// Copies this network to a new `IpNet::V4`.
+    #[inline]
+    fn from(net: Ipv4Net) -> IpNet {
+        IpNet::V4(net)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv6Net> for IpNet {
+    /// Copies this network to a new `IpNet::V6`.
+    #[inline]
+    fn from(net: Ipv6Net) -> IpNet {
+        IpNet::V6(net)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv4Addr> for Ipv4Net {
+    /// Creates a network containing only `addr`, with a prefix length of 32.
+    #[inline]
+    fn from(addr: Ipv4Addr) -> Ipv4Net {
+        Ipv4Net { addr, prefix_len: Ipv4Addr::BITS as u8 }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv6Addr> for Ipv6Net {
+    /// Creates a network containing only `addr`, with a prefix length of 128.
+    #[inline]
+    fn from(addr: Ipv6Addr) -> Ipv6Net {
+        Ipv6Net { addr, prefix_len: Ipv6Addr::BITS as u8 }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<IpAddr> for IpNet {
+    /// Creates a network containing only `addr`, with the longest possible prefix length.
+    #[inline]
+    fn from(addr: IpAddr) -> IpNet {
+        match addr {
+            IpAddr::V4(addr) => IpNet::V4(addr.into()),
+            IpAddr::V6(addr) => IpNet::V6(addr.into()),
+        }
+    }
+}
@@ -3,11 +3,16 @@
 //! This module contains a sorting algorithm based on Orson Peters' pattern-defeating quicksort,
 //! published at: <https://github.com/orlp/pdqsort>
 //!
+//! On top of pdqsort, the unstable sort detects fully sorted or reversed inputs up front, sorts
+//! small sub-slices with branchless sorting networks, and chooses pivots with a recursive
+//! median-of-three that approximates the median of `sqrt(len)` elements.
+//!
 //! Unstable sorting is compatible with core because it doesn't allocate memory, unlike our
 //! stable sorting implementation.
 //!
-//! In addition it also contains the core logic of the stable sort used by `slice::sort` based on
-//! TimSort.
+//! In addition it also contains the core logic of the stable sort used by `slice::sort`, an
+//! adaptive merge sort that detects natural runs and merges them following the Powersort merge
+//! policy.
 
 use crate::cmp;
 use crate::mem::{self, MaybeUninit, SizedTypeProperties};
@@ -191,6 +196,128 @@ fn insertion_sort_shift_right<T, F>(v: &mut [T], offset: usize, is_less: &mut F)
     }
 }
 
+/// Swaps `v[a]` and `v[b]` if `v[b] < v[a]`, without branching on the outcome of the comparison.
+///
+/// # Safety
+///
+/// `a` and `b` must be distinct and in bounds of the slice starting at `v_base`.
+#[inline(always)]
+unsafe fn swap_if_less<T, F>(v_base: *mut T, a: usize, b: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    // SAFETY: the caller must guarantee that `a` and `b` are distinct and in bounds.
+    unsafe {
+        let a_ptr = v_base.add(a);
+        let b_ptr = v_base.add(b);
+
+        // PANIC SAFETY: if `is_less` panics, no element has been moved yet and the slice is left
+        // untouched. All the copies below can't panic.
+        let should_swap = is_less(&*b_ptr, &*a_ptr);
+
+        // Selecting the pointers instead of branching on `should_swap` lets the compiler emit
+        // conditional moves, which avoids costly branch mispredictions on random data.
+        let a_swap_ptr = if should_swap { b_ptr } else { a_ptr };
+        let b_swap_ptr = if should_swap { a_ptr } else { b_ptr };
+
+        // `a_swap_ptr` may be equal to `a_ptr`, so a possibly overlapping copy is required.
+        let tmp = mem::ManuallyDrop::new(ptr::read(b_swap_ptr));
+        ptr::copy(a_swap_ptr, a_ptr, 1);
+        ptr::copy_nonoverlapping(&*tmp, b_ptr, 1);
+    }
+}
+
+/// Sorts the first 4 elements of `v` with an optimal sorting network.
+///
+/// # Safety
+///
+/// `v_base` must point to at least 4 initialized elements.
+unsafe fn sort4_network<T, F>(v_base: *mut T, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    // SAFETY: the caller must guarantee that `v_base` points to at least 4 elements.
+    unsafe {
+        swap_if_less(v_base, 0, 1, is_less);
+        swap_if_less(v_base, 2, 3, is_less);
+        swap_if_less(v_base, 0, 2, is_less);
+        swap_if_less(v_base, 1, 3, is_less);
+        swap_if_less(v_base, 1, 2, is_less);
+    }
+}
+
+/// Sorts the first 8 elements of `v` with an optimal sorting network of 19 comparators.
+///
+/// # Safety
+///
+/// `v_base` must point to at least 8 initialized elements.
+unsafe fn sort8_network<T, F>(v_base: *mut T, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    // SAFETY: the caller must guarantee that `v_base` points to at least 8 elements.
+    unsafe {
+        swap_if_less(v_base, 0, 2, is_less);
+        swap_if_less(v_base, 1, 3, is_less);
+        swap_if_less(v_base, 4, 6, is_less);
+        swap_if_less(v_base, 5, 7, is_less);
+
+        swap_if_less(v_base, 0, 4, is_less);
+        swap_if_less(v_base, 1, 5, is_less);
+        swap_if_less(v_base, 2, 6, is_less);
+        swap_if_less(v_base, 3, 7, is_less);
+
+        swap_if_less(v_base, 0, 1, is_less);
+        swap_if_less(v_base, 2, 3, is_less);
+        swap_if_less(v_base, 4, 5, is_less);
+        swap_if_less(v_base, 6, 7, is_less);
+
+        swap_if_less(v_base, 2, 4, is_less);
+        swap_if_less(v_base, 3, 5, is_less);
+
+        swap_if_less(v_base, 1, 4, is_less);
+        swap_if_less(v_base, 3, 6, is_less);
+
+        swap_if_less(v_base, 1, 2, is_less);
+        swap_if_less(v_base, 3, 4, is_less);
+        swap_if_less(v_base, 5, 6, is_less);
+    }
+}
+
+/// Returns `true` if sorting networks are likely to be faster than insertion sort for `T`.
+///
+/// Networks perform more moves than insertion sort, which only pays off as long as the elements
+/// are cheap to copy.
+const fn qualifies_for_sort_network<T>() -> bool {
+    mem::size_of::<T>() <= mem::size_of::<[usize; 4]>()
+}
+
+/// Sorts short slices, using branchless sorting networks for a prefix of the slice where that
+/// is profitable and insertion sort for the rest.
+pub(super) fn small_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = v.len();
+    if len < 2 {
+        return;
+    }
+
+    let presorted_len = if qualifies_for_sort_network::<T>() && len >= 8 {
+        // SAFETY: we just checked that `v` has at least 8 elements.
+        unsafe { sort8_network(v.as_mut_ptr(), is_less) };
+        8
+    } else if qualifies_for_sort_network::<T>() && len >= 4 {
+        // SAFETY: we just checked that `v` has at least 4 elements.
+        unsafe { sort4_network(v.as_mut_ptr(), is_less) };
+        4
+    } else {
+        1
+    };
+
+    insertion_sort_shift_left(v, presorted_len, is_less);
+}
+
 /// Partially sorts a slice by shifting several out-of-order elements around.
 ///
 /// Returns `true` if the slice is sorted at the end. This function is *O*(*n*) worst-case.
@@ -723,14 +850,28 @@ pub(super) fn choose_pivot<T, F>(v: &mut [T], is_less: &mut F) -> (usize, bool)
 where
     F: FnMut(&T, &T) -> bool,
 {
-    // Minimum length to choose the median-of-medians method.
+    // Minimum length to choose the recursive median-of-three method.
     // Shorter slices use the simple median-of-three method.
-    const SHORTEST_MEDIAN_OF_MEDIANS: usize = 50;
+    const SHORTEST_RECURSIVE_MEDIAN: usize = 64;
     // Maximum number of swaps that can be performed in this function.
-    const MAX_SWAPS: usize = 4 * 3;
+    const MAX_SWAPS: usize = 3;
 
     let len = v.len();
 
+    if len >= SHORTEST_RECURSIVE_MEDIAN {
+        // For longer slices, approximate the median of `sqrt(len)` elements by recursively taking
+        // the median of three medians. This picks much better pivots than the median of a few
+        // fixed candidates and is cheap compared to the partitioning that follows.
+        let len_div_8 = len / 8;
+        let (a, b, c) = (0, len_div_8 * 4, len_div_8 * 7);
+        let pivot = median3_rec(v, a, b, c, len_div_8, is_less);
+
+        // The pivot candidates are spread over the whole slice, so if they are in order the slice
+        // is worth checking for being already sorted.
+        let likely_sorted = !is_less(&v[b], &v[a]) && !is_less(&v[c], &v[b]);
+        return (pivot, likely_sorted);
+    }
+
     // Three indices near which we are going to choose a pivot.
     let mut a = len / 4 * 1;
     let mut b = len / 4 * 2;
@@ -741,12 +882,8 @@ pub(super) fn choose_pivot<T, F>(v: &mut [T], is_less: &mut F) -> (usize, bool)
 
     if len >= 8 {
         // Swaps indices so that `v[a] <= v[b]`.
-        // SAFETY: `len >= 8` so there are at least two elements in the neighborhoods of
-        // `a`, `b` and `c`. This means the three calls to `sort_adjacent` result in
-        // corresponding calls to `sort3` with valid 3-item neighborhoods around each
-        // pointer, which in turn means the calls to `sort2` are done with valid
-        // references. Thus the `v.get_unchecked` calls are safe, as is the `ptr::swap`
-        // call.
+        // SAFETY: `len >= 8` so `a`, `b` and `c` are valid indices. Thus the `v.get_unchecked`
+        // calls are safe, as is the `ptr::swap` call.
         let mut sort2 = |a: &mut usize, b: &mut usize| unsafe {
             if is_less(v.get_unchecked(*b), v.get_unchecked(*a)) {
                 ptr::swap(a, b);
@@ -754,28 +891,11 @@ pub(super) fn choose_pivot<T, F>(v: &mut [T], is_less: &mut F) -> (usize, bool)
             }
         };
 
-        // Swaps indices so that `v[a] <= v[b] <= v[c]`.
-        let mut sort3 = |a: &mut usize, b: &mut usize, c: &mut usize| {
-            sort2(a, b);
-            sort2(b, c);
-            sort2(a, b);
-        };
-
-        if len >= SHORTEST_MEDIAN_OF_MEDIANS {
-            // Finds the median of `v[a - 1], v[a], v[a + 1]` and stores the index into `a`.
-            let mut sort_adjacent = |a: &mut usize| {
-                let tmp = *a;
-                sort3(&mut (tmp - 1), a, &mut (tmp + 1));
-            };
-
-            // Find medians in the neighborhoods of `a`, `b`, and `c`.
-            sort_adjacent(&mut a);
-            sort_adjacent(&mut b);
-            sort_adjacent(&mut c);
-        }
-
-        // Find the median among `a`, `b`, and `c`.
-        sort3(&mut a, &mut b, &mut c);
+        // Find the median among `a`, `b`, and `c`, sorting the indices so that
+        // `v[a] <= v[b] <= v[c]`.
+        sort2(&mut a, &mut b);
+        sort2(&mut b, &mut c);
+        sort2(&mut a, &mut b);
     }
 
     if swaps < MAX_SWAPS {
@@ -788,6 +908,49 @@ pub(super) fn choose_pivot<T, F>(v: &mut [T], is_less: &mut F) -> (usize, bool)
     }
 }
 
+/// Calculates an approximate median of `3^k` elements of `v` by recursively taking the median of
+/// three medians, spread out by `n` elements starting at `a`, `b` and `c`.
+///
+/// Returns the index of the median.
+fn median3_rec<T, F>(
+    v: &[T],
+    mut a: usize,
+    mut b: usize,
+    mut c: usize,
+    n: usize,
+    is_less: &mut F,
+) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    // Stop recursing once the neighborhoods get small, a median of three is good enough there.
+    const RECURSION_THRESHOLD: usize = 64;
+
+    if n * 8 >= RECURSION_THRESHOLD {
+        let n8 = n / 8;
+        a = median3_rec(v, a, a + n8 * 4, a + n8 * 7, n8, is_less);
+        b = median3_rec(v, b, b + n8 * 4, b + n8 * 7, n8, is_less);
+        c = median3_rec(v, c, c + n8 * 4, c + n8 * 7, n8, is_less);
+    }
+    median3(v, a, b, c, is_less)
+}
+
+/// Returns the index of the median of `v[a]`, `v[b]` and `v[c]`.
+fn median3<T, F>(v: &[T], a: usize, b: usize, c: usize, is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let x = is_less(&v[a], &v[b]);
+    let y = is_less(&v[a], &v[c]);
+    if x == y {
+        // `v[a]` is either the minimum or the maximum, the median is one of `v[b]` and `v[c]`.
+        let z = is_less(&v[b], &v[c]);
+        if z ^ x { c } else { b }
+    } else {
+        a
+    }
+}
+
 /// Sorts `v` recursively.
 ///
 /// If the slice had a predecessor in the original array, it is specified as `pred`.
@@ -809,11 +972,9 @@ fn recurse<'a, T, F>(mut v: &'a mut [T], is_less: &mut F, mut pred: Option<&'a T
     loop {
         let len = v.len();
 
-        // Very short slices get sorted using insertion sort.
+        // Very short slices get sorted using sorting networks and insertion sort.
         if len <= MAX_INSERTION {
-            if len >= 2 {
-                insertion_sort_shift_left(v, 1, is_less);
-            }
+            small_sort(v, is_less);
             return;
         }
 
@@ -891,8 +1052,23 @@ pub fn quicksort<T, F>(v: &mut [T], mut is_less: F)
         return;
     }
 
+    let len = v.len();
+    if len < 2 {
+        return;
+    }
+
+    // Inputs that are already sorted, or sorted in reverse, are common in practice. Detecting them
+    // up front costs only `len - 1` comparisons and sorts them in linear time.
+    let (run_len, was_reversed) = find_streak(v, &mut is_less);
+    if run_len == len {
+        if was_reversed {
+            v.reverse();
+        }
+        return;
+    }
+
     // Limit the number of imbalanced partitions to `floor(log2(len)) + 1`.
-    let limit = usize::BITS - v.len().leading_zeros();
+    let limit = usize::BITS - len.leading_zeros();
 
     recurse(v, &mut is_less, None, limit);
 }
@@ -1011,19 +1187,17 @@ fn drop(&mut self) {
     }
 }
 
-/// This merge sort borrows some (but not all) ideas from TimSort, which used to be described in
-/// detail [here](https://github.com/python/cpython/blob/main/Objects/listsort.txt). However Python
-/// has switched to a Powersort based implementation.
+/// This merge sort identifies strictly descending and non-descending subsequences, which are
+/// called natural runs, and merges them following the merge policy of Powersort, described in
+/// [Nearly-Optimal Mergesorts](https://arxiv.org/abs/1805.04154) by J. Ian Munro and Sebastian
+/// Wild. Python's `list.sort` switched to the same policy, replacing the one of TimSort.
 ///
-/// The algorithm identifies strictly descending and non-descending subsequences, which are called
-/// natural runs. There is a stack of pending runs yet to be merged. Each newly found run is pushed
-/// onto the stack, and then some pairs of adjacent runs are merged until these two invariants are
-/// satisfied:
-///
-/// 1. for every `i` in `1..runs.len()`: `runs[i - 1].len > runs[i].len`
-/// 2. for every `i` in `2..runs.len()`: `runs[i - 2].len > runs[i - 1].len + runs[i].len`
-///
-/// The invariants ensure that the total running time is *O*(*n* \* log(*n*)) worst-case.
+/// Each boundary between two adjacent runs is assigned a depth in a conceptual perfectly balanced
+/// merge tree over the whole slice. There is a stack of pending runs yet to be merged, together
+/// with the depth of the boundary to their right. Whenever a new run is found, runs whose boundary
+/// is deeper than the new boundary get merged first. This keeps the merge tree close to optimal
+/// for the given run lengths, which guarantees *O*(*n* \* log(*n*)) worst-case and *O*(*n*) time
+/// on inputs made of a few long runs, while needing fewer comparisons than TimSort on average.
 pub fn merge_sort<T, CmpF, ElemAllocF, ElemDeallocF, RunAllocF, RunDeallocF>(
     v: &mut [T],
     is_less: &mut CmpF,
@@ -1063,15 +1237,53 @@ pub fn merge_sort<T, CmpF, ElemAllocF, ElemDeallocF, RunAllocF, RunDeallocF>(
 
     let mut runs = RunVec::new(run_alloc_fn, run_dealloc_fn);
 
-    let mut end = 0;
-    let mut start = 0;
+    let scale_factor = merge_tree_scale_factor(len);
 
     // Scan forward. Memory pre-fetching prefers forward scanning vs backwards scanning, and the
     // code-gen is usually better. For the most sensitive types such as integers, these are merged
     // bidirectionally at once. So there is no benefit in scanning backwards.
-    while end < len {
+    //
+    // `prev_run` is the most recently found run. It is only pushed onto the stack once the run
+    // following it is known, because that determines the depth of the boundary between them.
+    let mut prev_run = create_run(v, 0, is_less);
+    while prev_run.start + prev_run.len < len {
+        let next_run = create_run(v, prev_run.start + prev_run.len, is_less);
+        let depth = merge_tree_depth(
+            prev_run.start,
+            next_run.start,
+            next_run.start + next_run.len,
+            scale_factor,
+        );
+
+        // Merge the runs on the stack whose right boundary is at least as deep in the merge tree
+        // as the boundary between `prev_run` and `next_run`.
+        while let Some(left) = runs.pop_if_deeper(depth) {
+            // SAFETY: `buf_ptr` must hold enough capacity for the shorter of the two sides, and
+            // neither side may be on length 0.
+            prev_run = unsafe { merge_runs(v, left, prev_run, buf_ptr, is_less) };
+        }
+
+        prev_run.depth = depth;
+        runs.push(prev_run);
+        prev_run = next_run;
+    }
+
+    // Merge all the remaining runs on the stack, from the top down.
+    while let Some(left) = runs.pop_if_deeper(0) {
+        // SAFETY: see above.
+        prev_run = unsafe { merge_runs(v, left, prev_run, buf_ptr, is_less) };
+    }
+
+    // Finally, exactly one run covering the whole slice must remain.
+    debug_assert!(runs.len() == 0 && prev_run.start == 0 && prev_run.len == len);
+
+    // Finds the run starting at `start`, extending it to a minimum length if it's too short.
+    fn create_run<T, F>(v: &mut [T], start: usize, is_less: &mut F) -> TimSortRun
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
         let (streak_end, was_reversed) = find_streak(&v[start..], is_less);
-        end += streak_end;
+        let mut end = start + streak_end;
         if was_reversed {
             v[start..end].reverse();
         }
@@ -1080,55 +1292,54 @@ pub fn merge_sort<T, CmpF, ElemAllocF, ElemDeallocF, RunAllocF, RunDeallocF>(
         // merge sort on short sequences, so this significantly improves performance.
         end = provide_sorted_batch(v, start, end, is_less);
 
-        // Push this run onto the stack.
-        runs.push(TimSortRun { start, len: end - start });
-        start = end;
+        TimSortRun { start, len: end - start, depth: 0 }
+    }
 
-        // Merge some pairs of adjacent runs to satisfy the invariants.
-        while let Some(r) = collapse(runs.as_slice(), len) {
-            let left = runs[r];
-            let right = runs[r + 1];
-            let merge_slice = &mut v[left.start..right.start + right.len];
-            // SAFETY: `buf_ptr` must hold enough capacity for the shorter of the two sides, and
-            // neither side may be on length 0.
-            unsafe {
-                merge(merge_slice, left.len, buf_ptr, is_less);
-            }
-            runs[r + 1] = TimSortRun { start: left.start, len: left.len + right.len };
-            runs.remove(r);
+    // Merges the adjacent runs `left` and `right` and returns the resulting run.
+    //
+    // SAFETY: `left` must end where `right` starts, both must be non-empty and in bounds of `v`,
+    // and `buf_ptr` must have enough capacity for the shorter of the two runs.
+    unsafe fn merge_runs<T, F>(
+        v: &mut [T],
+        left: TimSortRun,
+        right: TimSortRun,
+        buf_ptr: *mut T,
+        is_less: &mut F,
+    ) -> TimSortRun
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        debug_assert_eq!(left.start + left.len, right.start);
+        let merge_slice = &mut v[left.start..right.start + right.len];
+        // SAFETY: guaranteed by the caller.
+        unsafe {
+            merge(merge_slice, left.len, buf_ptr, is_less);
         }
+        TimSortRun { start: left.start, len: left.len + right.len, depth: left.depth }
     }
 
-    // Finally, exactly one run must remain in the stack.
-    debug_assert!(runs.len() == 1 && runs[0].start == 0 && runs[0].len == len);
+    // Computes the scale factor used by `merge_tree_depth` for a slice of length `len`, which maps
+    // positions in the slice to fixed-point fractions in `[0, 1)` with 62 bits of precision.
+    #[inline]
+    fn merge_tree_scale_factor(len: usize) -> u64 {
+        if usize::BITS > u64::BITS {
+            panic!("Platform not supported");
+        }
 
-    // Examines the stack of runs and identifies the next pair of runs to merge. More specifically,
-    // if `Some(r)` is returned, that means `runs[r]` and `runs[r + 1]` must be merged next. If the
-    // algorithm should continue building a new run instead, `None` is returned.
-    //
-    // TimSort is infamous for its buggy implementations, as described here:
-    // http://envisage-project.eu/timsort-specification-and-verification/
-    //
-    // The gist of the story is: we must enforce the invariants on the top four runs on the stack.
-    // Enforcing them on just top three is not sufficient to ensure that the invariants will still
-    // hold for *all* runs in the stack.
+        ((1 << 62) + len as u64 - 1) / len as u64
+    }
+
+    // Returns the depth in the perfectly balanced merge tree over the whole slice of the node that
+    // would merge the runs `left..mid` and `mid..right`. Deeper nodes have to be merged first.
     //
-    // This function correctly checks invariants for the top four runs. Additionally, if the top
-    // run starts at index 0, it will always demand a merge operation until the stack is fully
-    // collapsed, in order to complete the sort.
+    // The depth is the position of the first bit in which the fractional midpoints of the two runs
+    // differ, which is computed in a branchless way without needing a loop.
     #[inline]
-    fn collapse(runs: &[TimSortRun], stop: usize) -> Option<usize> {
-        let n = runs.len();
-        if n >= 2
-            && (runs[n - 1].start + runs[n - 1].len == stop
-                || runs[n - 2].len <= runs[n - 1].len
-                || (n >= 3 && runs[n - 3].len <= runs[n - 2].len + runs[n - 1].len)
-                || (n >= 4 && runs[n - 4].len <= runs[n - 3].len + runs[n - 2].len))
-        {
-            if n >= 3 && runs[n - 3].len < runs[n - 1].len { Some(n - 3) } else { Some(n - 2) }
-        } else {
-            None
-        }
+    fn merge_tree_depth(left: usize, mid: usize, right: usize, scale_factor: u64) -> u8 {
+        // Twice the midpoints of both runs, which avoids having to deal with the halves.
+        let x = left as u64 + mid as u64;
+        let y = mid as u64 + right as u64;
+        ((scale_factor * x) ^ (scale_factor * y)).leading_zeros() as u8
     }
 
     // Extremely basic versions of Vec.
@@ -1226,25 +1437,20 @@ fn push(&mut self, val: TimSortRun) {
             self.len += 1;
         }
 
-        fn remove(&mut self, index: usize) {
-            if index >= self.len {
-                panic!("Index out of bounds");
+        // Removes and returns the top run if its right boundary is at least `depth` deep.
+        fn pop_if_deeper(&mut self, depth: u8) -> Option<TimSortRun> {
+            if self.len == 0 {
+                return None;
             }
 
             // SAFETY: buf_ptr needs to be valid and len invariant upheld.
-            unsafe {
-                // the place we are taking from.
-                let ptr = self.buf_ptr.as_ptr().add(index);
-
-                // Shift everything down to fill in that spot.
-                ptr::copy(ptr.add(1), ptr, self.len - index - 1);
+            let top = unsafe { *self.buf_ptr.as_ptr().add(self.len - 1) };
+            if top.depth < depth {
+                return None;
             }
-            self.len -= 1;
-        }
 
-        fn as_slice(&self) -> &[TimSortRun] {
-            // SAFETY: Safe as long as buf_ptr is valid and len invariant was upheld.
-            unsafe { &*ptr::slice_from_raw_parts(self.buf_ptr.as_ptr(), self.len) }
+            self.len -= 1;
+            Some(top)
         }
 
         fn len(&self) -> usize {
@@ -1252,42 +1458,6 @@ fn len(&self) -> usize {
         }
     }
 
-    impl<RunAllocF, RunDeallocF> core::ops::Index<usize> for RunVec<RunAllocF, RunDeallocF>
-    where
-        RunAllocF: Fn(usize) -> *mut TimSortRun,
-        RunDeallocF: Fn(*mut TimSortRun, usize),
-    {
-        type Output = TimSortRun;
-
-        fn index(&self, index: usize) -> &Self::Output {
-            if index < self.len {
-                // SAFETY: buf_ptr and len invariant must be upheld.
-                unsafe {
-                    return &*(self.buf_ptr.as_ptr().add(index));
-                }
-            }
-
-            panic!("Index out of bounds");
-        }
-    }
-
-    impl<RunAllocF, RunDeallocF> core::ops::IndexMut<usize> for RunVec<RunAllocF, RunDeallocF>
-    where
-        RunAllocF: Fn(usize) -> *mut TimSortRun,
-        RunDeallocF: Fn(*mut TimSortRun, usize),
-    {
-        fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-            if index < self.len {
-                // SAFETY: buf_ptr and len invariant must be upheld.
-                unsafe {
-                    return &mut *(self.buf_ptr.as_ptr().add(index));
-                }
-            }
-
-            panic!("Index out of bounds");
-        }
-    }
-
     impl<RunAllocF, RunDeallocF> Drop for RunVec<RunAllocF, RunDeallocF>
     where
         RunAllocF: Fn(usize) -> *mut TimSortRun,
@@ -1306,6 +1476,8 @@ fn drop(&mut self) {
 pub struct TimSortRun {
     len: usize,
     start: usize,
+    // Depth in the merge tree of the boundary to the right of this run.
+    depth: u8,
 }
 
 /// Takes a range as denoted by start and end, that is already sorted and extends it to the right if
@@ -8,6 +8,7 @@
 use crate::ops::Try;
 use crate::option;
 use crate::slice::{self, Split as SliceSplit};
+use crate::unicode::segmentation::{grapheme_len, word_len};
 
 use super::from_utf8_unchecked;
 use super::pattern::Pattern;
@@ -1525,3 +1526,99 @@ impl<'a> FusedIterator for $Name<'a> {}
 }
 
 escape_types_impls!(EscapeDebug, EscapeDefault, EscapeUnicode);
+
+/// An iterator over the extended grapheme clusters of a string slice.
+///
+/// This struct is created by the [`graphemes`] method on [`str`].
+/// See its documentation for more.
+///
+/// [`graphemes`]: str::graphemes
+#[unstable(feature = "str_segmentation", issue = "none")]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Graphemes<'a> {
+    pub(super) remainder: &'a str,
+}
+
+impl<'a> Graphemes<'a> {
+    /// Views the underlying data as a subslice of the original data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(str_segmentation)]
+    ///
+    /// let mut graphemes = "e\u{301}te\u{301}".graphemes();
+    /// graphemes.next();
+    /// assert_eq!(graphemes.as_str(), "te\u{301}");
+    /// ```
+    #[must_use]
+    #[unstable(feature = "str_segmentation", issue = "none")]
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.remainder
+    }
+}
+
+#[unstable(feature = "str_segmentation", issue = "none")]
+impl<'a> Iterator for Graphemes<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        if self.remainder.is_empty() {
+            return None;
+        }
+        let (grapheme, remainder) = self.remainder.split_at(grapheme_len(self.remainder));
+        self.remainder = remainder;
+        Some(grapheme)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A grapheme cluster can be made up of any number of chars.
+        let len = self.remainder.len();
+        (if len == 0 { 0 } else { 1 }, Some(len))
+    }
+}
+
+#[unstable(feature = "str_segmentation", issue = "none")]
+impl FusedIterator for Graphemes<'_> {}
+
+/// An iterator over the words of a string slice.
+///
+/// This struct is created by the [`unicode_words`] method on [`str`].
+/// See its documentation for more.
+///
+/// [`unicode_words`]: str::unicode_words
+#[unstable(feature = "str_segmentation", issue = "none")]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct UnicodeWords<'a> {
+    pub(super) remainder: &'a str,
+}
+
+#[unstable(feature = "str_segmentation", issue = "none")]
+impl<'a> Iterator for UnicodeWords<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        while !self.remainder.is_empty() {
+            let (word, remainder) = self.remainder.split_at(word_len(self.remainder));
+            self.remainder = remainder;
+            if word.chars().any(char::is_alphanumeric) {
+                return Some(word);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remainder.len()))
+    }
+}
+
+#[unstable(feature = "str_segmentation", issue = "none")]
+impl FusedIterator for UnicodeWords<'_> {}
@@ -70,9 +70,17 @@
 #[stable(feature = "split_inclusive", since = "1.51.0")]
 pub use iter::SplitInclusive;
 
+#[unstable(feature = "str_segmentation", issue = "none")]
+pub use iter::{Graphemes, UnicodeWords};
+
 #[unstable(feature = "str_internals", issue = "none")]
 pub use validations::{next_code_point, utf8_char_width};
 
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+#[unstable(feature = "str_internals", issue = "none")]
+#[doc(hidden)]
+pub use validations::enable_x86_runtime_features;
+
 use iter::MatchIndicesInternal;
 use iter::SplitInternal;
 use iter::{MatchesInternal, SplitNInternal};
@@ -723,8 +731,8 @@ pub fn split_at_mut(&mut self, mid: usize) -> (&mut str, &mut str) {
     ///
     /// It's important to remember that [`char`] represents a Unicode Scalar
     /// Value, and might not match your idea of what a 'character' is. Iteration
-    /// over grapheme clusters may be what you actually want. This functionality
-    /// is not provided by Rust's standard library, check crates.io instead.
+    /// over grapheme clusters may be what you actually want, see
+    /// [`graphemes`](str::graphemes).
     ///
     /// # Examples
     ///
@@ -952,6 +960,76 @@ pub fn split_ascii_whitespace(&self) -> SplitAsciiWhitespace<'_> {
         SplitAsciiWhitespace { inner }
     }
 
+    /// Returns an iterator over the extended grapheme clusters of a string slice.
+    ///
+    /// A grapheme cluster is what a user would think of as a single character,
+    /// which may be made up of several [`char`]s, like a letter followed by
+    /// combining accents, a flag made up of two regional indicators, or a
+    /// sequence of emoji joined by zero width joiners. The clusters are found
+    /// according to the rules in [Unicode Standard Annex #29][uax29].
+    ///
+    /// Concatenating the clusters gives back the original string slice.
+    ///
+    /// [`char`]: prim@char
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(str_segmentation)]
+    ///
+    /// let graphemes: Vec<&str> = "y\u{306}es".graphemes().collect();
+    /// assert_eq!(graphemes, ["y\u{306}", "e", "s"]);
+    ///
+    /// // A family emoji, made up of four emoji and three zero width joiners.
+    /// let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+    /// assert_eq!(family.chars().count(), 7);
+    /// assert_eq!(family.graphemes().count(), 1);
+    ///
+    /// // "\r\n" is a single grapheme cluster.
+    /// let graphemes: Vec<&str> = "a\r\nb".graphemes().collect();
+    /// assert_eq!(graphemes, ["a", "\r\n", "b"]);
+    /// ```
+    #[must_use = "this returns the split string as an iterator, \
+                  without modifying the original"]
+    #[unstable(feature = "str_segmentation", issue = "none")]
+    #[inline]
+    pub fn graphemes(&self) -> Graphemes<'_> {
+        Graphemes { remainder: self }
+    }
+
+    /// Returns an iterator over the words of a string slice.
+    ///
+    /// The string slice is split at the word boundaries described in
+    /// [Unicode Standard Annex #29][uax29], and only the pieces that contain an
+    /// [alphanumeric] character are returned. Whitespace and punctuation
+    /// between the words is skipped, while punctuation that is part of a word,
+    /// like the apostrophe in "can't" or the decimal point in "3.14", is kept.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    /// [alphanumeric]: char::is_alphanumeric
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(str_segmentation)]
+    ///
+    /// let text = "The quick (\"brown\") fox can't jump 32.3 feet, right?";
+    /// let words: Vec<&str> = text.unicode_words().collect();
+    /// assert_eq!(words, ["The", "quick", "brown", "fox", "can't", "jump", "32.3", "feet", "right"]);
+    /// ```
+    #[must_use = "this returns the split string as an iterator, \
+                  without modifying the original"]
+    #[unstable(feature = "str_segmentation", issue = "none")]
+    #[inline]
+    pub fn unicode_words(&self) -> UnicodeWords<'_> {
+        UnicodeWords { remainder: self }
+    }
+
     /// An iterator over the lines of a string, as string slices.
     ///
     /// Lines are split at line endings that are either newlines (`\n`) or
@@ -1,9 +1,19 @@
 //! Operations related to UTF-8 validation.
 
+use crate::intrinsics;
 use crate::mem;
 
 use super::Utf8Error;
 
+#[cfg(any(
+    all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+    all(target_arch = "aarch64", target_endian = "little", target_feature = "neon")
+))]
+mod simd;
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+pub use simd::enable_x86_runtime_features;
+
 /// Returns the initial codepoint accumulator for the first byte.
 /// The first byte is special, only want bottom 5 bits for width 2, 4 bits
 /// for width 3, and 3 bits for width 4.
@@ -125,6 +135,31 @@ const fn contains_nonascii(x: usize) -> bool {
 #[inline(always)]
 #[rustc_const_unstable(feature = "str_internals", issue = "none")]
 pub(super) const fn run_utf8_validation(v: &[u8]) -> Result<(), Utf8Error> {
+    // SAFETY: Both implementations return the same result for every input.
+    unsafe {
+        intrinsics::const_eval_select((v,), run_utf8_validation_scalar, run_utf8_validation_rt)
+    }
+}
+
+/// Runtime implementation of `run_utf8_validation`, which uses SIMD instructions if the target
+/// has the ones needed by the `simd` module, or more of them if `std` detected them at startup.
+#[inline]
+fn run_utf8_validation_rt(v: &[u8]) -> Result<(), Utf8Error> {
+    #[cfg(any(
+        all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"),
+        all(target_arch = "aarch64", target_endian = "little", target_feature = "neon")
+    ))]
+    if v.len() >= simd::MIN_LEN {
+        return simd::run_utf8_validation(v);
+    }
+
+    run_utf8_validation_scalar(v)
+}
+
+/// Scalar implementation of `run_utf8_validation`, which is also used in const contexts.
+#[inline(always)]
+#[rustc_const_unstable(feature = "str_internals", issue = "none")]
+const fn run_utf8_validation_scalar(v: &[u8]) -> Result<(), Utf8Error> {
     let mut index = 0;
     let len = v.len();
 
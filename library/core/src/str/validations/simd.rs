@@ -0,0 +1,438 @@
+//! UTF-8 validation using SIMD instructions.
+//!
+//! This is an implementation of the "lookup" algorithm from [Validating UTF-8 In Less Than One
+//! Instruction Per Byte][paper] by John Keiser and Daniel Lemire. Every byte is classified together
+//! with the byte preceding it by three 16-entry table lookups, which detects all errors that can
+//! be seen in a pair of bytes. The remaining errors, missing or excess continuation bytes of 3- and
+//! 4-byte sequences, are found by looking two and three bytes back.
+//!
+//! The table lookups are done with a byte shuffle instruction (`pshufb` on x86, `tbl` on AArch64)
+//! where the target is known to have one at compile time. On x86 that means SSSE3, and blocks of 32
+//! bytes are used if AVX2 is available as well. Every x86_64 target has SSE2, which has no byte
+//! shuffle, so without SSSE3 the tables are looked up with comparisons instead.
+//!
+//! `core` can't detect CPU features at runtime, but `std` can: on x86 it calls
+//! [`enable_x86_runtime_features`] at startup, after which the SSSE3 and AVX2 implementations are
+//! used even if the target doesn't enable them.
+//!
+//! The SIMD code only finds out whether a block contains an error, not where. Errors are located
+//! by running the scalar implementation from the start of the character that was being validated
+//! when the error was found, so the results are identical to the scalar path.
+//!
+//! [paper]: https://arxiv.org/abs/2010.03090
+
+use crate::simd::{
+    LaneCount, Simd, SimdPartialEq, SimdPartialOrd, SimdUint, SupportedLaneCount, Swizzle2, Which,
+};
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+use crate::sync::atomic::{AtomicU8, Ordering};
+
+use super::{run_utf8_validation_scalar, utf8_is_cont_byte, Utf8Error};
+
+/// Number of bytes in a block of the implementation the target is compiled for.
+#[cfg(target_feature = "avx2")]
+const LANES: usize = 32;
+#[cfg(not(target_feature = "avx2"))]
+const LANES: usize = 16;
+
+/// Table lookup of the implementation the target is compiled for.
+#[cfg(any(target_feature = "ssse3", target_feature = "neon"))]
+type DefaultLookup = Swizzle;
+#[cfg(not(any(target_feature = "ssse3", target_feature = "neon")))]
+type DefaultLookup = Compare;
+
+/// Inputs shorter than this are validated by the scalar implementation directly.
+pub(super) const MIN_LEN: usize = 2 * LANES;
+
+// Error classes of a pair of bytes, one bit each. A pair is invalid if it belongs to a class in
+// each of the three lookup tables, which is computed by and-ing the results of the lookups.
+//
+// 11______ 0_______
+// 11______ 11______
+const TOO_SHORT: u8 = 1 << 0;
+// 0_______ 10______
+const TOO_LONG: u8 = 1 << 1;
+// 11100000 100_____
+const OVERLONG_3: u8 = 1 << 2;
+// 11110100 1001____
+// 11110100 101_____
+// 11110101 1001____
+// 11110101 101_____
+// 1111011_ 1001____
+// 1111011_ 101_____
+// 11111___ 1001____
+// 11111___ 101_____
+const TOO_LARGE: u8 = 1 << 3;
+// 11101101 101_____
+const SURROGATE: u8 = 1 << 4;
+// 1100000_ 10______
+const OVERLONG_2: u8 = 1 << 5;
+// 11110101 1000____
+// 1111011_ 1000____
+// 11111___ 1000____
+const TOO_LARGE_1000: u8 = 1 << 6;
+// 11110000 1000____
+const OVERLONG_4: u8 = 1 << 6;
+// 10______ 10______
+const TWO_CONTS: u8 = 1 << 7;
+// Classes that don't depend on the low nibble of the first byte.
+const CARRY: u8 = TOO_SHORT | TOO_LONG | TWO_CONTS;
+
+/// Error classes by the high nibble of the first byte of a pair.
+const BYTE_1_HIGH: [u8; 16] = [
+    // 0_______ ________ <ASCII in byte 1>
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    TOO_LONG,
+    // 10______ ________ <continuation in byte 1>
+    TWO_CONTS,
+    TWO_CONTS,
+    TWO_CONTS,
+    TWO_CONTS,
+    // 1100____ ________ <two byte lead in byte 1>
+    TOO_SHORT | OVERLONG_2,
+    // 1101____ ________ <two byte lead in byte 1>
+    TOO_SHORT,
+    // 1110____ ________ <three byte lead in byte 1>
+    TOO_SHORT | OVERLONG_3 | SURROGATE,
+    // 1111____ ________ <four+ byte lead in byte 1>
+    TOO_SHORT | TOO_LARGE | TOO_LARGE_1000 | OVERLONG_4,
+];
+
+/// Error classes by the low nibble of the first byte of a pair.
+const BYTE_1_LOW: [u8; 16] = [
+    // ____0000 ________
+    CARRY | OVERLONG_3 | OVERLONG_2 | OVERLONG_4,
+    // ____0001 ________
+    CARRY | OVERLONG_2,
+    // ____001_ ________
+    CARRY,
+    CARRY,
+    // ____0100 ________
+    CARRY | TOO_LARGE,
+    // ____0101 ________
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    // ____011_ ________
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    // ____1___ ________
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    // ____1101 ________
+    CARRY | TOO_LARGE | TOO_LARGE_1000 | SURROGATE,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+    CARRY | TOO_LARGE | TOO_LARGE_1000,
+];
+
+/// Error classes by the high nibble of the second byte of a pair.
+const BYTE_2_HIGH: [u8; 16] = [
+    // ________ 0_______ <ASCII in byte 2>
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    // ________ 1000____
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE_1000 | OVERLONG_4,
+    // ________ 1001____
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | OVERLONG_3 | TOO_LARGE,
+    // ________ 101_____
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    TOO_LONG | OVERLONG_2 | TWO_CONTS | SURROGATE | TOO_LARGE,
+    // ________ 11______
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+    TOO_SHORT,
+];
+
+/// A way of looking up each lane of a block in a 16-entry table.
+trait Lookup<const N: usize>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    /// Looks up each lane of `nibbles`, which must be less than 16, in `table`.
+    fn lookup(table: [u8; 16], nibbles: Simd<u8, N>) -> Simd<u8, N>;
+}
+
+/// Looks up tables with the byte shuffle that the target is compiled for.
+#[cfg(any(target_feature = "ssse3", target_feature = "neon"))]
+struct Swizzle;
+
+#[cfg(any(target_feature = "ssse3", target_feature = "neon"))]
+impl<const N: usize> Lookup<N> for Swizzle
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline(always)]
+    fn lookup(table: [u8; 16], nibbles: Simd<u8, N>) -> Simd<u8, N> {
+        // The indices are nibbles, so only the first 16 lanes are ever selected.
+        let mut lanes = [0; N];
+        lanes[..16].copy_from_slice(&table);
+        Simd::from_array(lanes).swizzle_dyn(nibbles)
+    }
+}
+
+/// Looks up tables with comparisons and selects, for targets without a byte shuffle.
+///
+/// Every entry of `table` that differs from the one before it costs a comparison and a select.
+/// The tables are constants, so the loop unrolls into 4 to 6 of each.
+#[cfg(not(any(target_feature = "ssse3", target_feature = "neon")))]
+struct Compare;
+
+#[cfg(not(any(target_feature = "ssse3", target_feature = "neon")))]
+impl<const N: usize> Lookup<N> for Compare
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    #[inline(always)]
+    fn lookup(table: [u8; 16], nibbles: Simd<u8, N>) -> Simd<u8, N> {
+        let mut result = Simd::splat(table[0]);
+        for i in 1..16 {
+            if table[i] != table[i - 1] {
+                let selected = nibbles.simd_ge(Simd::splat(i as u8));
+                result = selected.select(Simd::splat(table[i]), result);
+            }
+        }
+        result
+    }
+}
+
+/// Looks up tables with `pshufb`, which the target may not be compiled for.
+///
+/// Must only be used in functions that enable SSSE3, or AVX2 for blocks of 32 bytes.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+struct Pshufb;
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+impl Lookup<16> for Pshufb {
+    #[inline(always)]
+    fn lookup(table: [u8; 16], nibbles: Simd<u8, 16>) -> Simd<u8, 16> {
+        #[cfg(target_arch = "x86")]
+        use crate::arch::x86::_mm_shuffle_epi8;
+        #[cfg(target_arch = "x86_64")]
+        use crate::arch::x86_64::_mm_shuffle_epi8;
+
+        // SAFETY: the caller enables SSSE3.
+        unsafe { _mm_shuffle_epi8(Simd::from_array(table).into(), nibbles.into()).into() }
+    }
+}
+
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+impl Lookup<32> for Pshufb {
+    #[inline(always)]
+    fn lookup(table: [u8; 16], nibbles: Simd<u8, 32>) -> Simd<u8, 32> {
+        #[cfg(target_arch = "x86")]
+        use crate::arch::x86::_mm256_shuffle_epi8;
+        #[cfg(target_arch = "x86_64")]
+        use crate::arch::x86_64::_mm256_shuffle_epi8;
+
+        // `vpshufb` shuffles each 128-bit half separately, so both halves need the whole table.
+        let mut lanes = [0; 32];
+        lanes[..16].copy_from_slice(&table);
+        lanes[16..].copy_from_slice(&table);
+        // SAFETY: the caller enables AVX2.
+        unsafe { _mm256_shuffle_epi8(Simd::from_array(lanes).into(), nibbles.into()).into() }
+    }
+}
+
+/// Selects the lanes of the concatenation of two blocks that start `K` lanes before the second.
+struct Prev<const K: usize>;
+
+impl<const N: usize, const K: usize> Swizzle2<N, N> for Prev<K> {
+    const INDEX: [Which; N] = {
+        let mut index = [Which::First(0); N];
+        let mut i = 0;
+        while i < N {
+            index[i] = if i < K { Which::First(N - K + i) } else { Which::Second(i - K) };
+            i += 1;
+        }
+        index
+    };
+}
+
+/// Returns the bytes of `input` shifted by `K` lanes, with the last `K` bytes of `prev_input`
+/// shifted in.
+#[inline(always)]
+fn prev<const K: usize, const N: usize>(input: Simd<u8, N>, prev_input: Simd<u8, N>) -> Simd<u8, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    Prev::<K>::swizzle2(prev_input, input)
+}
+
+/// Returns a block that is non-zero if `input` contains an invalid sequence, given the block
+/// preceding it.
+#[inline(always)]
+fn check_block<const N: usize, L: Lookup<N>>(
+    input: Simd<u8, N>,
+    prev_input: Simd<u8, N>,
+) -> Simd<u8, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let low_nibble = Simd::splat(0x0F);
+
+    // Errors that can be seen in pairs of bytes.
+    let prev1 = prev::<1, N>(input, prev_input);
+    let byte_1_high = L::lookup(BYTE_1_HIGH, prev1 >> Simd::splat(4));
+    let byte_1_low = L::lookup(BYTE_1_LOW, prev1 & low_nibble);
+    let byte_2_high = L::lookup(BYTE_2_HIGH, input >> Simd::splat(4));
+    let special_cases = byte_1_high & byte_1_low & byte_2_high;
+
+    // Continuation bytes that must follow a 3- or 4-byte lead two or three bytes back. These were
+    // flagged as `TWO_CONTS` by the lookups above, so the two have to match exactly.
+    let prev2 = prev::<2, N>(input, prev_input);
+    let prev3 = prev::<3, N>(input, prev_input);
+    let is_third_byte = prev2.saturating_sub(Simd::splat(0b1110_0000 - 1));
+    let is_fourth_byte = prev3.saturating_sub(Simd::splat(0b1111_0000 - 1));
+    let must_be_continuation = (is_third_byte | is_fourth_byte)
+        .simd_ne(Simd::splat(0))
+        .select(Simd::splat(TWO_CONTS), Simd::splat(0));
+
+    must_be_continuation ^ special_cases
+}
+
+/// Returns a block that is non-zero if `input` ends with an incomplete multi-byte sequence.
+#[inline(always)]
+fn is_incomplete<const N: usize>(input: Simd<u8, N>) -> Simd<u8, N>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let mut max = [u8::MAX; N];
+    max[N - 3] = 0b1111_0000 - 1;
+    max[N - 2] = 0b1110_0000 - 1;
+    max[N - 1] = 0b1100_0000 - 1;
+    input.saturating_sub(Simd::from_array(max))
+}
+
+/// Features found by `std` at runtime, which the target isn't compiled for.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+static RUNTIME_FEATURES: AtomicU8 = AtomicU8::new(0);
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+const SSSE3: u8 = 1;
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+const AVX2: u8 = 2;
+
+/// Makes UTF-8 validation use SSSE3 or AVX2 instructions even if the target isn't compiled for
+/// them.
+///
+/// # Safety
+///
+/// The CPU must support every feature that is passed as `true`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[unstable(feature = "str_internals", issue = "none")]
+#[doc(hidden)]
+pub unsafe fn enable_x86_runtime_features(ssse3: bool, avx2: bool) {
+    #[cfg(not(target_feature = "avx2"))]
+    {
+        let features = if avx2 {
+            AVX2
+        } else if ssse3 {
+            SSSE3
+        } else {
+            return;
+        };
+        RUNTIME_FEATURES.store(features, Ordering::Relaxed);
+    }
+    #[cfg(target_feature = "avx2")]
+    let _ = (ssse3, avx2);
+}
+
+/// Walks through `v` checking that it's a valid UTF-8 sequence,
+/// returning `Ok(())` in that case, or, if it is invalid, `Err(err)`.
+///
+/// `v` should be at least `MIN_LEN` bytes long.
+pub(super) fn run_utf8_validation(v: &[u8]) -> Result<(), Utf8Error> {
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+    match RUNTIME_FEATURES.load(Ordering::Relaxed) {
+        // SAFETY: `enable_x86_runtime_features` requires the CPU to support these.
+        AVX2 => return unsafe { run_utf8_validation_avx2(v) },
+        SSSE3 => return unsafe { run_utf8_validation_ssse3(v) },
+        _ => {}
+    }
+
+    validate::<LANES, DefaultLookup>(v)
+}
+
+/// `run_utf8_validation` with SSSE3 instructions.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+#[target_feature(enable = "ssse3")]
+unsafe fn run_utf8_validation_ssse3(v: &[u8]) -> Result<(), Utf8Error> {
+    validate::<16, Pshufb>(v)
+}
+
+/// `run_utf8_validation` with AVX2 instructions.
+#[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_feature = "avx2")))]
+#[target_feature(enable = "avx2")]
+unsafe fn run_utf8_validation_avx2(v: &[u8]) -> Result<(), Utf8Error> {
+    validate::<32, Pshufb>(v)
+}
+
+/// Validates `v` in blocks of `N` bytes, looking up tables with `L`.
+#[inline(always)]
+fn validate<const N: usize, L: Lookup<N>>(v: &[u8]) -> Result<(), Utf8Error>
+where
+    LaneCount<N>: SupportedLaneCount,
+{
+    let len = v.len();
+    let mut index = 0;
+    let mut prev_input = Simd::splat(0);
+    let mut prev_incomplete = Simd::splat(0);
+
+    while index + N <= len {
+        // SAFETY: the loop condition guarantees that `N` bytes are readable at `index`.
+        let input = unsafe { v.as_ptr().add(index).cast::<Simd<u8, N>>().read_unaligned() };
+
+        let error = if !(input & Simd::splat(0x80)).simd_ne(Simd::splat(0)).any() {
+            // An ASCII block is valid by itself, but it can't follow an incomplete sequence.
+            let error = prev_incomplete;
+            prev_incomplete = Simd::splat(0);
+            error
+        } else {
+            let error = check_block::<N, L>(input, prev_input);
+            prev_incomplete = is_incomplete(input);
+            error
+        };
+
+        if error.simd_ne(Simd::splat(0)).any() {
+            break;
+        }
+
+        prev_input = input;
+        index += N;
+    }
+
+    // Everything before `index` is valid, except for a sequence that may have been cut off at the
+    // end of the last block. Either the block at `index` contains an error, or fewer than `N` bytes
+    // are left. In both cases the rest is validated by the scalar implementation, starting at the
+    // lead byte of the last sequence that began before `index`, if it might not be complete.
+    let mut start = index;
+    for back in 1..=3 {
+        if index < back {
+            break;
+        }
+        if !utf8_is_cont_byte(v[index - back]) {
+            start = index - back;
+            break;
+        }
+    }
+
+    match run_utf8_validation_scalar(&v[start..]) {
+        Ok(()) => Ok(()),
+        Err(Utf8Error { valid_up_to, error_len }) => {
+            Err(Utf8Error { valid_up_to: start + valid_up_to, error_len })
+        }
+    }
+}
@@ -2,6 +2,7 @@
 #![allow(missing_docs)]
 
 pub(crate) mod printable;
+pub(crate) mod segmentation;
 mod unicode_data;
 
 /// The version of [Unicode](https://www.unicode.org/) that the Unicode parts of
@@ -24,8 +25,11 @@
 
 pub(crate) use unicode_data::alphabetic::lookup as Alphabetic;
 pub(crate) use unicode_data::cc::lookup as Cc;
+pub(crate) use unicode_data::extended_pictographic::lookup as Extended_Pictographic;
+pub(crate) use unicode_data::grapheme_cluster_break::lookup as Grapheme_Cluster_Break;
 pub(crate) use unicode_data::grapheme_extend::lookup as Grapheme_Extend;
 pub(crate) use unicode_data::lowercase::lookup as Lowercase;
 pub(crate) use unicode_data::n::lookup as N;
 pub(crate) use unicode_data::uppercase::lookup as Uppercase;
 pub(crate) use unicode_data::white_space::lookup as White_Space;
+pub(crate) use unicode_data::word_break::lookup as Word_Break;
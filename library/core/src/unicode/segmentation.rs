@@ -0,0 +1,172 @@
+//! Text segmentation as described in [Unicode Standard Annex #29][uax29].
+//!
+//! The boundaries are found by walking forwards through the text and applying the rules to each
+//! pair of adjacent characters. The rules that look further back are handled by keeping track of
+//! the characters seen so far, and the ones that look further ahead by peeking at the rest of the
+//! text.
+//!
+//! [uax29]: https://www.unicode.org/reports/tr29/
+
+use super::unicode_data::grapheme_cluster_break::GraphemeClusterBreak;
+use super::unicode_data::word_break::WordBreak;
+use super::{Extended_Pictographic, Grapheme_Cluster_Break, Word_Break};
+
+#[inline]
+fn grapheme_cluster_break(c: char) -> GraphemeClusterBreak {
+    // The only ASCII characters with a special value are the control characters.
+    if c.is_ascii() {
+        match c {
+            '\r' => GraphemeClusterBreak::CR,
+            '\n' => GraphemeClusterBreak::LF,
+            _ if c.is_ascii_control() => GraphemeClusterBreak::Control,
+            _ => GraphemeClusterBreak::Other,
+        }
+    } else {
+        Grapheme_Cluster_Break(c)
+    }
+}
+
+#[inline]
+fn is_extended_pictographic(c: char) -> bool {
+    !c.is_ascii() && Extended_Pictographic(c)
+}
+
+/// Returns the length in bytes of the extended grapheme cluster at the start of `s`, or 0 if `s`
+/// is empty.
+pub(crate) fn grapheme_len(s: &str) -> usize {
+    use GraphemeClusterBreak::*;
+
+    let mut chars = s.char_indices();
+    let Some((_, first)) = chars.next() else {
+        return 0;
+    };
+    let mut prev = grapheme_cluster_break(first);
+    // Whether the text so far ends in `Extended_Pictographic Extend*`.
+    let mut pictographic = is_extended_pictographic(first);
+    // Whether the text so far ends in `Extended_Pictographic Extend* ZWJ`.
+    let mut pictographic_zwj = false;
+    // The number of regional indicators the text so far ends in.
+    let mut regional_indicators = (prev == RegionalIndicator) as usize;
+
+    for (i, c) in chars {
+        let next = grapheme_cluster_break(c);
+        let next_pictographic = is_extended_pictographic(c);
+        let is_break = match (prev, next) {
+            // GB3
+            (CR, LF) => false,
+            // GB4 and GB5
+            (Control | CR | LF, _) | (_, Control | CR | LF) => true,
+            // GB6, GB7 and GB8
+            (L, L | V | LV | LVT) | (LV | V, V | T) | (LVT | T, T) => false,
+            // GB9, GB9a and GB9b
+            (_, Extend | ZWJ | SpacingMark) | (Prepend, _) => false,
+            // GB11
+            (ZWJ, _) if pictographic_zwj && next_pictographic => false,
+            // GB12 and GB13
+            (RegionalIndicator, RegionalIndicator) => regional_indicators % 2 == 0,
+            // GB999
+            _ => true,
+        };
+        if is_break {
+            return i;
+        }
+
+        pictographic_zwj = next == ZWJ && pictographic;
+        pictographic = next_pictographic || (next == Extend && pictographic);
+        regional_indicators = if next == RegionalIndicator { regional_indicators + 1 } else { 0 };
+        prev = next;
+    }
+    s.len()
+}
+
+/// Returns the `Word_Break` value of the first character of `s` that isn't ignored by WB4.
+fn next_word_break(s: &str) -> Option<WordBreak> {
+    s.chars()
+        .map(Word_Break)
+        .find(|wb| !matches!(wb, WordBreak::Extend | WordBreak::Format | WordBreak::ZWJ))
+}
+
+/// Returns the length in bytes of the text up to the first word boundary in `s`, or 0 if `s` is
+/// empty.
+pub(crate) fn word_len(s: &str) -> usize {
+    use WordBreak::*;
+
+    let mut chars = s.char_indices();
+    let Some((_, first)) = chars.next() else {
+        return 0;
+    };
+    // The value of the previous character, for the rules that apply before WB4.
+    let mut prev_raw = Word_Break(first);
+    // The values of the last and second to last characters that aren't ignored by WB4.
+    let mut prev = prev_raw;
+    let mut prev_prev = None;
+    // The number of regional indicators the text so far ends in, ignoring WB4 characters.
+    let mut regional_indicators = (prev == RegionalIndicator) as usize;
+
+    for (i, c) in chars {
+        let next = Word_Break(c);
+        let lookahead = || next_word_break(&s[i + c.len_utf8()..]);
+        let is_break = match (prev_raw, next) {
+            // WB3
+            (CR, LF) => false,
+            // WB3a and WB3b
+            (Newline | CR | LF, _) | (_, Newline | CR | LF) => true,
+            // WB3c
+            (ZWJ, _) if is_extended_pictographic(c) => false,
+            // WB3d
+            (WSegSpace, WSegSpace) => false,
+            // WB4
+            (_, Extend | Format | ZWJ) => false,
+            _ => match (prev, next) {
+                // WB5
+                (ALetter | HebrewLetter, ALetter | HebrewLetter) => false,
+                // WB6
+                (ALetter | HebrewLetter, MidLetter | MidNumLet | SingleQuote)
+                    if matches!(lookahead(), Some(ALetter | HebrewLetter)) =>
+                {
+                    false
+                }
+                // WB7
+                (MidLetter | MidNumLet | SingleQuote, ALetter | HebrewLetter)
+                    if matches!(prev_prev, Some(ALetter | HebrewLetter)) =>
+                {
+                    false
+                }
+                // WB7a
+                (HebrewLetter, SingleQuote) => false,
+                // WB7b
+                (HebrewLetter, DoubleQuote) if lookahead() == Some(HebrewLetter) => false,
+                // WB7c
+                (DoubleQuote, HebrewLetter) if prev_prev == Some(HebrewLetter) => false,
+                // WB8, WB9 and WB10
+                (ALetter | HebrewLetter | Numeric, ALetter | HebrewLetter | Numeric) => false,
+                // WB11
+                (MidNum | MidNumLet | SingleQuote, Numeric) if prev_prev == Some(Numeric) => false,
+                // WB12
+                (Numeric, MidNum | MidNumLet | SingleQuote) if lookahead() == Some(Numeric) => {
+                    false
+                }
+                // WB13, WB13a and WB13b
+                (Katakana, Katakana)
+                | (ALetter | HebrewLetter | Numeric | Katakana | ExtendNumLet, ExtendNumLet)
+                | (ExtendNumLet, ALetter | HebrewLetter | Numeric | Katakana) => false,
+                // WB15 and WB16
+                (RegionalIndicator, RegionalIndicator) => regional_indicators % 2 == 0,
+                // WB999
+                _ => true,
+            },
+        };
+        if is_break {
+            return i;
+        }
+
+        prev_raw = next;
+        if !matches!(next, Extend | Format | ZWJ) {
+            prev_prev = Some(prev);
+            prev = next;
+            regional_indicators =
+                if next == RegionalIndicator { regional_indicators + 1 } else { 0 };
+        }
+    }
+    s.len()
+}
@@ -277,6 +277,29 @@ pub fn lookup(c: char) -> bool {
     }
 }
 
+#[rustfmt::skip]
+pub mod extended_pictographic {
+    static SHORT_OFFSET_RUNS: [u32; 15] = [
+        8252, 10494746, 35662132, 174074629, 178270256, 195048087, 203550720, 211939584, 214037499,
+        268563774, 272758352, 276953856, 323091456, 325189630, 328400894,
+    ];
+    static OFFSETS: [u8; 157] = [
+        169, 1, 4, 1, 0, 1, 12, 1, 216, 1, 22, 1, 90, 6, 15, 2, 0, 2, 12, 1, 95, 1, 70, 1, 25, 11,
+        4, 3, 199, 1, 231, 2, 10, 1, 9, 1, 58, 4, 1, 6, 1, 12, 1, 114, 10, 118, 2, 11, 1, 1, 1, 1,
+        6, 1, 3, 1, 6, 1, 10, 2, 15, 1, 2, 1, 4, 1, 1, 1, 4, 3, 1, 1, 11, 5, 45, 3, 9, 1, 14, 1, 14,
+        1, 0, 2, 0, 3, 19, 2, 51, 1, 4, 1, 0, 1, 12, 1, 0, 1, 1, 1, 0, 0, 13, 3, 31, 1, 60, 6, 12,
+        2, 14, 1, 2, 10, 18, 57, 27, 15, 10, 1, 20, 1, 2, 9, 1, 4, 9, 0, 5, 0, 8, 0, 48, 128, 116,
+        12, 85, 43, 12, 4, 56, 8, 10, 6, 40, 8, 30, 82, 12, 47, 1, 10, 1, 0, 0, 0, 0,
+    ];
+    pub fn lookup(c: char) -> bool {
+        super::skip_search(
+            c as u32,
+            &SHORT_OFFSET_RUNS,
+            &OFFSETS,
+        )
+    }
+}
+
 #[rustfmt::skip]
 pub mod grapheme_extend {
     static SHORT_OFFSET_RUNS: [u32; 33] = [
@@ -1381,3 +1404,453 @@ pub mod conversions {
         ['\u{544}', '\u{53d}', '\u{0}'],
     ];
 }
+
+#[rustfmt::skip]
+pub mod grapheme_cluster_break {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum GraphemeClusterBreak {
+        Other,
+        CR,
+        Control,
+        Extend,
+        L,
+        LF,
+        LV,
+        LVT,
+        Prepend,
+        RegionalIndicator,
+        SpacingMark,
+        T,
+        V,
+        ZWJ,
+    }
+
+    static VALUES: [GraphemeClusterBreak; 14] = [
+        GraphemeClusterBreak::Other, GraphemeClusterBreak::CR, GraphemeClusterBreak::Control,
+        GraphemeClusterBreak::Extend, GraphemeClusterBreak::L, GraphemeClusterBreak::LF,
+        GraphemeClusterBreak::LV, GraphemeClusterBreak::LVT, GraphemeClusterBreak::Prepend,
+        GraphemeClusterBreak::RegionalIndicator, GraphemeClusterBreak::SpacingMark,
+        GraphemeClusterBreak::T, GraphemeClusterBreak::V, GraphemeClusterBreak::ZWJ,
+    ];
+    static RUNS: [u32; 1700] = [
+        2, 2565, 2818, 3329, 3586, 8192, 32514, 40960, 44290, 44544, 196611, 225280, 295683,
+        297472, 364803, 376320, 376579, 376832, 377091, 377600, 377859, 378368, 378627, 378880,
+        393224, 394752, 397315, 400128, 400386, 400640, 412419, 417792, 421891, 422144, 448003,
+        449800, 450048, 450307, 451840, 452355, 452864, 453123, 454144, 462600, 462848, 463107,
+        463360, 471043, 477952, 501251, 504064, 518915, 521216, 523523, 523776, 529923, 530944,
+        531203, 533504, 533763, 534528, 534787, 536064, 547075, 547840, 561160, 561664, 563203,
+        565248, 576003, 582152, 582403, 590602, 590848, 604675, 604938, 605187, 605440, 605706,
+        606467, 608522, 609539, 609802, 610304, 610563, 612352, 614915, 615424, 622851, 623114,
+        623616, 637955, 638208, 638467, 638730, 639235, 640256, 640778, 641280, 641802, 642307,
+        642560, 644867, 645120, 647683, 648192, 654851, 655104, 655619, 656138, 656384, 670723,
+        670976, 671242, 672003, 672512, 673539, 674048, 674563, 675328, 676099, 676352, 684035,
+        684544, 685315, 685568, 688387, 688906, 689152, 703491, 703744, 704010, 704771, 706048,
+        706307, 706826, 707072, 707338, 707843, 708096, 713219, 713728, 719363, 720896, 721155,
+        721418, 721920, 736259, 736512, 736771, 737290, 737539, 738560, 739082, 739584, 740106,
+        740611, 740864, 742659, 743424, 745987, 746496, 754179, 754432, 769539, 769802, 770051,
+        770314, 770816, 771594, 772352, 772618, 773379, 773632, 775939, 776192, 786435, 786698,
+        787459, 787712, 801795, 802048, 802307, 803082, 804096, 804355, 805120, 805379, 806400,
+        808195, 808704, 811523, 812032, 819459, 819722, 820224, 834563, 834816, 835082, 835331,
+        835594, 836099, 836362, 836864, 837123, 837386, 837888, 838154, 838659, 839168, 840963,
+        841472, 844291, 844800, 848650, 848896, 851971, 852490, 852992, 867075, 867584, 867843,
+        868106, 868611, 869632, 869898, 870656, 870922, 871683, 871944, 872192, 874243, 874496,
+        877059, 877568, 884995, 885258, 885760, 903683, 903936, 904963, 905226, 905731, 906496,
+        906755, 907008, 907274, 909059, 909312, 913930, 914432, 930051, 930304, 930570, 930819,
+        932608, 935683, 937728, 962819, 963072, 963338, 963587, 965888, 968707, 970496, 989187,
+        989696, 996611, 996864, 997123, 997376, 997635, 997888, 998922, 999424, 1011971, 1015562,
+        1015811, 1017088, 1017347, 1017856, 1019139, 1021952, 1022211, 1031424, 1033731, 1033984,
+        1060099, 1061130, 1061379, 1062912, 1063171, 1063690, 1064195, 1064704, 1070602, 1071107,
+        1071616, 1072643, 1073408, 1077507, 1078528, 1081859, 1082112, 1082378, 1082627, 1083136,
+        1084675, 1084928, 1088771, 1089024, 1114116, 1138700, 1157131, 1179648, 1268995, 1269760,
+        1511939, 1512714, 1512960, 1520131, 1520650, 1520896, 1528323, 1528832, 1536515, 1537024,
+        1553411, 1553930, 1554179, 1555978, 1558019, 1558282, 1558787, 1561600, 1563907, 1564160,
+        1575683, 1576450, 1576707, 1576960, 1606915, 1607424, 1616131, 1616384, 1646595, 1647370,
+        1648387, 1648906, 1649664, 1650698, 1651203, 1651466, 1652995, 1653760, 1709827, 1710346,
+        1710851, 1711104, 1725706, 1725955, 1726218, 1726467, 1728256, 1728515, 1728768, 1729027,
+        1729280, 1729795, 1731850, 1733379, 1735936, 1736451, 1736704, 1748995, 1756928, 1769475,
+        1770506, 1770752, 1782787, 1784586, 1784835, 1785098, 1786371, 1786634, 1787136, 1796867,
+        1799168, 1802243, 1802762, 1803008, 1810698, 1810947, 1811978, 1812483, 1813002, 1813251,
+        1814016, 1828355, 1828618, 1828867, 1829386, 1830147, 1830410, 1830659, 1831434, 1831936,
+        1844234, 1846275, 1848330, 1848835, 1849344, 1888259, 1889024, 1889283, 1892618, 1892867,
+        1894656, 1895683, 1895936, 1897475, 1897728, 1898250, 1898499, 1899008, 1949699, 1966080,
+        2099970, 2100227, 2100493, 2100738, 2101248, 2107394, 2109184, 2121730, 2125824, 2150403,
+        2158848, 2944771, 2945536, 2981635, 2981888, 3006467, 3014656, 3156483, 3158016, 3184899,
+        3185408, 10907395, 10908416, 10908675, 10911232, 10919427, 10919936, 10940419, 10940928,
+        11010563, 11010816, 11011587, 11011840, 11012867, 11013120, 11019018, 11019523, 11020042,
+        11020288, 11021315, 11021568, 11042826, 11043328, 11056138, 11060227, 11060736, 11067395,
+        11072000, 11075331, 11075584, 11085315, 11087360, 11093763, 11096586, 11097088, 11100164,
+        11107584, 11108355, 11109130, 11109376, 11121411, 11121674, 11122179, 11123210, 11123715,
+        11124234, 11124992, 11134211, 11134464, 11151619, 11153162, 11153667, 11154186, 11154691,
+        11155200, 11158275, 11158528, 11160579, 11160842, 11161088, 11172867, 11173120, 11186179,
+        11186432, 11186691, 11187456, 11187971, 11188480, 11189763, 11190272, 11190531, 11190784,
+        11201290, 11201539, 11202058, 11202560, 11203850, 11204099, 11204352, 11264778, 11265283,
+        11265546, 11266051, 11266314, 11266816, 11267082, 11267331, 11267584, 11272198, 11272455,
+        11279366, 11279623, 11286534, 11286791, 11293702, 11293959, 11300870, 11301127, 11308038,
+        11308295, 11315206, 11315463, 11322374, 11322631, 11329542, 11329799, 11336710, 11336967,
+        11343878, 11344135, 11351046, 11351303, 11358214, 11358471, 11365382, 11365639, 11372550,
+        11372807, 11379718, 11379975, 11386886, 11387143, 11394054, 11394311, 11401222, 11401479,
+        11408390, 11408647, 11415558, 11415815, 11422726, 11422983, 11429894, 11430151, 11437062,
+        11437319, 11444230, 11444487, 11451398, 11451655, 11458566, 11458823, 11465734, 11465991,
+        11472902, 11473159, 11480070, 11480327, 11487238, 11487495, 11494406, 11494663, 11501574,
+        11501831, 11508742, 11508999, 11515910, 11516167, 11523078, 11523335, 11530246, 11530503,
+        11537414, 11537671, 11544582, 11544839, 11551750, 11552007, 11558918, 11559175, 11566086,
+        11566343, 11573254, 11573511, 11580422, 11580679, 11587590, 11587847, 11594758, 11595015,
+        11601926, 11602183, 11609094, 11609351, 11616262, 11616519, 11623430, 11623687, 11630598,
+        11630855, 11637766, 11638023, 11644934, 11645191, 11652102, 11652359, 11659270, 11659527,
+        11666438, 11666695, 11673606, 11673863, 11680774, 11681031, 11687942, 11688199, 11695110,
+        11695367, 11702278, 11702535, 11709446, 11709703, 11716614, 11716871, 11723782, 11724039,
+        11730950, 11731207, 11738118, 11738375, 11745286, 11745543, 11752454, 11752711, 11759622,
+        11759879, 11766790, 11767047, 11773958, 11774215, 11781126, 11781383, 11788294, 11788551,
+        11795462, 11795719, 11802630, 11802887, 11809798, 11810055, 11816966, 11817223, 11824134,
+        11824391, 11831302, 11831559, 11838470, 11838727, 11845638, 11845895, 11852806, 11853063,
+        11859974, 11860231, 11867142, 11867399, 11874310, 11874567, 11881478, 11881735, 11888646,
+        11888903, 11895814, 11896071, 11902982, 11903239, 11910150, 11910407, 11917318, 11917575,
+        11924486, 11924743, 11931654, 11931911, 11938822, 11939079, 11945990, 11946247, 11953158,
+        11953415, 11960326, 11960583, 11967494, 11967751, 11974662, 11974919, 11981830, 11982087,
+        11988998, 11989255, 11996166, 11996423, 12003334, 12003591, 12010502, 12010759, 12017670,
+        12017927, 12024838, 12025095, 12032006, 12032263, 12039174, 12039431, 12046342, 12046599,
+        12053510, 12053767, 12060678, 12060935, 12067846, 12068103, 12075014, 12075271, 12082182,
+        12082439, 12089350, 12089607, 12096518, 12096775, 12103686, 12103943, 12110854, 12111111,
+        12118022, 12118279, 12125190, 12125447, 12132358, 12132615, 12139526, 12139783, 12146694,
+        12146951, 12153862, 12154119, 12161030, 12161287, 12168198, 12168455, 12175366, 12175623,
+        12182534, 12182791, 12189702, 12189959, 12196870, 12197127, 12204038, 12204295, 12211206,
+        12211463, 12218374, 12218631, 12225542, 12225799, 12232710, 12232967, 12239878, 12240135,
+        12247046, 12247303, 12254214, 12254471, 12261382, 12261639, 12268550, 12268807, 12275718,
+        12275975, 12282886, 12283143, 12290054, 12290311, 12297222, 12297479, 12304390, 12304647,
+        12311558, 12311815, 12318726, 12318983, 12325894, 12326151, 12333062, 12333319, 12340230,
+        12340487, 12347398, 12347655, 12354566, 12354823, 12361734, 12361991, 12368902, 12369159,
+        12376070, 12376327, 12383238, 12383495, 12390406, 12390663, 12397574, 12397831, 12404742,
+        12404999, 12411910, 12412167, 12419078, 12419335, 12426246, 12426503, 12433414, 12433671,
+        12440582, 12440839, 12447750, 12448007, 12454918, 12455175, 12462086, 12462343, 12469254,
+        12469511, 12476422, 12476679, 12483590, 12483847, 12490758, 12491015, 12497926, 12498183,
+        12505094, 12505351, 12512262, 12512519, 12519430, 12519687, 12526598, 12526855, 12533766,
+        12534023, 12540934, 12541191, 12548102, 12548359, 12555270, 12555527, 12562438, 12562695,
+        12569606, 12569863, 12576774, 12577031, 12583942, 12584199, 12591110, 12591367, 12598278,
+        12598535, 12605446, 12605703, 12612614, 12612871, 12619782, 12620039, 12626950, 12627207,
+        12634118, 12634375, 12641286, 12641543, 12648454, 12648711, 12655622, 12655879, 12662790,
+        12663047, 12669958, 12670215, 12677126, 12677383, 12684294, 12684551, 12691462, 12691719,
+        12698630, 12698887, 12705798, 12706055, 12712966, 12713223, 12720134, 12720391, 12727302,
+        12727559, 12734470, 12734727, 12741638, 12741895, 12748806, 12749063, 12755974, 12756231,
+        12763142, 12763399, 12770310, 12770567, 12777478, 12777735, 12784646, 12784903, 12791814,
+        12792071, 12798982, 12799239, 12806150, 12806407, 12813318, 12813575, 12820486, 12820743,
+        12827654, 12827911, 12834822, 12835079, 12841990, 12842247, 12849158, 12849415, 12856326,
+        12856583, 12863494, 12863751, 12870662, 12870919, 12877830, 12878087, 12884998, 12885255,
+        12892166, 12892423, 12899334, 12899591, 12906502, 12906759, 12913670, 12913927, 12920838,
+        12921095, 12928006, 12928263, 12935174, 12935431, 12942342, 12942599, 12949510, 12949767,
+        12956678, 12956935, 12963846, 12964103, 12971014, 12971271, 12978182, 12978439, 12985350,
+        12985607, 12992518, 12992775, 12999686, 12999943, 13006854, 13007111, 13014022, 13014279,
+        13021190, 13021447, 13028358, 13028615, 13035526, 13035783, 13042694, 13042951, 13049862,
+        13050119, 13057030, 13057287, 13064198, 13064455, 13071366, 13071623, 13078534, 13078791,
+        13085702, 13085959, 13092870, 13093127, 13100038, 13100295, 13107206, 13107463, 13114374,
+        13114631, 13121542, 13121799, 13128710, 13128967, 13135878, 13136135, 13143046, 13143303,
+        13150214, 13150471, 13157382, 13157639, 13164550, 13164807, 13171718, 13171975, 13178886,
+        13179143, 13186054, 13186311, 13193222, 13193479, 13200390, 13200647, 13207558, 13207815,
+        13214726, 13214983, 13221894, 13222151, 13229062, 13229319, 13236230, 13236487, 13243398,
+        13243655, 13250566, 13250823, 13257734, 13257991, 13264902, 13265159, 13272070, 13272327,
+        13279238, 13279495, 13286406, 13286663, 13293574, 13293831, 13300742, 13300999, 13307910,
+        13308167, 13315078, 13315335, 13322246, 13322503, 13329414, 13329671, 13336582, 13336839,
+        13343750, 13344007, 13350918, 13351175, 13358086, 13358343, 13365254, 13365511, 13372422,
+        13372679, 13379590, 13379847, 13386758, 13387015, 13393926, 13394183, 13401094, 13401351,
+        13408262, 13408519, 13415430, 13415687, 13422598, 13422855, 13429766, 13430023, 13436934,
+        13437191, 13444102, 13444359, 13451270, 13451527, 13458438, 13458695, 13465606, 13465863,
+        13472774, 13473031, 13479942, 13480199, 13487110, 13487367, 13494278, 13494535, 13501446,
+        13501703, 13508614, 13508871, 13515782, 13516039, 13522950, 13523207, 13530118, 13530375,
+        13537286, 13537543, 13544454, 13544711, 13551622, 13551879, 13558790, 13559047, 13565958,
+        13566215, 13573126, 13573383, 13580294, 13580551, 13587462, 13587719, 13594630, 13594887,
+        13601798, 13602055, 13608966, 13609223, 13616134, 13616391, 13623302, 13623559, 13630470,
+        13630727, 13637638, 13637895, 13644806, 13645063, 13651974, 13652231, 13659142, 13659399,
+        13666310, 13666567, 13673478, 13673735, 13680646, 13680903, 13687814, 13688071, 13694982,
+        13695239, 13702150, 13702407, 13709318, 13709575, 13716486, 13716743, 13723654, 13723911,
+        13730822, 13731079, 13737990, 13738247, 13745158, 13745415, 13752326, 13752583, 13759494,
+        13759751, 13766662, 13766919, 13773830, 13774087, 13780998, 13781255, 13788166, 13788423,
+        13795334, 13795591, 13802502, 13802759, 13809670, 13809927, 13816838, 13817095, 13824006,
+        13824263, 13831174, 13831431, 13838342, 13838599, 13845510, 13845767, 13852678, 13852935,
+        13859846, 13860103, 13867014, 13867271, 13874182, 13874439, 13881350, 13881607, 13888518,
+        13888775, 13895686, 13895943, 13902854, 13903111, 13910022, 13910279, 13917190, 13917447,
+        13924358, 13924615, 13931526, 13931783, 13938694, 13938951, 13945862, 13946119, 13953030,
+        13953287, 13960198, 13960455, 13967366, 13967623, 13974534, 13974791, 13981702, 13981959,
+        13988870, 13989127, 13996038, 13996295, 14003206, 14003463, 14010374, 14010631, 14017542,
+        14017799, 14024710, 14024967, 14031878, 14032135, 14039046, 14039303, 14046214, 14046471,
+        14053382, 14053639, 14060550, 14060807, 14067718, 14067975, 14074886, 14075143, 14082054,
+        14082311, 14089222, 14089479, 14096390, 14096647, 14103558, 14103815, 14110726, 14110983,
+        14117894, 14118151, 14125062, 14125319, 14132224, 14135308, 14141184, 14142219, 14154752,
+        16457219, 16457472, 16646147, 16650240, 16654339, 16658432, 16711426, 16711680, 16752131,
+        16752640, 16773122, 16776192, 16907523, 16907776, 16965635, 16965888, 17004035, 17005312,
+        17432835, 17433600, 17433859, 17434368, 17435651, 17436672, 17446915, 17447680, 17448707,
+        17448960, 17491203, 17491712, 17638403, 17639424, 17738499, 17739008, 17759491, 17760256,
+        17778179, 17780992, 17793539, 17794560, 17825802, 17826051, 17826314, 17826560, 17840131,
+        17843968, 17854467, 17854720, 17855235, 17855744, 17858307, 17859082, 17859328, 17870858,
+        17871619, 17872650, 17873155, 17873664, 17874184, 17874432, 17875459, 17875712, 17878280,
+        17878528, 17891331, 17892096, 17901315, 17902602, 17902851, 17904896, 17909002, 17909504,
+        17920771, 17921024, 17924099, 17924618, 17924864, 17937162, 17937923, 17940234, 17940736,
+        17941000, 17941504, 17942787, 17943808, 17944074, 17944323, 17944576, 17968138, 17968899,
+        17969674, 17970179, 17970442, 17970691, 17971200, 17972739, 17972992, 17973507, 17973760,
+        18013955, 18014218, 18014979, 18017024, 18022403, 18022922, 18023424, 18037507, 18038016,
+        18038275, 18038538, 18038787, 18039050, 18040064, 18040586, 18041088, 18041610, 18042368,
+        18044675, 18044928, 18047498, 18048000, 18048515, 18050304, 18051075, 18052352, 18101514,
+        18102275, 18104330, 18104835, 18105610, 18105859, 18106112, 18112003, 18112256, 18132995,
+        18133258, 18133763, 18135306, 18135555, 18135818, 18136323, 18136586, 18136835, 18137354,
+        18137603, 18138112, 18198275, 18198538, 18199043, 18200064, 18200586, 18201603, 18202122,
+        18202371, 18202880, 18209795, 18210304, 18231306, 18232067, 18234122, 18234627, 18234890,
+        18235139, 18235648, 18262787, 18263050, 18263299, 18263562, 18264067, 18265610, 18265859,
+        18266112, 18291971, 18292736, 18293251, 18294282, 18294531, 18295808, 18361354, 18362115,
+        18364426, 18364675, 18365184, 18427907, 18428170, 18429440, 18429706, 18430208, 18430723,
+        18431242, 18431491, 18431752, 18432010, 18432264, 18432522, 18432771, 18433024, 18469130,
+        18469891, 18470912, 18471427, 18471946, 18472963, 18473216, 18473994, 18474240, 18481411,
+        18483968, 18494211, 18495754, 18496008, 18496259, 18497280, 18499331, 18499584, 18501891,
+        18503434, 18503939, 18504704, 18514952, 18516483, 18519818, 18520067, 18520576, 18624266,
+        18624515, 18626304, 18626563, 18628106, 18628355, 18628608, 18649603, 18655232, 18655498,
+        18655747, 18657546, 18657795, 18658314, 18658563, 18659072, 18690307, 18691840, 18692611,
+        18692864, 18693123, 18693632, 18693891, 18695688, 18695939, 18696192, 18713098, 18714368,
+        18714627, 18715136, 18715402, 18715907, 18716170, 18716419, 18716672, 18805507, 18806026,
+        18806528, 18808835, 18809352, 18809610, 18809856, 18822154, 18822659, 18823936, 18824714,
+        18825219, 18825482, 18825731, 18825984, 20197378, 20201475, 20201728, 20203267, 20207104,
+        23785475, 23786752, 23801859, 23803648, 24071939, 24072192, 24072458, 24086528, 24088323,
+        24089344, 24110083, 24110336, 24113162, 24113664, 29138179, 29138688, 29138946, 29139968,
+        30343171, 30354944, 30355459, 30361344, 30500099, 30500362, 30500611, 30501376, 30502154,
+        30502403, 30503682, 30505731, 30507776, 30508291, 30510080, 30517763, 30518784, 30556675,
+        30557440, 31064067, 31078144, 31079171, 31091968, 31094019, 31094272, 31097859, 31098112,
+        31103747, 31105024, 31105283, 31109120, 31457283, 31459072, 31459331, 31463680, 31464195,
+        31465984, 31466243, 31466752, 31467011, 31468288, 31493891, 31494144, 31535107, 31536896,
+        31632899, 31633152, 31648771, 31649792, 31779843, 31780864, 32034819, 32036608, 32064515,
+        32066304, 32630281, 32636928, 32766723, 32768000, 234881026, 234889219, 234913794,
+        234946563, 235008002, 235929600,
+    ];
+
+    pub fn lookup(c: char) -> GraphemeClusterBreak {
+        // The first run starts at code point 0, so this can't underflow.
+        let idx = RUNS.partition_point(|&run| run >> 8 <= c as u32) - 1;
+        VALUES[(RUNS[idx] & 0xff) as usize]
+    }
+}
+
+#[rustfmt::skip]
+pub mod word_break {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum WordBreak {
+        Other,
+        ALetter,
+        CR,
+        DoubleQuote,
+        Extend,
+        ExtendNumLet,
+        Format,
+        HebrewLetter,
+        Katakana,
+        LF,
+        MidLetter,
+        MidNum,
+        MidNumLet,
+        Newline,
+        Numeric,
+        RegionalIndicator,
+        SingleQuote,
+        WSegSpace,
+        ZWJ,
+    }
+
+    static VALUES: [WordBreak; 19] = [
+        WordBreak::Other, WordBreak::ALetter, WordBreak::CR, WordBreak::DoubleQuote,
+        WordBreak::Extend, WordBreak::ExtendNumLet, WordBreak::Format, WordBreak::HebrewLetter,
+        WordBreak::Katakana, WordBreak::LF, WordBreak::MidLetter, WordBreak::MidNum,
+        WordBreak::MidNumLet, WordBreak::Newline, WordBreak::Numeric, WordBreak::RegionalIndicator,
+        WordBreak::SingleQuote, WordBreak::WSegSpace, WordBreak::ZWJ,
+    ];
+    static RUNS: [u32; 1832] = [
+        0, 2569, 2829, 3330, 3584, 8209, 8448, 8707, 8960, 10000, 10240, 11275, 11520, 11788,
+        12032, 12302, 14858, 15115, 15360, 16641, 23296, 24325, 24576, 24833, 31488, 34061, 34304,
+        43521, 43776, 44294, 44544, 46337, 46592, 46858, 47104, 47617, 47872, 49153, 55040, 55297,
+        63232, 63489, 186368, 187905, 196612, 225281, 226560, 226817, 227328, 227841, 228875,
+        229121, 229376, 230913, 231178, 231425, 232192, 232449, 232704, 232961, 238080, 238337,
+        259584, 259841, 295424, 295684, 297473, 339968, 340225, 349952, 350465, 351488, 351745,
+        352010, 352257, 362763, 363009, 363264, 364804, 376320, 376580, 376832, 377092, 377600,
+        377860, 378368, 378628, 378880, 380935, 387840, 388871, 389889, 390154, 390400, 393222,
+        394752, 396299, 396800, 397316, 400128, 400390, 400640, 401409, 412420, 417806, 420352,
+        420622, 420875, 421120, 421377, 421892, 422145, 447488, 447745, 448004, 449798, 450048,
+        450308, 451841, 452356, 452864, 453124, 454145, 454670, 457217, 457984, 458497, 458752,
+        462598, 462849, 463108, 463361, 471044, 477952, 478465, 501252, 504065, 504320, 507918,
+        510465, 518916, 521217, 521728, 522251, 522496, 522753, 523008, 523524, 523776, 524289,
+        529924, 530945, 531204, 533505, 533764, 534529, 534788, 536064, 540673, 547076, 547840,
+        548865, 551680, 552961, 559104, 559361, 560896, 561158, 561664, 563204, 565249, 576004,
+        582150, 582404, 590849, 604676, 605441, 605700, 610305, 610564, 612353, 614916, 615424,
+        615950, 618496, 618753, 622852, 623616, 623873, 625920, 626433, 626944, 627457, 633088,
+        633345, 635136, 635393, 635648, 636417, 637440, 637956, 638209, 638468, 640256, 640772,
+        641280, 641796, 642561, 642816, 644868, 645120, 646145, 646656, 646913, 647684, 648192,
+        648718, 651265, 651776, 654337, 654592, 654852, 655104, 655620, 656384, 656641, 658176,
+        659201, 659712, 660225, 665856, 666113, 667904, 668161, 668672, 668929, 669440, 669697,
+        670208, 670724, 670976, 671236, 672512, 673540, 674048, 674564, 675328, 676100, 676352,
+        678145, 679168, 679425, 679680, 681486, 684036, 684545, 685316, 685568, 688388, 689152,
+        689409, 691712, 691969, 692736, 692993, 698624, 698881, 700672, 700929, 701440, 701697,
+        702976, 703492, 703745, 704004, 706048, 706308, 707072, 707332, 708096, 708609, 708864,
+        712705, 713220, 713728, 714254, 716800, 719105, 719364, 720896, 721156, 721920, 722177,
+        724224, 724737, 725248, 725761, 731392, 731649, 733440, 733697, 734208, 734465, 735744,
+        736260, 736513, 736772, 738560, 739076, 739584, 740100, 740864, 742660, 743424, 744449,
+        744960, 745217, 745988, 746496, 747022, 749568, 749825, 750080, 754180, 754433, 754688,
+        754945, 756480, 757249, 758016, 758273, 759296, 760065, 760576, 760833, 761088, 761345,
+        761856, 762625, 763136, 763905, 764672, 765441, 768512, 769540, 770816, 771588, 772352,
+        772612, 773632, 774145, 774400, 775940, 776192, 779790, 782336, 786436, 787713, 789760,
+        790017, 790784, 791041, 796928, 797185, 801280, 801796, 802049, 802308, 804096, 804356,
+        805120, 805380, 806400, 808196, 808704, 808961, 809728, 810241, 810496, 811009, 811524,
+        812032, 812558, 815104, 819201, 819460, 820224, 820481, 822528, 822785, 823552, 823809,
+        829696, 829953, 832512, 832769, 834048, 834564, 834817, 835076, 836864, 837124, 837888,
+        838148, 839168, 840964, 841472, 843009, 843520, 843777, 844292, 844800, 845326, 847872,
+        848129, 848644, 848896, 851972, 852993, 855296, 855553, 856320, 856577, 867076, 867585,
+        867844, 869632, 869892, 870656, 870916, 871937, 872192, 873473, 874244, 874496, 876289,
+        877060, 877568, 878094, 880640, 883201, 884736, 884996, 885760, 886017, 890624, 891393,
+        897536, 897793, 900096, 900353, 900608, 901121, 902912, 903684, 903936, 904964, 906496,
+        906756, 907008, 907268, 909312, 910862, 913408, 913924, 914432, 930052, 930304, 930820,
+        932608, 935684, 937728, 937998, 940544, 962820, 963072, 963588, 965888, 968708, 970496,
+        970766, 973312, 983041, 983296, 989188, 989696, 991246, 993792, 996612, 996864, 997124,
+        997376, 997636, 997888, 998916, 999425, 1001472, 1001729, 1010944, 1011972, 1017088,
+        1017348, 1017857, 1019140, 1021952, 1022212, 1031424, 1033732, 1033984, 1059588, 1064704,
+        1064974, 1067520, 1070596, 1071616, 1072644, 1073408, 1073668, 1074432, 1074948, 1076736,
+        1077508, 1078528, 1081860, 1084928, 1085188, 1085454, 1088004, 1089024, 1089537, 1099264,
+        1099521, 1099776, 1101057, 1101312, 1101825, 1112832, 1113089, 1198336, 1198593, 1199616,
+        1200129, 1201920, 1202177, 1202432, 1202689, 1203712, 1204225, 1214720, 1214977, 1216000,
+        1216513, 1224960, 1225217, 1226240, 1226753, 1228544, 1228801, 1229056, 1229313, 1230336,
+        1230849, 1234688, 1234945, 1249536, 1249793, 1250816, 1251329, 1268480, 1268996, 1269760,
+        1277953, 1282048, 1286145, 1308160, 1308673, 1310208, 1310977, 1469696, 1470209, 1474577,
+        1474817, 1481472, 1482753, 1501952, 1502721, 1505536, 1507329, 1511940, 1512960, 1515265,
+        1520132, 1520896, 1523713, 1528324, 1528832, 1531905, 1535232, 1535489, 1536256, 1536516,
+        1537024, 1553412, 1561600, 1563908, 1564160, 1564686, 1567232, 1575684, 1576454, 1576708,
+        1576974, 1579520, 1581057, 1603840, 1605633, 1606916, 1607425, 1616132, 1616385, 1616640,
+        1617921, 1635840, 1638401, 1646336, 1646596, 1649664, 1650692, 1653760, 1656334, 1658880,
+        1691662, 1694208, 1703937, 1709828, 1711104, 1725700, 1728256, 1728516, 1735936, 1736452,
+        1736718, 1739264, 1740814, 1743360, 1748996, 1756928, 1769476, 1770753, 1782788, 1787137,
+        1789184, 1789966, 1792512, 1796868, 1799168, 1802244, 1803009, 1810692, 1814017, 1814542,
+        1817089, 1828356, 1831936, 1835009, 1844228, 1849344, 1851406, 1853952, 1854721, 1855502,
+        1858049, 1867264, 1867777, 1870080, 1871873, 1882880, 1883393, 1884160, 1888260, 1889024,
+        1889284, 1894657, 1895684, 1895937, 1897476, 1897729, 1898244, 1899009, 1899264, 1900545,
+        1949700, 1966081, 2037248, 2037761, 2039296, 2039809, 2049536, 2050049, 2051584, 2052097,
+        2054144, 2054401, 2054656, 2054913, 2055168, 2055425, 2055680, 2055937, 2063872, 2064385,
+        2077952, 2078209, 2080000, 2080257, 2080512, 2081281, 2082048, 2082305, 2084096, 2084865,
+        2085888, 2086401, 2087936, 2088961, 2092288, 2093569, 2094336, 2094593, 2096384, 2097169,
+        2098944, 2099217, 2099968, 2100228, 2100498, 2100742, 2101248, 2103308, 2103808, 2106380,
+        2106624, 2107146, 2107405, 2107910, 2109189, 2109440, 2113285, 2113792, 2114571, 2114816,
+        2118661, 2118912, 2121489, 2121734, 2123008, 2123270, 2125824, 2126081, 2126336, 2129665,
+        2129920, 2134017, 2137344, 2150404, 2158848, 2163201, 2163456, 2164481, 2164736, 2165249,
+        2167808, 2168065, 2168320, 2169089, 2170368, 2171905, 2172160, 2172417, 2172672, 2172929,
+        2173184, 2173441, 2174464, 2174721, 2177536, 2178049, 2179072, 2180353, 2181632, 2182657,
+        2182912, 2187265, 2197760, 2405889, 2419200, 2883585, 2942208, 2943745, 2944772, 2945537,
+        2946048, 2949121, 2958848, 2959105, 2959360, 2960641, 2960896, 2961409, 2975744, 2977537,
+        2977792, 2981636, 2981889, 2987776, 2990081, 2991872, 2992129, 2993920, 2994177, 2995968,
+        2996225, 2998016, 2998273, 3000064, 3000321, 3002112, 3002369, 3004160, 3004417, 3006208,
+        3006468, 3014656, 3026689, 3026944, 3145745, 3145984, 3147009, 3147264, 3156484, 3158016,
+        3158280, 3159552, 3160833, 3161344, 3184900, 3185416, 3185920, 3186696, 3209984, 3210248,
+        3211264, 3212545, 3223552, 3223809, 3247872, 3252225, 3260416, 3272712, 3276800, 3330056,
+        3342080, 3342344, 3364864, 10485761, 10784000, 10801153, 10812928, 10813441, 10882304,
+        10883073, 10887182, 10889729, 10890240, 10895361, 10907396, 10908416, 10908676, 10911232,
+        10911489, 10919428, 10919937, 10940420, 10940928, 10946561, 10996480, 10997761, 10998272,
+        10998529, 10998784, 10999041, 11000320, 11006465, 11010564, 11010817, 11011588, 11011841,
+        11012868, 11013121, 11019012, 11020288, 11021316, 11021568, 11026433, 11039744, 11042820,
+        11043329, 11056132, 11060736, 11063310, 11065856, 11067396, 11072001, 11073536, 11074305,
+        11074560, 11074817, 11075332, 11075598, 11078145, 11085316, 11087360, 11087873, 11093764,
+        11097088, 11100161, 11107584, 11108356, 11109377, 11121412, 11124992, 11128577, 11128846,
+        11131392, 11134212, 11134464, 11137038, 11139584, 11141121, 11151620, 11155200, 11157505,
+        11158276, 11158529, 11160580, 11161088, 11161614, 11164160, 11172612, 11173376, 11186180,
+        11186432, 11186692, 11187456, 11187972, 11188480, 11189764, 11190272, 11190532, 11190784,
+        11198465, 11201284, 11202560, 11203073, 11203844, 11204352, 11206913, 11208448, 11208961,
+        11210496, 11211009, 11212544, 11214849, 11216640, 11216897, 11218688, 11218945, 11233792,
+        11235329, 11264772, 11266816, 11267076, 11267584, 11268110, 11270656, 11272193, 14132224,
+        14135297, 14141184, 14142209, 14154752, 16449537, 16451328, 16454401, 16455680, 16456967,
+        16457220, 16457479, 16460032, 16460295, 16463616, 16463879, 16465152, 16465415, 16465664,
+        16465927, 16466432, 16466695, 16467200, 16467463, 16470017, 16495104, 16503553, 16596480,
+        16601089, 16617472, 16617985, 16631808, 16642049, 16645120, 16646148, 16650251, 16650496,
+        16651018, 16651275, 16651520, 16654340, 16658432, 16659205, 16659712, 16665861, 16666635,
+        16666880, 16667148, 16667392, 16667659, 16667914, 16668160, 16674817, 16676096, 16676353,
+        16710912, 16711430, 16711680, 16713484, 16713728, 16714763, 16715008, 16715276, 16715520,
+        16715790, 16718346, 16718603, 16718848, 16720129, 16726784, 16727813, 16728064, 16728321,
+        16734976, 16737800, 16752132, 16752641, 16760576, 16761345, 16762880, 16763393, 16764928,
+        16765441, 16766976, 16767489, 16768256, 16775430, 16776192, 16777217, 16780288, 16780545,
+        16787200, 16787457, 16792320, 16792577, 16793088, 16793345, 16797184, 16797697, 16801280,
+        16809985, 16841472, 16859137, 16872704, 16907524, 16907776, 16941057, 16948480, 16949249,
+        16961792, 16965636, 16965888, 16973825, 16982016, 16985345, 16993024, 16994305, 17004036,
+        17005312, 17006593, 17014272, 17014785, 17024000, 17025025, 17027072, 17027329, 17028608,
+        17039361, 17079808, 17080334, 17082880, 17084417, 17093632, 17094657, 17103872, 17104897,
+        17115136, 17117185, 17130496, 17133569, 17136384, 17136641, 17140480, 17140737, 17142528,
+        17142785, 17143296, 17143553, 17146368, 17146625, 17150464, 17150721, 17152512, 17152769,
+        17153280, 17170433, 17250048, 17252353, 17257984, 17260545, 17262592, 17268737, 17270272,
+        17270529, 17281280, 17281537, 17283840, 17301505, 17303040, 17303553, 17303808, 17304065,
+        17315328, 17315585, 17316096, 17316865, 17317120, 17317633, 17323520, 17326081, 17331968,
+        17334273, 17342208, 17358849, 17363712, 17363969, 17364480, 17367041, 17372672, 17375233,
+        17381888, 17399809, 17414144, 17415681, 17416192, 17432577, 17432836, 17433600, 17433860,
+        17434368, 17435652, 17436673, 17437696, 17437953, 17438720, 17438977, 17446400, 17446916,
+        17447680, 17448708, 17448960, 17457153, 17464576, 17465345, 17472768, 17481729, 17483776,
+        17484033, 17491204, 17491712, 17498113, 17511936, 17514497, 17520128, 17522689, 17527552,
+        17530881, 17535488, 17563649, 17582336, 17596417, 17609472, 17612801, 17625856, 17629185,
+        17638404, 17639424, 17641486, 17644032, 17727489, 17738240, 17738500, 17739008, 17739777,
+        17740288, 17759492, 17760257, 17767680, 17770241, 17770496, 17772545, 17778180, 17780992,
+        17788929, 17793540, 17794560, 17805313, 17810688, 17817601, 17823488, 17825796, 17826561,
+        17840132, 17843968, 17851918, 17854468, 17854721, 17855236, 17855745, 17856000, 17858308,
+        17859329, 17870852, 17873664, 17874182, 17874432, 17875460, 17875712, 17878278, 17878528,
+        17879041, 17885440, 17887246, 17889792, 17891332, 17892097, 17901316, 17904896, 17905166,
+        17907712, 17908737, 17908996, 17909505, 17909760, 17911809, 17920772, 17921024, 17921537,
+        17921792, 17924100, 17924865, 17937156, 17940737, 17941760, 17942788, 17943808, 17944068,
+        17944590, 17947137, 17947392, 17947649, 17947904, 17956865, 17961472, 17961729, 17968132,
+        17971200, 17972740, 17972993, 17973508, 17973760, 17989633, 17991424, 17991681, 17991936,
+        17992193, 17993216, 17993473, 17997312, 17997569, 18000128, 18001921, 18013956, 18017024,
+        18018318, 18020864, 18022404, 18023424, 18023681, 18025728, 18026241, 18026752, 18027265,
+        18032896, 18033153, 18034944, 18035201, 18035712, 18035969, 18037248, 18037508, 18038017,
+        18038276, 18040064, 18040580, 18041088, 18041604, 18042368, 18042881, 18043136, 18044676,
+        18044928, 18046209, 18047492, 18048000, 18048516, 18050304, 18051076, 18052352, 18087937,
+        18101508, 18106113, 18107136, 18108430, 18110976, 18112004, 18112257, 18113024, 18120705,
+        18132996, 18138113, 18138624, 18138881, 18139136, 18141198, 18143744, 18186241, 18198276,
+        18200064, 18200580, 18202880, 18208769, 18209796, 18210304, 18219009, 18231300, 18235648,
+        18236417, 18236672, 18239502, 18242048, 18251777, 18262788, 18266113, 18266368, 18268174,
+        18270720, 18291972, 18295808, 18296846, 18299392, 18350081, 18361348, 18365184, 18391041,
+        18407438, 18409984, 18415361, 18417408, 18417921, 18418176, 18418689, 18420736, 18420993,
+        18421504, 18421761, 18427908, 18429440, 18429700, 18430208, 18430724, 18431745, 18432004,
+        18432257, 18432516, 18433024, 18436110, 18438656, 18456577, 18458624, 18459137, 18469124,
+        18470912, 18471428, 18473217, 18473472, 18473729, 18473988, 18474240, 18481153, 18481412,
+        18483969, 18494212, 18496001, 18496260, 18497280, 18499332, 18499584, 18501633, 18501892,
+        18504705, 18516484, 18520576, 18521345, 18521600, 18526209, 18544896, 18612225, 18614528,
+        18614785, 18624260, 18626304, 18626564, 18628609, 18628864, 18632718, 18635264, 18641409,
+        18649088, 18649604, 18655232, 18655492, 18659072, 18677761, 18679552, 18679809, 18680320,
+        18680577, 18690308, 18691840, 18692612, 18692864, 18693124, 18693632, 18693892, 18695681,
+        18695940, 18696192, 18698254, 18700800, 18702337, 18703872, 18704129, 18704640, 18704897,
+        18713092, 18714368, 18714628, 18715136, 18715396, 18716673, 18716928, 18718734, 18721280,
+        18800641, 18805508, 18806528, 18808836, 18809345, 18809604, 18809857, 18813184, 18813441,
+        18822148, 18823936, 18824708, 18825984, 18829326, 18831872, 18853889, 18854144, 18874369,
+        19110400, 19136513, 19164928, 19169281, 19219456, 19894273, 19919104, 19922945, 20197382,
+        20201476, 20201729, 20203268, 20207104, 21233665, 21382912, 23592961, 23738624, 23740417,
+        23748352, 23748622, 23751168, 23752705, 23772928, 23773198, 23775744, 23777281, 23784960,
+        23785476, 23786752, 23789569, 23801860, 23803648, 23805953, 23806976, 23810062, 23812608,
+        23814913, 23820288, 23821569, 23826432, 24002561, 24018944, 24051713, 24070912, 24071940,
+        24072193, 24072452, 24086528, 24088324, 24089345, 24092672, 24109057, 24109568, 24109825,
+        24110084, 24110336, 24113156, 24113664, 28307464, 28308480, 28308744, 28310528, 28310792,
+        28311296, 28311560, 28311808, 28385288, 28386048, 28398856, 28399104, 28402696, 28403712,
+        29097985, 29125376, 29126657, 29129984, 29130753, 29133056, 29134849, 29137408, 29138180,
+        29138688, 29138950, 29139968, 30343172, 30354944, 30355460, 30361344, 30500100, 30501376,
+        30502148, 30503686, 30505732, 30507776, 30508292, 30510080, 30517764, 30518784, 30556676,
+        30557440, 30670849, 30692608, 30692865, 30711040, 30711297, 30711808, 30712321, 30712576,
+        30713089, 30713600, 30714113, 30715136, 30715393, 30718464, 30718721, 30718976, 30719233,
+        30721024, 30721281, 30737920, 30738177, 30739200, 30739713, 30741760, 30742017, 30743808,
+        30744065, 30751232, 30751489, 30752512, 30752769, 30754048, 30754305, 30754560, 30755329,
+        30757120, 30757377, 30844416, 30844929, 30851328, 30851585, 30857984, 30858241, 30866176,
+        30866433, 30872832, 30873089, 30881024, 30881281, 30887680, 30887937, 30895872, 30896129,
+        30902528, 30902785, 30910720, 30910977, 30917376, 30917633, 30919680, 30920206, 30932992,
+        31064068, 31078144, 31079172, 31091968, 31094020, 31094272, 31097860, 31098112, 31103748,
+        31105024, 31105284, 31109120, 31391745, 31399680, 31401217, 31402752, 31457284, 31459072,
+        31459332, 31463680, 31464196, 31465984, 31466244, 31466752, 31467012, 31468288, 31469569,
+        31485440, 31493892, 31494144, 31522817, 31534336, 31535108, 31536897, 31538688, 31539214,
+        31541760, 31542785, 31543040, 31625217, 31632900, 31633152, 31637505, 31648772, 31649806,
+        31652352, 31772673, 31779844, 31780878, 31783424, 31973377, 31975168, 31975425, 31976448,
+        31976705, 31977216, 31977473, 31981312, 31981569, 32032000, 32034820, 32036608, 32047105,
+        32064516, 32066305, 32066560, 32067598, 32070144, 32374785, 32375808, 32376065, 32382976,
+        32383233, 32383744, 32384001, 32384256, 32384769, 32385024, 32385281, 32387840, 32388097,
+        32389120, 32389377, 32389632, 32389889, 32390144, 32391681, 32391936, 32392961, 32393216,
+        32393473, 32393728, 32393985, 32394240, 32394497, 32395264, 32395521, 32396032, 32396289,
+        32396544, 32397057, 32397312, 32397569, 32397824, 32398081, 32398336, 32398593, 32398848,
+        32399105, 32399360, 32399617, 32400128, 32400385, 32400640, 32401153, 32402176, 32402433,
+        32404224, 32404481, 32405504, 32405761, 32406784, 32407041, 32407296, 32407553, 32410112,
+        32410369, 32414720, 32416001, 32416768, 32417025, 32418304, 32418561, 32422912, 32583681,
+        32590336, 32591873, 32598528, 32600065, 32606720, 32630287, 32636928, 32766724, 32768000,
+        33288206, 33290752, 234881286, 234881536, 234889220, 234913792, 234946564, 235008000,
+    ];
+
+    pub fn lookup(c: char) -> WordBreak {
+        // The first run starts at code point 0, so this can't underflow.
+        let idx = RUNS.partition_point(|&run| run >> 8 <= c as u32) - 1;
+        VALUES[(RUNS[idx] & 0xff) as usize]
+    }
+}
@@ -0,0 +1,303 @@
+use core::net::{IpAddr, IpNet, Ipv4Addr, Ipv4Net, Ipv6Addr, Ipv6Net};
+use core::str::FromStr;
+
+macro_rules! net {
+    ($s:expr) => {
+        $s.parse().unwrap()
+    };
+}
+
+#[test]
+fn test_from_str_ipv4_net() {
+    assert_eq!(Ok(Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap()), "10.0.0.0/8".parse());
+    assert_eq!(Ok(Ipv4Net::new(Ipv4Addr::new(0, 0, 0, 0), 0).unwrap()), "0.0.0.0/0".parse());
+    assert_eq!(
+        Ok(Ipv4Net::new(Ipv4Addr::new(255, 255, 255, 255), 32).unwrap()),
+        "255.255.255.255/32".parse()
+    );
+    // host bits are kept
+    assert_eq!(Ok(Ipv4Net::new(Ipv4Addr::new(10, 1, 2, 3), 8).unwrap()), "10.1.2.3/8".parse());
+
+    // prefix too long
+    assert!(Ipv4Net::from_str("10.0.0.0/33").is_err());
+    assert!(Ipv4Net::from_str("10.0.0.0/256").is_err());
+    // leading zero
+    assert!(Ipv4Net::from_str("10.0.0.0/08").is_err());
+    // no prefix
+    assert!(Ipv4Net::from_str("10.0.0.0").is_err());
+    assert!(Ipv4Net::from_str("10.0.0.0/").is_err());
+    // trailing garbage
+    assert!(Ipv4Net::from_str("10.0.0.0/8/8").is_err());
+    assert!(Ipv4Net::from_str("10.0.0.0/8 ").is_err());
+    // IPv6
+    assert!(Ipv4Net::from_str("::/0").is_err());
+}
+
+#[test]
+fn test_from_str_ipv6_net() {
+    let doc = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0);
+    assert_eq!(Ok(Ipv6Net::new(doc, 32).unwrap()), "2001:db8::/32".parse());
+    assert_eq!(Ok(Ipv6Net::new(Ipv6Addr::UNSPECIFIED, 0).unwrap()), "::/0".parse());
+    assert_eq!(Ok(Ipv6Net::new(Ipv6Addr::LOCALHOST, 128).unwrap()), "::1/128".parse());
+    assert_eq!(
+        Ok(Ipv6Net::new(Ipv4Addr::new(192, 0, 2, 0).to_ipv6_mapped(), 120).unwrap()),
+        "::ffff:192.0.2.0/120".parse()
+    );
+
+    assert!(Ipv6Net::from_str("2001:db8::/129").is_err());
+    assert!(Ipv6Net::from_str("2001:db8::/032").is_err());
+    assert!(Ipv6Net::from_str("2001:db8::").is_err());
+    assert!(Ipv6Net::from_str("[2001:db8::]/32").is_err());
+    assert!(Ipv6Net::from_str("2001:db8::%1/32").is_err());
+    assert!(Ipv6Net::from_str("10.0.0.0/8").is_err());
+}
+
+#[test]
+fn test_from_str_ip_net() {
+    assert_eq!(Ok(IpNet::V4(net!("10.0.0.0/8"))), "10.0.0.0/8".parse());
+    assert_eq!(Ok(IpNet::V6(net!("2001:db8::/32"))), "2001:db8::/32".parse());
+    assert!(IpNet::from_str("10.0.0.0/64").is_err());
+    assert!(IpNet::from_str("2001:db8::/200").is_err());
+    assert!(IpNet::from_str("10.0.0.0").is_err());
+}
+
+#[test]
+fn ip_net_to_string() {
+    assert_eq!(Ipv4Net::from_str("10.0.0.0/8").unwrap().to_string(), "10.0.0.0/8");
+    assert_eq!(Ipv6Net::from_str("2001:db8::/32").unwrap().to_string(), "2001:db8::/32");
+    assert_eq!(IpNet::from_str("::ffff:1.2.3.4/128").unwrap().to_string(), "::ffff:1.2.3.4/128");
+
+    let net: Ipv4Net = net!("10.0.0.0/8");
+    assert_eq!(format!("{net:>14}"), "    10.0.0.0/8");
+    assert_eq!(format!("{net:<14}"), "10.0.0.0/8    ");
+    assert_eq!(format!("{net:?}"), "10.0.0.0/8");
+    let net: Ipv6Net = net!("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128");
+    assert_eq!(format!("{net:^50}"), "   ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128    ");
+}
+
+#[test]
+fn ipv4_net_masks() {
+    fn check(
+        net: &str,
+        netmask: Ipv4Addr,
+        hostmask: Ipv4Addr,
+        network: Ipv4Addr,
+        broadcast: Ipv4Addr,
+    ) {
+        let net: Ipv4Net = net!(net);
+        assert_eq!(net.netmask(), netmask);
+        assert_eq!(net.hostmask(), hostmask);
+        assert_eq!(net.network(), network);
+        assert_eq!(net.broadcast(), broadcast);
+        assert_eq!(Ipv4Net::with_netmask(net.addr(), netmask), Some(net));
+    }
+
+    check(
+        "0.0.0.0/0",
+        Ipv4Addr::UNSPECIFIED,
+        Ipv4Addr::BROADCAST,
+        Ipv4Addr::UNSPECIFIED,
+        Ipv4Addr::BROADCAST,
+    );
+    check(
+        "10.1.2.3/8",
+        Ipv4Addr::new(255, 0, 0, 0),
+        Ipv4Addr::new(0, 255, 255, 255),
+        Ipv4Addr::new(10, 0, 0, 0),
+        Ipv4Addr::new(10, 255, 255, 255),
+    );
+    check(
+        "192.168.100.1/22",
+        Ipv4Addr::new(255, 255, 252, 0),
+        Ipv4Addr::new(0, 0, 3, 255),
+        Ipv4Addr::new(192, 168, 100, 0),
+        Ipv4Addr::new(192, 168, 103, 255),
+    );
+    check(
+        "192.168.0.1/32",
+        Ipv4Addr::BROADCAST,
+        Ipv4Addr::UNSPECIFIED,
+        Ipv4Addr::new(192, 168, 0, 1),
+        Ipv4Addr::new(192, 168, 0, 1),
+    );
+
+    let addr = Ipv4Addr::new(10, 0, 0, 0);
+    assert_eq!(Ipv4Net::with_netmask(addr, Ipv4Addr::new(255, 0, 255, 0)), None);
+    assert_eq!(Ipv4Net::with_netmask(addr, Ipv4Addr::new(0, 0, 0, 255)), None);
+    assert_eq!(Ipv4Net::new(addr, 33), None);
+}
+
+#[test]
+fn ipv6_net_masks() {
+    let net: Ipv6Net = net!("2001:db8:1:2::1/48");
+    assert_eq!(net.netmask(), Ipv6Addr::new(0xffff, 0xffff, 0xffff, 0, 0, 0, 0, 0));
+    assert_eq!(net.hostmask(), Ipv6Addr::new(0, 0, 0, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff));
+    assert_eq!(net.network(), Ipv6Addr::new(0x2001, 0xdb8, 1, 0, 0, 0, 0, 0));
+    assert_eq!(net.last(), Ipv6Addr::new(0x2001, 0xdb8, 1, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff));
+    assert_eq!(net.trunc(), net!("2001:db8:1::/48"));
+    assert_eq!(Ipv6Net::with_netmask(net.addr(), net.netmask()), Some(net));
+
+    let net: Ipv6Net = net!("::/0");
+    assert_eq!(net.netmask(), Ipv6Addr::UNSPECIFIED);
+    assert_eq!(net.last(), Ipv6Addr::from_bits(u128::MAX));
+    let net: Ipv6Net = net!("::1/128");
+    assert_eq!(net.hostmask(), Ipv6Addr::UNSPECIFIED);
+    assert_eq!(net.network(), Ipv6Addr::LOCALHOST);
+
+    let netmask = Ipv6Addr::new(0xffff, 0, 0, 0, 0, 0, 0, 1);
+    assert_eq!(Ipv6Net::with_netmask(Ipv6Addr::UNSPECIFIED, netmask), None);
+    assert_eq!(Ipv6Net::new(Ipv6Addr::UNSPECIFIED, 129), None);
+}
+
+#[test]
+fn ip_net_contains() {
+    let net: Ipv4Net = net!("192.168.0.0/16");
+    assert!(net.contains(&Ipv4Addr::new(192, 168, 0, 0)));
+    assert!(net.contains(&Ipv4Addr::new(192, 168, 255, 255)));
+    assert!(!net.contains(&Ipv4Addr::new(192, 169, 0, 0)));
+    assert!(!net.contains(&Ipv4Addr::new(192, 167, 255, 255)));
+
+    // the host bits of the network don't matter
+    let net: Ipv4Net = net!("192.168.1.1/16");
+    assert!(net.contains(&Ipv4Addr::new(192, 168, 2, 2)));
+
+    let net: Ipv4Net = net!("0.0.0.0/0");
+    assert!(net.contains(&Ipv4Addr::BROADCAST));
+    let net: Ipv4Net = net!("10.0.0.1/32");
+    assert!(net.contains(&Ipv4Addr::new(10, 0, 0, 1)));
+    assert!(!net.contains(&Ipv4Addr::new(10, 0, 0, 2)));
+
+    let net: Ipv6Net = net!("2001:db8::/32");
+    assert!(net.contains(&Ipv6Addr::new(0x2001, 0xdb8, 0xffff, 0, 0, 0, 0, 1)));
+    assert!(!net.contains(&Ipv6Addr::new(0x2001, 0xdb9, 0, 0, 0, 0, 0, 1)));
+    let net: Ipv6Net = net!("::/0");
+    assert!(net.contains(&Ipv6Addr::from_bits(u128::MAX)));
+
+    let net: IpNet = net!("10.0.0.0/8");
+    assert!(net.contains(&IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))));
+    assert!(!net.contains(&IpAddr::V6(Ipv4Addr::new(10, 0, 0, 1).to_ipv6_mapped())));
+    let net: IpNet = net!("::/0");
+    assert!(!net.contains(&IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))));
+}
+
+#[test]
+fn ip_net_supernet() {
+    let net: Ipv4Net = net!("10.1.2.3/32");
+    assert_eq!(net.supernet(), Some(net!("10.1.2.2/31")));
+
+    let mut net: Ipv4Net = net!("255.255.255.255/32");
+    for prefix_len in (0..32).rev() {
+        net = net.supernet().unwrap();
+        assert_eq!(net.prefix_len(), prefix_len);
+        assert_eq!(net.addr(), net.network());
+        assert!(net.contains(&Ipv4Addr::BROADCAST));
+    }
+    assert_eq!(net, net!("0.0.0.0/0"));
+    assert_eq!(net.supernet(), None);
+
+    let net: Ipv6Net = net!("2001:db8:1::1/48");
+    assert_eq!(net.supernet(), Some(net!("2001:db8::/47")));
+    let net: Ipv6Net = net!("::/0");
+    assert_eq!(net.supernet(), None);
+
+    let net: IpNet = net!("10.1.0.0/16");
+    assert_eq!(net.supernet(), Some(net!("10.0.0.0/15")));
+    assert_eq!(IpNet::from_str("::/0").unwrap().supernet(), None);
+}
+
+#[test]
+fn ipv4_net_hosts() {
+    let net: Ipv4Net = net!("10.0.0.5/29");
+    let hosts: Vec<_> = net.hosts().collect();
+    assert_eq!(hosts.len(), 6);
+    assert_eq!(hosts[0], Ipv4Addr::new(10, 0, 0, 1));
+    assert_eq!(hosts[5], Ipv4Addr::new(10, 0, 0, 6));
+    assert!(net.hosts().rev().eq(hosts.iter().copied().rev()));
+
+    let net: Ipv4Net = net!("10.0.0.1/31");
+    assert!(net.hosts().eq([Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 0, 1)]));
+    let net: Ipv4Net = net!("10.0.0.1/32");
+    assert!(net.hosts().eq([Ipv4Addr::new(10, 0, 0, 1)]));
+
+    let net: Ipv4Net = net!("0.0.0.0/0");
+    let mut hosts = net.hosts();
+    assert_eq!(hosts.size_hint(), (u32::MAX as usize - 1, Some(u32::MAX as usize - 1)));
+    assert_eq!(hosts.next(), Some(Ipv4Addr::new(0, 0, 0, 1)));
+    assert_eq!(hosts.next_back(), Some(Ipv4Addr::new(255, 255, 255, 254)));
+    assert_eq!(hosts.nth(1), Some(Ipv4Addr::new(0, 0, 0, 3)));
+}
+
+#[test]
+fn ipv6_net_hosts() {
+    let net: Ipv6Net = net!("2001:db8::/126");
+    assert!(
+        net.hosts().map(Ipv6Addr::to_bits).eq(0x2001_0db8_u128 << 96..=(0x2001_0db8 << 96) + 3)
+    );
+
+    let net: Ipv6Net = net!("::1/128");
+    assert!(net.hosts().eq([Ipv6Addr::LOCALHOST]));
+
+    let net: Ipv6Net = net!("::/0");
+    let mut hosts = net.hosts();
+    assert_eq!(hosts.size_hint(), (usize::MAX, None));
+    assert_eq!(hosts.next(), Some(Ipv6Addr::UNSPECIFIED));
+    assert_eq!(hosts.next_back(), Some(Ipv6Addr::from_bits(u128::MAX)));
+}
+
+#[test]
+fn ipv4_net_subnets() {
+    let net: Ipv4Net = net!("10.0.0.0/8");
+    assert!(net.subnets(7).is_none());
+    assert!(net.subnets(33).is_none());
+    assert!(net.subnets(8).unwrap().eq([net]));
+
+    let subnets: Vec<Ipv4Net> = net.subnets(10).unwrap().collect();
+    assert_eq!(
+        subnets,
+        [net!("10.0.0.0/10"), net!("10.64.0.0/10"), net!("10.128.0.0/10"), net!("10.192.0.0/10")]
+    );
+    for subnet in &subnets {
+        assert!(net.contains(&subnet.addr()));
+        assert_eq!(subnet.supernet().unwrap().supernet(), Some(net));
+    }
+
+    // the host bits of the network are ignored
+    let net: Ipv4Net = net!("10.0.0.255/31");
+    assert!(net.subnets(32).unwrap().eq([net!("10.0.0.254/32"), net!("10.0.0.255/32")]));
+
+    let net: Ipv4Net = net!("0.0.0.0/0");
+    let mut subnets = net.subnets(32).unwrap();
+    assert_eq!(subnets.next_back(), Some(net!("255.255.255.255/32")));
+    assert_eq!(subnets.nth(2), Some(net!("0.0.0.2/32")));
+    assert!(net.subnets(0).unwrap().eq([net]));
+}
+
+#[test]
+fn ipv6_net_subnets() {
+    let net: Ipv6Net = net!("2001:db8::/32");
+    assert!(net.subnets(31).is_none());
+    assert!(net.subnets(129).is_none());
+
+    let mut subnets = net.subnets(48).unwrap();
+    assert_eq!(subnets.size_hint(), (0x10000, Some(0x10000)));
+    assert_eq!(subnets.next(), Some(net!("2001:db8::/48")));
+    assert_eq!(subnets.next(), Some(net!("2001:db8:1::/48")));
+    assert_eq!(subnets.next_back(), Some(net!("2001:db8:ffff::/48")));
+
+    let net: Ipv6Net = net!("::/0");
+    let mut subnets = net.subnets(128).unwrap();
+    assert_eq!(subnets.next(), Some(net!("::/128")));
+    assert_eq!(subnets.next_back(), Some(net!("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128")));
+    assert!(net.subnets(1).unwrap().eq([net!("::/1"), net!("8000::/1")]));
+    assert!(net.subnets(0).unwrap().eq([net]));
+}
+
+#[test]
+fn ip_net_from() {
+    let addr = Ipv4Addr::new(10, 0, 0, 1);
+    assert_eq!(Ipv4Net::from(addr), net!("10.0.0.1/32"));
+    assert_eq!(Ipv6Net::from(Ipv6Addr::LOCALHOST), net!("::1/128"));
+    assert_eq!(IpNet::from(IpAddr::V4(addr)), net!("10.0.0.1/32"));
+    assert_eq!(IpNet::from(Ipv4Net::from(addr)), IpNet::V4(net!("10.0.0.1/32")));
+    assert!(IpNet::from(Ipv6Net::from(Ipv6Addr::LOCALHOST)).is_ipv6());
+}
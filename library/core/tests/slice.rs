@@ -1875,6 +1875,47 @@ fn sort_unstable() {
     assert!(v == [0xDEADBEEF]);
 }
 
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn sort_unstable_patterns() {
+    use rand::Rng;
+
+    // Every 0-1 input of the lengths sorted by sorting networks, plus some insertion sort steps.
+    for len in [4, 5, 8, 9] {
+        for bits in 0u32..(1 << len) {
+            let mut v: Vec<u32> = (0..len).map(|i| (bits >> i) & 1).collect();
+            v.sort_unstable();
+            assert!(v.windows(2).all(|w| w[0] <= w[1]));
+            assert_eq!(v.iter().sum::<u32>(), bits.count_ones());
+        }
+    }
+
+    let mut rng = crate::test_rng();
+    // Miri is too slow for the longer slices.
+    let lens: &[usize] =
+        if cfg!(miri) { &[0, 1, 2, 70] } else { &[0, 1, 2, 3, 19, 20, 21, 64, 65, 1000] };
+    for &len in lens {
+        let random: Vec<i32> = (0..len).map(|_| rng.gen::<i32>()).collect();
+        let mut sorted = random.clone();
+        sorted.sort();
+
+        let ascending = sorted.clone();
+        let descending: Vec<i32> = sorted.iter().rev().copied().collect();
+        let mut pipe_organ = sorted[..len / 2].to_vec();
+        pipe_organ.extend(sorted[len / 2..].iter().rev());
+        let mut mostly_ascending = sorted.clone();
+        if len > 2 {
+            mostly_ascending.swap(0, len - 1);
+        }
+
+        for v in [random, ascending, descending, pipe_organ, mostly_ascending] {
+            let mut v = v;
+            v.sort_unstable();
+            assert_eq!(v, sorted);
+        }
+    }
+}
+
 #[test]
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg_attr(miri, ignore)] // Miri is too slow
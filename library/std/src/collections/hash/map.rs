@@ -12,7 +12,7 @@
 use crate::error::Error;
 use crate::fmt::{self, Debug};
 #[allow(deprecated)]
-use crate::hash::{BuildHasher, Hash, Hasher, SipHasher13};
+use crate::hash::{BuildHasher, FastState, Hash, Hasher, SipHasher13};
 use crate::iter::FusedIterator;
 use crate::ops::Index;
 use crate::sys;
@@ -256,6 +256,51 @@ pub fn with_capacity(capacity: usize) -> HashMap<K, V, RandomState> {
     }
 }
 
+impl<K, V> HashMap<K, V, FastState> {
+    /// Creates an empty `HashMap` which uses [`FastState`] to hash keys.
+    ///
+    /// This is faster than [`HashMap::new`] for small keys such as integers, but it is not
+    /// resistant against HashDoS attacks. Only use it if the keys are trusted.
+    ///
+    /// The hash map is initially created with a capacity of 0, so it will not allocate until it
+    /// is first inserted into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fast_hasher)]
+    /// use std::collections::HashMap;
+    /// use std::hash::FastState;
+    /// let mut map: HashMap<u32, i32, FastState> = HashMap::new_fast();
+    /// ```
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "fast_hasher", issue = "none")]
+    pub fn new_fast() -> HashMap<K, V, FastState> {
+        Default::default()
+    }
+
+    /// Creates an empty `HashMap` with at least the specified capacity, which uses [`FastState`]
+    /// to hash keys.
+    ///
+    /// See [`HashMap::new_fast`] for when this hasher should be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fast_hasher)]
+    /// use std::collections::HashMap;
+    /// use std::hash::FastState;
+    /// let mut map: HashMap<u32, i32, FastState> = HashMap::with_capacity_fast(10);
+    /// ```
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "fast_hasher", issue = "none")]
+    pub fn with_capacity_fast(capacity: usize) -> HashMap<K, V, FastState> {
+        HashMap::with_capacity_and_hasher(capacity, Default::default())
+    }
+}
+
 impl<K, V, S> HashMap<K, V, S> {
     /// Creates an empty `HashMap` which will use the given hash builder to hash
     /// keys.
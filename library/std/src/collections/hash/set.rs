@@ -6,7 +6,7 @@
 use crate::borrow::Borrow;
 use crate::collections::TryReserveError;
 use crate::fmt;
-use crate::hash::{BuildHasher, Hash};
+use crate::hash::{BuildHasher, FastState, Hash};
 use crate::iter::{Chain, FusedIterator};
 use crate::ops::{BitAnd, BitOr, BitXor, Sub};
 
@@ -148,6 +148,52 @@ pub fn with_capacity(capacity: usize) -> HashSet<T, RandomState> {
     }
 }
 
+impl<T> HashSet<T, FastState> {
+    /// Creates an empty `HashSet` which uses [`FastState`] to hash values.
+    ///
+    /// This is faster than [`HashSet::new`] for small values such as integers, but it is not
+    /// resistant against HashDoS attacks. Only use it if the values are trusted.
+    ///
+    /// The hash set is initially created with a capacity of 0, so it will not allocate until it
+    /// is first inserted into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fast_hasher)]
+    /// use std::collections::HashSet;
+    /// use std::hash::FastState;
+    /// let set: HashSet<i32, FastState> = HashSet::new_fast();
+    /// ```
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "fast_hasher", issue = "none")]
+    pub fn new_fast() -> HashSet<T, FastState> {
+        Default::default()
+    }
+
+    /// Creates an empty `HashSet` with at least the specified capacity, which uses [`FastState`]
+    /// to hash values.
+    ///
+    /// See [`HashSet::new_fast`] for when this hasher should be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fast_hasher)]
+    /// use std::collections::HashSet;
+    /// use std::hash::FastState;
+    /// let set: HashSet<i32, FastState> = HashSet::with_capacity_fast(10);
+    /// assert!(set.capacity() >= 10);
+    /// ```
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "fast_hasher", issue = "none")]
+    pub fn with_capacity_fast(capacity: usize) -> HashSet<T, FastState> {
+        HashSet::with_capacity_and_hasher(capacity, Default::default())
+    }
+}
+
 impl<T, S> HashSet<T, S> {
     /// Returns the number of elements the set can hold without reallocating.
     ///
@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod tests;
+
+use crate::fmt;
+use crate::hash::{BuildHasher, Hasher};
+
+/// An implementation of a fast, non-cryptographic hash function.
+///
+/// This is the algorithm used by Firefox and by the Rust compiler (where it is known as
+/// `FxHasher`). It processes a word at a time and needs only a rotate, an xor and a multiplication
+/// per word, which makes it much faster than the [`DefaultHasher`] for small keys like integers,
+/// pointers or short strings.
+///
+/// **`FastHasher` is not resistant against HashDoS attacks.** Anyone who can choose the keys
+/// inserted into a [`HashMap`] using it can easily craft keys that all collide, degrading the map
+/// to quadratic time. It also uses no random seed, so hashes are stable between program runs.
+/// Only use it when the keys are trusted, and prefer the [`DefaultHasher`] otherwise.
+///
+/// The hash values computed by `FastHasher` are not guaranteed to be the same across platforms or
+/// Rust versions.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(fast_hasher)]
+/// use std::hash::{FastHasher, Hash, Hasher};
+///
+/// let mut hasher = FastHasher::new();
+/// 7920.hash(&mut hasher);
+/// println!("Hash is {:x}!", hasher.finish());
+/// ```
+///
+/// [`DefaultHasher`]: crate::collections::hash_map::DefaultHasher
+/// [`HashMap`]: crate::collections::HashMap
+#[unstable(feature = "fast_hasher", issue = "none")]
+#[derive(Clone, Default)]
+pub struct FastHasher {
+    hash: u64,
+}
+
+/// The multiplier of the hash function, an odd number with a good distribution of bits.
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FastHasher {
+    /// Creates a new `FastHasher`.
+    ///
+    /// All `FastHasher` instances start from the same state, so hashing the same values
+    /// produces the same result every time.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "fast_hasher", issue = "none")]
+    pub const fn new() -> FastHasher {
+        FastHasher { hash: 0 }
+    }
+
+    #[inline]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+#[unstable(feature = "fast_hasher", issue = "none")]
+impl fmt::Debug for FastHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FastHasher").finish_non_exhaustive()
+    }
+}
+
+#[unstable(feature = "fast_hasher", issue = "none")]
+impl Hasher for FastHasher {
+    #[inline]
+    fn write(&mut self, mut bytes: &[u8]) {
+        while let Some((word, rest)) = bytes.split_first_chunk::<8>() {
+            self.add_to_hash(u64::from_ne_bytes(*word));
+            bytes = rest;
+        }
+        if let Some((word, rest)) = bytes.split_first_chunk::<4>() {
+            self.add_to_hash(u32::from_ne_bytes(*word) as u64);
+            bytes = rest;
+        }
+        if let Some((word, rest)) = bytes.split_first_chunk::<2>() {
+            self.add_to_hash(u16::from_ne_bytes(*word) as u64);
+            bytes = rest;
+        }
+        if let Some(&byte) = bytes.first() {
+            self.add_to_hash(byte as u64);
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.add_to_hash(i as u64);
+        self.add_to_hash((i >> 64) as u64);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        // The multiplication only carries entropy upwards, so the low bits of the hash only depend
+        // on the low bits of the input, and keys that are all multiples of a power of two end up
+        // in a few buckets of a table indexed by the low bits. Rotating brings the well-mixed high
+        // bits down to where hash tables look.
+        self.hash.rotate_left(26)
+    }
+}
+
+/// A [`BuildHasher`] that creates [`FastHasher`]s.
+///
+/// This can be used as the hash builder of a [`HashMap`] or [`HashSet`] whose keys are trusted, to
+/// speed up hashing small keys compared to the default [`RandomState`]. See [`FastHasher`] for why
+/// it must not be used with keys chosen by an attacker.
+///
+/// [`HashMap::new_fast`] and [`HashSet::new_fast`] create maps and sets that use it.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(fast_hasher)]
+/// use std::collections::HashMap;
+///
+/// let mut map = HashMap::new_fast();
+/// map.insert(1, "one");
+/// map.insert(2, "two");
+/// assert_eq!(map.get(&1), Some(&"one"));
+/// ```
+///
+/// [`HashMap`]: crate::collections::HashMap
+/// [`HashMap::new_fast`]: crate::collections::HashMap::new_fast
+/// [`HashSet`]: crate::collections::HashSet
+/// [`HashSet::new_fast`]: crate::collections::HashSet::new_fast
+/// [`RandomState`]: crate::collections::hash_map::RandomState
+#[unstable(feature = "fast_hasher", issue = "none")]
+#[derive(Clone, Copy, Default)]
+pub struct FastState {
+    _private: (),
+}
+
+impl FastState {
+    /// Creates a new `FastState`.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "fast_hasher", issue = "none")]
+    pub const fn new() -> FastState {
+        FastState { _private: () }
+    }
+}
+
+#[unstable(feature = "fast_hasher", issue = "none")]
+impl BuildHasher for FastState {
+    type Hasher = FastHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> FastHasher {
+        FastHasher::new()
+    }
+}
+
+#[unstable(feature = "fast_hasher", issue = "none")]
+impl fmt::Debug for FastState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FastState").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,76 @@
+use super::{FastHasher, FastState};
+use crate::collections::{HashMap, HashSet};
+use crate::hash::{BuildHasher, Hash, Hasher};
+
+fn hash<T: Hash + ?Sized>(t: &T) -> u64 {
+    FastState::new().hash_one(t)
+}
+
+#[test]
+fn test_deterministic() {
+    assert_eq!(hash(&0u32), hash(&0u32));
+    assert_eq!(hash("hello"), hash("hello"));
+    assert_eq!(FastState::new().build_hasher().finish(), FastHasher::new().finish());
+}
+
+#[test]
+fn test_distinguishes_values() {
+    let hashes: HashSet<u64> = (0..1000u64).map(|i| hash(&i)).collect();
+    assert_eq!(hashes.len(), 1000);
+
+    assert_ne!(hash("hello"), hash("world"));
+    assert_ne!(hash(&(1u8, 2u8)), hash(&(2u8, 1u8)));
+}
+
+#[test]
+fn test_aligned_keys_spread_over_low_bits() {
+    // Keys like aligned pointers or indices scaled by a struct size have zeroes in their low bits,
+    // which must not stay zero in the hash, since hash tables pick buckets by the low bits.
+    for stride in [8u64, 16] {
+        let buckets: HashSet<u64> = (0..1024u64).map(|i| hash(&(i * stride)) % 1024).collect();
+        assert!(buckets.len() > 512, "stride = {stride}, buckets = {}", buckets.len());
+    }
+}
+
+#[test]
+fn test_write_partial_words() {
+    // Byte slices of all lengths up to a few words, differing only in the last byte.
+    for len in 1..=24 {
+        let mut a = vec![0u8; len];
+        let mut b = a.clone();
+        b[len - 1] = 1;
+
+        let mut ha = FastHasher::new();
+        ha.write(&a);
+        let mut hb = FastHasher::new();
+        hb.write(&b);
+        assert_ne!(ha.finish(), hb.finish(), "len = {len}");
+
+        a[0] = 1;
+        let mut hc = FastHasher::new();
+        hc.write(&a);
+        assert_ne!(ha.finish(), hc.finish(), "len = {len}");
+    }
+}
+
+#[test]
+fn test_collections() {
+    let mut map = HashMap::new_fast();
+    for i in 0..100 {
+        map.insert(i, i * 2);
+    }
+    assert_eq!(map.len(), 100);
+    assert_eq!(map[&42], 84);
+
+    let mut map: HashMap<&str, i32, FastState> = HashMap::with_capacity_fast(10);
+    assert!(map.capacity() >= 10);
+    map.insert("a", 1);
+    assert_eq!(map.get("a"), Some(&1));
+
+    let mut set = HashSet::new_fast();
+    assert!(set.insert("a"));
+    assert!(!set.insert("a"));
+
+    let set: HashSet<u8, FastState> = HashSet::with_capacity_fast(10);
+    assert!(set.capacity() >= 10);
+}
@@ -96,6 +96,14 @@ unsafe fn init(argc: isize, argv: *const *const u8, sigpipe: u8) {
     unsafe {
         sys::init(argc, argv, sigpipe);
 
+        // `core` validates UTF-8 with the SIMD instructions the target is compiled for, but it
+        // can't detect the ones the CPU has beyond that.
+        #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+        core::str::enable_x86_runtime_features(
+            crate::is_x86_feature_detected!("ssse3"),
+            crate::is_x86_feature_detected!("avx2"),
+        );
+
         let main_guard = sys::thread::guard::init();
         // Next, set up the current Thread with the guard information we just
         // created. Note that this isn't necessary in general for new threads,
@@ -1817,12 +1817,9 @@ pub fn run_cargo(
                 // During check builds we need to keep crate metadata
                 keep = true;
             } else if rlib_only_metadata {
-                if filename.contains("jemalloc_sys")
-                    || filename.contains("rustc_smir")
-                    || filename.contains("stable_mir")
-                {
-                    // jemalloc_sys and rustc_smir are not linked into librustc_driver.so,
-                    // so we need to distribute them as rlib to be able to use them.
+                if filename.contains("jemalloc_sys") {
+                    // jemalloc_sys is not linked into librustc_driver.so, so we need to
+                    // distribute it as rlib to be able to use it.
                     keep |= filename.ends_with(".rlib");
                 } else {
                     // Distribute the rest of the rustc crates as rmeta files only to reduce
@@ -1,6 +1,7 @@
 use rustc_ast as ast;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lrc;
+use rustc_errors::emitter::OutputTheme;
 use rustc_errors::{ColorConfig, ErrorGuaranteed, FatalError};
 use rustc_hir::def_id::{LocalDefId, CRATE_DEF_ID, LOCAL_CRATE};
 use rustc_hir::{self as hir, intravisit, CRATE_HIR_ID};
@@ -107,6 +108,7 @@ pub(crate) fn run(options: RustdocOptions) -> Result<(), ErrorGuaranteed> {
         ice_file: None,
         using_internal_features: Arc::default(),
         expanded_args: options.expanded_args.clone(),
+        caches: None,
     };
 
     let test_args = options.test_args.clone();
@@ -383,6 +385,10 @@ fn run_test(
 
         if short {
             compiler.arg("--error-format").arg("short");
+        } else if kind.theme() == OutputTheme::Unicode {
+            // `-Z unstable-options` is passed on with the other unstable options, since rustdoc
+            // needed it to accept this error format too.
+            compiler.arg("--error-format").arg("human-unicode");
         }
 
         match color_config {
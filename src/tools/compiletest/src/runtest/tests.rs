@@ -48,3 +48,30 @@ fn normalize_platform_differences() {
         r#"println!("test\ntest")"#,
     );
 }
+
+#[test]
+fn anonymize_coverage_line_numbers() {
+    let report = "   12|      1|    if a && b {
+  ------------------
+  |  Branch (12:8): [True: 1, False: 0]
+  |  Branch (12:13): [True: 1, False: 0]
+  ------------------
+  |---> MC/DC Decision Region (12:8) to (12:14)
+  |
+  |  Number of Conditions: 2
+  |     Condition C1 --> (12:8)
+  |     Condition C2 --> (12:13)
+";
+    let expected = "   LL|      1|    if a && b {
+  ------------------
+  |  Branch (LL:8): [True: 1, False: 0]
+  |  Branch (LL:13): [True: 1, False: 0]
+  ------------------
+  |---> MC/DC Decision Region (LL:8) to (LL:14)
+  |
+  |  Number of Conditions: 2
+  |     Condition C1 --> (LL:8)
+  |     Condition C2 --> (LL:13)
+";
+    assert_eq!(TestCx::anonymize_coverage_line_numbers(report), expected);
+}
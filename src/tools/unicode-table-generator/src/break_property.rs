@@ -0,0 +1,116 @@
+//! Tables for the `Grapheme_Cluster_Break` and `Word_Break` properties.
+//!
+//! Unlike the binary properties, these have a value for every code point, so
+//! they can't be encoded as a set. Instead, the code points are split into
+//! runs of the same value, and each run is packed into a `u32` holding the
+//! first code point of the run in the upper 24 bits and the index of the value
+//! in the lower 8 bits. The runs cover all code points, so a lookup is a binary
+//! search for the last run starting at or before the needle.
+//!
+//! The segmentation built on top of these tables is checked against the
+//! `GraphemeBreakTest.txt` and `WordBreakTest.txt` files of the same version.
+
+use crate::fmt_list;
+use std::fmt::{self, Write};
+use std::ops::Range;
+
+/// The value of code points that aren't listed in the data files.
+const DEFAULT_VALUE: &str = "Other";
+
+pub(crate) fn generate_break_property(
+    enum_name: &str,
+    ranges: &[(Range<u32>, String)],
+) -> (String, usize) {
+    let mut values = ranges.iter().map(|(_, value)| value.as_str()).collect::<Vec<_>>();
+    values.sort_unstable();
+    values.dedup();
+    values.insert(0, DEFAULT_VALUE);
+    assert!(values.len() <= u8::MAX as usize, "too many values for {enum_name}");
+
+    let mut ranges = ranges.to_vec();
+    ranges.sort_by_key(|(range, _)| range.start);
+
+    let mut runs: Vec<(u32, usize)> = Vec::new();
+    let mut push_run = |start: u32, value: usize| {
+        if runs.last().map_or(true, |&(_, last)| last != value) {
+            runs.push((start, value));
+        }
+    };
+    let mut next = 0;
+    for (range, value) in &ranges {
+        assert!(range.start >= next, "overlapping ranges for {enum_name}: {range:?}");
+        if range.start > next {
+            push_run(next, 0);
+        }
+        push_run(range.start, values.iter().position(|v| v == value).unwrap());
+        next = range.end;
+    }
+    if next <= char::MAX as u32 {
+        push_run(next, 0);
+    }
+
+    let mut file = String::new();
+    writeln!(file, "#[derive(Clone, Copy, Debug, PartialEq, Eq)]").unwrap();
+    writeln!(file, "pub enum {enum_name} {{").unwrap();
+    for value in &values {
+        writeln!(file, "    {},", variant_name(value)).unwrap();
+    }
+    writeln!(file, "}}").unwrap();
+    file.push('\n');
+
+    struct Variant<'a>(&'a str, String);
+    impl fmt::Debug for Variant<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}::{}", self.0, self.1)
+        }
+    }
+
+    writeln!(
+        file,
+        "static VALUES: [{enum_name}; {}] = [{}];",
+        values.len(),
+        fmt_list(values.iter().map(|v| Variant(enum_name, variant_name(v)))),
+    )
+    .unwrap();
+    writeln!(
+        file,
+        "static RUNS: [u32; {}] = [{}];",
+        runs.len(),
+        fmt_list(runs.iter().map(|&(start, value)| start << 8 | value as u32)),
+    )
+    .unwrap();
+    let bytes_used = values.len() + 4 * runs.len();
+
+    file.push('\n');
+    writeln!(file, "pub fn lookup(c: char) -> {enum_name} {{").unwrap();
+    writeln!(file, "    // The first run starts at code point 0, so this can't underflow.")
+        .unwrap();
+    writeln!(file, "    let idx = RUNS.partition_point(|&run| run >> 8 <= c as u32) - 1;").unwrap();
+    writeln!(file, "    VALUES[(RUNS[idx] & 0xff) as usize]").unwrap();
+    writeln!(file, "}}").unwrap();
+
+    (file, bytes_used)
+}
+
+/// Generates a test function named `fn_name` checking that `len_fn`, which
+/// returns the length of the first segment of a string, splits each string of
+/// `tests` into the expected segments.
+pub(crate) fn generate_break_tests(
+    fn_name: &str,
+    len_fn: &str,
+    tests: &[(String, Vec<String>)],
+) -> String {
+    let mut s = String::new();
+    writeln!(s, "    fn {fn_name}() {{").unwrap();
+    for (string, segments) in tests {
+        writeln!(s, "        check_segments({len_fn}, {string:?}, &{segments:?});").unwrap();
+    }
+    writeln!(s, "    }}").unwrap();
+    s
+}
+
+/// Converts a property value like `Regional_Indicator` into a variant name like
+/// `RegionalIndicator`.
+fn variant_name(value: &str) -> String {
+    value.replace('_', "")
+}
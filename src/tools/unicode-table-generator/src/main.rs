@@ -73,8 +73,10 @@
 
 use std::collections::{BTreeMap, HashMap};
 use std::ops::Range;
+use std::path::Path;
 use ucd_parse::Codepoints;
 
+mod break_property;
 mod cascading_map;
 mod case_mapping;
 mod raw_emitter;
@@ -89,6 +91,7 @@
     "Uppercase",
     "Cased",
     "Case_Ignorable",
+    "Extended_Pictographic",
     "Grapheme_Extend",
     "White_Space",
     "Cc",
@@ -99,6 +102,8 @@ struct UnicodeData {
     ranges: Vec<(&'static str, Vec<Range<u32>>)>,
     to_upper: BTreeMap<u32, (u32, u32, u32)>,
     to_lower: BTreeMap<u32, (u32, u32, u32)>,
+    grapheme_cluster_break: Vec<(Range<u32>, String)>,
+    word_break: Vec<(Range<u32>, String)>,
 }
 
 fn to_mapping(origin: u32, codepoints: Vec<ucd_parse::Codepoint>) -> Option<(u32, u32, u32)> {
@@ -142,6 +147,24 @@ fn load_data() -> UnicodeData {
         }
     }
 
+    for row in ucd_parse::parse::<_, ucd_parse::EmojiProperty>(&UNICODE_DIRECTORY).unwrap() {
+        if let Some(name) = PROPERTIES.iter().find(|prop| **prop == row.property.as_str()) {
+            properties.entry(*name).or_insert_with(Vec::new).push(row.codepoints);
+        }
+    }
+
+    let grapheme_cluster_break =
+        ucd_parse::parse::<_, ucd_parse::GraphemeClusterBreak>(&UNICODE_DIRECTORY)
+            .unwrap()
+            .into_iter()
+            .map(|row| (codepoints_to_range(row.codepoints), row.value))
+            .collect();
+    let word_break = ucd_parse::parse::<_, ucd_parse::WordBreak>(&UNICODE_DIRECTORY)
+        .unwrap()
+        .into_iter()
+        .map(|row| (codepoints_to_range(row.codepoints), row.value))
+        .collect();
+
     let mut to_lower = BTreeMap::new();
     let mut to_upper = BTreeMap::new();
     for row in ucd_parse::UnicodeDataExpander::new(
@@ -214,7 +237,14 @@ fn load_data() -> UnicodeData {
 
     let mut properties = properties.into_iter().collect::<Vec<_>>();
     properties.sort_by_key(|p| p.0);
-    UnicodeData { ranges: properties, to_lower, to_upper }
+    UnicodeData { ranges: properties, to_lower, to_upper, grapheme_cluster_break, word_break }
+}
+
+fn codepoints_to_range(codepoints: Codepoints) -> Range<u32> {
+    match codepoints {
+        Codepoints::Single(c) => c.value()..c.value() + 1,
+        Codepoints::Range(c) => c.start.value()..c.end.value() + 1,
+    }
 }
 
 fn main() {
@@ -281,6 +311,16 @@ fn main() {
 
     modules.push((String::from("conversions"), case_mapping::generate_case_mapping(&unicode_data)));
 
+    for (property, enum_name, ranges) in [
+        ("Grapheme_Cluster_Break", "GraphemeClusterBreak", &unicode_data.grapheme_cluster_break),
+        ("Word_Break", "WordBreak", &unicode_data.word_break),
+    ] {
+        let (file, bytes_used) = break_property::generate_break_property(enum_name, ranges);
+        modules.push((property.to_lowercase(), file));
+        println!("{:15}: {} bytes, {} ranges", property, bytes_used, ranges.len());
+        total_bytes += bytes_used;
+    }
+
     for (name, contents) in modules {
         table_file.push_str("#[rustfmt::skip]\n");
         table_file.push_str(&format!("pub mod {name} {{\n"));
@@ -339,11 +379,33 @@ fn fmt_list<V: std::fmt::Debug>(values: impl IntoIterator<Item = V>) -> String {
 fn generate_tests(data_path: &str, ranges: &[(&str, Vec<Range<u32>>)]) -> String {
     let mut s = String::new();
     s.push_str("#![allow(incomplete_features, unused)]\n");
-    s.push_str("#![feature(const_generics)]\n\n");
+    // The tables carry the stability attributes of `core`.
+    s.push_str("#![feature(staged_api)]\n");
+    s.push_str("#![unstable(feature = \"unicode_internals\", issue = \"none\")]\n\n");
     s.push_str("\n#[allow(unused)]\nuse std::hint;\n");
     s.push_str(&format!("#[path = \"{data_path}\"]\n"));
     s.push_str("mod unicode_data;\n\n");
 
+    // The segmentation algorithms live next to the tables and refer to the
+    // lookups through their parent module, like in `core::unicode`.
+    let segmentation_path = Path::new(data_path).with_file_name("segmentation.rs");
+    s.push_str(&format!("#[path = \"{}\"]\n", segmentation_path.display()));
+    s.push_str("mod segmentation;\n");
+    s.push_str("use unicode_data::extended_pictographic::lookup as Extended_Pictographic;\n");
+    s.push_str("use unicode_data::grapheme_cluster_break::lookup as Grapheme_Cluster_Break;\n");
+    s.push_str("use unicode_data::word_break::lookup as Word_Break;\n\n");
+
+    s.push_str("fn check_segments(len: fn(&str) -> usize, s: &str, expected: &[&str]) {\n");
+    s.push_str("    let mut segments = Vec::new();\n");
+    s.push_str("    let mut rest = s;\n");
+    s.push_str("    while !rest.is_empty() {\n");
+    s.push_str("        let (segment, tail) = rest.split_at(len(rest));\n");
+    s.push_str("        segments.push(segment);\n");
+    s.push_str("        rest = tail;\n");
+    s.push_str("    }\n");
+    s.push_str("    assert_eq!(segments, expected, \"{:?}\", s);\n");
+    s.push_str("}\n");
+
     s.push_str("\nfn main() {\n");
 
     for (property, ranges) in ranges {
@@ -372,6 +434,29 @@ fn generate_tests(data_path: &str, ranges: &[(&str, Vec<Range<u32>>)]) -> String
         s.push_str("    }\n\n");
     }
 
+    let grapheme_cluster_break_tests =
+        ucd_parse::parse::<_, ucd_parse::GraphemeClusterBreakTest>(&UNICODE_DIRECTORY)
+            .unwrap()
+            .into_iter()
+            .map(|test| (test.grapheme_clusters.concat(), test.grapheme_clusters))
+            .collect::<Vec<_>>();
+    let word_break_tests = ucd_parse::parse::<_, ucd_parse::WordBreakTest>(&UNICODE_DIRECTORY)
+        .unwrap()
+        .into_iter()
+        .map(|test| (test.words.concat(), test.words))
+        .collect::<Vec<_>>();
+    for (property, len_fn, tests) in [
+        ("Grapheme_Cluster_Break", "segmentation::grapheme_len", &grapheme_cluster_break_tests),
+        ("Word_Break", "segmentation::word_len", &word_break_tests),
+    ] {
+        let fn_name = format!("{}_conformance", property.to_lowercase());
+        s.push_str(&format!(r#"    println!("Testing {} conformance");"#, property));
+        s.push('\n');
+        s.push_str(&format!("    {fn_name}();\n"));
+        s.push_str(&break_property::generate_break_tests(&fn_name, len_fn, tests));
+        s.push('\n');
+    }
+
     s.push_str("}");
     s
 }
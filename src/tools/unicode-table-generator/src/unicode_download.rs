@@ -6,8 +6,17 @@
 
 static README: &str = "ReadMe.txt";
 
-static RESOURCES: &[&str] =
-    &["DerivedCoreProperties.txt", "PropList.txt", "UnicodeData.txt", "SpecialCasing.txt"];
+static RESOURCES: &[&str] = &[
+    "DerivedCoreProperties.txt",
+    "PropList.txt",
+    "UnicodeData.txt",
+    "SpecialCasing.txt",
+    "auxiliary/GraphemeBreakProperty.txt",
+    "auxiliary/GraphemeBreakTest.txt",
+    "auxiliary/WordBreakProperty.txt",
+    "auxiliary/WordBreakTest.txt",
+    "emoji/emoji-data.txt",
+];
 
 #[track_caller]
 fn fetch(url: &str) -> Output {
@@ -40,6 +49,10 @@ pub fn fetch_latest() {
 
     for resource in RESOURCES {
         let output = fetch(resource);
-        std::fs::write(directory.join(resource), output.stdout).unwrap();
+        let path = directory.join(resource);
+        if let Err(e) = std::fs::create_dir_all(path.parent().unwrap()) {
+            panic!("Failed to create directory for {path:?}: {e}");
+        }
+        std::fs::write(path, output.stdout).unwrap();
     }
 }
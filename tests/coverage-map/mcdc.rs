@@ -0,0 +1,31 @@
+#![feature(coverage_attribute)]
+// compile-flags: --edition=2021 -Zunstable-options -Cinstrument-coverage=mcdc
+// min-llvm-version: 18
+// llvm-cov-flags: --show-branches=count --show-mcdc
+
+#[inline(never)]
+fn and(a: bool, b: bool) {
+    if a && b {
+        say("both");
+    }
+}
+
+#[inline(never)]
+fn or(a: bool, b: bool) {
+    if a || b {
+        say("either");
+    }
+}
+
+#[coverage(off)]
+fn say(message: &str) {
+    core::hint::black_box(message);
+}
+
+#[coverage(off)]
+fn main() {
+    for (a, b) in [(false, false), (true, false), (true, true)] {
+        and(a, b);
+        or(a, b);
+    }
+}
@@ -0,0 +1,13 @@
+// Checks that the lint levels set by `-Z lint-config` are recomputed when the contents of the
+// configuration change between sessions, even though the source code stays the same.
+
+// revisions: rpass1 cfail2 rpass3
+// [rpass1]compile-flags: -Zlint-config={{src-base}}/lint-config/allow.toml
+// [cfail2]compile-flags: -Zlint-config={{src-base}}/lint-config/deny.toml
+// [cfail2]compile-flags: -Zassert-incr-state=loaded
+// [rpass3]compile-flags: -Zlint-config={{src-base}}/lint-config/allow.toml
+
+fn main() {
+    let unused = 1;
+    //[cfail2]~^ ERROR unused variable: `unused`
+}
@@ -0,0 +1,52 @@
+#![feature(coverage_attribute, let_chains)]
+// compile-flags: --edition=2021 -Zunstable-options -Cinstrument-coverage=branch
+// llvm-cov-flags: --show-branches=count
+
+#[inline(never)]
+fn if_else(cond: bool) {
+    if cond {
+        say("yes");
+    } else {
+        say("no");
+    }
+}
+
+#[inline(never)]
+fn if_not(cond: bool) {
+    if !cond {
+        say("not");
+    }
+}
+
+#[inline(never)]
+fn match_guard(x: u32) {
+    match x {
+        n if n > 1 => say("big"),
+        _ => say("small"),
+    }
+}
+
+#[inline(never)]
+fn let_chain(x: Option<u32>) {
+    if let Some(n) = x && n > 1 {
+        say("big");
+    }
+}
+
+#[coverage(off)]
+fn say(message: &str) {
+    core::hint::black_box(message);
+}
+
+#[coverage(off)]
+fn main() {
+    for cond in [false, true, true] {
+        if_else(cond);
+        if_not(cond);
+    }
+    for x in [0, 1, 2] {
+        match_guard(x);
+        let_chain(Some(x));
+    }
+    let_chain(None);
+}
@@ -0,0 +1,106 @@
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_middle;
+extern crate rustc_session;
+extern crate rustc_span;
+
+use rustc_interface::interface;
+use rustc_session::config::{CrateType, Input, Options, OutputType, OutputTypes};
+use rustc_span::def_id::LOCAL_CRATE;
+use rustc_span::source_map::InMemoryFileLoader;
+
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.len() < 4 {
+        panic!("expected rustc path");
+    }
+
+    let tmpdir = PathBuf::from(&args[1]);
+
+    let mut sysroot = PathBuf::from(&args[3]);
+    sysroot.pop();
+    sysroot.pop();
+
+    let real_file = tmpdir.join("real.txt");
+    let mut loader = InMemoryFileLoader::new();
+    loader.add_file(
+        "src/lib.rs",
+        format!(
+            r#"
+            #![debugger_visualizer(natvis_file = "answer.natvis")]
+            mod generated;
+            #[path = "../other/bar.rs"]
+            mod bar;
+            include!("consts.rs");
+            pub const DATA: &str = include_str!("data.txt");
+            pub const REAL: &str = include_str!({real_file:?});
+            pub fn answer() -> u32 {{ generated::ANSWER + bar::ONE + CONST }}
+            "#
+        ),
+    );
+    loader.add_file("src/generated/mod.rs", "pub const ANSWER: u32 = 40;");
+    loader.add_file("other/bar.rs", "pub const ONE: u32 = 1;");
+    loader.add_file("src/consts.rs", "const CONST: u32 = 1;");
+    loader.add_file("src/data.txt", "in memory");
+    loader.add_file("src/answer.natvis", "<AutoVisualizer />");
+
+    compile(loader, &tmpdir, sysroot);
+
+    // Only the file that really exists may be in the dep-info.
+    let dep_info = std::fs::read_to_string(tmpdir.join("lib.d")).unwrap();
+    assert!(dep_info.contains(real_file.to_str().unwrap()), "{dep_info}");
+    for virtual_file in ["lib.rs", "mod.rs", "bar.rs", "consts.rs", "data.txt", "answer.natvis"] {
+        assert!(!dep_info.contains(virtual_file), "{dep_info}");
+    }
+}
+
+fn compile(loader: InMemoryFileLoader, tmpdir: &Path, sysroot: PathBuf) {
+    let mut opts = Options::default();
+    opts.output_types = OutputTypes::new(&[(OutputType::DepInfo, None)]);
+    opts.crate_types = vec![CrateType::Rlib];
+    opts.maybe_sysroot = Some(sysroot);
+
+    let config = interface::Config {
+        opts,
+        crate_cfg: Default::default(),
+        crate_check_cfg: Default::default(),
+        input: Input::File(PathBuf::from("src/lib.rs")),
+        output_file: None,
+        output_dir: Some(tmpdir.to_owned()),
+        ice_file: None,
+        file_loader: Some(Box::new(loader)),
+        locale_resources: &[],
+        lint_caps: Default::default(),
+        parse_sess_created: None,
+        hash_untracked_state: None,
+        register_lints: None,
+        override_queries: None,
+        make_codegen_backend: None,
+        registry: rustc_driver::diagnostics_registry(),
+        using_internal_features: std::sync::Arc::default(),
+        expanded_args: Default::default(),
+        caches: None,
+    };
+
+    interface::run_compiler(config, |compiler| {
+        compiler
+            .enter(|queries| {
+                queries.global_ctxt()?.enter(|tcx| {
+                    tcx.analysis(())?;
+                    // Debugger visualizers are read through the file loader too.
+                    let visualizers = tcx.debugger_visualizers(LOCAL_CRATE);
+                    assert_eq!(visualizers.len(), 1);
+                    assert_eq!(&*visualizers[0].src, b"<AutoVisualizer />");
+                    // Writes the dep-info.
+                    tcx.output_filenames(());
+                    Ok(())
+                })
+            })
+            .unwrap();
+    });
+}
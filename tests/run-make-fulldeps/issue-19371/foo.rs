@@ -64,6 +64,7 @@ fn compile(code: String, output: PathBuf, sysroot: PathBuf) {
         registry: rustc_driver::diagnostics_registry(),
         using_internal_features: std::sync::Arc::default(),
         expanded_args: Default::default(),
+        caches: None,
     };
 
     interface::run_compiler(config, |compiler| {
@@ -0,0 +1,12 @@
+// Code has been added above the warnings of the baseline, and they have been
+// reformatted.
+
+pub fn zeroth() {
+    let c = 0;
+}
+
+pub fn first() {
+    let   a = 1;
+}
+
+pub fn second() { let b = 2; }
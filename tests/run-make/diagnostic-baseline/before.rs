@@ -0,0 +1,7 @@
+pub fn first() {
+    let a = 1;
+}
+
+pub fn second() {
+    let b = 2;
+}
@@ -0,0 +1,20 @@
+#![crate_type = "lib"]
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub fn manhattan(p: &Point) -> i32 {
+    p.x.abs() + p.y.abs()
+}
+
+pub fn sum(values: &[u64]) -> u64 {
+    let mut total = 0;
+    for value in values {
+        total += value;
+    }
+    total
+}
+
+pub const ORIGIN: Point = Point { x: 0, y: 0 };
@@ -0,0 +1,41 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+with open(sys.argv[1]) as f:
+    krate = json.load(f)
+
+assert krate["schema_version"] == 1
+assert krate["crate_name"] == "foo"
+
+# Every table is indexed by the id of its entries.
+for table in ["types", "def_ids", "spans"]:
+    assert [entry["id"] for entry in krate[table]] == list(range(len(krate[table]))), table
+
+items = {item["name"]: item for item in krate["items"]}
+assert set(items) >= {"manhattan", "sum", "ORIGIN"}, list(items)
+assert items["manhattan"]["kind"] == "Fn"
+assert items["ORIGIN"]["kind"] == "Const"
+
+# `manhattan` returns an `i32` and takes a reference to a `Point`.
+types = krate["types"]
+locals = items["manhattan"]["body"]["locals"]
+assert types[locals[0]["ty"]]["kind"] == {"RigidTy": {"Int": "I32"}}
+point_ref = types[locals[1]["ty"]]["kind"]["RigidTy"]["Ref"]
+point = types[point_ref[1]]["kind"]["RigidTy"]["Adt"]
+assert krate["def_ids"][point[0]]["name"] == "Point"
+
+# All types and spans that are referenced by the bodies are in the tables.
+def check_refs(value, key=None):
+    if isinstance(value, dict):
+        for k, v in value.items():
+            check_refs(v, k)
+    elif isinstance(value, list):
+        for v in value:
+            check_refs(v, key)
+    elif key in ("ty", "span") and isinstance(value, int):
+        table = "types" if key == "ty" else "spans"
+        assert value < len(krate[table]), (key, value)
+
+check_refs(krate["items"])
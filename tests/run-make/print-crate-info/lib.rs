@@ -0,0 +1,11 @@
+#![crate_name = "mylib"]
+
+macro_rules! forward {
+    ($name:ident) => {
+        pub fn $name() -> u32 {
+            dep::$name()
+        }
+    };
+}
+
+forward!(answer);
@@ -0,0 +1,25 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+with open(sys.argv[1]) as f:
+    info = json.load(f)
+
+assert info["crate_name"] == "mylib"
+assert sorted(info["crate_types"]) == ["cdylib", "rlib"], info["crate_types"]
+assert info["edition"] == "2021"
+assert "libmylib.rlib" in info["file_names"], info["file_names"]
+assert len(info["file_names"]) == 2
+assert {"name": "feature", "value": "foo"} in info["cfg"]
+assert {"name": "debug_assertions", "value": None} in info["cfg"]
+
+externs = {extern["name"]: extern for extern in info["externs"]}
+# `dep` is only named by a macro expansion, and `unused` isn't named at all.
+assert "dep" in externs and "unused" not in externs, list(externs)
+assert externs["dep"]["source"] == "path"
+assert externs["dep"]["from_extern_flag"]
+assert externs["dep"]["path"].endswith("libdep.rlib")
+# The standard library is injected by the compiler.
+assert externs["std"]["source"] == "extern-crate"
+assert not externs["std"]["from_extern_flag"]
@@ -0,0 +1,16 @@
+pub mod nested;
+
+struct Unrelated {
+    field: u32,
+}
+
+impl Unrelated {
+    fn method(&self) -> u32 {
+        self.field + 1
+    }
+}
+
+pub fn helper() -> u32 {
+    let unrelated = Unrelated { field: 1 };
+    unrelated.method()
+}
@@ -0,0 +1,9 @@
+#![feature(rustc_attrs)]
+
+mod helpers;
+
+#[rustc_error(delay_span_bug_from_inside_query)]
+fn main() -> helpers::nested::Status {
+    let x = helpers::helper();
+    println!("{x}");
+}
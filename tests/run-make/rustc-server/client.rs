@@ -0,0 +1,140 @@
+use std::env;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+struct Response {
+    exit_code: i32,
+    stdout: String,
+    stderr: String,
+}
+
+fn connect() -> UnixStream {
+    // The server is started in the background, so give it some time to bind
+    // its socket.
+    for _ in 0..600 {
+        if let Ok(stream) = UnixStream::connect("../rustc.sock") {
+            return stream;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    panic!("the server never started listening");
+}
+
+/// Quotes `s` as a JSON string.
+fn escape(s: &str) -> String {
+    let mut escaped = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Reads the number after `"key":` in the JSON header of a response.
+fn field(header: &str, key: &str) -> i64 {
+    let start = header.find(&format!("\"{key}\":")).unwrap() + key.len() + 3;
+    let len = header[start..].find(|c| c == ',' || c == '}').unwrap();
+    header[start..start + len].parse().unwrap()
+}
+
+fn send(line: &str) -> (String, Vec<u8>) {
+    let mut stream = connect();
+    writeln!(stream, "{line}").unwrap();
+    let mut reader = BufReader::new(stream);
+    let mut header = String::new();
+    reader.read_line(&mut header).unwrap();
+    let mut body = Vec::new();
+    reader.read_to_end(&mut body).unwrap();
+    (header, body)
+}
+
+/// Compiles in the current directory, with the environment of the client and
+/// `GREETING` set to `greeting`.
+fn compile(args: &[&str], greeting: Option<&str>) -> Response {
+    let cwd = env::current_dir().unwrap();
+    let mut vars: Vec<_> = env::vars().filter(|(key, _)| key != "GREETING").collect();
+    if let Some(greeting) = greeting {
+        vars.push(("GREETING".to_owned(), greeting.to_owned()));
+    }
+    let env: Vec<_> =
+        vars.iter().map(|(key, value)| format!("{}: {}", escape(key), escape(value))).collect();
+    let args: Vec<_> = args.iter().map(|arg| escape(arg)).collect();
+    let (header, body) = send(&format!(
+        r#"{{"args": [{}], "cwd": {}, "env": {{{}}}}}"#,
+        args.join(", "),
+        escape(cwd.to_str().unwrap()),
+        env.join(", ")
+    ));
+    let exit_code = field(&header, "exit_code") as i32;
+    let stdout_len = field(&header, "stdout") as usize;
+    let stderr_len = field(&header, "stderr") as usize;
+    assert_eq!(body.len(), stdout_len + stderr_len, "{header}");
+    let (stdout, stderr) = body.split_at(stdout_len);
+    Response {
+        exit_code,
+        stdout: String::from_utf8(stdout.to_vec()).unwrap(),
+        stderr: String::from_utf8(stderr.to_vec()).unwrap(),
+    }
+}
+
+fn main() {
+    let args: Vec<_> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        // Run a single compilation, like `rustc` would.
+        let args: Vec<_> = args.iter().map(String::as_str).collect();
+        let response = compile(&args, None);
+        io::stdout().write_all(response.stdout.as_bytes()).unwrap();
+        io::stderr().write_all(response.stderr.as_bytes()).unwrap();
+        process::exit(response.exit_code);
+    }
+
+    let greeting = ["--crate-type=lib", "--emit=metadata", "greeting.rs"];
+    let first = compile(&greeting, Some("hello"));
+    assert_eq!(first.exit_code, 0, "{}", first.stderr);
+    assert_eq!(first.stdout, "");
+    assert_eq!(first.stderr, "");
+    assert!(Path::new("libgreeting.rmeta").exists());
+    assert!(!Path::new("../server/libgreeting.rmeta").exists());
+
+    // The environment of the first request must not leak into the second one.
+    let second = compile(&greeting, None);
+    assert_eq!(second.exit_code, 1);
+    assert!(
+        second.stderr.contains("environment variable `GREETING` not defined"),
+        "{}",
+        second.stderr
+    );
+
+    // The server keeps running after a request that it can't parse.
+    let (header, _) = send("not a request");
+    assert!(header.starts_with("{\"error\":"), "{header}");
+    let third = compile(&greeting, Some("hello again"));
+    assert_eq!(third.exit_code, 0, "{}", third.stderr);
+
+    // The second compilation in the incremental directory starts from what the
+    // server kept of the first one.
+    let incremental = ["--crate-type=lib", "--emit=metadata", "-Cincremental=incr", "greeting.rs"];
+    for _ in 0..2 {
+        let response = compile(&incremental, Some("hello"));
+        assert_eq!(response.exit_code, 0, "{}", response.stderr);
+    }
+
+    // Requests can't change the variables that the compiler reads from the
+    // environment of the server.
+    env::set_var("RUSTC_BOOTSTRAP", "not the one of the server");
+    let bootstrap = compile(&greeting, Some("hello"));
+    env::remove_var("RUSTC_BOOTSTRAP");
+    assert_eq!(bootstrap.exit_code, 1);
+    assert!(bootstrap.stderr.contains("`RUSTC_BOOTSTRAP` must be set"), "{}", bootstrap.stderr);
+}
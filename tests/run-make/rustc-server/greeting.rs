@@ -0,0 +1 @@
+pub const GREETING: &str = env!("GREETING");
@@ -0,0 +1,3 @@
+pub fn greet_world() -> String {
+    dep::greet("world")
+}
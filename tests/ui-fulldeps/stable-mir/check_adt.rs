@@ -0,0 +1,148 @@
+// run-pass
+//! Test that users are able to inspect the definition of ADTs.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::abi::{IntegerLength, IntegerType};
+use stable_mir::ty::{AdtDef, AdtKind, GenericArgs, RigidTy, Ty, TyKind, UintTy};
+use stable_mir::*;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(_tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let target_fn = items.iter().find(|item| item.name() == "adts").unwrap();
+    let body = target_fn.body();
+    let locals = body.locals();
+
+    // `Shape<u16>`
+    let (shape, args) = expect_adt(locals[1].ty);
+    assert_eq!(shape.kind(), AdtKind::Enum);
+    assert!(!shape.is_box());
+    let repr = shape.repr();
+    assert_eq!(repr.int, Some(IntegerType::Fixed { length: IntegerLength::I8, is_signed: false }));
+    assert!(!repr.is_c);
+    let variants = shape.variants();
+    assert_eq!(variants.len(), shape.num_variants());
+    let names: Vec<_> = variants.iter().map(|variant| variant.name()).collect();
+    assert_eq!(names, ["Empty", "Point", "Rect"]);
+    assert!(variants[0].fields().is_empty());
+    let fields = variants[2].fields();
+    let field_names: Vec<_> = fields.iter().map(|field| field.name.as_str()).collect();
+    assert_eq!(field_names, ["width", "height"]);
+    assert_matches!(
+        fields[0].ty_with_args(&args).kind(),
+        TyKind::RigidTy(RigidTy::Uint(UintTy::U16))
+    );
+    assert_matches!(fields[0].ty().kind(), TyKind::Param(_));
+    assert_eq!(variants[1].fields()[0].name, "0");
+    assert!(shape.variant(3).is_none());
+
+    // `Wrapper`
+    let (wrapper, args) = expect_adt(locals[2].ty);
+    assert_eq!(wrapper.kind(), AdtKind::Struct);
+    let repr = wrapper.repr();
+    assert!(repr.is_transparent);
+    assert_eq!(repr.int, None);
+    let fields = wrapper.variant(0).unwrap().fields();
+    assert_eq!(fields.len(), 2);
+    let (phantom, _) = expect_adt(fields[1].ty_with_args(&args));
+    assert!(phantom.is_phantom_data());
+
+    // `Box<Cell<u8>>`
+    let (boxed, args) = expect_adt(locals[3].ty);
+    assert!(boxed.is_box());
+    let (cell, args) = expect_adt(*args.0[0].expect_ty());
+    let value = &cell.variants()[0].fields()[0];
+    let (unsafe_cell, _) = expect_adt(value.ty_with_args(&args));
+    assert!(unsafe_cell.is_unsafe_cell());
+
+    // `Bits`
+    let (bits, _) = expect_adt(locals[4].ty);
+    assert_eq!(bits.kind(), AdtKind::Union);
+    let repr = bits.repr();
+    assert!(repr.is_c);
+    assert_eq!(repr.align, Some(8));
+    assert_eq!(bits.num_variants(), 1);
+
+    ControlFlow::Continue(())
+}
+
+fn expect_adt(ty: Ty) -> (AdtDef, GenericArgs) {
+    match ty.kind() {
+        TyKind::RigidTy(RigidTy::Adt(def, args)) => (def, args),
+        kind => unreachable!("Expected an ADT, but found {kind:?}"),
+    }
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "adt_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, tcx, test_stable_mir(tcx)).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        use std::cell::Cell;
+        use std::marker::PhantomData;
+
+        #[repr(u8)]
+        pub enum Shape<T> {{
+            Empty,
+            Point(T),
+            Rect {{ width: T, height: T }},
+        }}
+
+        #[repr(transparent)]
+        pub struct Wrapper<T> {{
+            value: u32,
+            marker: PhantomData<T>,
+        }}
+
+        #[repr(C, align(8))]
+        pub union Bits {{
+            int: u32,
+            float: f32,
+        }}
+
+        pub fn adts(_s: Shape<u16>, _w: Wrapper<char>, _c: Box<Cell<u8>>, _b: Bits) {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,139 @@
+// run-pass
+//! Test that users are able to evaluate constants and statics, and read their memory.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::abi::MachineInfo;
+use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::mono::StaticDef;
+use stable_mir::mir::visit::{Location, MirVisitor};
+use stable_mir::ty::Const;
+use stable_mir::*;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(_tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let find = |name: &str| *items.iter().find(|item| item.name() == name).unwrap();
+    let eval_static = |name: &str| StaticDef(find(name).0).eval_initializer().unwrap();
+    let pointer_size = MachineInfo::target().pointer_size;
+
+    let table = eval_static("TABLE");
+    assert_eq!(table.bytes.len(), 6);
+    assert_eq!(table.read_partial_uint(0..2).unwrap(), 1);
+    assert_eq!(table.read_partial_uint(4..6).unwrap(), 0xFFFF);
+    assert!(table.read_partial_uint(4..8).is_err());
+
+    assert_eq!(eval_static("NEGATIVE").read_int().unwrap(), -5);
+    assert!(eval_static("FLAG").read_bool().unwrap());
+
+    // `&TABLE[1]` points to the second element of the static.
+    let reference = eval_static("REF");
+    assert!(reference.read_uint().is_err());
+    let (target, offset) = reference.read_pointer(0).unwrap().unwrap();
+    assert_matches!(target, GlobalAlloc::Static(def) if def.0 == find("TABLE").0);
+    assert_eq!(offset, 2);
+
+    // A `&str` is a pointer to the string literal, followed by its length.
+    let name = eval_static("NAME");
+    let (target, offset) = name.read_pointer(0).unwrap().unwrap();
+    let GlobalAlloc::Memory(literal) = target else { unreachable!("Unexpected {target:?}") };
+    assert_eq!(offset, 0);
+    assert_eq!(literal.raw_bytes().unwrap(), b"hello");
+    assert_eq!(name.read_partial_uint(pointer_size..2 * pointer_size).unwrap(), 5);
+
+    let null = eval_static("NULL");
+    assert!(null.read_pointer(0).unwrap().is_none());
+    assert_eq!(null.read_uint().unwrap(), 0);
+
+    let consts = collect_consts(&find("read_const").body());
+    assert!(consts.iter().any(|cnst| {
+        cnst.eval().is_ok_and(|alloc| alloc.read_uint().unwrap() == 3)
+    }));
+
+    // Constants that depend on generic parameters can't be evaluated.
+    let consts = collect_consts(&find("generic").body());
+    assert!(!consts.is_empty());
+    assert!(consts.iter().all(|cnst| cnst.eval().is_err()));
+
+    ControlFlow::Continue(())
+}
+
+fn collect_consts(body: &mir::Body) -> Vec<Const> {
+    struct ConstCollector(Vec<Const>);
+
+    impl MirVisitor for ConstCollector {
+        fn visit_const(&mut self, constant: &Const, _location: Location) {
+            self.0.push(constant.clone());
+        }
+    }
+
+    let mut collector = ConstCollector(vec![]);
+    collector.visit_body(body);
+    collector.0
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "allocation_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, tcx, test_stable_mir(tcx)).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub const LEN: usize = 3;
+
+        pub static TABLE: [u16; LEN] = [1, 2, 0xFFFF];
+        pub static NEGATIVE: i32 = -5;
+        pub static FLAG: bool = true;
+        pub static REF: &u16 = &TABLE[1];
+        pub static NAME: &str = "hello";
+        pub static NULL: Option<&u8> = None;
+
+        pub fn read_const() -> usize {{
+            LEN
+        }}
+
+        pub fn generic<const N: usize>() -> usize {{
+            N
+        }}
+    "#
+    )?;
+    Ok(())
+}
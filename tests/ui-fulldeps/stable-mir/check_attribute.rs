@@ -0,0 +1,116 @@
+// run-pass
+//! Test that users are able to query the attributes, visibility and source code of items.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::{CrateItem, Visibility};
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(_tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let find = |name: &str| *items.iter().find(|item| item.name() == name).unwrap();
+    let attrs = |item: CrateItem, path: &[&str]| {
+        let path: Vec<_> = path.iter().map(|seg| seg.to_string()).collect();
+        item.attrs_by_path(&path).iter().map(|attr| attr.as_str().to_string()).collect::<Vec<_>>()
+    };
+
+    let verified = find("verified");
+    assert_eq!(attrs(verified, &["rustfmt", "skip"]), ["#[rustfmt::skip]"]);
+    assert_eq!(attrs(verified, &["inline"]), ["#[inline(never)]"]);
+    assert!(attrs(verified, &["skip"]).is_empty());
+    assert_eq!(attrs(verified, &["doc"]), ["/// Documented function.", "#[doc = \"More docs.\"]"]);
+    assert_eq!(verified.visibility(), Visibility::Public);
+
+    let inline = verified.attrs_by_path(&["inline".to_string()]);
+    assert_eq!(inline[0].span().source_text().unwrap(), "#[inline(never)]");
+
+    let private = find("private");
+    assert!(attrs(private, &["doc"]).is_empty());
+    assert_matches!(private.visibility(), Visibility::Restricted(_));
+    assert_matches!(find("inner::restricted").visibility(), Visibility::Restricted(_));
+
+    let source = private.span().source_text().unwrap();
+    assert!(source.starts_with("fn private() -> u8"), "{source}");
+
+    // Array lengths and enum discriminants are anonymous constants, which are visible in the
+    // module that contains them.
+    let anon_consts: Vec<_> =
+        items.iter().filter(|item| item.name().contains("{constant#")).collect();
+    assert_eq!(anon_consts.len(), 2, "{anon_consts:?}");
+    for anon_const in anon_consts {
+        assert_matches!(anon_const.visibility(), Visibility::Restricted(_));
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "attribute_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, tcx, test_stable_mir(tcx)).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        /// Documented function.
+        #[doc = "More docs."]
+        #[rustfmt::skip]
+        #[inline(never)]
+        pub fn verified() -> u8 {{
+            private()
+        }}
+
+        fn private() -> u8 {{
+            1
+        }}
+
+        pub mod inner {{
+            pub(crate) fn restricted() {{}}
+        }}
+
+        pub static TABLE: [u8; 2 + 2] = [1, 2, 3, 4];
+
+        pub enum Discriminant {{
+            First = 1 << 2,
+        }}
+    "#
+    )?;
+    Ok(())
+}
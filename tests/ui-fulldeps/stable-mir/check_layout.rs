@@ -0,0 +1,125 @@
+// run-pass
+//! Test that users are able to retrieve the layout of types and the ABI of instances.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::abi::{
+    FieldsShape, IntegerLength, PassMode, Primitive, Scalar, TagEncoding, ValueAbi, VariantsShape,
+};
+use stable_mir::mir::mono::Instance;
+use stable_mir::*;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(_tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let target_fn = items.iter().find(|item| item.name() == "fn_abi").unwrap();
+    let instance = Instance::try_from(*target_fn).unwrap();
+    let fn_abi = instance.fn_abi().unwrap();
+    assert_eq!(fn_abi.args.len(), 3);
+    assert!(!fn_abi.c_variadic);
+
+    // The zero-sized argument is ignored.
+    let ignore = &fn_abi.args[0];
+    assert_matches!(ignore.mode, PassMode::Ignore);
+    let shape = ignore.layout.shape();
+    assert_eq!(shape.size, 0);
+    assert_matches!(shape.fields, FieldsShape::Array { stride: 1, count: 0 });
+
+    // A `char` is an unsigned 32-bit integer with a restricted range of valid values.
+    let primitive = &fn_abi.args[1];
+    assert_matches!(primitive.mode, PassMode::Direct(_));
+    assert_eq!(primitive.ty.layout().unwrap(), primitive.layout);
+    let shape = primitive.layout.shape();
+    assert_eq!(shape.size, 4);
+    assert_eq!(shape.abi_align, 4);
+    assert_matches!(shape.fields, FieldsShape::Primitive);
+    let ValueAbi::Scalar(Scalar::Initialized { value, valid_range }) = shape.abi else {
+        unreachable!("Unexpected ABI for `char`: {:?}", shape.abi)
+    };
+    assert_eq!(value, Primitive::Int { length: IntegerLength::I32, signed: false });
+    assert_eq!((valid_range.start, valid_range.end), (0, 0x10FFFF));
+    assert!(shape.largest_niche.is_some());
+
+    // Struct fields may be reordered.
+    let pair = &fn_abi.args[2];
+    let shape = pair.layout.shape();
+    assert_eq!(shape.size, 8);
+    let FieldsShape::Arbitrary { offsets } = &shape.fields else {
+        unreachable!("Unexpected fields for `Pair`: {:?}", shape.fields)
+    };
+    assert_eq!(offsets.len(), 2);
+    assert_matches!(shape.variants, VariantsShape::Single { index: 0 });
+
+    // `Option<bool>` uses the invalid values of `bool` to encode `None`.
+    let shape = fn_abi.ret.layout.shape();
+    assert_eq!(shape.size, 1);
+    let VariantsShape::Multiple { tag_encoding, variants, .. } = &shape.variants else {
+        unreachable!("Unexpected variants for `Option<bool>`: {:?}", shape.variants)
+    };
+    assert_eq!(variants.len(), 2);
+    assert_matches!(tag_encoding, TagEncoding::Niche { untagged_variant: 1, niche_start: 2, .. });
+
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "layout_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, tcx, test_stable_mir(tcx)).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub struct Pair {{
+            pub small: u8,
+            pub big: u32,
+        }}
+
+        pub fn fn_abi(ignore: [u8; 0], primitive: char, pair: Pair) -> Option<bool> {{
+            if ignore.len() == 0 && primitive.is_alphanumeric() {{
+                Some(pair.small < pair.big as u8)
+            }} else {{
+                None
+            }}
+        }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,145 @@
+// run-pass
+//! Test that users are able to collect the mono items of a crate and the edges between them.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{collect_items, InstanceKind, MonoItem};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(_tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let graph = collect_items().unwrap();
+    assert!(graph.edges.iter().all(|edge| {
+        edge.user < graph.items.len() && edge.used < graph.items.len()
+    }));
+
+    let instance_of = |item: &MonoItem| match item {
+        MonoItem::Fn(instance) => Some(*instance),
+        MonoItem::Static(_) | MonoItem::GlobalAsm(_) => None,
+    };
+    // The items are sorted by their symbol names, and the edges by their indices, so the graph
+    // is the same in every run.
+    let names: Vec<_> = graph
+        .items
+        .iter()
+        .filter_map(instance_of)
+        .map(|instance| instance.mangled_name())
+        .collect();
+    assert!(names.windows(2).all(|names| names[0] < names[1]));
+    assert!(graph.edges.windows(2).all(|edges| {
+        (edges[0].user, edges[0].used) < (edges[1].user, edges[1].used)
+    }));
+
+    let main_idx = graph
+        .items
+        .iter()
+        .position(|item| {
+            instance_of(item).is_some_and(|instance| {
+                CrateItem::try_from(instance).is_ok_and(|item| item.name() == "main")
+            })
+        })
+        .unwrap();
+
+    // `main` reads the static and calls `total`.
+    let used: Vec<_> = graph.used_items(main_idx).collect();
+    assert!(used.iter().any(|item| matches!(item, MonoItem::Static(_))));
+    assert!(used.iter().filter_map(|item| instance_of(item)).any(|instance| {
+        instance.mangled_name().contains("total")
+    }));
+
+    // The drop glue of `Vec<Box<dyn Shape>>` and the methods of the vtables are collected.
+    let instances: Vec<_> = graph.items.iter().filter_map(instance_of).collect();
+    assert!(instances.iter().any(|instance| instance.kind == InstanceKind::Shim));
+    assert!(instances.iter().any(|instance| instance.mangled_name().contains("area")));
+
+    assert!(graph.items.iter().any(|item| matches!(item, MonoItem::GlobalAsm(_))));
+
+    // Generic functions are only collected through their uses.
+    let generic = graph.items.iter().position(|item| {
+        instance_of(item).is_some_and(|instance| instance.mangled_name().contains("twice"))
+    });
+    assert!(graph.user_items(generic.unwrap()).next().is_some());
+
+    // The collection runs once per session, so asking again gives back the same graph.
+    let again = collect_items().unwrap();
+    assert_eq!(again.items.len(), graph.items.len());
+    assert_eq!(again.edges, graph.edges);
+
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "mono_items_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, tcx, test_stable_mir(tcx)).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        std::arch::global_asm!("");
+
+        static SCALE: u32 = 2;
+
+        trait Shape {{
+            fn area(&self) -> u32;
+        }}
+
+        struct Square(u32);
+
+        impl Shape for Square {{
+            fn area(&self) -> u32 {{
+                twice(self.0 * self.0)
+            }}
+        }}
+
+        fn twice<T: std::ops::Add<Output = T> + Copy>(value: T) -> T {{
+            value + value
+        }}
+
+        fn total(shapes: &[Box<dyn Shape>]) -> u32 {{
+            shapes.iter().map(|shape| shape.area()).sum()
+        }}
+
+        fn main() {{
+            let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Square(SCALE))];
+            std::process::exit(total(&shapes) as i32);
+        }}
+    "#
+    )?;
+    Ok(())
+}
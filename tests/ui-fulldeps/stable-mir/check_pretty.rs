@@ -0,0 +1,102 @@
+// run-pass
+//! Test that the textual MIR of stable bodies is the same as the one the compiler prints.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(control_flow_enum)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_middle::mir::pretty::write_mir_fn;
+use rustc_middle::ty::{InstanceDef, TyCtxt};
+use rustc_smir::rustc_internal;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    for item in stable_mir::all_local_items() {
+        let name = item.name();
+        let mut stable = Vec::new();
+        item.body().dump(&mut stable, &name).unwrap();
+        let stable = String::from_utf8(stable).unwrap();
+
+        // This is what `--emit=mir` writes for the same function.
+        let def_id = rustc_internal::internal(&item.0);
+        let mut internal = Vec::new();
+        let body = tcx.instance_mir(InstanceDef::Item(def_id));
+        write_mir_fn(tcx, body, &mut |_, _| Ok(()), &mut internal).unwrap();
+        let internal = String::from_utf8(internal).unwrap();
+
+        assert_eq!(stable, internal, "{name}");
+        if name == "branch" {
+            // Make sure that the input has a scope nested within another one.
+            assert!(stable.lines().any(|line| line.starts_with("        scope ")), "{stable}");
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "pretty_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, tcx, test_stable_mir(tcx)).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub struct Wrapper(pub u8);
+
+        pub fn add(left: u8, right: u8) -> u8 {{
+            left + right
+        }}
+
+        pub fn branch(input: bool) -> u8 {{
+            if input {{
+                let value = 1;
+                let doubled = value + value;
+                doubled
+            }} else {{
+                0
+            }}
+        }}
+
+        pub fn wrap(input: &u8) -> Wrapper {{
+            Wrapper(*input)
+        }}
+
+        pub fn call(input: u8) -> Wrapper {{
+            wrap(&input)
+        }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,147 @@
+// run-pass
+//! Test that users are able to solve traits, normalize types and resolve vtable and drop glue
+//! instances.
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{Instance, InstanceKind};
+use stable_mir::mir::TerminatorKind;
+use stable_mir::ty::{GenericArgs, RigidTy, TyKind, UintTy};
+use stable_mir::*;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir(_tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let shape = stable_mir::all_trait_decls()
+        .into_iter()
+        .find(|trait_def| CrateItem(trait_def.0).name() == "Shape")
+        .unwrap();
+
+    let call_item = items.iter().find(|item| item.name() == "call").unwrap();
+    let call = call_item.body();
+    let square = call.arg_locals()[0].ty;
+    let circle = call.arg_locals()[1].ty;
+    let no_args = GenericArgs(vec![]);
+    assert!(square.implements_trait(shape, &no_args, call_item.0));
+    assert!(!circle.implements_trait(shape, &no_args, call_item.0));
+
+    // A type parameter only implements the traits that the where clauses of its item require.
+    let generic_item = items.iter().find(|item| item.name() == "generic").unwrap();
+    let generic = generic_item.body();
+    let param = generic.arg_locals()[0].ty;
+    assert_matches!(param.kind(), TyKind::Param(..));
+    assert!(param.implements_trait(shape, &no_args, generic_item.0));
+    assert!(!param.implements_trait(shape, &no_args, call_item.0));
+    let param_unit = generic.ret_local().ty;
+    assert_matches!(param_unit.kind(), TyKind::Alias(..));
+    assert_matches!(param_unit.normalize(generic_item.0).unwrap().kind(), TyKind::Alias(..));
+    assert!(param_unit.normalize(call_item.0).is_err());
+
+    // The call to `Shape::area` resolves to the method of the impl, for vtables too.
+    let TerminatorKind::Call { func, .. } = &call.blocks[0].terminator.kind else {
+        unreachable!("Unexpected terminator {:?}", call.blocks[0].terminator)
+    };
+    let TyKind::RigidTy(RigidTy::FnDef(def, args)) = func.ty(call.locals()).kind() else {
+        unreachable!()
+    };
+    let instance = Instance::resolve_for_vtable(def, &args).unwrap();
+    assert_eq!(instance.kind, InstanceKind::Item);
+    assert_eq!(CrateItem::try_from(instance).unwrap().name(), "<Square as Shape>::area");
+
+    // Drop glue is a shim, even for types that don't need to be dropped.
+    let drop_glue = Instance::resolve_drop_in_place(call.arg_locals()[2].ty);
+    assert_eq!(drop_glue.kind, InstanceKind::Shim);
+    assert_eq!(Instance::resolve_drop_in_place(square).kind, InstanceKind::Shim);
+
+    // The type of the field is the projection as written, which normalizes to `u8`.
+    let hold_item = items.iter().find(|item| item.name() == "hold").unwrap();
+    let hold = hold_item.body();
+    let TyKind::RigidTy(RigidTy::Adt(holder, _)) = hold.arg_locals()[0].ty.kind() else {
+        unreachable!()
+    };
+    let unit = holder.variants()[0].fields()[0].ty();
+    assert_matches!(unit.kind(), TyKind::Alias(..));
+    let normalized = unit.normalize(hold_item.0).unwrap();
+    assert_matches!(normalized.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U8)));
+
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "trait_queries_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, tcx, test_stable_mir(tcx)).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+        pub trait Shape {{
+            type Unit;
+            fn area(&self) -> u32;
+        }}
+
+        pub struct Square(pub u32);
+
+        pub struct Circle;
+
+        impl Shape for Square {{
+            type Unit = u8;
+            fn area(&self) -> u32 {{
+                self.0 * self.0
+            }}
+        }}
+
+        pub struct Holder {{
+            pub unit: <Square as Shape>::Unit,
+        }}
+
+        pub fn call(square: Square, _circle: Circle, _names: Vec<String>) -> u32 {{
+            Shape::area(&square)
+        }}
+
+        pub fn hold(_holder: Holder) {{}}
+
+        pub fn generic<T: Shape>(_shape: T) -> T::Unit {{
+            loop {{}}
+        }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+// run-pass
+// Test StableMIR behavior when the compiler is invoked as a wrapper
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::CompilerError;
+use std::io::Write;
+
+/// This test will generate a dummy crate, and compile it through `run_driver!` with the
+/// arguments that Cargo uses when invoking a `RUSTC_WORKSPACE_WRAPPER`.
+fn main() {
+    let path = "input_run_driver_test.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "driver".to_string(),
+        "/path/to/rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        path.to_string(),
+    ];
+    test_analyzed(args.clone());
+    test_dependency(args.clone());
+    test_failed_dependency(args.clone());
+    test_query(args);
+}
+
+fn test_analyzed(args: Vec<String>) {
+    let result = run_driver!(args, tcx, {
+        // The flags required by stable MIR are set by the driver.
+        assert!(tcx.sess.opts.unstable_opts.always_encode_mir);
+        let items = stable_mir::all_local_items();
+        ControlFlow::Continue::<(), usize>(items.len())
+    });
+    assert_eq!(result, Ok(1));
+}
+
+#[allow(unreachable_code)]
+fn test_dependency(mut args: Vec<String>) {
+    args.extend(["--cap-lints".to_string(), "allow".to_string()]);
+    let result = run_driver!(args, unreachable!() as ControlFlow<()>);
+    assert_eq!(result, Err(CompilerError::Skipped));
+}
+
+#[allow(unreachable_code)]
+fn test_failed_dependency(mut args: Vec<String>) {
+    args.extend(["--cap-lints=allow".to_string(), "--cfg=broken".to_string()]);
+    let result = run_driver!(args, unreachable!() as ControlFlow<()>);
+    assert_eq!(result, Err(CompilerError::CompilationFailed));
+}
+
+#[allow(unreachable_code)]
+fn test_query(args: Vec<String>) {
+    let args = vec![args[0].clone(), args[1].clone(), "-vV".to_string()];
+    let result = run_driver!(args, unreachable!() as ControlFlow<()>);
+    assert_eq!(result, Err(CompilerError::Skipped));
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    // This should trigger a compilation failure when enabled.
+    #[cfg(broken)]
+    mod broken_mod {{
+        fn call_invalid() {{
+            invalid_fn();
+        }}
+    }}
+
+    pub fn foo() {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,180 @@
+// run-pass
+//! Sanity check Stable MIR Visitor
+
+// ignore-stage1
+// ignore-cross-compile
+// ignore-remote
+// ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+// edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+#![feature(control_flow_enum)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::{Location, MirVisitor, MutMirVisitor, PlaceContext, TyContext};
+use stable_mir::mir::{Local, TerminatorKind};
+use stable_mir::ty::Ty;
+use stable_mir::*;
+use std::collections::HashSet;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+fn test_visitor(_tcx: TyCtxt<'_>) -> ControlFlow<()> {
+    let main_fn = stable_mir::entry_fn();
+    let main_body = main_fn.unwrap().body();
+    let main_visitor = TestVisitor::collect(&main_body);
+    assert!(main_visitor.ret_val.is_some());
+    assert!(main_visitor.args.is_empty());
+    assert!(main_visitor.tys.contains(&main_visitor.ret_val.unwrap()));
+    assert!(!main_visitor.calls.is_empty());
+
+    let exit_fn = main_visitor.calls.last().unwrap();
+    assert!(exit_fn.block > 0);
+
+    let items = stable_mir::all_local_items();
+    let sum = items.iter().find(|item| item.name() == "sum").unwrap();
+    let sum_body = sum.body();
+    let sum_visitor = TestVisitor::collect(&sum_body);
+    assert_eq!(sum_visitor.args.len(), 2);
+    assert!(sum_visitor.written.contains(&0));
+    assert!(sum_visitor.read.contains(&1));
+    assert!(sum_visitor.read.contains(&2));
+
+    // Rename the first argument into the second one in a copy of the body.
+    let mut renamed = sum_body.clone();
+    LocalRenamer { from: 1, to: 2 }.visit_body(&mut renamed);
+    let renamed_visitor = TestVisitor::collect(&renamed);
+    assert!(!renamed_visitor.read.contains(&1));
+    assert!(renamed_visitor.read.contains(&2));
+    ControlFlow::Continue(())
+}
+
+struct TestVisitor<'a> {
+    pub body: &'a mir::Body,
+    pub tys: HashSet<Ty>,
+    pub ret_val: Option<Ty>,
+    pub args: Vec<Ty>,
+    pub calls: Vec<Location>,
+    pub read: HashSet<Local>,
+    pub written: HashSet<Local>,
+}
+
+impl<'a> TestVisitor<'a> {
+    fn collect(body: &'a mir::Body) -> TestVisitor<'a> {
+        let mut visitor = TestVisitor {
+            body,
+            tys: Default::default(),
+            ret_val: None,
+            args: vec![],
+            calls: vec![],
+            read: Default::default(),
+            written: Default::default(),
+        };
+        visitor.visit_body(body);
+        visitor
+    }
+}
+
+impl<'a> MirVisitor for TestVisitor<'a> {
+    fn visit_ty(&mut self, ty: &Ty, context: TyContext) {
+        self.tys.insert(*ty);
+        match context {
+            TyContext::LocalDecl(0) => self.ret_val = Some(*ty),
+            TyContext::LocalDecl(local) if local <= self.body.arg_locals().len() => {
+                self.args.push(*ty)
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_terminator(&mut self, term: &mir::Terminator, location: Location) {
+        if let TerminatorKind::Call { .. } = term.kind {
+            let statements = &self.body.blocks[location.block].statements;
+            assert_eq!(location.statement_index, statements.len());
+            self.calls.push(location);
+        }
+        self.super_terminator(term, location);
+    }
+
+    fn visit_local(&mut self, local: &Local, context: PlaceContext, _location: Location) {
+        if context.is_mutating() {
+            self.written.insert(*local);
+        } else if context.is_use() {
+            self.read.insert(*local);
+        }
+    }
+}
+
+struct LocalRenamer {
+    from: Local,
+    to: Local,
+}
+
+impl MutMirVisitor for LocalRenamer {
+    fn visit_local(&mut self, local: &mut Local, _context: PlaceContext, _location: Location) {
+        if *local == self.from {
+            *local = self.to;
+        }
+    }
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "sim_visitor_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, tcx, test_visitor(tcx)).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    fn main() -> std::process::ExitCode {{
+        let inputs = Inputs::new();
+        let total = inputs.values.iter().sum();
+        exit_code(sum(total, 0))
+    }}
+
+    fn sum(left: u8, right: u8) -> u8 {{
+        left.wrapping_add(right)
+    }}
+
+    struct Inputs {{
+        values: [u8; 3],
+    }}
+
+    impl Inputs {{
+        pub fn new() -> Inputs {{
+            Inputs {{ values: [0, 1, 2] }}
+        }}
+    }}
+
+    fn exit_code(ret: u8) -> std::process::ExitCode {{
+        std::process::ExitCode::from(ret)
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,6 @@
+// compile-flags: --error-format=human-unicode -Zunstable-options -Zterminal-urls=yes
+
+fn main() {
+    let x: i32 = "";
+    //~^ ERROR mismatched types
+}
@@ -0,0 +1,13 @@
+// compile-flags: --error-format=human-unicode -Zunstable-options
+
+fn main() {
+    compile_error!(
+        //~^ ERROR starts at the beginning of a line
+        "starts at the beginning of a line"
+    );
+
+    let _x = compile_error!(
+        //~^ ERROR starts in the middle of a line
+        "starts in the middle of a line"
+    );
+}
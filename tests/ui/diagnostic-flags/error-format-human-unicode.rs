@@ -0,0 +1,6 @@
+// compile-flags: --error-format=human-unicode -Zunstable-options
+
+fn main() {
+    let x: i32 = "";
+    //~^ ERROR mismatched types
+}
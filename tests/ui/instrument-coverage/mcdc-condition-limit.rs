@@ -0,0 +1,21 @@
+// edition: 2021
+// min-llvm-version: 18
+// compile-flags: -Zunstable-options -Cinstrument-coverage=mcdc
+// needs-profiler-support
+// check-pass
+
+// Check that MC/DC instrumentation warns about, and falls back to branch
+// coverage for, decisions with more conditions than LLVM can track.
+
+fn main() {
+    let [a, b, c, d, e, f, g] = [true; 7];
+    if a && b && c && d && e && f && g {
+        //~^ WARNING number of conditions in decision (7) exceeds limit (6)
+        core::hint::black_box("hello");
+    }
+
+    // Decisions within the limit are instrumented without a warning.
+    if a && (b || c) && d && e && f {
+        core::hint::black_box("world");
+    }
+}
@@ -0,0 +1,24 @@
+// Checks that `-Z lint-config` sets the lint levels of the modules of the files matching its
+// globs, and that attributes override them.
+
+// compile-flags: -Zlint-config={{src-base}}/lint/lint-config/lint-config.toml
+
+mod ffi;
+
+mod inline {
+    pub fn read(p: *const u8) -> u8 {
+        unsafe { *p } //~ ERROR usage of an `unsafe` block
+    }
+}
+
+#[allow(unsafe_code)]
+fn allowed(p: *const u8) -> u8 {
+    unsafe { *p }
+}
+
+fn main() {
+    let x = 0u8;
+    inline::read(&x);
+    ffi::read(&x);
+    allowed(&x);
+}
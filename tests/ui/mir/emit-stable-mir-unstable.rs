@@ -0,0 +1,3 @@
+// compile-flags: --emit=stable-mir
+
+fn main() {}
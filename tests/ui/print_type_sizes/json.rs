@@ -0,0 +1,45 @@
+// compile-flags: -Z print-type-sizes -Z print-sizes-format=json --crate-type=lib
+// build-pass
+// ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+//     FIXME: consider using an attribute instead of side-effects.
+
+// This file illustrates the JSON output of `-Z print-type-sizes`: one object per type, with the
+// fields of each variant sorted by offset and the padding between them listed separately.
+//
+// It avoids using u64/i64 because on some targets that is only 4-byte
+// aligned (while on most it is 8-byte aligned) and so the resulting
+// padding and overall computed sizes can be quite different.
+
+#![allow(dead_code)]
+
+pub struct S {
+    a: u8,
+    b: u8,
+    g: i32,
+}
+
+// A tagged enum: the variant sizes include the discriminant, and the tag is the niche.
+pub enum E {
+    A(u8),
+    B(u16),
+}
+
+// A niche-filling enum: there is no discriminant, `Nothing` is stored in the invalid values of
+// the `char`, and the remaining invalid values are the niche of the whole type.
+pub enum Maybe<T> {
+    Nothing,
+    Just(T),
+}
+
+// A generic struct is only reported once it is monomorphized.
+pub struct Tagged<T> {
+    tag: u8,
+    value: T,
+}
+
+pub fn test() {
+    let _e = E::A(0);
+    let _m: Maybe<char> = Maybe::Nothing;
+    let _t: Tagged<u16> = Tagged { tag: 0, value: 0 };
+}